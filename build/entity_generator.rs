@@ -27,8 +27,17 @@ use crate::{
     DxfError,
     DxfResult,
     ExtensionGroup,
+    Handle,
+    HatchBoundaryPath,
+    HatchGradientColor,
+    HatchPatternDefinitionLine,
     LwPolylineVertex,
+    MLeaderContextData,
+    MeshSubEntityOverride,
     Point,
+    TableCell,
+    TransformationMatrix,
+    UnknownEntity,
     Vector,
     XData,
 };
@@ -54,6 +63,8 @@ use std::io::{Read, Write};
     generate_type_string(&mut fun, &element);
     generate_try_apply_code_pair(&mut fun, &element);
     generate_write(&mut fun, &element);
+    generate_remove_pointers_to(&mut fun, &element);
+    generate_collect_pointers(&mut fun, &element);
     fun.push_str("}\n");
 
     let mut file = File::create(generated_dir.join("entities.rs"))
@@ -89,9 +100,9 @@ fn generate_base_entity(fun: &mut String, element: &Element) {
             }
             "Pointer" => {
                 let typ = if allow_multiples(&c) {
-                    "Vec<u32>"
+                    "Vec<Handle>"
                 } else {
-                    "u32"
+                    "Handle"
                 };
                 fun.push_str("    #[doc(hidden)]\n");
                 fun.push_str(&format!(
@@ -130,7 +141,7 @@ fn generate_base_entity(fun: &mut String, element: &Element) {
             }
             "Pointer" => {
                 fun.push_str(&format!(
-                    "            __{name}_handle: 0,\n",
+                    "            __{name}_handle: Handle::empty(),\n",
                     name = name(c)
                 ));
             }
@@ -152,9 +163,13 @@ fn generate_base_entity(fun: &mut String, element: &Element) {
             fun.push_str(&*get_methods_for_pointer_access(p));
         }
     }
+    fun.push_str(&get_method_for_pointer_removal(&pointer_fields(entity)));
+    fun.push_str(&get_method_for_pointer_collection(&forward_pointer_fields(
+        entity,
+    )));
 
     ////////////////////////////////////////////////////// apply_individual_pair
-    fun.push_str("    pub(crate) fn apply_individual_pair<I>(&mut self, pair: &CodePair, iter: &mut CodePairPutBack<I>) -> DxfResult<()>\n");
+    fun.push_str("    pub(crate) fn apply_individual_pair<I>(&mut self, pair: &CodePair, iter: &mut CodePairPutBack<I>) -> DxfResult<bool>\n");
     fun.push_str("        where I: Read {\n");
     fun.push_str("\n");
     fun.push_str("        match pair.code {\n");
@@ -193,9 +208,9 @@ fn generate_base_entity(fun: &mut String, element: &Element) {
     fun.push_str("                let x = XData::read_item(pair.assert_string()?, iter)?;\n");
     fun.push_str("                self.x_data.push(x);\n");
     fun.push_str("            },\n");
-    fun.push_str("            _ => (), // unknown code, just ignore\n");
+    fun.push_str("            _ => return Ok(false), // unknown code\n");
     fun.push_str("        }\n");
-    fun.push_str("        Ok(())\n");
+    fun.push_str("        Ok(true)\n");
     fun.push_str("    }\n");
 
     ////////////////////////////////////////////////////////////////////// write
@@ -227,6 +242,7 @@ fn generate_entity_types(fun: &mut String, element: &Element) {
         }
     }
 
+    fun.push_str("    Unknown(UnknownEntity),\n");
     fun.push_str("}\n");
     fun.push_str("\n");
 
@@ -266,9 +282,9 @@ fn generate_entity_types(fun: &mut String, element: &Element) {
                     }
                     "Pointer" => {
                         let typ = if allow_multiples(&f) {
-                            "Vec<u32>"
+                            "Vec<Handle>"
                         } else {
-                            "u32"
+                            "Handle"
                         };
                         fun.push_str("    #[doc(hidden)]\n");
                         fun.push_str(&format!(
@@ -290,7 +306,15 @@ fn generate_entity_types(fun: &mut String, element: &Element) {
             fun.push_str(&format!("    fn default() -> {typ} {{\n", typ = name(c)));
             fun.push_str(&format!("        {typ} {{\n", typ = name(c)));
             if base_class(&c) == "DimensionBase" {
-                fun.push_str("            dimension_base: Default::default(),\n");
+                let dimension_type = attr(c, "DimensionType");
+                if dimension_type.is_empty() {
+                    fun.push_str("            dimension_base: Default::default(),\n");
+                } else {
+                    fun.push_str(&format!(
+                        "            dimension_base: DimensionBase {{ dimension_type: DimensionType::{typ}, ..Default::default() }},\n",
+                        typ = dimension_type
+                    ));
+                }
             }
             for f in &c.children {
                 match &*f.name {
@@ -302,7 +326,11 @@ fn generate_entity_types(fun: &mut String, element: &Element) {
                         ));
                     }
                     "Pointer" => {
-                        let val = if allow_multiples(&f) { "vec![]" } else { "0" };
+                        let val = if allow_multiples(f) {
+                            "vec![]"
+                        } else {
+                            "Handle::empty()"
+                        };
                         fun.push_str(&format!(
                             "            __{name}_handle: {val},\n",
                             name = name(f),
@@ -389,6 +417,14 @@ fn generate_implementation(fun: &mut String, element: &Element) {
             implementation.push_str(&*get_methods_for_pointer_access(field));
         }
     }
+    let pointers = pointer_fields(element);
+    if !pointers.is_empty() {
+        implementation.push_str(&get_method_for_pointer_removal(&pointers));
+    }
+    let forward_pointers = forward_pointer_fields(element);
+    if !forward_pointers.is_empty() {
+        implementation.push_str(&get_method_for_pointer_collection(&forward_pointers));
+    }
 
     if !implementation.is_empty() {
         fun.push_str(&format!("impl {typ} {{\n", typ = name(&element)));
@@ -424,6 +460,8 @@ fn generate_is_supported_on_version(fun: &mut String, element: &Element) {
             ));
         }
     }
+    // unsupported entities are preserved verbatim, but custom entity types don't exist before R13
+    fun.push_str("            &EntityType::Unknown(_) => { version >= AcadVersion::R13 },\n");
     fun.push_str("        }\n");
     fun.push_str("    }\n");
 }
@@ -463,6 +501,7 @@ fn generate_type_string(fun: &mut String, element: &Element) {
             ));
         }
     }
+    fun.push_str("            &EntityType::Unknown(ref ent) => { &ent.type_string },\n");
     fun.push_str("        }\n");
     fun.push_str("    }\n");
 }
@@ -545,6 +584,7 @@ fn generate_try_apply_code_pair(fun: &mut String, element: &Element) {
             }
         }
     }
+    fun.push_str("            &mut EntityType::Unknown(_) => { panic!(\"this case should have been covered in a custom reader\"); },\n");
 
     fun.push_str("        }\n");
     fun.push_str("        Ok(true)\n");
@@ -579,12 +619,48 @@ fn generate_write(fun: &mut String, element: &Element) {
             }
         }
     }
+    fun.push_str("            &EntityType::Unknown(_) => { panic!(\"this case should have been covered in a custom writer\"); },\n");
     fun.push_str("        }\n");
     fun.push_str("\n");
     fun.push_str("        Ok(())\n");
     fun.push_str("    }\n");
 }
 
+fn generate_remove_pointers_to(fun: &mut String, element: &Element) {
+    fun.push_str("    pub(crate) fn remove_pointers_to(&mut self, handle: Handle) -> bool {\n");
+    fun.push_str("        match self {\n");
+    for c in &element.children {
+        if name(c) != "Entity" && name(c) != "DimensionBase" && !pointer_fields(c).is_empty() {
+            fun.push_str(&format!(
+                "            EntityType::{typ}(ref mut v) => v.remove_pointers_to(handle),\n",
+                typ = name(c)
+            ));
+        }
+    }
+    fun.push_str("            _ => false,\n");
+    fun.push_str("        }\n");
+    fun.push_str("    }\n");
+}
+
+fn generate_collect_pointers(fun: &mut String, element: &Element) {
+    fun.push_str("    pub(crate) fn collect_pointers(&self, handles: &mut Vec<Handle>) {\n");
+    fun.push_str("        match self {\n");
+    for c in &element.children {
+        if name(c) != "Entity"
+            && name(c) != "DimensionBase"
+            && !forward_pointer_fields(c).is_empty()
+        {
+            fun.push_str(&format!(
+                "            EntityType::{typ}(ref v) => v.collect_pointers(handles),\n",
+                typ = name(c)
+            ));
+        }
+    }
+    fun.push_str("            _ => (),\n");
+    fun.push_str("        }\n");
+    fun.push_str("    }\n");
+}
+
 fn get_field_with_name<'a>(entity: &'a Element, field_name: &String) -> &'a Element {
     for field in &entity.children {
         if name(&field) == *field_name {
@@ -29,11 +29,13 @@ use crate::{
     DxfResult,
     ExtensionGroup,
     GeoMeshPoint,
+    Handle,
     MLineStyleElement,
     Point,
     SectionTypeSettings,
     TableCellStyle,
     TransformationMatrix,
+    UnknownObject,
     Vector,
     XData,
 };
@@ -62,6 +64,8 @@ use self::chrono::{DateTime, Local};
     generate_type_string(&mut fun, &element);
     generate_try_apply_code_pair(&mut fun, &element);
     generate_write(&mut fun, &element);
+    generate_remove_pointers_to(&mut fun, &element);
+    generate_collect_pointers(&mut fun, &element);
     fun.push_str("}\n");
 
     let mut file = File::create(generated_dir.join("objects.rs")).ok().unwrap();
@@ -95,9 +99,9 @@ fn generate_base_object(fun: &mut String, element: &Element) {
             }
             "Pointer" => {
                 let typ = if allow_multiples(&c) {
-                    "Vec<u32>"
+                    "Vec<Handle>"
                 } else {
-                    "u32"
+                    "Handle"
                 };
                 fun.push_str("    #[doc(hidden)]\n");
                 fun.push_str(&format!(
@@ -136,7 +140,7 @@ fn generate_base_object(fun: &mut String, element: &Element) {
             }
             "Pointer" => {
                 fun.push_str(&format!(
-                    "            __{name}_handle: 0,\n",
+                    "            __{name}_handle: Handle::empty(),\n",
                     name = name(c)
                 ));
             }
@@ -158,6 +162,7 @@ fn generate_base_object(fun: &mut String, element: &Element) {
             fun.push_str(&*get_methods_for_pointer_access(p));
         }
     }
+    fun.push_str(&get_method_for_pointer_removal(&pointer_fields(object)));
 
     ////////////////////////////////////////////////////// apply_individual_pair
     fun.push_str("    pub(crate) fn apply_individual_pair<I>(&mut self, pair: &CodePair, iter: &mut CodePairPutBack<I>) -> DxfResult<bool>\n");
@@ -233,6 +238,7 @@ fn generate_object_types(fun: &mut String, element: &Element) {
         }
     }
 
+    fun.push_str("    Unknown(UnknownObject),\n");
     fun.push_str("}\n");
     fun.push_str("\n");
 
@@ -269,9 +275,9 @@ fn generate_object_types(fun: &mut String, element: &Element) {
                     }
                     "Pointer" => {
                         let typ = if allow_multiples(&f) {
-                            "Vec<u32>"
+                            "Vec<Handle>"
                         } else {
-                            "u32"
+                            "Handle"
                         };
                         fun.push_str("    #[doc(hidden)]\n");
                         fun.push_str(&format!(
@@ -302,7 +308,11 @@ fn generate_object_types(fun: &mut String, element: &Element) {
                         ));
                     }
                     "Pointer" => {
-                        let val = if allow_multiples(&f) { "vec![]" } else { "0" };
+                        let val = if allow_multiples(f) {
+                            "vec![]"
+                        } else {
+                            "Handle::empty()"
+                        };
                         fun.push_str(&format!(
                             "            __{name}_handle: {val},\n",
                             name = name(f),
@@ -374,6 +384,14 @@ fn generate_implementation(fun: &mut String, element: &Element) {
             implementation.push_str(&*get_methods_for_pointer_access(field));
         }
     }
+    let pointers = pointer_fields(element);
+    if !pointers.is_empty() {
+        implementation.push_str(&get_method_for_pointer_removal(&pointers));
+    }
+    let forward_pointers = forward_pointer_fields(element);
+    if !forward_pointers.is_empty() {
+        implementation.push_str(&get_method_for_pointer_collection(&forward_pointers));
+    }
 
     if !implementation.is_empty() {
         fun.push_str(&format!("impl {typ} {{\n", typ = name(&element)));
@@ -409,6 +427,8 @@ fn generate_is_supported_on_version(fun: &mut String, element: &Element) {
             ));
         }
     }
+    // unsupported objects are preserved verbatim, but there are no objects before R13
+    fun.push_str("            ObjectType::Unknown(_) => { version >= AcadVersion::R13 },\n");
     fun.push_str("        }\n");
     fun.push_str("    }\n");
 }
@@ -444,6 +464,7 @@ fn generate_type_string(fun: &mut String, element: &Element) {
             ));
         }
     }
+    fun.push_str("            ObjectType::Unknown(ref obj) => { &obj.type_string },\n");
     fun.push_str("        }\n");
     fun.push_str("    }\n");
 }
@@ -538,6 +559,7 @@ fn generate_try_apply_code_pair(fun: &mut String, element: &Element) {
             }
         }
     }
+    fun.push_str("            ObjectType::Unknown(_) => { panic!(\"this case should have been covered in a custom reader\"); },\n");
 
     fun.push_str("        }\n");
     fun.push_str("        Ok(true)\n");
@@ -584,6 +606,7 @@ fn generate_write(fun: &mut String, element: &Element) {
             }
         }
     }
+    fun.push_str("            ObjectType::Unknown(_) => { panic!(\"this case should have been covered in a custom writer\"); },\n");
     fun.push_str("        }\n");
     fun.push_str("\n");
     fun.push_str("        Ok(())\n");
@@ -594,6 +617,38 @@ fn generate_write(fun: &mut String, element: &Element) {
     }
 }
 
+fn generate_remove_pointers_to(fun: &mut String, element: &Element) {
+    fun.push_str("    pub(crate) fn remove_pointers_to(&mut self, handle: Handle) -> bool {\n");
+    fun.push_str("        match self {\n");
+    for c in &element.children {
+        if name(c) != "Object" && !pointer_fields(c).is_empty() {
+            fun.push_str(&format!(
+                "            ObjectType::{typ}(ref mut v) => v.remove_pointers_to(handle),\n",
+                typ = name(c)
+            ));
+        }
+    }
+    fun.push_str("            _ => false,\n");
+    fun.push_str("        }\n");
+    fun.push_str("    }\n");
+}
+
+fn generate_collect_pointers(fun: &mut String, element: &Element) {
+    fun.push_str("    pub(crate) fn collect_pointers(&self, handles: &mut Vec<Handle>) {\n");
+    fun.push_str("        match self {\n");
+    for c in &element.children {
+        if name(c) != "Object" && !forward_pointer_fields(c).is_empty() {
+            fun.push_str(&format!(
+                "            ObjectType::{typ}(ref v) => v.collect_pointers(handles),\n",
+                typ = name(c)
+            ));
+        }
+    }
+    fun.push_str("            _ => (),\n");
+    fun.push_str("        }\n");
+    fun.push_str("    }\n");
+}
+
 fn get_field_with_name<'a>(object: &'a Element, field_name: &String) -> &'a Element {
     for field in &object.children {
         if name(&field) == *field_name {
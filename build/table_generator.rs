@@ -29,6 +29,7 @@ use crate::{
     DxfError,
     DxfResult,
     ExtensionGroup,
+    Handle,
     LineWeight,
     Point,
     Vector,
@@ -61,9 +62,9 @@ fn generate_table_items(fun: &mut String, element: &Element) {
         fun.push_str("#[cfg_attr(feature = \"serialize\", derive(Serialize, Deserialize))]\n");
         fun.push_str(&format!("pub struct {name} {{\n", name = name(&table_item)));
         fun.push_str("    pub name: String,\n");
-        fun.push_str("    pub handle: u32,\n");
+        fun.push_str("    pub handle: Handle,\n");
         fun.push_str("    #[doc(hidden)]\n");
-        fun.push_str("    pub __owner_handle: u32,\n");
+        fun.push_str("    pub __owner_handle: Handle,\n");
         fun.push_str("    pub extension_data_groups: Vec<ExtensionGroup>,\n");
         fun.push_str("    pub x_data: Vec<XData>,\n");
         for field in &table_item.children {
@@ -75,7 +76,7 @@ fn generate_table_items(fun: &mut String, element: &Element) {
             if !seen_fields.contains(&name) {
                 seen_fields.insert(name.clone());
                 let mut typ = if field.name == "Pointer" {
-                    String::from("u32")
+                    String::from("Handle")
                 } else {
                     attr(&field, "Type")
                 };
@@ -100,8 +101,8 @@ fn generate_table_items(fun: &mut String, element: &Element) {
         fun.push_str("    fn default() -> Self {\n");
         fun.push_str(&format!("        {name} {{\n", name = name(&table_item)));
         fun.push_str("            name: String::new(),\n");
-        fun.push_str("            handle: 0,\n");
-        fun.push_str("            __owner_handle: 0,\n");
+        fun.push_str("            handle: Handle::empty(),\n");
+        fun.push_str("            __owner_handle: Handle::empty(),\n");
         fun.push_str("            extension_data_groups: vec![],\n");
         fun.push_str("            x_data: vec![],\n");
         for field in &table_item.children {
@@ -114,7 +115,7 @@ fn generate_table_items(fun: &mut String, element: &Element) {
                 seen_fields.insert(name.clone());
                 let default_value = match (&*field.name, allow_multiples(&field)) {
                     ("Pointer", true) => String::from("vec![]"),
-                    ("Pointer", false) => String::from("0"),
+                    ("Pointer", false) => String::from("Handle::empty()"),
                     (_, _) => attr(&field, "DefaultValue"),
                 };
                 fun.push_str(&format!(
@@ -139,6 +140,34 @@ fn generate_table_items(fun: &mut String, element: &Element) {
         fun.push_str("    pub fn set_owner<'a>(&mut self, item: &'a mut DrawingItemMut, drawing: &'a mut Drawing) {\n");
         fun.push_str("        self.__owner_handle = drawing.assign_and_get_handle(item);\n");
         fun.push_str("    }\n");
+        let mut pointers = vec![(String::from("__owner_handle"), false)];
+        pointers.extend(pointer_fields(table_item));
+        fun.push_str(&get_method_for_pointer_removal(&pointers));
+        fun.push_str(
+            "    pub(crate) fn remove_references_to(&mut self, handle: Handle) -> bool {\n",
+        );
+        fun.push_str("        let mut removed = self.remove_pointers_to(handle);\n");
+        fun.push_str("        for group in self.extension_data_groups.iter_mut() {\n");
+        fun.push_str("            removed |= group.remove_handle(handle);\n");
+        fun.push_str("        }\n");
+        fun.push('\n');
+        fun.push_str("        removed\n");
+        fun.push_str("    }\n");
+        let forward_pointers = forward_pointer_fields(table_item);
+        if !forward_pointers.is_empty() {
+            fun.push_str(&get_method_for_pointer_collection(&forward_pointers));
+        }
+        fun.push_str("    pub(crate) fn collect_references(&self, handles: &mut Vec<Handle>) {\n");
+        if !forward_pointers.is_empty() {
+            fun.push_str("        self.collect_pointers(handles);\n");
+        }
+        fun.push_str("        for group in &self.extension_data_groups {\n");
+        fun.push_str("            group.collect_handles(handles);\n");
+        fun.push_str("        }\n");
+        fun.push_str("        for x_data in &self.x_data {\n");
+        fun.push_str("            x_data.collect_handles(handles);\n");
+        fun.push_str("        }\n");
+        fun.push_str("    }\n");
         fun.push_str("}\n");
         fun.push_str("\n");
     }
@@ -292,7 +321,7 @@ fn generate_table_reader(fun: &mut String, element: &Element) {
         fun.push_str("                        }\n");
         fun.push_str("                    }\n");
         fun.push_str("\n");
-        fun.push_str("                    if item.handle == 0 {\n");
+        fun.push_str("                    if item.handle.is_empty() {\n");
         fun.push_str(&format!(
             "                        drawing.add_{item_type}(item);\n",
             item_type = item_type
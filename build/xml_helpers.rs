@@ -265,6 +265,89 @@ pub fn get_methods_for_pointer_access(pointer: &Element) -> String {
     fun
 }
 
+pub fn pointer_fields(element: &Element) -> Vec<(String, bool)> {
+    let mut fields: Vec<(String, bool)> = vec![];
+    for pointer in &element.children {
+        if pointer.name == "Pointer" {
+            let field = (
+                format!("__{}_handle", name(pointer)),
+                allow_multiples(pointer),
+            );
+            if !fields.contains(&field) {
+                fields.push(field);
+            }
+        }
+    }
+
+    fields
+}
+
+pub fn get_method_for_pointer_removal(fields: &[(String, bool)]) -> String {
+    let mut fun = String::new();
+    fun.push_str("    pub(crate) fn remove_pointers_to(&mut self, handle: Handle) -> bool {\n");
+    fun.push_str("        let mut removed = false;\n");
+    for (field, allow_multiples) in fields {
+        if *allow_multiples {
+            fun.push_str(&format!(
+                "        let count = self.{field}.len();\n",
+                field = field
+            ));
+            fun.push_str(&format!(
+                "        self.{field}.retain(|&h| h != handle);\n",
+                field = field
+            ));
+            fun.push_str(&format!(
+                "        removed |= self.{field}.len() != count;\n",
+                field = field
+            ));
+        } else {
+            fun.push_str(&format!(
+                "        if self.{field} == handle {{\n",
+                field = field
+            ));
+            fun.push_str(&format!(
+                "            self.{field} = Handle::empty();\n",
+                field = field
+            ));
+            fun.push_str("            removed = true;\n");
+            fun.push_str("        }\n");
+        }
+    }
+
+    fun.push_str("        removed\n");
+    fun.push_str("    }\n");
+    fun
+}
+
+/// The pointers that refer to other items, i.e., everything but the back-pointer to the owner.
+pub fn forward_pointer_fields(element: &Element) -> Vec<(String, bool)> {
+    pointer_fields(element)
+        .into_iter()
+        .filter(|(field, _)| field != "__owner_handle")
+        .collect()
+}
+
+pub fn get_method_for_pointer_collection(fields: &[(String, bool)]) -> String {
+    let mut fun = String::new();
+    fun.push_str("    pub(crate) fn collect_pointers(&self, handles: &mut Vec<Handle>) {\n");
+    for (field, allow_multiples) in fields {
+        if *allow_multiples {
+            fun.push_str(&format!(
+                "        handles.extend(self.{field}.iter().cloned());\n",
+                field = field
+            ));
+        } else {
+            fun.push_str(&format!(
+                "        handles.push(self.{field});\n",
+                field = field
+            ));
+        }
+    }
+
+    fun.push_str("    }\n");
+    fun
+}
+
 pub fn min_version(element: &Element) -> String {
     attr(&element, "MinVersion")
 }
@@ -0,0 +1,308 @@
+use crate::{
+    CodePair, Drawing, DrawingItem, DxfError, DxfResult, Handle, SectionPosition, UnknownSection,
+};
+
+use crate::entities::*;
+use crate::helper_functions::*;
+
+pub(crate) const ACDSDATA_SECTION_NAME: &str = "ACDSDATA";
+
+const ID_PROPERTY_NAME: &str = "AcDbDs::ID";
+const ASM_DATA_PROPERTY_NAME: &str = "ASM_Data";
+
+/// Represents the contents of the `ACDSDATA` section, which holds the ACIS/SAB data of `Solid3D`
+/// and similar entities in R2013 and later drawings.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct AcdsData {
+    /// The code pairs preceding the first schema, e.g., the section version.
+    pub header_pairs: Vec<CodePair>,
+    pub schemas: Vec<AcdsSchema>,
+    pub records: Vec<AcdsRecord>,
+}
+
+/// Represents a schema in the `ACDSDATA` section.  Schemas describe the layout of `AcdsRecord`s.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct AcdsSchema {
+    pub index: i32,
+    pub name: String,
+    /// The raw code pairs of the schema's property definitions.
+    pub code_pairs: Vec<CodePair>,
+}
+
+/// Represents a record in the `ACDSDATA` section.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct AcdsRecord {
+    /// The index of the `AcdsSchema` describing this record.
+    pub schema_index: i32,
+    /// The handle of the entity that owns this record, usually a `Solid3D`.
+    pub owner_handle: Handle,
+    /// The binary SAB data of the owning entity.
+    pub sab_data: Vec<u8>,
+    /// The raw code pairs of any other properties.
+    pub code_pairs: Vec<CodePair>,
+}
+
+// public implementation
+impl AcdsData {
+    /// Parses the contents of an `ACDSDATA` section.
+    pub fn from_section(section: &UnknownSection) -> DxfResult<AcdsData> {
+        let mut data = AcdsData::default();
+        let mut pairs = section.code_pairs.iter().peekable();
+        while let Some(pair) = pairs.next() {
+            if pair.code != 0 {
+                data.header_pairs.push(pair.clone());
+                continue;
+            }
+
+            // collect everything up to the next 0/<item-type>
+            let mut item_pairs = vec![];
+            while let Some(next) = pairs.next_if(|p| p.code != 0) {
+                item_pairs.push(next.clone());
+            }
+
+            match &*pair.assert_string()? {
+                "ACDSSCHEMA" => data.schemas.push(AcdsSchema::from_pairs(item_pairs)?),
+                "ACDSRECORD" => data.records.push(AcdsRecord::from_pairs(item_pairs)?),
+                _ => {
+                    return Err(DxfError::UnexpectedCodePair(
+                        pair.clone(),
+                        String::from("expected 0/ACDSSCHEMA or 0/ACDSRECORD"),
+                    ))
+                }
+            }
+        }
+
+        Ok(data)
+    }
+    /// Creates an `ACDSDATA` section from this data, to be written after the `OBJECTS` section.
+    pub fn to_section(&self) -> UnknownSection {
+        let mut section = UnknownSection::new(ACDSDATA_SECTION_NAME);
+        section.position = SectionPosition::After(String::from("OBJECTS"));
+        section.code_pairs.extend(self.header_pairs.iter().cloned());
+        for schema in &self.schemas {
+            schema.add_code_pairs(&mut section.code_pairs);
+        }
+        for record in &self.records {
+            record.add_code_pairs(&mut section.code_pairs);
+        }
+
+        section
+    }
+    /// Returns the record owned by the entity with the specified handle, if any.
+    pub fn record_for_owner(&self, owner_handle: Handle) -> Option<&AcdsRecord> {
+        self.records.iter().find(|r| r.owner_handle == owner_handle)
+    }
+}
+
+impl AcdsSchema {
+    fn from_pairs(pairs: Vec<CodePair>) -> DxfResult<AcdsSchema> {
+        let mut schema = AcdsSchema::default();
+        let mut pairs = pairs.into_iter();
+        // the index and name come first; everything after is kept as-is
+        for pair in pairs.by_ref() {
+            match pair.code {
+                90 => schema.index = pair.assert_i32()?,
+                1 => {
+                    schema.name = pair.assert_string()?;
+                    break;
+                }
+                _ => schema.code_pairs.push(pair),
+            }
+        }
+
+        schema.code_pairs.extend(pairs);
+        Ok(schema)
+    }
+    fn add_code_pairs(&self, pairs: &mut Vec<CodePair>) {
+        pairs.push(CodePair::new_str(0, "ACDSSCHEMA"));
+        pairs.push(CodePair::new_i32(90, self.index));
+        pairs.push(CodePair::new_string(1, &self.name));
+        pairs.extend(self.code_pairs.iter().cloned());
+    }
+}
+
+impl AcdsRecord {
+    /// Returns the `Solid3D` (or other entity) that owns this record.
+    pub fn owner<'a>(&self, drawing: &'a Drawing) -> Option<&'a Entity> {
+        match drawing.get_item_by_handle(self.owner_handle) {
+            Some(DrawingItem::Entity(ent)) => Some(ent),
+            _ => None,
+        }
+    }
+    fn from_pairs(pairs: Vec<CodePair>) -> DxfResult<AcdsRecord> {
+        let mut record = AcdsRecord::default();
+        let mut property_name = String::new();
+        for pair in pairs {
+            if pair.code == 2 {
+                property_name = pair.assert_string()?;
+                if property_name == ID_PROPERTY_NAME || property_name == ASM_DATA_PROPERTY_NAME {
+                    continue; // written with the typed values
+                }
+            }
+
+            match (&*property_name, pair.code) {
+                ("", 90) => record.schema_index = pair.assert_i32()?,
+                (ID_PROPERTY_NAME, 280) => (), // property type
+                (ID_PROPERTY_NAME, 320) => record.owner_handle = pair.as_handle()?,
+                (ASM_DATA_PROPERTY_NAME, 280) => (), // property type
+                (ASM_DATA_PROPERTY_NAME, 94) => (), // data length; we just read as much as we're given
+                (ASM_DATA_PROPERTY_NAME, 310) => {
+                    parse_hex_string(&pair.assert_string()?, &mut record.sab_data, pair.offset)?
+                }
+                _ => record.code_pairs.push(pair),
+            }
+        }
+
+        Ok(record)
+    }
+    fn add_code_pairs(&self, pairs: &mut Vec<CodePair>) {
+        pairs.push(CodePair::new_str(0, "ACDSRECORD"));
+        pairs.push(CodePair::new_i32(90, self.schema_index));
+        pairs.push(CodePair::new_str(2, ID_PROPERTY_NAME));
+        pairs.push(CodePair::new_i16(280, 10));
+        pairs.push(CodePair::new_string(320, &as_handle(self.owner_handle)));
+        if !self.sab_data.is_empty() {
+            pairs.push(CodePair::new_str(2, ASM_DATA_PROPERTY_NAME));
+            pairs.push(CodePair::new_i16(280, 15));
+            pairs.push(CodePair::new_i32(94, self.sab_data.len() as i32));
+            for chunk in self.sab_data.chunks(127) {
+                let mut line = String::new();
+                for b in chunk {
+                    line.push_str(&format!("{:02X}", b));
+                }
+                pairs.push(CodePair::new_string(310, &line));
+            }
+        }
+
+        pairs.extend(self.code_pairs.iter().cloned());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::*;
+    use crate::helper_functions::tests::*;
+    use crate::*;
+
+    fn drawing_with_acds_data() -> Drawing {
+        parse_drawing(
+            [
+                "  0",
+                "SECTION",
+                "  2",
+                "ENTITIES",
+                "  0",
+                "3DSOLID",
+                "  5",
+                "A1",
+                "  0",
+                "ENDSEC",
+                "  0",
+                "SECTION",
+                "  2",
+                "ACDSDATA",
+                " 70",
+                "     2",
+                " 71",
+                "     6",
+                "  0",
+                "ACDSSCHEMA",
+                " 90",
+                "        0",
+                "  1",
+                "AcDb3DSolid_ASM_Data",
+                "  2",
+                "AcDbDs::ID",
+                "280",
+                "    10",
+                " 91",
+                "        8",
+                "  0",
+                "ACDSRECORD",
+                " 90",
+                "        0",
+                "  2",
+                "AcDbDs::ID",
+                "280",
+                "    10",
+                "320",
+                "A1",
+                "  2",
+                "ASM_Data",
+                "280",
+                "    15",
+                " 94",
+                "        4",
+                "310",
+                "01020304",
+                "  0",
+                "ENDSEC",
+                "  0",
+                "EOF",
+            ]
+            .join("\r\n")
+            .as_str(),
+        )
+    }
+
+    #[test]
+    fn read_acds_data() {
+        let drawing = drawing_with_acds_data();
+        let data = drawing.acds_data().unwrap().unwrap();
+        assert_eq!(
+            vec![CodePair::new_i16(70, 2), CodePair::new_i16(71, 6)],
+            data.header_pairs
+        );
+        assert_eq!(1, data.schemas.len());
+        assert_eq!(0, data.schemas[0].index);
+        assert_eq!("AcDb3DSolid_ASM_Data", data.schemas[0].name);
+        assert_eq!(3, data.schemas[0].code_pairs.len());
+        assert_eq!(1, data.records.len());
+        assert_eq!(Handle(0xA1), data.records[0].owner_handle);
+        assert_eq!(vec![0x01, 0x02, 0x03, 0x04], data.records[0].sab_data);
+        assert!(data.records[0].code_pairs.is_empty());
+    }
+
+    #[test]
+    fn acds_record_is_linked_to_solid() {
+        let drawing = drawing_with_acds_data();
+        let data = drawing.acds_data().unwrap().unwrap();
+        let record = data.record_for_owner(Handle(0xA1)).unwrap();
+        match record.owner(&drawing).unwrap().specific {
+            EntityType::Solid3D(_) => (),
+            _ => panic!("expected a 3D solid"),
+        }
+        assert!(data.record_for_owner(Handle(0xA2)).is_none());
+    }
+
+    #[test]
+    fn round_trip_acds_data() {
+        let mut drawing = drawing_with_acds_data();
+        let original_section = drawing.unknown_sections[0].clone();
+        let mut data = drawing.acds_data().unwrap().unwrap();
+        assert_eq!(original_section.code_pairs, data.to_section().code_pairs);
+
+        data.records[0].sab_data = vec![0xAB, 0xCD];
+        drawing.set_acds_data(&data);
+        assert_eq!(1, drawing.unknown_sections.len());
+        assert_contains(
+            &drawing,
+            [
+                "  2",
+                "ASM_Data",
+                "280",
+                "    15",
+                " 94",
+                "        2",
+                "310",
+                "ABCD",
+                "  0",
+                "ENDSEC",
+            ]
+            .join("\r\n"),
+        );
+    }
+}
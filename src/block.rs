@@ -2,7 +2,7 @@ use std::io::{Read, Write};
 
 use crate::{
     CodePair, CodePairValue, Drawing, DrawingItem, DrawingItemMut, DxfError, DxfResult,
-    ExtensionGroup, Point, XData,
+    ExtensionGroup, Handle, Point, XData,
 };
 
 use crate::code_pair_put_back::CodePairPutBack;
@@ -19,9 +19,9 @@ use crate::x_data;
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct Block {
     /// The block's handle.
-    pub handle: u32,
+    pub handle: Handle,
     #[doc(hidden)]
-    pub __owner_handle: u32,
+    pub __owner_handle: Handle,
     /// The name of the layer containing the block.
     pub layer: String,
     /// The name of the block.
@@ -103,8 +103,8 @@ impl Block {
 impl Default for Block {
     fn default() -> Self {
         Block {
-            handle: 0,
-            __owner_handle: 0,
+            handle: Handle::empty(),
+            __owner_handle: Handle::empty(),
             layer: String::from("0"),
             name: String::new(),
             flags: 0,
@@ -121,6 +121,37 @@ impl Default for Block {
 
 // internal visibility only
 impl Block {
+    /// Removes any pointers to the item with the specified handle from the block and its entities.
+    /// Returns `true` if anything was removed.
+    pub(crate) fn remove_references_to(&mut self, handle: Handle) -> bool {
+        let mut removed = false;
+        if self.__owner_handle == handle {
+            self.__owner_handle = Handle::empty();
+            removed = true;
+        }
+
+        for group in self.extension_data_groups.iter_mut() {
+            removed |= group.remove_handle(handle);
+        }
+
+        for ent in self.entities.iter_mut() {
+            removed |= ent.remove_references_to(handle);
+        }
+
+        removed
+    }
+    /// Collects the handles of the items this block or its entities refer to.
+    pub(crate) fn collect_references(&self, handles: &mut Vec<Handle>) {
+        for group in &self.extension_data_groups {
+            group.collect_handles(handles);
+        }
+        for x_data in &self.x_data {
+            x_data.collect_handles(handles);
+        }
+        for ent in &self.entities {
+            ent.collect_references(handles);
+        }
+    }
     pub(crate) fn read_block<I>(
         drawing: &mut Drawing,
         iter: &mut CodePairPutBack<I>,
@@ -156,7 +187,7 @@ impl Block {
                                 }
                             }
 
-                            if current.handle == 0 {
+                            if current.handle.is_empty() {
                                 drawing.add_block(current);
                             } else {
                                 drawing.add_block_no_handle_set(current);
@@ -166,7 +197,7 @@ impl Block {
                         CodePair { code: 0, .. } => {
                             // should be an entity
                             iter.put_back(Ok(pair));
-                            let mut iter = EntityIter { iter };
+                            let mut iter = EntityIter::new(iter);
                             iter.read_entities_into_vec(&mut current.entities)?;
                         }
                         _ => {
@@ -229,7 +260,7 @@ impl Block {
         }
 
         if version >= AcadVersion::R13 {
-            if self.__owner_handle != 0 {
+            if !self.__owner_handle.is_empty() {
                 writer
                     .write_code_pair(&CodePair::new_string(330, &as_handle(self.__owner_handle)))?;
             }
@@ -265,7 +296,7 @@ impl Block {
         }
 
         writer.write_code_pair(&CodePair::new_str(0, "ENDBLK"))?;
-        if write_handles && self.handle != 0 {
+        if write_handles && !self.handle.is_empty() {
             writer.write_code_pair(&CodePair::new_string(5, &as_handle(self.handle)))?;
         }
 
@@ -275,7 +306,7 @@ impl Block {
             }
         }
 
-        if version >= AcadVersion::R2000 && self.__owner_handle != 0 {
+        if version >= AcadVersion::R2000 && !self.__owner_handle.is_empty() {
             writer.write_code_pair(&CodePair::new_string(330, &as_handle(self.__owner_handle)))?;
         }
 
@@ -439,8 +470,12 @@ mod tests {
     #[test]
     fn read_block_with_unsupported_entity_first() {
         let block = read_single_block(vec!["0", "UNSUPPORTED_ENTITY", "0", "LINE"]);
-        assert_eq!(1, block.entities.len());
+        assert_eq!(2, block.entities.len());
         match block.entities[0].specific {
+            EntityType::Unknown(_) => (),
+            _ => panic!("expected an unknown entity"),
+        }
+        match block.entities[1].specific {
             EntityType::Line(_) => (),
             _ => panic!("expected a line"),
         }
@@ -449,22 +484,30 @@ mod tests {
     #[test]
     fn read_block_with_unsupported_entity_last() {
         let block = read_single_block(vec!["0", "LINE", "0", "UNSUPPORTED_ENTITY"]);
-        assert_eq!(1, block.entities.len());
+        assert_eq!(2, block.entities.len());
         match block.entities[0].specific {
             EntityType::Line(_) => (),
             _ => panic!("expected a line"),
         }
+        match block.entities[1].specific {
+            EntityType::Unknown(_) => (),
+            _ => panic!("expected an unknown entity"),
+        }
     }
 
     #[test]
     fn read_block_with_unsupported_entity_in_the_middle() {
         let block = read_single_block(vec!["0", "LINE", "0", "UNSUPPORTED_ENTITY", "0", "CIRCLE"]);
-        assert_eq!(2, block.entities.len());
+        assert_eq!(3, block.entities.len());
         match block.entities[0].specific {
             EntityType::Line(_) => (),
             _ => panic!("expected a line"),
         }
         match block.entities[1].specific {
+            EntityType::Unknown(_) => (),
+            _ => panic!("expected an unknown entity"),
+        }
+        match block.entities[2].specific {
             EntityType::Circle(_) => (),
             _ => panic!("expected a circle"),
         }
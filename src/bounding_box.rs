@@ -0,0 +1,854 @@
+use std::f64::consts::PI;
+
+use crate::entities::*;
+use crate::{Drawing, HatchBoundaryPathEdge, Point, TransformationMatrix, Vector};
+
+/// Represents an axis-aligned box in the World Coordinate System (WCS).
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct BoundingBox {
+    /// The corner with the smallest X, Y, and Z values.
+    pub min: Point,
+    /// The corner with the largest X, Y, and Z values.
+    pub max: Point,
+}
+
+impl BoundingBox {
+    /// Creates a new `BoundingBox` that contains both of the specified points.
+    pub fn new(a: &Point, b: &Point) -> Self {
+        let mut bounds = BoundingBox::from_point(a);
+        bounds.include_point(b);
+        bounds
+    }
+    /// Creates a new `BoundingBox` that only contains the specified point.
+    pub fn from_point(point: &Point) -> Self {
+        BoundingBox {
+            min: point.clone(),
+            max: point.clone(),
+        }
+    }
+    /// Creates the smallest `BoundingBox` that contains all of the specified points, or `None` if
+    /// there were no points.
+    pub fn from_points<'a, I>(points: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = &'a Point>,
+    {
+        let mut bounds = None;
+        for point in points {
+            include(&mut bounds, point);
+        }
+
+        bounds
+    }
+    /// Grows the box so that it contains `point`.
+    pub fn include_point(&mut self, point: &Point) {
+        self.min.x = self.min.x.min(point.x);
+        self.min.y = self.min.y.min(point.y);
+        self.min.z = self.min.z.min(point.z);
+        self.max.x = self.max.x.max(point.x);
+        self.max.y = self.max.y.max(point.y);
+        self.max.z = self.max.z.max(point.z);
+    }
+    /// Returns the smallest box that contains both this box and `other`.
+    pub fn union(&self, other: &BoundingBox) -> BoundingBox {
+        let mut bounds = self.clone();
+        bounds.include_point(&other.min);
+        bounds.include_point(&other.max);
+        bounds
+    }
+    /// Returns `true` if `point` is inside or on the boundary of the box.
+    pub fn contains(&self, point: &Point) -> bool {
+        self.min.x <= point.x
+            && point.x <= self.max.x
+            && self.min.y <= point.y
+            && point.y <= self.max.y
+            && self.min.z <= point.z
+            && point.z <= self.max.z
+    }
+    /// Returns the point in the middle of the box.
+    pub fn center(&self) -> Point {
+        self.min.midpoint(&self.max)
+    }
+    /// Returns the size of the box along each axis.
+    pub fn size(&self) -> Vector {
+        &self.max - &self.min
+    }
+    /// Returns the 8 corners of the box.
+    pub fn corners(&self) -> Vec<Point> {
+        let (min, max) = (&self.min, &self.max);
+        vec![
+            Point::new(min.x, min.y, min.z),
+            Point::new(max.x, min.y, min.z),
+            Point::new(min.x, max.y, min.z),
+            Point::new(max.x, max.y, min.z),
+            Point::new(min.x, min.y, max.z),
+            Point::new(max.x, min.y, max.z),
+            Point::new(min.x, max.y, max.z),
+            Point::new(max.x, max.y, max.z),
+        ]
+    }
+    /// Returns the axis-aligned box that contains this box after it has been transformed.
+    pub fn transform(&self, matrix: &TransformationMatrix) -> BoundingBox {
+        let corners = self
+            .corners()
+            .iter()
+            .map(|c| matrix.transform_point(c))
+            .collect::<Vec<_>>();
+        // there are always 8 corners
+        BoundingBox::from_points(&corners).unwrap()
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                                        Entity
+//------------------------------------------------------------------------------
+impl Entity {
+    /// Returns the extents of the entity in WCS, or `None` if the entity has no finite geometry,
+    /// e.g., `Ray`, `XLine`, ACIS solids, or an `Insert` of an empty block.
+    ///
+    /// Arcs, circles, and ellipses are measured by their true extents and splines by their
+    /// control and fit points.  `Insert` entities are measured through the referenced block, so
+    /// the containing `drawing` is required.  Text is approximated from its height and character
+    /// count, and thickness is ignored.
+    pub fn bounding_box(&self, drawing: &Drawing) -> Option<BoundingBox> {
+        let mut bounds = None;
+        self.add_bounds(drawing, &mut vec![], &mut bounds);
+        bounds
+    }
+    fn add_bounds<'a>(
+        &self,
+        drawing: &'a Drawing,
+        blocks_in_progress: &mut Vec<&'a str>,
+        bounds: &mut Option<BoundingBox>,
+    ) {
+        let ocs = self.ocs_to_wcs();
+        let add_ocs_point =
+            |bounds: &mut Option<BoundingBox>, p: &Point| include(bounds, &ocs.transform_point(p));
+        match self.specific {
+            EntityType::Arc(ref arc) => add_arc(
+                bounds,
+                &ocs,
+                &arc.center,
+                arc.radius,
+                arc.start_angle.to_radians(),
+                arc.end_angle.to_radians(),
+            ),
+            EntityType::Attribute(ref att) => add_text(
+                bounds,
+                &ocs,
+                &att.location,
+                att.text_height,
+                att.relative_x_scale_factor,
+                att.rotation,
+                &att.value,
+            ),
+            EntityType::AttributeDefinition(ref att) => add_text(
+                bounds,
+                &ocs,
+                &att.location,
+                att.text_height,
+                att.relative_x_scale_factor,
+                att.rotation,
+                &att.value,
+            ),
+            EntityType::Circle(ref circle) => {
+                add_arc(bounds, &ocs, &circle.center, circle.radius, 0.0, 2.0 * PI)
+            }
+            EntityType::Ellipse(ref el) => {
+                let minor_axis = el.normal.normalize().cross(&el.major_axis) * el.minor_axis_ratio;
+                add_elliptical_arc(
+                    bounds,
+                    &el.center,
+                    &el.major_axis,
+                    &minor_axis,
+                    el.start_parameter,
+                    el.end_parameter,
+                );
+            }
+            EntityType::Face3D(ref face) => {
+                for p in [
+                    &face.first_corner,
+                    &face.second_corner,
+                    &face.third_corner,
+                    &face.fourth_corner,
+                ] {
+                    include(bounds, p);
+                }
+            }
+            EntityType::Hatch(ref hatch) => {
+                let elevation = hatch.elevation_point.z;
+                for path in &hatch.boundary_paths {
+                    let vertices = path
+                        .polyline_vertices
+                        .iter()
+                        .map(|v| (v.x, v.y, v.bulge))
+                        .collect::<Vec<_>>();
+                    add_polyline(bounds, &ocs, &vertices, elevation, path.is_closed);
+                    for edge in &path.edges {
+                        add_hatch_edge(bounds, &ocs, edge, elevation);
+                    }
+                }
+            }
+            EntityType::Image(ref image) => add_image(
+                bounds,
+                &image.location,
+                &image.u_vector,
+                &image.v_vector,
+                &image.image_size,
+            ),
+            EntityType::Insert(ref ins) => {
+                if let Some(block) = drawing.find_block(&ins.name) {
+                    if !blocks_in_progress.contains(&&*block.name) {
+                        // the entities are placed before measuring so that rotated inserts aren't
+                        // measured by the rotated corners of the block's extents
+                        blocks_in_progress.push(&block.name);
+                        for matrix in ins.block_transformations(&block.base_point) {
+                            for ent in &block.entities {
+                                let mut ent = ent.clone();
+                                ent.transform(&matrix);
+                                ent.add_bounds(drawing, blocks_in_progress, bounds);
+                            }
+                        }
+
+                        blocks_in_progress.pop();
+                    }
+                }
+
+                for att in ins.attributes() {
+                    let att = Entity::new(EntityType::Attribute(att.clone()));
+                    att.add_bounds(drawing, blocks_in_progress, bounds);
+                }
+            }
+            EntityType::Leader(ref leader) => {
+                for p in &leader.vertices {
+                    include(bounds, p);
+                }
+            }
+            EntityType::Line(ref line) => {
+                include(bounds, &line.p1);
+                include(bounds, &line.p2);
+            }
+            EntityType::LwPolyline(ref poly) => {
+                let vertices = poly
+                    .vertices
+                    .iter()
+                    .map(|v| (v.x, v.y, v.bulge))
+                    .collect::<Vec<_>>();
+                add_polyline(
+                    bounds,
+                    &ocs,
+                    &vertices,
+                    self.common.elevation,
+                    poly.get_is_closed(),
+                );
+            }
+            EntityType::Mesh(ref mesh) => {
+                for p in &mesh.vertices {
+                    include(bounds, p);
+                }
+            }
+            EntityType::MLine(ref mline) => {
+                for p in &mline.vertices {
+                    include(bounds, p);
+                }
+            }
+            EntityType::ModelPoint(ref point) => include(bounds, &point.location),
+            EntityType::MText(ref mtext) => add_m_text(bounds, &ocs, mtext),
+            EntityType::Polyline(ref poly) => {
+                if poly.ocs_normal_is_used() {
+                    let vertices = poly
+                        .vertices()
+                        .map(|v| (v.location.x, v.location.y, v.bulge))
+                        .collect::<Vec<_>>();
+                    add_polyline(
+                        bounds,
+                        &ocs,
+                        &vertices,
+                        poly.location.z,
+                        poly.get_is_closed(),
+                    );
+                } else {
+                    // polyface mesh face records don't have a location
+                    for v in poly
+                        .vertices()
+                        .filter(|v| !v.get_is_polyface_mesh_vertex() || v.get_is_3d_polygon_mesh())
+                    {
+                        include(bounds, &v.location);
+                    }
+                }
+            }
+            EntityType::Shape(ref shape) => add_ocs_point(bounds, &shape.location),
+            EntityType::Solid(ref solid) => {
+                for p in [
+                    &solid.first_corner,
+                    &solid.second_corner,
+                    &solid.third_corner,
+                    &solid.fourth_corner,
+                ] {
+                    add_ocs_point(bounds, p);
+                }
+            }
+            EntityType::Spline(ref spline) => {
+                // a spline is contained by the convex hull of its control points
+                for p in spline.control_points.iter().chain(spline.fit_points.iter()) {
+                    include(bounds, p);
+                }
+            }
+            EntityType::Text(ref text) => add_text(
+                bounds,
+                &ocs,
+                &text.location,
+                text.text_height,
+                text.relative_x_scale_factor,
+                text.rotation,
+                &text.value,
+            ),
+            EntityType::Tolerance(ref tol) => include(bounds, &tol.insertion_point),
+            EntityType::Trace(ref trace) => {
+                for p in [
+                    &trace.first_corner,
+                    &trace.second_corner,
+                    &trace.third_corner,
+                    &trace.fourth_corner,
+                ] {
+                    add_ocs_point(bounds, p);
+                }
+            }
+            EntityType::Viewport(ref vp) => {
+                let half = Vector::new(vp.width / 2.0, vp.height / 2.0, 0.0);
+                include(bounds, &(&vp.center - &half));
+                include(bounds, &(&vp.center + &half));
+            }
+            EntityType::Wipeout(ref wipeout) => add_image(
+                bounds,
+                &wipeout.location,
+                &wipeout.u_vector,
+                &wipeout.v_vector,
+                &wipeout.image_size,
+            ),
+            _ => {
+                if let Some(base) = self.dimension_base() {
+                    // the dimension's block contains the fully rendered dimension in WCS
+                    match drawing.find_block(&base.block_name) {
+                        Some(block) if !block.entities.is_empty() => {
+                            for ent in &block.entities {
+                                ent.add_bounds(drawing, blocks_in_progress, bounds);
+                            }
+                        }
+                        _ => {
+                            include(bounds, &base.definition_point_1);
+                            include(bounds, &base.text_mid_point);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn include(bounds: &mut Option<BoundingBox>, point: &Point) {
+    match bounds {
+        Some(ref mut b) => b.include_point(point),
+        None => *bounds = Some(BoundingBox::from_point(point)),
+    }
+}
+
+// includes the extents of the arc with the specified OCS center; the angles are in radians
+fn add_arc(
+    bounds: &mut Option<BoundingBox>,
+    ocs: &TransformationMatrix,
+    center: &Point,
+    radius: f64,
+    start: f64,
+    end: f64,
+) {
+    add_elliptical_arc(
+        bounds,
+        &ocs.transform_point(center),
+        &(ocs.transform_vector(&Vector::x_axis()) * radius),
+        &(ocs.transform_vector(&Vector::y_axis()) * radius),
+        start,
+        end,
+    );
+}
+
+// includes the extents of the curve `center + cos(t) * u + sin(t) * v` for `t` from `start` to
+// `end` radians, counter-clockwise
+fn add_elliptical_arc(
+    bounds: &mut Option<BoundingBox>,
+    center: &Point,
+    u: &Vector,
+    v: &Vector,
+    start: f64,
+    end: f64,
+) {
+    let mut sweep = (end - start).rem_euclid(2.0 * PI);
+    if sweep == 0.0 {
+        sweep = 2.0 * PI;
+    }
+
+    let point_at = |t: f64| center + u * t.cos() + v * t.sin();
+    include(bounds, &point_at(start));
+    include(bounds, &point_at(start + sweep));
+
+    // each coordinate is extreme where its derivative, `-sin(t) * u + cos(t) * v`, is 0
+    for (u, v) in [(u.x, v.x), (u.y, v.y), (u.z, v.z)] {
+        if u == 0.0 && v == 0.0 {
+            continue;
+        }
+
+        let t = v.atan2(u);
+        for t in [t, t + PI] {
+            if (t - start).rem_euclid(2.0 * PI) <= sweep {
+                include(bounds, &point_at(t));
+            }
+        }
+    }
+}
+
+// includes the extents of a polyline with (x, y, bulge) vertices in OCS
+fn add_polyline(
+    bounds: &mut Option<BoundingBox>,
+    ocs: &TransformationMatrix,
+    vertices: &[(f64, f64, f64)],
+    elevation: f64,
+    is_closed: bool,
+) {
+    for (i, &(x, y, bulge)) in vertices.iter().enumerate() {
+        let start = Point::new(x, y, elevation);
+        include(bounds, &ocs.transform_point(&start));
+        let next = if i + 1 < vertices.len() {
+            vertices[i + 1]
+        } else if is_closed && vertices.len() > 1 {
+            vertices[0]
+        } else {
+            continue;
+        };
+
+        if bulge != 0.0 {
+            let end = Point::new(next.0, next.1, elevation);
+            add_bulge_arc(bounds, ocs, &start, &end, bulge);
+        }
+    }
+}
+
+// includes the extents of the arc between two OCS points; a positive bulge is counter-clockwise
+fn add_bulge_arc(
+    bounds: &mut Option<BoundingBox>,
+    ocs: &TransformationMatrix,
+    start: &Point,
+    end: &Point,
+    bulge: f64,
+) {
+    let chord = end - start;
+    let length = chord.length();
+    if length == 0.0 {
+        return;
+    }
+
+    let radius = length * (1.0 + bulge * bulge) / (4.0 * bulge.abs());
+    let offset = (1.0 - bulge * bulge) / (4.0 * bulge);
+    let center = start.midpoint(end) + Vector::new(-chord.y, chord.x, 0.0) * offset;
+    let start_angle = (start.y - center.y).atan2(start.x - center.x);
+    let end_angle = (end.y - center.y).atan2(end.x - center.x);
+    let (start_angle, end_angle) = if bulge > 0.0 {
+        (start_angle, end_angle)
+    } else {
+        (end_angle, start_angle)
+    };
+    add_arc(bounds, ocs, &center, radius, start_angle, end_angle);
+}
+
+fn add_hatch_edge(
+    bounds: &mut Option<BoundingBox>,
+    ocs: &TransformationMatrix,
+    edge: &HatchBoundaryPathEdge,
+    elevation: f64,
+) {
+    let at_elevation = |p: &Point| Point::new(p.x, p.y, elevation);
+    match edge {
+        HatchBoundaryPathEdge::Line { start, end } => {
+            include(bounds, &ocs.transform_point(&at_elevation(start)));
+            include(bounds, &ocs.transform_point(&at_elevation(end)));
+        }
+        HatchBoundaryPathEdge::Arc {
+            center,
+            radius,
+            start_angle,
+            end_angle,
+            is_counter_clockwise,
+        } => {
+            // clockwise arcs are conservatively measured as a full circle
+            let (start, end) = if *is_counter_clockwise {
+                (start_angle.to_radians(), end_angle.to_radians())
+            } else {
+                (0.0, 2.0 * PI)
+            };
+            add_arc(bounds, ocs, &at_elevation(center), *radius, start, end);
+        }
+        HatchBoundaryPathEdge::Ellipse {
+            center,
+            major_axis,
+            minor_axis_ratio,
+            start_angle,
+            end_angle,
+            is_counter_clockwise,
+        } => {
+            let (start, end) = if *is_counter_clockwise {
+                (start_angle.to_radians(), end_angle.to_radians())
+            } else {
+                (0.0, 2.0 * PI)
+            };
+            let major_axis = Vector::new(major_axis.x, major_axis.y, 0.0);
+            let minor_axis = Vector::z_axis().cross(&major_axis) * *minor_axis_ratio;
+            add_elliptical_arc(
+                bounds,
+                &ocs.transform_point(&at_elevation(center)),
+                &ocs.transform_vector(&major_axis),
+                &ocs.transform_vector(&minor_axis),
+                start,
+                end,
+            );
+        }
+        HatchBoundaryPathEdge::Spline {
+            control_points,
+            fit_points,
+            ..
+        } => {
+            for p in control_points.iter().chain(fit_points.iter()) {
+                include(bounds, &ocs.transform_point(&at_elevation(p)));
+            }
+        }
+    }
+}
+
+fn add_image(
+    bounds: &mut Option<BoundingBox>,
+    location: &Point,
+    u_vector: &Vector,
+    v_vector: &Vector,
+    size: &Vector,
+) {
+    let u = u_vector * size.x;
+    let v = v_vector * size.y;
+    include(bounds, location);
+    include(bounds, &(location + &u));
+    include(bounds, &(location + &v));
+    include(bounds, &(location + &u + &v));
+}
+
+// approximates single-line text by assuming every character is as wide as the text is tall
+fn add_text(
+    bounds: &mut Option<BoundingBox>,
+    ocs: &TransformationMatrix,
+    location: &Point,
+    height: f64,
+    width_factor: f64,
+    rotation: f64,
+    value: &str,
+) {
+    let width_factor = if width_factor == 0.0 {
+        1.0
+    } else {
+        width_factor
+    };
+    let width = value.chars().count() as f64 * height * width_factor;
+    let matrix = *ocs
+        * TransformationMatrix::translation(&location.to_vector())
+        * TransformationMatrix::rotation_about_z(rotation);
+    for (x, y) in [(0.0, 0.0), (width, 0.0), (0.0, height), (width, height)] {
+        include(bounds, &matrix.transform_point(&Point::new(x, y, 0.0)));
+    }
+}
+
+// approximates multi-line text from the reference rectangle or the longest line of text
+fn add_m_text(bounds: &mut Option<BoundingBox>, ocs: &TransformationMatrix, mtext: &MText) {
+    let height = mtext.initial_text_height;
+    let value = format!("{}{}", mtext.extended_text.concat(), mtext.text);
+    let lines = value.split("\\P").collect::<Vec<_>>();
+    let width = if mtext.reference_rectangle_width > 0.0 {
+        mtext.reference_rectangle_width
+    } else {
+        lines.iter().map(|l| l.chars().count()).max().unwrap_or(0) as f64 * height
+    };
+    let line_spacing = if mtext.line_spacing_factor > 0.0 {
+        mtext.line_spacing_factor
+    } else {
+        1.0
+    };
+    let total_height = height + (lines.len() - 1) as f64 * height * 5.0 / 3.0 * line_spacing;
+
+    let attachment = mtext.attachment_point as i32 - 1;
+    let left = -f64::from(attachment % 3) * width / 2.0;
+    let top = f64::from(attachment / 3) * total_height / 2.0;
+
+    let x_axis = if mtext.x_axis_direction.is_zero() {
+        ocs.transform_vector(&Vector::new(
+            mtext.rotation_angle.cos(),
+            mtext.rotation_angle.sin(),
+            0.0,
+        ))
+    } else {
+        mtext.x_axis_direction.normalize()
+    };
+    let y_axis = ocs
+        .transform_vector(&Vector::z_axis())
+        .cross(&x_axis)
+        .normalize();
+    for x in [left, left + width] {
+        for y in [top, top - total_height] {
+            include(
+                bounds,
+                &(&mtext.insertion_point + &x_axis * x + &y_axis * y),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::*;
+    use crate::*;
+
+    fn assert_bounds(min: Point, max: Point, actual: Option<BoundingBox>) {
+        let actual = actual.expect("expected a bounding box");
+        assert!(
+            min.distance_to(&actual.min) < 1e-9 && max.distance_to(&actual.max) < 1e-9,
+            "expected {:?} to {:?}, got {:?}",
+            min,
+            max,
+            actual
+        );
+    }
+
+    fn bounds_of(specific: EntityType) -> Option<BoundingBox> {
+        Entity::new(specific).bounding_box(&Drawing::new())
+    }
+
+    #[test]
+    fn line_bounds() {
+        assert_bounds(
+            Point::new(1.0, 2.0, 0.0),
+            Point::new(3.0, 4.0, 5.0),
+            bounds_of(EntityType::Line(Line::new(
+                Point::new(3.0, 2.0, 5.0),
+                Point::new(1.0, 4.0, 0.0),
+            ))),
+        );
+    }
+
+    #[test]
+    fn circle_bounds() {
+        assert_bounds(
+            Point::new(0.0, 1.0, 0.0),
+            Point::new(4.0, 5.0, 0.0),
+            bounds_of(EntityType::Circle(Circle::new(
+                Point::new(2.0, 3.0, 0.0),
+                2.0,
+            ))),
+        );
+    }
+
+    #[test]
+    fn arc_bounds_use_true_extents() {
+        // quarter arc from 45 to 135 degrees passes through the top of the circle
+        let bounds = bounds_of(EntityType::Arc(Arc::new(Point::origin(), 2.0, 45.0, 135.0)));
+        let c = 2.0 * 45.0f64.to_radians().cos();
+        assert_bounds(Point::new(-c, c, 0.0), Point::new(c, 2.0, 0.0), bounds);
+
+        // crossing 0 degrees
+        let bounds = bounds_of(EntityType::Arc(Arc::new(Point::origin(), 1.0, 270.0, 90.0)));
+        assert_bounds(
+            Point::new(0.0, -1.0, 0.0),
+            Point::new(1.0, 1.0, 0.0),
+            bounds,
+        );
+    }
+
+    #[test]
+    fn mirrored_arc_bounds() {
+        let mut arc = Arc::new(Point::new(1.0, 0.0, 0.0), 1.0, 0.0, 90.0);
+        arc.normal = Vector::new(0.0, 0.0, -1.0);
+        assert_bounds(
+            Point::new(-2.0, 0.0, 0.0),
+            Point::new(-1.0, 1.0, 0.0),
+            bounds_of(EntityType::Arc(arc)),
+        );
+    }
+
+    #[test]
+    fn ellipse_bounds() {
+        let ellipse = Ellipse {
+            center: Point::new(1.0, 1.0, 0.0),
+            major_axis: Vector::new(0.0, 2.0, 0.0),
+            minor_axis_ratio: 0.5,
+            start_parameter: 0.0,
+            end_parameter: 2.0 * std::f64::consts::PI,
+            ..Default::default()
+        };
+        assert_bounds(
+            Point::new(0.0, -1.0, 0.0),
+            Point::new(2.0, 3.0, 0.0),
+            bounds_of(EntityType::Ellipse(ellipse)),
+        );
+    }
+
+    #[test]
+    fn lw_polyline_bounds_include_bulges() {
+        let mut poly = LwPolyline::default();
+        poly.vertices.push(LwPolylineVertex {
+            x: 0.0,
+            y: 0.0,
+            bulge: 1.0,
+            ..Default::default()
+        });
+        poly.vertices.push(LwPolylineVertex {
+            x: 2.0,
+            y: 0.0,
+            ..Default::default()
+        });
+        // a counter-clockwise semicircle from (0, 0) to (2, 0) dips below the X axis
+        assert_bounds(
+            Point::new(0.0, -1.0, 0.0),
+            Point::new(2.0, 0.0, 0.0),
+            bounds_of(EntityType::LwPolyline(poly)),
+        );
+    }
+
+    #[test]
+    fn insert_bounds_use_block() {
+        let mut drawing = Drawing::new();
+        drawing.add_block(Block {
+            name: String::from("square"),
+            base_point: Point::new(1.0, 1.0, 0.0),
+            entities: vec![Entity::new(EntityType::Line(Line::new(
+                Point::new(1.0, 1.0, 0.0),
+                Point::new(2.0, 2.0, 0.0),
+            )))],
+            ..Default::default()
+        });
+        let insert = Entity::new(EntityType::Insert(Insert {
+            name: String::from("square"),
+            location: Point::new(10.0, 0.0, 0.0),
+            x_scale_factor: 2.0,
+            column_count: 2,
+            column_spacing: 5.0,
+            ..Default::default()
+        }));
+        assert_bounds(
+            Point::new(10.0, 0.0, 0.0),
+            Point::new(17.0, 1.0, 0.0),
+            insert.bounding_box(&drawing),
+        );
+
+        let missing = Entity::new(EntityType::Insert(Insert {
+            name: String::from("missing"),
+            ..Default::default()
+        }));
+        assert_eq!(None, missing.bounding_box(&drawing));
+    }
+
+    #[test]
+    fn rotated_insert_bounds_use_placed_entities() {
+        let mut drawing = Drawing::new();
+        drawing.add_block(Block {
+            name: String::from("circle"),
+            entities: vec![Entity::new(EntityType::Circle(Circle::new(
+                Point::origin(),
+                1.0,
+            )))],
+            ..Default::default()
+        });
+        let insert = Entity::new(EntityType::Insert(Insert {
+            name: String::from("circle"),
+            rotation: 45.0,
+            ..Default::default()
+        }));
+        assert_bounds(
+            Point::new(-1.0, -1.0, 0.0),
+            Point::new(1.0, 1.0, 0.0),
+            insert.bounding_box(&drawing),
+        );
+    }
+
+    #[test]
+    fn self_referencing_block_does_not_recurse_forever() {
+        let mut drawing = Drawing::new();
+        let insert = Entity::new(EntityType::Insert(Insert {
+            name: String::from("loop"),
+            ..Default::default()
+        }));
+        drawing.add_block(Block {
+            name: String::from("loop"),
+            entities: vec![
+                insert.clone(),
+                Entity::new(EntityType::ModelPoint(ModelPoint::new(Point::new(
+                    1.0, 2.0, 3.0,
+                )))),
+            ],
+            ..Default::default()
+        });
+        assert_bounds(
+            Point::new(1.0, 2.0, 3.0),
+            Point::new(1.0, 2.0, 3.0),
+            insert.bounding_box(&drawing),
+        );
+    }
+
+    #[test]
+    fn text_bounds_are_approximate() {
+        let text = Text {
+            location: Point::new(1.0, 1.0, 0.0),
+            text_height: 2.0,
+            value: String::from("abc"),
+            rotation: 90.0,
+            ..Default::default()
+        };
+        assert_bounds(
+            Point::new(-1.0, 1.0, 0.0),
+            Point::new(1.0, 7.0, 0.0),
+            bounds_of(EntityType::Text(text)),
+        );
+    }
+
+    #[test]
+    fn infinite_entities_have_no_bounds() {
+        assert_eq!(None, bounds_of(EntityType::XLine(XLine::default())));
+        assert_eq!(None, bounds_of(EntityType::Ray(Ray::default())));
+    }
+
+    #[test]
+    fn compute_and_update_extents() {
+        let mut drawing = Drawing::new();
+        assert_eq!(None, drawing.compute_extents());
+        drawing.add_entity(Entity::new(EntityType::Line(Line::new(
+            Point::new(-1.0, 0.0, 0.0),
+            Point::new(1.0, 1.0, 0.0),
+        ))));
+        drawing.add_entity(Entity::new(EntityType::Circle(Circle::new(
+            Point::new(5.0, 5.0, 0.0),
+            1.0,
+        ))));
+        let mut paper_space = Entity::new(EntityType::Line(Line::new(
+            Point::new(100.0, 100.0, 0.0),
+            Point::new(200.0, 200.0, 0.0),
+        )));
+        paper_space.common.is_in_paper_space = true;
+        drawing.add_entity(paper_space);
+
+        let extents = drawing.update_extents();
+        assert_bounds(
+            Point::new(-1.0, 0.0, 0.0),
+            Point::new(6.0, 6.0, 0.0),
+            extents,
+        );
+        assert_eq!(
+            Point::new(-1.0, 0.0, 0.0),
+            drawing.header.minimum_drawing_extents
+        );
+        assert_eq!(
+            Point::new(6.0, 6.0, 0.0),
+            drawing.header.maximum_drawing_extents
+        );
+    }
+}
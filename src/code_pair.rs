@@ -1,13 +1,7 @@
-extern crate byteorder;
-
 use std::fmt;
 use std::fmt::{Debug, Display, Formatter};
 
-use self::byteorder::{BigEndian, ByteOrder};
-
-use crate::{CodePairValue, DxfError, DxfResult};
-
-use crate::helper_functions::parse_hex_string;
+use crate::{CodePairValue, DxfError, DxfResult, Handle};
 
 /// The basic primitive of a DXF file; a code indicating the type of the data contained, and the
 /// data itself.
@@ -88,13 +82,8 @@ impl CodePair {
 }
 
 impl CodePair {
-    pub(crate) fn as_handle(&self) -> DxfResult<u32> {
-        let mut bytes = vec![];
-        parse_hex_string(&self.assert_string()?, &mut bytes, self.offset)?;
-        while bytes.len() < 4 {
-            bytes.insert(0, 0);
-        }
-        Ok(BigEndian::read_u32(&bytes))
+    pub(crate) fn as_handle(&self) -> DxfResult<Handle> {
+        Handle::parse(&self.assert_string()?, self.offset)
     }
 }
 
@@ -119,13 +108,16 @@ impl PartialEq for CodePair {
 
 #[cfg(test)]
 mod tests {
-    use crate::CodePair;
+    use crate::{CodePair, Handle};
 
     #[test]
     fn as_handle() {
-        assert_eq!(0x00, CodePair::new_str(0, "0").as_handle().unwrap());
-        assert_eq!(0x01, CodePair::new_str(0, "1").as_handle().unwrap());
-        assert_eq!(0xABCD, CodePair::new_str(0, "ABCD").as_handle().unwrap());
+        assert_eq!(Handle(0x00), CodePair::new_str(0, "0").as_handle().unwrap());
+        assert_eq!(Handle(0x01), CodePair::new_str(0, "1").as_handle().unwrap());
+        assert_eq!(
+            Handle(0xABCD),
+            CodePair::new_str(0, "ABCD").as_handle().unwrap()
+        );
     }
 
     #[test]
@@ -8,6 +8,7 @@ use std::io::Read;
 pub(crate) struct CodePairIter<T: Read> {
     reader: T,
     string_encoding: &'static Encoding,
+    use_code_page: bool,
     first_line: String,
     read_first_line: bool,
     read_as_text: bool,
@@ -22,6 +23,7 @@ impl<T: Read> CodePairIter<T> {
         CodePairIter {
             reader,
             string_encoding,
+            use_code_page: true,
             first_line,
             read_first_line: false,
             read_as_text: true,
@@ -34,6 +36,24 @@ impl<T: Read> CodePairIter<T> {
     pub fn read_as_utf8(&mut self) {
         self.string_encoding = encoding_rs::UTF_8;
     }
+    /// Switches to the encoding of the drawing's `$DWGCODEPAGE`, unless already reading UTF-8 or the
+    /// encoding was explicitly specified.
+    pub fn read_with_code_page(&mut self, code_page: &str) {
+        if self.use_code_page && self.string_encoding != encoding_rs::UTF_8 {
+            if let Some(encoding) = get_encoding_from_code_page(code_page) {
+                self.string_encoding = encoding;
+            }
+        }
+    }
+    pub fn set_use_code_page(&mut self, use_code_page: bool) {
+        self.use_code_page = use_code_page;
+    }
+    pub fn is_text(&self) -> bool {
+        self.read_as_text
+    }
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
     fn detect_binary_or_text_file(&mut self) -> DxfResult<()> {
         match &*self.first_line {
             "AutoCAD Binary DXF" => {
@@ -82,7 +102,7 @@ impl<T: Read> CodePairIter<T> {
         }
 
         let code_offset = self.offset;
-        let code = try_into_option!(parse_i32(String::from(code_line), code_offset));
+        let code = parse_i32(String::from(code_line), code_offset);
 
         // Read value.  If no line is available die horribly.
         self.offset += 1;
@@ -92,6 +112,9 @@ impl<T: Read> CodePairIter<T> {
             None => return Some(Err(DxfError::UnexpectedEndOfInput)),
         };
 
+        // the value line is always consumed so a malformed code doesn't misalign the following pairs
+        let code = try_into_option!(code);
+
         // construct the value pair
         let expected_type = match ExpectedType::get_expected_type(code) {
             Some(t) => t,
@@ -114,7 +137,7 @@ impl<T: Read> CodePairIter<T> {
                 CodePairValue::Double(try_into_option!(parse_f64(value_line, self.offset)))
             }
             ExpectedType::Str => {
-                let value_line = if self.string_encoding == encoding_rs::WINDOWS_1252 {
+                let value_line = if self.string_encoding != encoding_rs::UTF_8 {
                     un_escape_ascii_to_unicode(&value_line)
                 } else {
                     value_line
@@ -182,8 +205,10 @@ impl<T: Read> CodePairIter<T> {
                 8,
             ),
             ExpectedType::Str => {
-                let mut value = try_from_dxf_result!(self.read_string_binary());
-                if !self.returned_binary_pair && code == 0 && value == "" {
+                let (value, length) = try_from_dxf_result!(self.read_string_binary());
+                let is_post_r13_marker =
+                    !self.returned_binary_pair && code == 0 && value.is_empty();
+                let (value, length) = if is_post_r13_marker {
                     // If this is the first pair being read and the code is 0, the only valid string value is "SECTION".
                     // If the read value is instead empty, that means the string reader found a single 0x00 byte which
                     // indicates that this is a post R13 binary file where codes are always read as 2 bytes.  The 0x00
@@ -191,11 +216,15 @@ impl<T: Read> CodePairIter<T> {
                     // the reader up.
                     self.is_post_r13_binary = true;
                     self.offset += 1; // account for the NULL byte that was interpreted as an empty string
-                    value = try_from_dxf_result!(self.read_string_binary()); // now read the actual value
-                }
+
+                    // now read the actual value
+                    try_from_dxf_result!(self.read_string_binary())
+                } else {
+                    (value, length)
+                };
                 (
                     CodePairValue::Str(CodePairValue::un_escape_string(&value).into_owned()),
-                    value.len() + 1, // +1 to account for the NULL terminator
+                    length + 1, // +1 to account for the NULL terminator
                 )
             }
         };
@@ -204,18 +233,28 @@ impl<T: Read> CodePairIter<T> {
 
         Some(Ok(CodePair::new(code, value, self.offset)))
     }
-    fn read_string_binary(&mut self) -> DxfResult<String> {
-        let mut s = String::new();
+    /// Reads a NULL-terminated string, returning it and the number of bytes it occupied.
+    fn read_string_binary(&mut self) -> DxfResult<(String, usize)> {
+        let mut bytes = vec![];
         loop {
             match read_u8(&mut self.reader) {
                 Some(Ok(0)) => break,
-                Some(Ok(c)) => s.push(c as char),
+                Some(Ok(c)) => bytes.push(c),
                 Some(Err(e)) => return Err(DxfError::IoError(e)),
                 None => return Err(DxfError::UnexpectedEndOfInput),
             }
         }
 
-        Ok(s)
+        let s = match self.string_encoding.decode_without_bom_handling(&bytes) {
+            (s, false) => s.into_owned(),
+            (_, true) => return Err(DxfError::MalformedString),
+        };
+        let s = if self.string_encoding != encoding_rs::UTF_8 {
+            un_escape_ascii_to_unicode(&s)
+        } else {
+            s
+        };
+        Ok((s, bytes.len()))
     }
 }
 
@@ -2,11 +2,17 @@ use std::io::Read;
 
 use crate::code_pair_iter::CodePairIter;
 use crate::dxf_result::DxfResult;
-use crate::CodePair;
+use crate::{CodePair, CodePairValue, Diagnostic, DiagnosticAction, DxfError, Handle};
 
 pub(crate) struct CodePairPutBack<T: Read> {
     top: Vec<DxfResult<CodePair>>,
     iter: CodePairIter<T>,
+    lenient: bool,
+    diagnostics: Vec<Diagnostic>,
+    section: String,
+    expecting_section_name: bool,
+    item_type: Option<String>,
+    handle: Option<Handle>,
 }
 
 impl<T: Read> CodePairPutBack<T> {
@@ -14,7 +20,16 @@ impl<T: Read> CodePairPutBack<T> {
     where
         T: Read,
     {
-        CodePairPutBack { top: vec![], iter }
+        CodePairPutBack {
+            top: vec![],
+            iter,
+            lenient: false,
+            diagnostics: vec![],
+            section: String::new(),
+            expecting_section_name: false,
+            item_type: None,
+            handle: None,
+        }
     }
     pub fn put_back(&mut self, item: DxfResult<CodePair>) {
         self.top.push(item);
@@ -22,16 +37,107 @@ impl<T: Read> CodePairPutBack<T> {
     pub fn read_as_utf8(&mut self) {
         self.iter.read_as_utf8()
     }
+    pub fn read_with_code_page(&mut self, code_page: &str) {
+        self.iter.read_with_code_page(code_page)
+    }
+    pub fn set_lenient(&mut self, lenient: bool) {
+        self.lenient = lenient;
+    }
+    pub fn is_lenient(&self) -> bool {
+        self.lenient
+    }
+    /// Records a problem with the item currently being read.
+    pub fn add_diagnostic(&mut self, error: &DxfError, action: DiagnosticAction) {
+        self.diagnostics.push(Diagnostic {
+            offset: error.offset().unwrap_or_else(|| self.iter.offset()),
+            section: self.section.clone(),
+            item_type: self.item_type.clone(),
+            handle: self.handle,
+            action,
+            message: error.to_string(),
+        });
+    }
+    pub fn take_diagnostics(&mut self) -> Vec<Diagnostic> {
+        std::mem::take(&mut self.diagnostics)
+    }
+    /// Returns a value that changes whenever a code pair is consumed or put back.
+    pub fn read_position(&self) -> (usize, usize) {
+        (self.iter.offset(), self.top.len())
+    }
+    /// Skips the remainder of the current item, leaving the next `0/<item-type>` pair to be read.
+    pub fn skip_to_next_item(&mut self) -> DxfResult<()> {
+        loop {
+            match self.next() {
+                Some(Ok(pair @ CodePair { code: 0, .. })) => {
+                    self.put_back(Ok(pair));
+                    return Ok(());
+                }
+                Some(Ok(_)) => (),
+                Some(Err(e)) => return Err(e),
+                None => return Ok(()),
+            }
+        }
+    }
+    /// Skips the remainder of the current section, leaving the `0/ENDSEC` pair to be read.
+    pub fn skip_section(&mut self) -> DxfResult<()> {
+        loop {
+            match self.next() {
+                Some(Ok(pair @ CodePair { code: 0, .. })) if pair.assert_string()? == "ENDSEC" => {
+                    self.put_back(Ok(pair));
+                    return Ok(());
+                }
+                Some(Ok(_)) => (),
+                Some(Err(e)) => return Err(e),
+                None => return Err(DxfError::UnexpectedEndOfInput),
+            }
+        }
+    }
+    fn track_context(&mut self, pair: &CodePair) {
+        match (pair.code, &pair.value) {
+            (0, CodePairValue::Str(ref s)) => {
+                match &**s {
+                    "SECTION" => self.expecting_section_name = true,
+                    "ENDSEC" | "EOF" => self.section.clear(),
+                    _ => (),
+                }
+                self.item_type = match &**s {
+                    "SECTION" | "ENDSEC" | "EOF" => None,
+                    _ => Some(s.clone()),
+                };
+                self.handle = None;
+                return;
+            }
+            (2, CodePairValue::Str(ref s)) if self.expecting_section_name => {
+                self.section = s.clone();
+            }
+            (5, _) | (105, _) if self.item_type.is_some() => self.handle = pair.as_handle().ok(),
+            _ => (),
+        }
+
+        self.expecting_section_name = false;
+    }
 }
 
 impl<T: Read> Iterator for CodePairPutBack<T> {
     type Item = DxfResult<CodePair>;
 
     fn next(&mut self) -> Option<DxfResult<CodePair>> {
-        if self.top.is_empty() {
-            self.iter.next()
-        } else {
-            Some(self.top.pop().unwrap())
+        if let Some(item) = self.top.pop() {
+            return Some(item);
+        }
+
+        loop {
+            match self.iter.next() {
+                Some(Ok(pair)) => {
+                    self.track_context(&pair);
+                    return Some(Ok(pair));
+                }
+                // a malformed value in a text file can be dropped without affecting the rest of the item
+                Some(Err(e)) if self.lenient && self.iter.is_text() && e.is_value_error() => {
+                    self.add_diagnostic(&e, DiagnosticAction::DroppedValue);
+                }
+                other => return other,
+            }
         }
     }
 }
@@ -0,0 +1,179 @@
+use std::io::Read;
+
+use encoding_rs::Encoding;
+
+use crate::{CodePair, CodePairValue, DxfError, DxfResult};
+
+use crate::code_pair_iter::CodePairIter;
+use crate::enums::AcadVersion;
+use crate::helper_functions::*;
+
+/// Reads the `CodePair`s of an ASCII or binary DXF file one at a time, without building a `Drawing`.
+///
+/// As with `Drawing::load`, string values switch to UTF-8 once a `$ACADVER` of R2007 or later is
+/// encountered, or to the encoding named by `$DWGCODEPAGE` for earlier versions.
+///
+/// ```
+/// # fn main() -> dxf::DxfResult<()> {
+/// let mut file = std::io::Cursor::new("0\r\nSECTION\r\n2\r\nENTITIES\r\n0\r\nENDSEC\r\n0\r\nEOF\r\n");
+/// let reader = dxf::CodePairReader::new(&mut file)?;
+/// let mut section_count = 0;
+/// for pair in reader {
+///     let pair = pair?;
+///     if pair.code == 0 && pair.assert_string()? == "SECTION" {
+///         section_count += 1;
+///     }
+/// }
+/// assert_eq!(1, section_count);
+/// # Ok(())
+/// # }
+/// ```
+pub struct CodePairReader<T: Read> {
+    iter: CodePairIter<T>,
+    header_variable: Option<String>,
+}
+
+// public implementation
+impl<T: Read> CodePairReader<T> {
+    /// Creates a `CodePairReader` from anything that implements the `Read` trait.
+    pub fn new(reader: T) -> DxfResult<Self> {
+        Ok(CodePairReader {
+            iter: new_code_pair_iter(reader, encoding_rs::WINDOWS_1252)?,
+            header_variable: None,
+        })
+    }
+    /// Creates a `CodePairReader` from anything that implements the `Read` trait using the specified
+    /// text encoding.  `$DWGCODEPAGE` is ignored.
+    pub fn new_with_encoding(reader: T, encoding: &'static Encoding) -> DxfResult<Self> {
+        let mut iter = new_code_pair_iter(reader, encoding)?;
+        iter.set_use_code_page(false);
+        Ok(CodePairReader {
+            iter,
+            header_variable: None,
+        })
+    }
+}
+
+impl<T: Read> Iterator for CodePairReader<T> {
+    type Item = DxfResult<CodePair>;
+
+    fn next(&mut self) -> Option<DxfResult<CodePair>> {
+        let pair = match self.iter.next() {
+            Some(Ok(pair)) => pair,
+            other => return other,
+        };
+        let header_variable = self.header_variable.take();
+        match (pair.code, &pair.value, header_variable.as_deref()) {
+            (9, CodePairValue::Str(ref s), _) => self.header_variable = Some(s.clone()),
+            (1, CodePairValue::Str(ref s), Some("$ACADVER"))
+                if AcadVersion::from_safe(s.clone()) >= AcadVersion::R2007 =>
+            {
+                self.iter.read_as_utf8()
+            }
+            (3, CodePairValue::Str(ref s), Some("$DWGCODEPAGE")) => {
+                self.iter.read_with_code_page(s)
+            }
+            _ => (),
+        }
+
+        Some(Ok(pair))
+    }
+}
+
+/// Reads the first line of a DXF file and prepares a `CodePairIter` for the rest.
+pub(crate) fn new_code_pair_iter<T>(
+    mut reader: T,
+    encoding: &'static Encoding,
+) -> DxfResult<CodePairIter<T>>
+where
+    T: Read,
+{
+    let first_line = match read_line(&mut reader, encoding) {
+        Some(Ok(line)) => line,
+        Some(Err(e)) => return Err(e),
+        None => return Err(DxfError::UnexpectedEndOfInput),
+    };
+    if first_line == "AutoCAD DXB 1.0" {
+        // DXB files don't contain code pairs
+        return Err(DxfError::InvalidBinaryFile);
+    }
+
+    Ok(CodePairIter::new(reader, encoding, first_line))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::*;
+    use crate::helper_functions::tests::*;
+    use crate::*;
+
+    fn read_pairs(bytes: &[u8]) -> Vec<CodePair> {
+        let mut bytes = bytes;
+        CodePairReader::new(&mut bytes)
+            .unwrap()
+            .map(|p| p.unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn read_ascii_code_pairs() {
+        let pairs = read_pairs(
+            ["0", "SECTION", "2", "ENTITIES", "0", "ENDSEC", "0", "EOF"]
+                .join("\r\n")
+                .as_bytes(),
+        );
+        assert_eq!(
+            vec![
+                CodePair::new_str(0, "SECTION"),
+                CodePair::new_str(2, "ENTITIES"),
+                CodePair::new_str(0, "ENDSEC"),
+                CodePair::new_str(0, "EOF"),
+            ],
+            pairs
+        );
+    }
+
+    #[test]
+    fn read_binary_code_pairs() {
+        let mut drawing = Drawing::new();
+        drawing.add_entity(Entity::new(EntityType::Line(Line::new(
+            Point::new(1.0, 2.0, 3.0),
+            Point::new(4.0, 5.0, 6.0),
+        ))));
+        let pairs = read_pairs(&to_binary(&drawing));
+        assert!(pairs.contains(&CodePair::new_str(0, "LINE")));
+        assert!(pairs.contains(&CodePair::new_f64(11, 4.0)));
+        assert_eq!(Some(&CodePair::new_str(0, "EOF")), pairs.last());
+    }
+
+    #[test]
+    fn read_utf8_strings_after_acad_version() {
+        let text = [
+            "0",
+            "SECTION",
+            "2",
+            "HEADER",
+            "9",
+            "$ACADVER",
+            "1",
+            "AC1021",
+            "9",
+            "$PROJECTNAME",
+            "1",
+            "è",
+            "0",
+            "ENDSEC",
+            "0",
+            "EOF",
+        ]
+        .join("\r\n");
+        let pairs = read_pairs(text.as_bytes());
+        assert_eq!(CodePair::new_str(1, "è"), pairs[5]);
+    }
+
+    #[test]
+    fn dxb_files_are_not_read_as_code_pairs() {
+        let mut bytes: &[u8] = b"AutoCAD DXB 1.0\r\n\x1A\x00";
+        assert!(CodePairReader::new(&mut bytes).is_err());
+    }
+}
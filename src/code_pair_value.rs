@@ -2,6 +2,8 @@ use std::borrow::Cow;
 use std::fmt;
 use std::fmt::{Debug, Display, Formatter};
 
+use encoding_rs::Encoding;
+
 /// Contains the data portion of a `CodePair`.
 #[derive(PartialEq)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
@@ -118,7 +120,7 @@ impl Display for CodePairValue {
 
 pub(crate) fn escape_control_characters(val: &str) -> String {
     fn needs_escaping(c: char) -> bool {
-        let c = c as u8;
+        let c = c as u32;
         c <= 0x1F || c == 0x5E
     }
 
@@ -173,6 +175,9 @@ pub(crate) fn escape_control_characters(val: &str) -> String {
 #[test]
 fn test_escape_control_characters() {
     assert_eq!("a^G^ ^^ b", escape_control_characters("a\u{7}^\u{1E} b"));
+
+    // characters whose low byte looks like a control character
+    assert_eq!("不", escape_control_characters("不"));
 }
 
 pub(crate) fn escape_unicode_to_ascii(val: &str) -> String {
@@ -202,6 +207,47 @@ fn test_unicode_escape() {
     assert_eq!("\\U+4F60\\U+597D", escape_unicode_to_ascii("你好"));
 }
 
+/// Encodes a string in the specified encoding, escaping any characters it can't represent as `\U+XXXX`.
+pub(crate) fn encode_string(val: &str, encoding: &'static Encoding) -> Vec<u8> {
+    if encoding == encoding_rs::UTF_8 {
+        return val.as_bytes().to_vec();
+    }
+
+    let mut result = vec![];
+    let mut buf = [0u8; 4];
+    for c in val.chars() {
+        if c.is_ascii() {
+            result.push(c as u8);
+            continue;
+        }
+
+        let c = c.encode_utf8(&mut buf);
+        match encoding.encode(c) {
+            (bytes, _, false) => result.extend_from_slice(&bytes),
+            (_, _, true) => result.extend_from_slice(escape_unicode_to_ascii(c).as_bytes()),
+        }
+    }
+
+    result
+}
+
+#[test]
+fn test_encode_string() {
+    assert_eq!(
+        b"a\xE8b".to_vec(),
+        encode_string("aèb", encoding_rs::WINDOWS_1252)
+    );
+    assert_eq!(
+        b"\\U+4F60".to_vec(),
+        encode_string("你", encoding_rs::WINDOWS_1252)
+    );
+    assert_eq!(vec![0xC4, 0xE3], encode_string("你", encoding_rs::GBK));
+    assert_eq!(
+        "你".as_bytes().to_vec(),
+        encode_string("你", encoding_rs::UTF_8)
+    );
+}
+
 pub(crate) fn un_escape_ascii_to_unicode(val: &str) -> String {
     let mut result = String::from("");
     let mut seq = String::from("");
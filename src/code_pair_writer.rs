@@ -3,9 +3,10 @@ use std::io::Write;
 extern crate byteorder;
 use self::byteorder::{LittleEndian, WriteBytesExt};
 
-use crate::code_pair_value::{escape_control_characters, escape_unicode_to_ascii};
+use crate::code_pair_value::{encode_string, escape_control_characters};
 use crate::enums::AcadVersion;
 use crate::{CodePair, CodePairValue, DxfResult};
+use encoding_rs::Encoding;
 
 pub(crate) struct CodePairWriter<'a, T>
 where
@@ -13,7 +14,7 @@ where
 {
     writer: &'a mut T,
     as_text: bool,
-    text_as_ascii: bool,
+    string_encoding: &'static Encoding,
     version: AcadVersion,
 }
 
@@ -21,16 +22,19 @@ impl<'a, T: Write + ?Sized> CodePairWriter<'a, T> {
     pub fn new(
         writer: &'a mut T,
         as_text: bool,
-        text_as_ascii: bool,
+        string_encoding: &'static Encoding,
         version: AcadVersion,
     ) -> Self {
         CodePairWriter {
             writer,
             as_text,
-            text_as_ascii,
+            string_encoding,
             version,
         }
     }
+    pub fn inner_writer(&mut self) -> &mut T {
+        self.writer
+    }
     pub fn write_prelude(&mut self) -> DxfResult<()> {
         if !self.as_text {
             self.writer
@@ -54,12 +58,9 @@ impl<'a, T: Write + ?Sized> CodePairWriter<'a, T> {
         match pair.value {
             CodePairValue::Str(ref s) => {
                 let s = escape_control_characters(&s);
-                let s = if self.text_as_ascii {
-                    escape_unicode_to_ascii(&s)
-                } else {
-                    s
-                };
-                self.writer.write_fmt(format_args!("{}\r\n", s))?;
+                self.writer
+                    .write_all(&encode_string(&s, self.string_encoding))?;
+                self.writer.write_all(b"\r\n")?;
             }
             _ => self.writer.write_fmt(format_args!("{}\r\n", &pair.value))?,
         };
@@ -90,9 +91,9 @@ impl<'a, T: Write + ?Sized> CodePairWriter<'a, T> {
             CodePairValue::Short(s) => self.writer.write_i16::<LittleEndian>(s)?,
             CodePairValue::Double(d) => self.writer.write_f64::<LittleEndian>(d)?,
             CodePairValue::Str(ref s) => {
-                for &b in escape_control_characters(s).as_bytes() {
-                    self.writer.write_u8(b)?;
-                }
+                let s = escape_control_characters(s);
+                self.writer
+                    .write_all(&encode_string(&s, self.string_encoding))?;
 
                 self.writer.write_u8(0)?;
             }
@@ -15,7 +15,11 @@ use crate::header::*;
 use crate::objects::*;
 use crate::tables::*;
 
-use crate::{CodePair, CodePairValue, DxfError, DxfResult};
+use crate::{
+    AcdsData, BoundingBox, CodePair, CodePairValue, Diagnostic, DxfError, DxfResult, Handle,
+    LineWeight, LoadMode, LoadOptions, PurgeOptions, PurgeReport, RemovalMode, RemovalReport,
+    SectionFilter, SectionPosition, TransformationMatrix, UnknownSection,
+};
 
 use crate::dxb_reader::DxbReader;
 use crate::dxb_writer::DxbWriter;
@@ -23,6 +27,7 @@ use crate::entity_iter::EntityIter;
 use crate::helper_functions::*;
 use crate::object_iter::ObjectIter;
 
+use crate::acds_data::ACDSDATA_SECTION_NAME;
 use crate::block::Block;
 use crate::class::Class;
 
@@ -33,11 +38,20 @@ use std::fs::File;
 use std::io::{BufReader, BufWriter, Read, Write};
 
 use itertools::put_back;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::iter::Iterator;
 use std::path::Path;
+use std::sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
-pub(crate) const AUTO_REPLACE_HANDLE: u32 = 0xFFFF_FFFF;
+const SUPPORTED_SECTIONS: [&str; 7] = [
+    "HEADER",
+    "CLASSES",
+    "TABLES",
+    "BLOCKS",
+    "ENTITIES",
+    "OBJECTS",
+    "THUMBNAILIMAGE",
+];
 
 /// Represents a DXF drawing.
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
@@ -78,6 +92,63 @@ pub struct Drawing {
     /// The thumbnail image preview of the drawing.
     #[cfg_attr(feature = "serialize", serde(skip))]
     pub thumbnail: Option<DynamicImage>,
+
+    /// Sections that aren't otherwise supported, e.g., `ACDSDATA`.  These are written back as-is.
+    pub unknown_sections: Vec<UnknownSection>,
+
+    /// Internal index of item handles to their location in the collections above.
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    __handle_index: RwLock<HandleIndex>,
+}
+
+/// The index of item handles and what's needed to keep it current.
+#[derive(Default)]
+struct HandleIndex {
+    /// The location of every item with a non-empty handle.
+    locations: HashMap<Handle, ItemLocation>,
+    /// Whether `locations` reflects all of the collections.  Cleared when a mutable iterator is handed
+    /// out since any handle could then change.
+    is_current: bool,
+    /// The items handed out by `get_item_by_handle_mut()` along with the handles they had at the time.
+    lent: Vec<(Handle, ItemLocation)>,
+}
+
+/// The tables whose items are referred to by name rather than by handle.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum NamedTable {
+    Block,
+    DimStyle,
+    Layer,
+    LineType,
+    Style,
+}
+
+/// The upper-case names of the items that are in use.  Used by `Drawing::purge`.
+#[derive(Default)]
+struct UsedNames {
+    blocks: HashSet<String>,
+    dim_styles: HashSet<String>,
+    layers: HashSet<String>,
+    line_types: HashSet<String>,
+    mline_styles: HashSet<String>,
+    text_styles: HashSet<String>,
+}
+
+/// The location of an item within one of the `Drawing`'s collections.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ItemLocation {
+    AppId(usize),
+    Block(usize),
+    BlockRecord(usize),
+    DimStyle(usize),
+    Entity(usize),
+    Layer(usize),
+    LineType(usize),
+    Object(usize),
+    Style(usize),
+    Ucs(usize),
+    View(usize),
+    ViewPort(usize),
 }
 
 // public implementation
@@ -100,22 +171,60 @@ impl Drawing {
             __entities: vec![],
             __objects: vec![],
             thumbnail: None,
+            unknown_sections: vec![],
+            __handle_index: RwLock::default(),
         };
         drawing.normalize();
         drawing
     }
-    /// Loads a `Drawing` from anything that implements the `Read` trait.
+    /// Loads a `Drawing` from anything that implements the `Read` trait.  The text encoding is
+    /// determined by the drawing's `$ACADVER` and `$DWGCODEPAGE`.
     pub fn load<T>(reader: &mut T) -> DxfResult<Drawing>
     where
         T: Read + ?Sized,
     {
-        Drawing::load_with_encoding(reader, encoding_rs::WINDOWS_1252)
+        let (drawing, _) = Drawing::load_with_options(reader, LoadOptions::default())?;
+        Ok(drawing)
     }
-    /// Loads a `Drawing` from anything that implements the `Read` trait using the specified text encoding.
+    /// Loads a `Drawing` from anything that implements the `Read` trait using the specified text
+    /// encoding.  `$DWGCODEPAGE` is ignored, but R2007 and later drawings are still read as UTF-8.
     pub fn load_with_encoding<T>(reader: &mut T, encoding: &'static Encoding) -> DxfResult<Drawing>
     where
         T: Read + ?Sized,
     {
+        let options = LoadOptions {
+            encoding,
+            detect_encoding: false,
+            ..Default::default()
+        };
+        let (drawing, _) = Drawing::load_with_options(reader, options)?;
+        Ok(drawing)
+    }
+    /// Loads a `Drawing` from anything that implements the `Read` trait using the specified options.
+    ///
+    /// In `LoadMode::Lenient`, malformed values and entities/objects that can't be read are skipped
+    /// and reported in the returned `Diagnostic`s instead of failing the whole load.
+    ///
+    /// ```
+    /// # fn main() -> dxf::DxfResult<()> {
+    /// # let mut file = std::io::Cursor::new("0\r\nSECTION\r\n2\r\nENTITIES\r\n0\r\nLINE\r\n10\r\nnot-a-number\r\n0\r\nENDSEC\r\n0\r\nEOF\r\n");
+    /// let (drawing, diagnostics) = dxf::Drawing::load_with_options(&mut file, dxf::LoadOptions::lenient())?;
+    /// for diagnostic in &diagnostics {
+    ///     println!("line {}: {}", diagnostic.offset, diagnostic.message);
+    /// }
+    /// # assert_eq!(1, drawing.entities().count());
+    /// # assert_eq!(1, diagnostics.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn load_with_options<T>(
+        reader: &mut T,
+        options: LoadOptions,
+    ) -> DxfResult<(Drawing, Vec<Diagnostic>)>
+    where
+        T: Read + ?Sized,
+    {
+        let encoding = options.encoding;
         let first_line = match read_line(reader, encoding) {
             Some(Ok(line)) => line,
             Some(Err(e)) => return Err(e),
@@ -124,33 +233,72 @@ impl Drawing {
         match &*first_line {
             "AutoCAD DXB 1.0" => {
                 let mut reader = DxbReader::new(reader);
-                reader.load()
+                Ok((reader.load()?, vec![]))
             }
             _ => {
-                let reader = CodePairIter::new(reader, encoding, first_line);
+                let mut reader = CodePairIter::new(reader, encoding, first_line);
+                reader.set_use_code_page(options.detect_encoding);
                 let mut drawing = Drawing::new();
                 drawing.clear();
                 let mut iter = CodePairPutBack::from_code_pair_iter(reader);
-                Drawing::read_sections(&mut drawing, &mut iter)?;
+                iter.set_lenient(options.mode == LoadMode::Lenient);
+                let finished_early =
+                    Drawing::read_sections(&mut drawing, &mut iter, &options.sections)?;
+                if finished_early {
+                    return Ok((drawing, iter.take_diagnostics()));
+                }
+
                 match iter.next() {
                     Some(Ok(CodePair {
                         code: 0,
                         value: CodePairValue::Str(ref s),
                         ..
-                    })) if s == "EOF" => Ok(drawing),
+                    })) if s == "EOF" => Ok((drawing, iter.take_diagnostics())),
                     Some(Ok(pair)) => Err(DxfError::UnexpectedCodePair(
                         pair,
                         String::from("expected 0/EOF"),
                     )),
                     Some(Err(e)) => Err(e),
-                    None => Ok(drawing),
+                    None => Ok((drawing, iter.take_diagnostics())),
                 }
             }
         }
     }
+    /// Loads only the specified sections of a `Drawing` from anything that implements the `Read`
+    /// trait.  This is much faster than `load` when, e.g., only the header and layers are needed.
+    ///
+    /// ```
+    /// # fn main() -> dxf::DxfResult<()> {
+    /// # let mut file = std::io::Cursor::new("0\r\nSECTION\r\n2\r\nENTITIES\r\n0\r\nLINE\r\n0\r\nENDSEC\r\n0\r\nEOF\r\n");
+    /// let filter = dxf::SectionFilter {
+    ///     header: true,
+    ///     tables: true,
+    ///     ..dxf::SectionFilter::none()
+    /// };
+    /// let drawing = dxf::Drawing::load_sections(&mut file, filter)?;
+    /// for layer in drawing.layers() {
+    ///     println!("layer {}", layer.name);
+    /// }
+    /// # assert_eq!(0, drawing.entities().count());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn load_sections<T>(reader: &mut T, sections: SectionFilter) -> DxfResult<Drawing>
+    where
+        T: Read + ?Sized,
+    {
+        let options = LoadOptions {
+            sections,
+            ..Default::default()
+        };
+        let (drawing, _) = Drawing::load_with_options(reader, options)?;
+        Ok(drawing)
+    }
     /// Loads a `Drawing` from disk, using a `BufReader`.
     pub fn load_file(path: impl AsRef<Path>) -> DxfResult<Drawing> {
-        Drawing::load_file_with_encoding(path, encoding_rs::WINDOWS_1252)
+        let file = File::open(&path)?;
+        let mut buf_reader = BufReader::new(file);
+        Drawing::load(&mut buf_reader)
     }
     /// Loads a `Drawing` from disk, using a `BufReader` with the specified text encoding.
     pub fn load_file_with_encoding(
@@ -179,19 +327,16 @@ impl Drawing {
     where
         T: Write + ?Sized,
     {
-        let text_as_ascii = self.header.version <= AcadVersion::R2004;
+        let string_encoding = self.header.string_encoding();
         let mut code_pair_writer =
-            CodePairWriter::new(writer, as_ascii, text_as_ascii, self.header.version);
+            CodePairWriter::new(writer, as_ascii, string_encoding, self.header.version);
         let write_handles = self.header.version >= AcadVersion::R13 || self.header.handles_enabled;
         code_pair_writer.write_prelude()?;
+        self.write_unknown_sections(None, &mut code_pair_writer)?;
         self.header.write(&mut code_pair_writer)?;
-        self.write_classes(&mut code_pair_writer)?;
-        self.write_tables(write_handles, &mut code_pair_writer)?;
-        self.write_blocks(write_handles, &mut code_pair_writer)?;
+        self.write_sections_before_entities(write_handles, &mut code_pair_writer)?;
         self.write_entities(write_handles, &mut code_pair_writer)?;
-        self.write_objects(&mut code_pair_writer)?;
-        self.write_thumbnail(&mut code_pair_writer)?;
-        code_pair_writer.write_code_pair(&CodePair::new_str(0, "EOF"))?;
+        self.write_sections_after_entities(&mut code_pair_writer)?;
         Ok(())
     }
     /// Writes a `Drawing` to disk, using a `BufWriter`.
@@ -221,12 +366,36 @@ impl Drawing {
         let mut buf_writer = BufWriter::new(file);
         self.save_dxb(&mut buf_writer)
     }
+    /// Returns the parsed contents of the `ACDSDATA` section, if present.
+    pub fn acds_data(&self) -> DxfResult<Option<AcdsData>> {
+        match self
+            .unknown_sections
+            .iter()
+            .find(|s| s.name == ACDSDATA_SECTION_NAME)
+        {
+            Some(section) => Ok(Some(AcdsData::from_section(section)?)),
+            None => Ok(None),
+        }
+    }
+    /// Sets the contents of the `ACDSDATA` section, keeping its position if already present.
+    pub fn set_acds_data(&mut self, data: &AcdsData) {
+        let new_section = data.to_section();
+        match self
+            .unknown_sections
+            .iter_mut()
+            .find(|s| s.name == ACDSDATA_SECTION_NAME)
+        {
+            Some(section) => section.code_pairs = new_section.code_pairs,
+            None => self.unknown_sections.push(new_section),
+        }
+    }
     /// Returns an iterator for all app ids.
     pub fn app_ids(&self) -> impl Iterator<Item = &AppId> {
         self.__app_ids.iter()
     }
     /// Returns an iterator for all mutable app ids.
     pub fn app_ids_mut(&mut self) -> impl Iterator<Item = &mut AppId> {
+        self.invalidate_handle_index();
         self.__app_ids.iter_mut()
     }
     /// Adds an app id to the `Drawing`.
@@ -236,7 +405,9 @@ impl Drawing {
     }
     /// Removes the specified `AppId` from the `Drawing`.
     pub fn remove_app_id(&mut self, index: usize) -> Option<AppId> {
-        Drawing::remove_item(&mut self.__app_ids, index)
+        let app_id = Drawing::remove_item(&mut self.__app_ids, index)?;
+        self.item_removed(app_id.handle, ItemLocation::AppId(index));
+        Some(app_id)
     }
     /// Returns an iterator for all block records.
     pub fn block_records(&self) -> impl Iterator<Item = &BlockRecord> {
@@ -244,6 +415,7 @@ impl Drawing {
     }
     /// Returns an iterator for all mutable block records.
     pub fn block_records_mut(&mut self) -> impl Iterator<Item = &mut BlockRecord> {
+        self.invalidate_handle_index();
         self.__block_records.iter_mut()
     }
     /// Adds a block record to the `Drawing`.
@@ -253,7 +425,9 @@ impl Drawing {
     }
     /// Removes the specified `BlockRecord` from the `Drawing`.
     pub fn remove_block_record(&mut self, index: usize) -> Option<BlockRecord> {
-        Drawing::remove_item(&mut self.__block_records, index)
+        let block_record = Drawing::remove_item(&mut self.__block_records, index)?;
+        self.item_removed(block_record.handle, ItemLocation::BlockRecord(index));
+        Some(block_record)
     }
     /// Returns an iterator for all dimension styles.
     pub fn dim_styles(&self) -> impl Iterator<Item = &DimStyle> {
@@ -261,6 +435,7 @@ impl Drawing {
     }
     /// Returns an iterator for all mutable dimension styles.
     pub fn dim_styles_mut(&mut self) -> impl Iterator<Item = &mut DimStyle> {
+        self.invalidate_handle_index();
         self.__dim_styles.iter_mut()
     }
     /// Adds a dimension style to the `Drawing`.
@@ -270,7 +445,9 @@ impl Drawing {
     }
     /// Removes the specified `DimStyle` from the `Drawing`.
     pub fn remove_dim_style(&mut self, index: usize) -> Option<DimStyle> {
-        Drawing::remove_item(&mut self.__dim_styles, index)
+        let dim_style = Drawing::remove_item(&mut self.__dim_styles, index)?;
+        self.item_removed(dim_style.handle, ItemLocation::DimStyle(index));
+        Some(dim_style)
     }
     /// Returns an iterator for all layers.
     pub fn layers(&self) -> impl Iterator<Item = &Layer> {
@@ -278,6 +455,7 @@ impl Drawing {
     }
     /// Returns an iterator for all mutable layers.
     pub fn layers_mut(&mut self) -> impl Iterator<Item = &mut Layer> {
+        self.invalidate_handle_index();
         self.__layers.iter_mut()
     }
     /// Adds a layer to the `Drawing`.
@@ -287,7 +465,9 @@ impl Drawing {
     }
     /// Removes the specified `Layer` from the `Drawing`.
     pub fn remove_layer(&mut self, index: usize) -> Option<Layer> {
-        Drawing::remove_item(&mut self.__layers, index)
+        let layer = Drawing::remove_item(&mut self.__layers, index)?;
+        self.item_removed(layer.handle, ItemLocation::Layer(index));
+        Some(layer)
     }
     /// Returns an iterator for all line types.
     pub fn line_types(&self) -> impl Iterator<Item = &LineType> {
@@ -295,6 +475,7 @@ impl Drawing {
     }
     /// Returns an iterator for all mutable line types.
     pub fn line_types_mut(&mut self) -> impl Iterator<Item = &mut LineType> {
+        self.invalidate_handle_index();
         self.__line_types.iter_mut()
     }
     /// Adds a line type to the `Drawing`.
@@ -304,7 +485,9 @@ impl Drawing {
     }
     /// Removes the specified `LineType` from the `Drawing`.
     pub fn remove_line_type(&mut self, index: usize) -> Option<LineType> {
-        Drawing::remove_item(&mut self.__line_types, index)
+        let line_type = Drawing::remove_item(&mut self.__line_types, index)?;
+        self.item_removed(line_type.handle, ItemLocation::LineType(index));
+        Some(line_type)
     }
     /// Returns an iterator for all styles.
     pub fn styles(&self) -> impl Iterator<Item = &Style> {
@@ -312,6 +495,7 @@ impl Drawing {
     }
     /// Returns an iterator for all mutable styles.
     pub fn styles_mut(&mut self) -> impl Iterator<Item = &mut Style> {
+        self.invalidate_handle_index();
         self.__styles.iter_mut()
     }
     /// Adds a style to the `Drawing`.
@@ -321,7 +505,9 @@ impl Drawing {
     }
     /// Removes the specified `Style` from the `Drawing`.
     pub fn remove_style(&mut self, index: usize) -> Option<Style> {
-        Drawing::remove_item(&mut self.__styles, index)
+        let style = Drawing::remove_item(&mut self.__styles, index)?;
+        self.item_removed(style.handle, ItemLocation::Style(index));
+        Some(style)
     }
     /// Returns an iterator for all ucss.
     pub fn ucss(&self) -> impl Iterator<Item = &Ucs> {
@@ -329,6 +515,7 @@ impl Drawing {
     }
     /// Returns an iterator for all mutable ucss.
     pub fn ucss_mut(&mut self) -> impl Iterator<Item = &mut Ucs> {
+        self.invalidate_handle_index();
         self.__ucss.iter_mut()
     }
     /// Add a ucs to the `Drawing`.
@@ -338,7 +525,9 @@ impl Drawing {
     }
     /// Removes the specified `Ucs` from the `Drawing`.
     pub fn remove_ucs(&mut self, index: usize) -> Option<Ucs> {
-        Drawing::remove_item(&mut self.__ucss, index)
+        let ucs = Drawing::remove_item(&mut self.__ucss, index)?;
+        self.item_removed(ucs.handle, ItemLocation::Ucs(index));
+        Some(ucs)
     }
     /// Returns an iterator for all views.
     pub fn views(&self) -> impl Iterator<Item = &View> {
@@ -346,6 +535,7 @@ impl Drawing {
     }
     /// Returns an iterator for all mutable views.
     pub fn views_mut(&mut self) -> impl Iterator<Item = &mut View> {
+        self.invalidate_handle_index();
         self.__views.iter_mut()
     }
     /// Add a view to the `Drawing`.
@@ -355,7 +545,9 @@ impl Drawing {
     }
     /// Removes the specified `View` from the `Drawing`.
     pub fn remove_view(&mut self, index: usize) -> Option<View> {
-        Drawing::remove_item(&mut self.__views, index)
+        let view = Drawing::remove_item(&mut self.__views, index)?;
+        self.item_removed(view.handle, ItemLocation::View(index));
+        Some(view)
     }
     /// Returns an iterator for all view ports.
     pub fn view_ports(&self) -> impl Iterator<Item = &ViewPort> {
@@ -363,6 +555,7 @@ impl Drawing {
     }
     /// Returns an iterator for all mutable view ports.
     pub fn view_ports_mut(&mut self) -> impl Iterator<Item = &mut ViewPort> {
+        self.invalidate_handle_index();
         self.__view_ports.iter_mut()
     }
     /// Add a view port to the `Drawing`.
@@ -372,7 +565,9 @@ impl Drawing {
     }
     /// Removes the specified `ViewPort` from the `Drawing`.
     pub fn remove_view_port(&mut self, index: usize) -> Option<ViewPort> {
-        Drawing::remove_item(&mut self.__view_ports, index)
+        let view_port = Drawing::remove_item(&mut self.__view_ports, index)?;
+        self.item_removed(view_port.handle, ItemLocation::ViewPort(index));
+        Some(view_port)
     }
     /// Returns an iterator for all blocks.
     pub fn blocks(&self) -> impl Iterator<Item = &Block> {
@@ -380,6 +575,7 @@ impl Drawing {
     }
     /// Returns an iterator for all mutable blocks.
     pub fn blocks_mut(&mut self) -> impl Iterator<Item = &mut Block> {
+        self.invalidate_handle_index();
         self.__blocks.iter_mut()
     }
     /// Add a block to the `Drawing`.
@@ -389,7 +585,9 @@ impl Drawing {
     }
     /// Removes the specified `Block` from the `Drawing`.
     pub fn remove_block(&mut self, index: usize) -> Option<Block> {
-        Drawing::remove_item(&mut self.__blocks, index)
+        let block = Drawing::remove_item(&mut self.__blocks, index)?;
+        self.item_removed(block.handle, ItemLocation::Block(index));
+        Some(block)
     }
     /// Returns an iterator for all contained entities.
     pub fn entities(&self) -> impl Iterator<Item = &Entity> {
@@ -397,37 +595,21 @@ impl Drawing {
     }
     /// Returns an iterator for all mutable entities.
     pub fn entities_mut(&mut self) -> impl Iterator<Item = &mut Entity> {
+        self.invalidate_handle_index();
         self.__entities.iter_mut()
     }
     /// Adds an entity to the `Drawing`.
     pub fn add_entity(&mut self, mut entity: Entity) -> &Entity {
-        entity.common.handle = self.next_handle();
-
-        // set child handles
-        match entity.specific {
-            EntityType::Insert(ref mut ins) => {
-                for a in ins.__attributes_and_handles.iter_mut() {
-                    if a.1 == AUTO_REPLACE_HANDLE {
-                        a.1 = self.next_handle();
-                    }
-                }
-            }
-            EntityType::Polyline(ref mut poly) => {
-                for v in poly.__vertices_and_handles.iter_mut() {
-                    if v.1 == AUTO_REPLACE_HANDLE {
-                        v.1 = self.next_handle();
-                    }
-                }
-            }
-            _ => (),
-        }
+        self.assign_entity_handles(&mut entity);
 
         // ensure invariants
         self.add_entity_no_handle_set(entity)
     }
     /// Removes the specified `Entity` from the `Drawing`.
     pub fn remove_entity(&mut self, index: usize) -> Option<Entity> {
-        Drawing::remove_item(&mut self.__entities, index)
+        let entity = Drawing::remove_item(&mut self.__entities, index)?;
+        self.item_removed(entity.common.handle, ItemLocation::Entity(index));
+        Some(entity)
     }
     /// Returns an iterator for all contained objects.
     pub fn objects(&self) -> impl Iterator<Item = &Object> {
@@ -435,6 +617,7 @@ impl Drawing {
     }
     /// Returns an iterator for all mutable objects.
     pub fn objects_mut(&mut self) -> impl Iterator<Item = &mut Object> {
+        self.invalidate_handle_index();
         self.__objects.iter_mut()
     }
     /// Adds an object to the `Drawing`.
@@ -448,7 +631,9 @@ impl Drawing {
     }
     /// Removes the specified `Object` from the `Drawing`.
     pub fn remove_object(&mut self, index: usize) -> Option<Object> {
-        Drawing::remove_item(&mut self.__objects, index)
+        let obj = Drawing::remove_item(&mut self.__objects, index)?;
+        self.item_removed(obj.common.handle, ItemLocation::Object(index));
+        Some(obj)
     }
     /// Clears all items from the `Drawing`.
     pub fn clear(&mut self) {
@@ -466,8 +651,10 @@ impl Drawing {
         self.__entities.clear();
         self.__objects.clear();
         self.thumbnail = None;
+        self.unknown_sections.clear();
+        self.invalidate_handle_index();
 
-        self.header.next_available_handle = 1;
+        self.header.next_available_handle = Handle(1);
     }
     /// Normalizes the `Drawing` by ensuring expected items are present.
     pub fn normalize(&mut self) {
@@ -498,153 +685,1508 @@ impl Drawing {
         self.__ucss.sort_by(|a, b| a.name.cmp(&b.name));
         self.__views.sort_by(|a, b| a.name.cmp(&b.name));
         self.__view_ports.sort_by(|a, b| a.name.cmp(&b.name));
+        self.invalidate_handle_index();
+    }
+    /// Gets a `DrawingItem` with the appropriate handle or `None`.  Items are found via an index
+    /// that's kept up to date as items are added and removed, so this is a constant-time lookup.
+    /// After a mutable iterator such as `entities_mut()` has been used the index is rebuilt once on
+    /// the next lookup.  The empty handle never refers to an item.
+    pub fn get_item_by_handle(&'_ self, handle: Handle) -> Option<DrawingItem<'_>> {
+        let location = self.indexed_location(handle)?;
+        self.item_at(location)
+    }
+    /// Gets a `DrawingItemMut` with the appropriate handle or `None`.
+    pub fn get_item_by_handle_mut(&'_ mut self, handle: Handle) -> Option<DrawingItemMut<'_>> {
+        let location = self.indexed_location(handle)?;
+
+        // the caller may change the item's handle, so check it on the next lookup
+        self.handle_index_mut().lent.push((handle, location));
+        self.item_at_mut(location)
+    }
+    /// Returns `true` if an item with the specified handle is in the `Drawing`.
+    pub fn contains_handle(&self, handle: Handle) -> bool {
+        self.get_item_by_handle(handle).is_some()
+    }
+    /// Gets the `DrawingItem` for each of the specified handles, in order.  Handles that don't refer
+    /// to an item in the `Drawing` produce `None`.
+    pub fn get_items_by_handle<I>(&'_ self, handles: I) -> Vec<Option<DrawingItem<'_>>>
+    where
+        I: IntoIterator<Item = Handle>,
+    {
+        handles
+            .into_iter()
+            .map(|h| self.get_item_by_handle(h))
+            .collect()
+    }
+    /// Gets the entities with the specified handles, in order.  Handles that don't refer to an
+    /// `Entity` are skipped.
+    pub fn get_entities_by_handle<I>(&self, handles: I) -> Vec<&Entity>
+    where
+        I: IntoIterator<Item = Handle>,
+    {
+        handles
+            .into_iter()
+            .filter_map(|h| match self.get_item_by_handle(h) {
+                Some(DrawingItem::Entity(ent)) => Some(ent),
+                _ => None,
+            })
+            .collect()
+    }
+    /// Removes the item with the specified handle along with every reference to it, e.g., owner
+    /// pointers, `Group` members, `SortentsTable` entries and reactors.  `DimensionAssoc` objects
+    /// linked to a removed dimension are removed as well.
+    ///
+    /// Removing a layer, line type, text style, dimension style or block that's still used by entities
+    /// fails with `DxfError::ItemInUse` unless `mode` is `RemovalMode::Cascade`, in which case those
+    /// entities are also removed.  A block and its block record are always removed together.  Layer
+    /// `0`, the `BYLAYER`, `BYBLOCK` and `CONTINUOUS` line types and the model and paper space blocks
+    /// can't be removed and fail with `DxfError::RequiredItem`.
+    ///
+    /// ```
+    /// # use dxf::*;
+    /// # use dxf::entities::*;
+    /// # use dxf::objects::*;
+    /// # fn main() -> DxfResult<()> {
+    /// let mut drawing = Drawing::new();
+    /// let line = drawing.add_entity(Entity::new(EntityType::Line(Default::default()))).common.handle;
+    /// let mut group = Group::default();
+    /// group.__entities_handle.push(line);
+    /// let group = drawing.add_object(Object::new(ObjectType::Group(group))).common.handle;
+    ///
+    /// let report = drawing.remove_by_handle(line, RemovalMode::Strict)?;
+    /// assert_eq!(vec![line], report.removed);
+    /// assert_eq!(vec![group], report.updated);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn remove_by_handle(
+        &mut self,
+        handle: Handle,
+        mode: RemovalMode,
+    ) -> DxfResult<RemovalReport> {
+        let mut report = RemovalReport::default();
+        let location = match self.indexed_location(handle) {
+            Some(location) => location,
+            None => return Ok(report),
+        };
+
+        // items that are used by name
+        if let Some((table, name)) = self.table_item_name(location) {
+            if Drawing::is_required_item(table, &name) {
+                return Err(DxfError::RequiredItem(name));
+            }
+
+            let count = self
+                .entities()
+                .chain(self.blocks().flat_map(|b| b.entities.iter()))
+                .filter(|e| Drawing::entity_uses(e, table, &name))
+                .count();
+            if count > 0 && mode == RemovalMode::Strict {
+                return Err(DxfError::ItemInUse(handle, count));
+            }
+
+            self.remove_item_at(location);
+            report.removed.push(handle);
+            if table == NamedTable::Block {
+                self.remove_block_counterpart(location, &name, &mut report);
+            }
+
+            self.remove_entities_using(table, &name, &mut report);
+        } else {
+            self.remove_item_at(location);
+            report.removed.push(handle);
+        }
+
+        // anything else pointing to a removed item
+        let mut i = 0;
+        while i < report.removed.len() {
+            for dependent in self.remove_references_to(report.removed[i], &mut report.updated) {
+                if let Some(location) = self.indexed_location(dependent) {
+                    self.remove_item_at(location);
+                    report.removed.push(dependent);
+                }
+            }
+
+            i += 1;
+        }
+
+        let mut seen = HashSet::new();
+        let removed = &report.removed;
+        report
+            .updated
+            .retain(|h| !removed.contains(h) && seen.insert(*h));
+        Ok(report)
+    }
+    /// Renames the layer `old_name` to `new_name` and updates every entity, block, object and header
+    /// variable that refers to it by name.  References by handle are unaffected.
+    ///
+    /// Names are compared case-insensitively.  Fails with `DxfError::ItemNotFound` if there is no such
+    /// layer, with `DxfError::DuplicateName` if another layer is already named `new_name` or with
+    /// `DxfError::RequiredItem` when renaming layer `0`.
+    ///
+    /// ```
+    /// # use dxf::*;
+    /// # use dxf::entities::*;
+    /// # fn main() -> DxfResult<()> {
+    /// let mut drawing = Drawing::new();
+    /// let mut line = Entity::new(EntityType::Line(Default::default()));
+    /// line.common.layer = String::from("walls");
+    /// drawing.add_entity(line);
+    ///
+    /// drawing.rename_layer("walls", "exterior-walls")?;
+    /// assert_eq!("exterior-walls", drawing.entities().next().unwrap().common.layer);
+    /// assert!(drawing.layers().any(|l| l.name == "exterior-walls"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn rename_layer(&mut self, old_name: &str, new_name: &str) -> DxfResult<()> {
+        self.rename_table_item(NamedTable::Layer, old_name, new_name)
+    }
+    /// Renames the line type `old_name` to `new_name` and updates every entity, layer, `MLineStyle`
+    /// and header variable that refers to it by name.  The `BYLAYER`, `BYBLOCK` and `CONTINUOUS` line
+    /// types can't be renamed.
+    pub fn rename_line_type(&mut self, old_name: &str, new_name: &str) -> DxfResult<()> {
+        self.rename_table_item(NamedTable::LineType, old_name, new_name)
+    }
+    /// Renames the text style `old_name` to `new_name` and updates every entity, attribute, dimension
+    /// style and header variable that refers to it by name.
+    pub fn rename_text_style(&mut self, old_name: &str, new_name: &str) -> DxfResult<()> {
+        self.rename_table_item(NamedTable::Style, old_name, new_name)
+    }
+    /// Renames the dimension style `old_name` to `new_name` and updates every dimension, leader,
+    /// tolerance and header variable that refers to it by name.
+    pub fn rename_dim_style(&mut self, old_name: &str, new_name: &str) -> DxfResult<()> {
+        self.rename_table_item(NamedTable::DimStyle, old_name, new_name)
+    }
+    /// Renames the block `old_name` to `new_name` along with its block record, and updates every
+    /// insert, dimension, table, dimension style, dimension style override and header variable that
+    /// refers to it by name.
+    pub fn rename_block(&mut self, old_name: &str, new_name: &str) -> DxfResult<()> {
+        self.rename_table_item(NamedTable::Block, old_name, new_name)
+    }
+    /// Removes unused layers, line types, text styles, dimension styles, `MLineStyle`s, blocks and
+    /// objects, similar to AutoCAD's `PURGE` command.  An item is in use if anything refers to it by
+    /// name or by handle.  The defaults created by `normalize()` and the current items named in the
+    /// header, e.g., `$CLAYER`, are never removed.
+    ///
+    /// ```
+    /// # use dxf::*;
+    /// # use dxf::tables::*;
+    /// let mut drawing = Drawing::new();
+    /// drawing.add_layer(Layer {
+    ///     name: String::from("unused"),
+    ///     ..Default::default()
+    /// });
+    ///
+    /// let report = drawing.purge(PurgeOptions::default());
+    /// assert_eq!(vec!["unused"], report.layers);
+    /// assert!(drawing.layers().any(|l| l.name == "0"));
+    /// ```
+    pub fn purge(&mut self, options: PurgeOptions) -> PurgeReport {
+        let mut report = PurgeReport::default();
+        loop {
+            let removed_any = self.purge_once(options, &mut report);
+            if !removed_any || !options.nested {
+                break;
+            }
+        }
+
+        report
+    }
+    /// Computes the extents of all model space entities, or `None` if there is no finite geometry.
+    /// See `Entity::bounding_box` for how each entity is measured.
+    pub fn compute_extents(&self) -> Option<BoundingBox> {
+        self.entities()
+            .filter(|e| !e.common.is_in_paper_space)
+            .filter_map(|e| e.bounding_box(self))
+            .reduce(|a, b| a.union(&b))
+    }
+    /// Computes the extents of all model space entities and stores them in the header's `$EXTMIN`
+    /// and `$EXTMAX` values so the drawing opens zoomed to its contents.  The header is unchanged if
+    /// there is no finite geometry.
+    ///
+    /// ```
+    /// # use dxf::*;
+    /// # use dxf::entities::*;
+    /// let mut drawing = Drawing::new();
+    /// drawing.add_entity(Entity::new(EntityType::Circle(Circle::new(Point::origin(), 2.0))));
+    /// drawing.update_extents();
+    /// assert_eq!(Point::new(-2.0, -2.0, 0.0), drawing.header.minimum_drawing_extents);
+    /// assert_eq!(Point::new(2.0, 2.0, 0.0), drawing.header.maximum_drawing_extents);
+    /// ```
+    pub fn update_extents(&mut self) -> Option<BoundingBox> {
+        let extents = self.compute_extents()?;
+        self.header.minimum_drawing_extents = extents.min.clone();
+        self.header.maximum_drawing_extents = extents.max.clone();
+        Some(extents)
+    }
+    /// Applies `matrix` to every entity in the drawing.  See [`Entity::transform`] for details.
+    ///
+    /// Block definitions aren't modified, so the contents of a transformed `Insert` follow the
+    /// insert itself.
+    pub fn transform_all(&mut self, matrix: &TransformationMatrix) {
+        for ent in self.entities_mut() {
+            ent.transform(matrix);
+        }
+    }
+    /// Returns the world-space entities that the specified `Insert` entity displays.
+    ///
+    /// Each instance of the insert's row/column array gets a copy of the block's entities, moved by
+    /// the block base point, insertion point, scale, rotation, and extrusion of the insert.  Entities
+    /// on layer `0` and BYBLOCK colors, line types, and line weights take the values of the insert,
+    /// and visible attributes, as well as constant attribute definitions from the block, become
+    /// `Text` entities.  Nested inserts are transformed, but not exploded; see
+    /// [`Drawing::flatten_inserts`].
+    ///
+    /// The returned entities haven't been added to the drawing and have no handles.
+    ///
+    /// ```
+    /// # use dxf::*;
+    /// # use dxf::entities::*;
+    /// let mut drawing = Drawing::new();
+    /// drawing.add_block(Block {
+    ///     name: String::from("unit-line"),
+    ///     entities: vec![Entity::new(EntityType::Line(Line::new(
+    ///         Point::origin(),
+    ///         Point::new(1.0, 0.0, 0.0),
+    ///     )))],
+    ///     ..Default::default()
+    /// });
+    /// let insert = Entity::new(EntityType::Insert(Insert {
+    ///     name: String::from("unit-line"),
+    ///     location: Point::new(5.0, 0.0, 0.0),
+    ///     x_scale_factor: 2.0,
+    ///     ..Default::default()
+    /// }));
+    ///
+    /// let exploded = drawing.explode_insert(&insert).unwrap();
+    /// match exploded[0].specific {
+    ///     EntityType::Line(ref line) => assert_eq!(Point::new(7.0, 0.0, 0.0), line.p2),
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn explode_insert(&self, insert: &Entity) -> DxfResult<Vec<Entity>> {
+        let ins = match insert.specific {
+            EntityType::Insert(ref ins) => ins,
+            _ => return Err(DxfError::WrongItemType),
+        };
+        let mut result = vec![];
+        let identity = TransformationMatrix::identity();
+        if self.explode_insert_into(
+            ins,
+            &insert.common,
+            &identity,
+            false,
+            &mut vec![],
+            &mut result,
+        ) {
+            Ok(result)
+        } else {
+            Err(DxfError::ItemNotFound(ins.name.clone()))
+        }
+    }
+    /// Replaces every `Insert` entity in the drawing with the entities it displays, recursively
+    /// exploding nested inserts.  See [`Drawing::explode_insert`] for details.
+    ///
+    /// Inserts of missing or self-referencing blocks are kept.  References to the replaced inserts
+    /// and their attributes, e.g., `Group` members, are removed.  Block definitions aren't modified,
+    /// but the blocks that are no longer used can be removed with [`Drawing::purge`].
+    pub fn flatten_inserts(&mut self) {
+        let entities = std::mem::take(&mut self.__entities);
+        self.invalidate_handle_index();
+        let identity = TransformationMatrix::identity();
+        let mut removed = vec![];
+        for entity in entities {
+            let mut exploded = vec![];
+            let is_exploded = match entity.specific {
+                EntityType::Insert(ref ins) => self.explode_insert_into(
+                    ins,
+                    &entity.common,
+                    &identity,
+                    true,
+                    &mut vec![],
+                    &mut exploded,
+                ),
+                _ => false,
+            };
+            if is_exploded {
+                removed.push(entity.common.handle);
+                if let EntityType::Insert(ref ins) = entity.specific {
+                    removed.extend(ins.__attributes_and_handles.iter().filter_map(|a| a.1));
+                }
+                for ent in exploded {
+                    self.add_entity(ent);
+                }
+            } else {
+                self.add_entity_no_handle_set(entity);
+            }
+        }
+
+        let mut updated = vec![];
+        for handle in removed.into_iter().filter(|h| !h.is_empty()) {
+            self.remove_references_to(handle, &mut updated);
+        }
+    }
+    /// Returns the block with the specified name, ignoring case.
+    pub(crate) fn find_block(&self, name: &str) -> Option<&Block> {
+        self.blocks().find(|b| b.name.eq_ignore_ascii_case(name))
+    }
+    pub(crate) fn assign_and_get_handle(&mut self, item: &mut DrawingItemMut) -> Handle {
+        if item.get_handle().is_empty() {
+            let handle = self.next_handle();
+            item.set_handle(handle);
+        }
+
+        item.get_handle()
+    }
+}
+
+// private implementation
+impl Drawing {
+    pub(crate) fn write_sections_before_entities<T>(
+        &self,
+        write_handles: bool,
+        writer: &mut CodePairWriter<T>,
+    ) -> DxfResult<()>
+    where
+        T: Write + ?Sized,
+    {
+        self.write_unknown_sections(Some("HEADER"), writer)?;
+        self.write_classes(writer)?;
+        self.write_unknown_sections(Some("CLASSES"), writer)?;
+        self.write_tables(write_handles, writer)?;
+        self.write_unknown_sections(Some("TABLES"), writer)?;
+        self.write_blocks(write_handles, writer)?;
+        self.write_unknown_sections(Some("BLOCKS"), writer)?;
+        Ok(())
+    }
+    pub(crate) fn write_sections_after_entities<T>(
+        &self,
+        writer: &mut CodePairWriter<T>,
+    ) -> DxfResult<()>
+    where
+        T: Write + ?Sized,
+    {
+        self.write_unknown_sections(Some("ENTITIES"), writer)?;
+        self.write_objects(writer)?;
+        self.write_unknown_sections(Some("OBJECTS"), writer)?;
+        self.write_thumbnail(writer)?;
+        self.write_unknown_sections(Some("THUMBNAILIMAGE"), writer)?;
+        self.write_trailing_unknown_sections(writer)?;
+        writer.write_code_pair(&CodePair::new_str(0, "EOF"))?;
+        Ok(())
+    }
+    pub(crate) fn assign_entity_handles(&mut self, entity: &mut Entity) {
+        entity.common.handle = self.next_handle();
+
+        // set child handles
+        match entity.specific {
+            EntityType::Insert(ref mut ins) => {
+                for a in ins.__attributes_and_handles.iter_mut() {
+                    if a.1.is_none() {
+                        a.1 = Some(self.next_handle());
+                    }
+                }
+            }
+            EntityType::Polyline(ref mut poly) => {
+                for v in poly.__vertices_and_handles.iter_mut() {
+                    if v.1.is_none() {
+                        v.1 = Some(self.next_handle());
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+    pub(crate) fn next_handle(&mut self) -> Handle {
+        let result = self.header.next_available_handle;
+        self.header.next_available_handle = result.next_handle();
+        result
+    }
+    fn item_at(&'_ self, location: ItemLocation) -> Option<DrawingItem<'_>> {
+        match location {
+            ItemLocation::AppId(i) => self.__app_ids.get(i).map(DrawingItem::AppId),
+            ItemLocation::Block(i) => self.__blocks.get(i).map(DrawingItem::Block),
+            ItemLocation::BlockRecord(i) => {
+                self.__block_records.get(i).map(DrawingItem::BlockRecord)
+            }
+            ItemLocation::DimStyle(i) => self.__dim_styles.get(i).map(DrawingItem::DimStyle),
+            ItemLocation::Entity(i) => self.__entities.get(i).map(DrawingItem::Entity),
+            ItemLocation::Layer(i) => self.__layers.get(i).map(DrawingItem::Layer),
+            ItemLocation::LineType(i) => self.__line_types.get(i).map(DrawingItem::LineType),
+            ItemLocation::Object(i) => self.__objects.get(i).map(DrawingItem::Object),
+            ItemLocation::Style(i) => self.__styles.get(i).map(DrawingItem::Style),
+            ItemLocation::Ucs(i) => self.__ucss.get(i).map(DrawingItem::Ucs),
+            ItemLocation::View(i) => self.__views.get(i).map(DrawingItem::View),
+            ItemLocation::ViewPort(i) => self.__view_ports.get(i).map(DrawingItem::ViewPort),
+        }
+    }
+    fn item_at_mut(&'_ mut self, location: ItemLocation) -> Option<DrawingItemMut<'_>> {
+        match location {
+            ItemLocation::AppId(i) => self.__app_ids.get_mut(i).map(DrawingItemMut::AppId),
+            ItemLocation::Block(i) => self.__blocks.get_mut(i).map(DrawingItemMut::Block),
+            ItemLocation::BlockRecord(i) => self
+                .__block_records
+                .get_mut(i)
+                .map(DrawingItemMut::BlockRecord),
+            ItemLocation::DimStyle(i) => self.__dim_styles.get_mut(i).map(DrawingItemMut::DimStyle),
+            ItemLocation::Entity(i) => self.__entities.get_mut(i).map(DrawingItemMut::Entity),
+            ItemLocation::Layer(i) => self.__layers.get_mut(i).map(DrawingItemMut::Layer),
+            ItemLocation::LineType(i) => self.__line_types.get_mut(i).map(DrawingItemMut::LineType),
+            ItemLocation::Object(i) => self.__objects.get_mut(i).map(DrawingItemMut::Object),
+            ItemLocation::Style(i) => self.__styles.get_mut(i).map(DrawingItemMut::Style),
+            ItemLocation::Ucs(i) => self.__ucss.get_mut(i).map(DrawingItemMut::Ucs),
+            ItemLocation::View(i) => self.__views.get_mut(i).map(DrawingItemMut::View),
+            ItemLocation::ViewPort(i) => self.__view_ports.get_mut(i).map(DrawingItemMut::ViewPort),
+        }
+    }
+    /// Returns the location of the item with the specified handle, bringing the index up to date
+    /// first if needed.
+    fn indexed_location(&self, handle: Handle) -> Option<ItemLocation> {
+        if handle.is_empty() {
+            return None;
+        }
+
+        self.refresh_handle_index();
+        self.read_handle_index().locations.get(&handle).copied()
+    }
+    fn read_handle_index(&self) -> RwLockReadGuard<'_, HandleIndex> {
+        self.__handle_index
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+    fn write_handle_index(&self) -> RwLockWriteGuard<'_, HandleIndex> {
+        self.__handle_index
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+    fn handle_index_mut(&mut self) -> &mut HandleIndex {
+        self.__handle_index
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+    /// Marks the handle index as needing a rebuild before the next lookup.
+    fn invalidate_handle_index(&mut self) {
+        self.handle_index_mut().is_current = false;
+    }
+    /// Applies any changes made through mutable access to the handle index.  Items lent out by
+    /// `get_item_by_handle_mut()` are re-checked individually; anything else rebuilds the index.
+    fn refresh_handle_index(&self) {
+        {
+            let index = self.read_handle_index();
+            if index.is_current && index.lent.is_empty() {
+                return;
+            }
+        }
+
+        let mut index = self.write_handle_index();
+        if !index.is_current {
+            index.locations.clear();
+            index.lent.clear();
+            for location in [
+                ItemLocation::AppId(0),
+                ItemLocation::Block(0),
+                ItemLocation::BlockRecord(0),
+                ItemLocation::DimStyle(0),
+                ItemLocation::Entity(0),
+                ItemLocation::Layer(0),
+                ItemLocation::LineType(0),
+                ItemLocation::Object(0),
+                ItemLocation::Style(0),
+                ItemLocation::Ucs(0),
+                ItemLocation::View(0),
+                ItemLocation::ViewPort(0),
+            ] {
+                self.index_collection(&mut index.locations, location);
+            }
+
+            index.is_current = true;
+            return;
+        }
+
+        for (old_handle, location) in std::mem::take(&mut index.lent) {
+            let handle = match self.item_at(location) {
+                Some(item) => item.get_handle(),
+                None => continue,
+            };
+            if handle != old_handle {
+                if index.locations.get(&old_handle) == Some(&location) {
+                    index.locations.remove(&old_handle);
+                }
+                if !handle.is_empty() {
+                    index.locations.insert(handle, location);
+                }
+            }
+        }
+    }
+    /// Adds the items of one collection to the handle index, starting at the specified location.
+    fn index_items_from(&mut self, location: ItemLocation) {
+        let mut index = self.write_handle_index();
+        if index.is_current {
+            self.index_collection(&mut index.locations, location);
+        }
+    }
+    fn index_collection(&self, index: &mut HashMap<Handle, ItemLocation>, location: ItemLocation) {
+        match location {
+            ItemLocation::AppId(start) => Drawing::index_items(
+                index,
+                self.__app_ids.iter().map(|i| i.handle),
+                start,
+                ItemLocation::AppId,
+            ),
+            ItemLocation::Block(start) => Drawing::index_items(
+                index,
+                self.__blocks.iter().map(|i| i.handle),
+                start,
+                ItemLocation::Block,
+            ),
+            ItemLocation::BlockRecord(start) => Drawing::index_items(
+                index,
+                self.__block_records.iter().map(|i| i.handle),
+                start,
+                ItemLocation::BlockRecord,
+            ),
+            ItemLocation::DimStyle(start) => Drawing::index_items(
+                index,
+                self.__dim_styles.iter().map(|i| i.handle),
+                start,
+                ItemLocation::DimStyle,
+            ),
+            ItemLocation::Entity(start) => Drawing::index_items(
+                index,
+                self.__entities.iter().map(|i| i.common.handle),
+                start,
+                ItemLocation::Entity,
+            ),
+            ItemLocation::Layer(start) => Drawing::index_items(
+                index,
+                self.__layers.iter().map(|i| i.handle),
+                start,
+                ItemLocation::Layer,
+            ),
+            ItemLocation::LineType(start) => Drawing::index_items(
+                index,
+                self.__line_types.iter().map(|i| i.handle),
+                start,
+                ItemLocation::LineType,
+            ),
+            ItemLocation::Object(start) => Drawing::index_items(
+                index,
+                self.__objects.iter().map(|i| i.common.handle),
+                start,
+                ItemLocation::Object,
+            ),
+            ItemLocation::Style(start) => Drawing::index_items(
+                index,
+                self.__styles.iter().map(|i| i.handle),
+                start,
+                ItemLocation::Style,
+            ),
+            ItemLocation::Ucs(start) => Drawing::index_items(
+                index,
+                self.__ucss.iter().map(|i| i.handle),
+                start,
+                ItemLocation::Ucs,
+            ),
+            ItemLocation::View(start) => Drawing::index_items(
+                index,
+                self.__views.iter().map(|i| i.handle),
+                start,
+                ItemLocation::View,
+            ),
+            ItemLocation::ViewPort(start) => Drawing::index_items(
+                index,
+                self.__view_ports.iter().map(|i| i.handle),
+                start,
+                ItemLocation::ViewPort,
+            ),
+        }
+    }
+    fn index_items<I>(
+        index: &mut HashMap<Handle, ItemLocation>,
+        handles: I,
+        start: usize,
+        location: fn(usize) -> ItemLocation,
+    ) where
+        I: Iterator<Item = Handle>,
+    {
+        for (i, handle) in handles.enumerate().skip(start) {
+            if !handle.is_empty() {
+                index.insert(handle, location(i));
+            }
+        }
+    }
+    fn item_removed(&mut self, handle: Handle, location: ItemLocation) {
+        let index = self.handle_index_mut();
+        if !index.lent.is_empty() {
+            // the locations of lent items may have shifted
+            index.is_current = false;
+        }
+        if index.locations.get(&handle) == Some(&location) {
+            index.locations.remove(&handle);
+        }
+
+        // everything after the removed item has shifted down
+        self.index_items_from(location);
+    }
+    fn remove_item_at(&mut self, location: ItemLocation) {
+        match location {
+            ItemLocation::AppId(i) => {
+                self.remove_app_id(i);
+            }
+            ItemLocation::Block(i) => {
+                self.remove_block(i);
+            }
+            ItemLocation::BlockRecord(i) => {
+                self.remove_block_record(i);
+            }
+            ItemLocation::DimStyle(i) => {
+                self.remove_dim_style(i);
+            }
+            ItemLocation::Entity(i) => {
+                self.remove_entity(i);
+            }
+            ItemLocation::Layer(i) => {
+                self.remove_layer(i);
+            }
+            ItemLocation::LineType(i) => {
+                self.remove_line_type(i);
+            }
+            ItemLocation::Object(i) => {
+                self.remove_object(i);
+            }
+            ItemLocation::Style(i) => {
+                self.remove_style(i);
+            }
+            ItemLocation::Ucs(i) => {
+                self.remove_ucs(i);
+            }
+            ItemLocation::View(i) => {
+                self.remove_view(i);
+            }
+            ItemLocation::ViewPort(i) => {
+                self.remove_view_port(i);
+            }
+        }
+    }
+    /// Returns the table and name of the item at the specified location if entities refer to it by
+    /// name.
+    fn table_item_name(&self, location: ItemLocation) -> Option<(NamedTable, String)> {
+        match location {
+            ItemLocation::Block(i) => Some((NamedTable::Block, self.__blocks[i].name.clone())),
+            ItemLocation::BlockRecord(i) => {
+                Some((NamedTable::Block, self.__block_records[i].name.clone()))
+            }
+            ItemLocation::DimStyle(i) => {
+                Some((NamedTable::DimStyle, self.__dim_styles[i].name.clone()))
+            }
+            ItemLocation::Layer(i) => Some((NamedTable::Layer, self.__layers[i].name.clone())),
+            ItemLocation::LineType(i) => {
+                Some((NamedTable::LineType, self.__line_types[i].name.clone()))
+            }
+            ItemLocation::Style(i) => Some((NamedTable::Style, self.__styles[i].name.clone())),
+            _ => None,
+        }
+    }
+    /// Returns `true` if the named table item must always be present in the drawing.
+    fn is_required_item(table: NamedTable, name: &str) -> bool {
+        let name = name.to_uppercase();
+        match table {
+            NamedTable::Block => {
+                name.starts_with("*MODEL_SPACE") || name.starts_with("*PAPER_SPACE")
+            }
+            NamedTable::Layer => name == "0",
+            NamedTable::LineType => name == "BYLAYER" || name == "BYBLOCK" || name == "CONTINUOUS",
+            NamedTable::DimStyle | NamedTable::Style => false,
+        }
+    }
+    fn entity_uses(entity: &Entity, table: NamedTable, name: &str) -> bool {
+        let name_matches = |n: &String| n.eq_ignore_ascii_case(name);
+        match table {
+            NamedTable::Block => entity.block_name().is_some_and(name_matches),
+            NamedTable::DimStyle => entity.dimension_style_name().is_some_and(name_matches),
+            NamedTable::Layer => name_matches(&entity.common.layer),
+            NamedTable::LineType => name_matches(&entity.common.line_type_name),
+            NamedTable::Style => entity.text_style_name().is_some_and(name_matches),
+        }
+    }
+    /// Removes the block record of a removed block or the block of a removed block record.
+    fn remove_block_counterpart(
+        &mut self,
+        location: ItemLocation,
+        name: &str,
+        report: &mut RemovalReport,
+    ) {
+        let handle = match location {
+            ItemLocation::Block(_) => self
+                .__block_records
+                .iter()
+                .position(|r| r.name.eq_ignore_ascii_case(name))
+                .and_then(|i| self.remove_block_record(i))
+                .map(|r| r.handle),
+            _ => self
+                .__blocks
+                .iter()
+                .position(|b| b.name.eq_ignore_ascii_case(name))
+                .and_then(|i| self.remove_block(i))
+                .map(|b| b.handle),
+        };
+        if let Some(handle) = handle.filter(|h| !h.is_empty()) {
+            report.removed.push(handle);
+        }
+    }
+    fn table_item_name_mut(&mut self, table: NamedTable, name: &str) -> Option<&mut String> {
+        match table {
+            NamedTable::Block => self
+                .__blocks
+                .iter_mut()
+                .find(|b| b.name.eq_ignore_ascii_case(name))
+                .map(|b| &mut b.name),
+            NamedTable::DimStyle => self
+                .__dim_styles
+                .iter_mut()
+                .find(|d| d.name.eq_ignore_ascii_case(name))
+                .map(|d| &mut d.name),
+            NamedTable::Layer => self
+                .__layers
+                .iter_mut()
+                .find(|l| l.name.eq_ignore_ascii_case(name))
+                .map(|l| &mut l.name),
+            NamedTable::LineType => self
+                .__line_types
+                .iter_mut()
+                .find(|l| l.name.eq_ignore_ascii_case(name))
+                .map(|l| &mut l.name),
+            NamedTable::Style => self
+                .__styles
+                .iter_mut()
+                .find(|s| s.name.eq_ignore_ascii_case(name))
+                .map(|s| &mut s.name),
+        }
+    }
+    fn rename_table_item(
+        &mut self,
+        table: NamedTable,
+        old_name: &str,
+        new_name: &str,
+    ) -> DxfResult<()> {
+        if self.table_item_name_mut(table, old_name).is_none() {
+            return Err(DxfError::ItemNotFound(String::from(old_name)));
+        }
+
+        if Drawing::is_required_item(table, old_name) {
+            return Err(DxfError::RequiredItem(String::from(old_name)));
+        }
+
+        if old_name == new_name {
+            return Ok(());
+        }
+
+        // changing only the case of the name is allowed
+        if !old_name.eq_ignore_ascii_case(new_name)
+            && self.table_item_name_mut(table, new_name).is_some()
+        {
+            return Err(DxfError::DuplicateName(String::from(new_name)));
+        }
+
+        if let Some(name) = self.table_item_name_mut(table, old_name) {
+            *name = String::from(new_name);
+        }
+
+        self.rename_references(table, old_name, new_name);
+        Ok(())
+    }
+    /// Updates everything that refers to the named table item `old_name` to refer to `new_name`.
+    fn rename_references(&mut self, table: NamedTable, old_name: &str, new_name: &str) {
+        let rename = |name: &mut String| {
+            if name.eq_ignore_ascii_case(old_name) {
+                *name = String::from(new_name);
+            }
+        };
+        for ent in self
+            .__entities
+            .iter_mut()
+            .chain(self.__blocks.iter_mut().flat_map(|b| b.entities.iter_mut()))
+        {
+            Drawing::rename_in_entity(ent, table, old_name, new_name);
+        }
+
+        match table {
+            NamedTable::Block => {
+                self.__block_records
+                    .iter_mut()
+                    .for_each(|r| rename(&mut r.name));
+                for dim_style in self.__dim_styles.iter_mut() {
+                    rename(&mut dim_style.arrow_block_name);
+                    rename(&mut dim_style.first_arrow_block_name);
+                    rename(&mut dim_style.second_arrow_block_name);
+                    rename(&mut dim_style.dimension_leader_block_name);
+                }
+                rename(&mut self.header.arrow_block_name);
+                rename(&mut self.header.first_arrow_block_name);
+                rename(&mut self.header.second_arrow_block_name);
+                rename(&mut self.header.dimension_leader_block_name);
+            }
+            NamedTable::DimStyle => rename(&mut self.header.dimension_style_name),
+            NamedTable::Layer => {
+                for block in self.__blocks.iter_mut() {
+                    rename(&mut block.layer);
+                    for x_data in block.x_data.iter_mut() {
+                        x_data.rename_layer(old_name, new_name);
+                    }
+                }
+                for obj in self.__objects.iter_mut() {
+                    match obj.specific {
+                        ObjectType::LayerFilter(ref mut f) => {
+                            f.layer_names.iter_mut().for_each(rename)
+                        }
+                        ObjectType::LayerIndex(ref mut i) => {
+                            i.layer_names.iter_mut().for_each(rename)
+                        }
+                        _ => (),
+                    }
+                    for x_data in obj.common.x_data.iter_mut() {
+                        x_data.rename_layer(old_name, new_name);
+                    }
+                }
+                rename(&mut self.header.current_layer);
+            }
+            NamedTable::LineType => {
+                self.__layers
+                    .iter_mut()
+                    .for_each(|l| rename(&mut l.line_type_name));
+                for obj in self.__objects.iter_mut() {
+                    if let ObjectType::MLineStyle(ref mut style) = obj.specific {
+                        style
+                            .elements
+                            .iter_mut()
+                            .for_each(|e| rename(&mut e.line_type));
+                    }
+                }
+                rename(&mut self.header.current_entity_line_type);
+            }
+            NamedTable::Style => {
+                self.__dim_styles
+                    .iter_mut()
+                    .for_each(|d| rename(&mut d.dimension_text_style));
+                rename(&mut self.header.text_style);
+                rename(&mut self.header.dimension_text_style);
+            }
+        }
+    }
+    fn rename_in_entity(entity: &mut Entity, table: NamedTable, old_name: &str, new_name: &str) {
+        let rename = |name: Option<&mut String>| match name {
+            Some(name) if name.eq_ignore_ascii_case(old_name) => *name = String::from(new_name),
+            _ => (),
+        };
+        match table {
+            NamedTable::Block => {
+                rename(entity.block_name_mut());
+                for x_data in entity.common.x_data.iter_mut() {
+                    x_data.rename_dimension_style_block(old_name, new_name);
+                }
+            }
+            NamedTable::DimStyle => rename(entity.dimension_style_name_mut()),
+            NamedTable::Layer => {
+                rename(Some(&mut entity.common.layer));
+                for x_data in entity.common.x_data.iter_mut() {
+                    x_data.rename_layer(old_name, new_name);
+                }
+            }
+            NamedTable::LineType => rename(Some(&mut entity.common.line_type_name)),
+            NamedTable::Style => {
+                rename(entity.text_style_name_mut());
+                match entity.specific {
+                    EntityType::Attribute(ref mut att) => {
+                        rename(Some(&mut att.m_text.text_style_name))
+                    }
+                    EntityType::AttributeDefinition(ref mut att) => {
+                        rename(Some(&mut att.m_text.text_style_name))
+                    }
+                    EntityType::Insert(ref mut ins) => {
+                        for att in ins.attributes_mut() {
+                            rename(Some(&mut att.text_style_name));
+                            rename(Some(&mut att.m_text.text_style_name));
+                        }
+                    }
+                    _ => (),
+                }
+            }
+        }
     }
-    /// Gets a `DrawingItem` with the appropriate handle or `None`.
-    pub fn get_item_by_handle(&'_ self, handle: u32) -> Option<DrawingItem<'_>> {
-        for item in &self.__app_ids {
-            if item.handle == handle {
-                return Some(DrawingItem::AppId(item));
+    /// Performs a single purge pass.  Returns `true` if anything was removed.
+    fn purge_once(&mut self, options: PurgeOptions, report: &mut PurgeReport) -> bool {
+        let used = self.used_names();
+        let referenced = self.referenced_handles();
+        let mut removed = vec![];
+        let mut removed_any = false;
+        if options.layers {
+            let names = Drawing::purge_table(
+                &mut self.__layers,
+                |l| (&l.name, l.handle),
+                &used.layers,
+                &referenced,
+                &mut removed,
+            );
+            removed_any |= !names.is_empty();
+            report.layers.extend(names);
+        }
+        if options.line_types {
+            let names = Drawing::purge_table(
+                &mut self.__line_types,
+                |l| (&l.name, l.handle),
+                &used.line_types,
+                &referenced,
+                &mut removed,
+            );
+            removed_any |= !names.is_empty();
+            report.line_types.extend(names);
+        }
+        if options.text_styles {
+            let names = Drawing::purge_table(
+                &mut self.__styles,
+                |s| (&s.name, s.handle),
+                &used.text_styles,
+                &referenced,
+                &mut removed,
+            );
+            removed_any |= !names.is_empty();
+            report.text_styles.extend(names);
+        }
+        if options.dim_styles {
+            let names = Drawing::purge_table(
+                &mut self.__dim_styles,
+                |d| (&d.name, d.handle),
+                &used.dim_styles,
+                &referenced,
+                &mut removed,
+            );
+            removed_any |= !names.is_empty();
+            report.dim_styles.extend(names);
+        }
+        if options.mline_styles {
+            let mut names = vec![];
+            self.__objects.retain(|o| match o.specific {
+                ObjectType::MLineStyle(ref style)
+                    if !used.mline_styles.contains(&style.style_name.to_uppercase()) =>
+                {
+                    names.push(style.style_name.clone());
+                    removed.push(o.common.handle);
+                    false
+                }
+                _ => true,
+            });
+            removed_any |= !names.is_empty();
+            report.mline_styles.extend(names);
+        }
+        if options.blocks {
+            let mut names = vec![];
+            let records = &self.__block_records;
+            self.__blocks.retain(|b| {
+                let name = b.name.to_uppercase();
+                let record_handle = records
+                    .iter()
+                    .find(|r| r.name.to_uppercase() == name)
+                    .map(|r| r.handle);
+                let keep = name.starts_with("*MODEL_SPACE")
+                    || name.starts_with("*PAPER_SPACE")
+                    || b.get_is_xref()
+                    || b.get_is_externally_dependent()
+                    || used.blocks.contains(&name)
+                    || referenced.contains(&b.handle)
+                    || record_handle.is_some_and(|h| referenced.contains(&h));
+                if !keep {
+                    names.push(b.name.clone());
+                    removed.push(b.handle);
+                }
+
+                keep
+            });
+            self.__block_records.retain(|r| {
+                let keep = !names.iter().any(|n| n.eq_ignore_ascii_case(&r.name));
+                if !keep {
+                    removed.push(r.handle);
+                }
+
+                keep
+            });
+            removed_any |= !names.is_empty();
+            report.blocks.extend(names);
+        }
+        if options.orphaned_objects {
+            let mut handles = vec![];
+            self.__objects.retain(|o| {
+                let is_root = o.common.__owner_handle.is_empty()
+                    && matches!(
+                        o.specific,
+                        ObjectType::Dictionary(_) | ObjectType::DictionaryWithDefault(_)
+                    );
+                let keep =
+                    is_root || o.common.handle.is_empty() || referenced.contains(&o.common.handle);
+                if !keep {
+                    handles.push(o.common.handle);
+                }
+
+                keep
+            });
+            removed_any |= !handles.is_empty();
+            removed.extend(handles.iter().cloned());
+            report.objects.extend(handles);
+        }
+
+        if removed_any {
+            self.invalidate_handle_index();
+            let mut updated = vec![];
+            for handle in removed.into_iter().filter(|h| !h.is_empty()) {
+                self.remove_references_to(handle, &mut updated);
+            }
+        }
+
+        removed_any
+    }
+    /// Removes the unused items from a table.  Returns the names of the removed items.
+    fn purge_table<T, F>(
+        items: &mut Vec<T>,
+        name_and_handle: F,
+        used: &HashSet<String>,
+        referenced: &HashSet<Handle>,
+        removed: &mut Vec<Handle>,
+    ) -> Vec<String>
+    where
+        F: Fn(&T) -> (&String, Handle),
+    {
+        let mut names = vec![];
+        items.retain(|item| {
+            let (name, handle) = name_and_handle(item);
+            let keep = used.contains(&name.to_uppercase()) || referenced.contains(&handle);
+            if !keep {
+                names.push(name.clone());
+                removed.push(handle);
+            }
+
+            keep
+        });
+
+        names
+    }
+    /// Collects the names of everything that's referred to by name, including the defaults that
+    /// `normalize()` ensures.
+    fn used_names(&self) -> UsedNames {
+        let mut used = UsedNames::default();
+        let use_name = |names: &mut HashSet<String>, name: &str| {
+            names.insert(name.to_uppercase());
+        };
+        for name in ["0", &self.header.current_layer] {
+            use_name(&mut used.layers, name);
+        }
+        for name in [
+            "BYLAYER",
+            "BYBLOCK",
+            "CONTINUOUS",
+            &self.header.current_entity_line_type,
+        ] {
+            use_name(&mut used.line_types, name);
+        }
+        for name in [
+            "STANDARD",
+            "ANNOTATIVE",
+            &self.header.text_style,
+            &self.header.dimension_text_style,
+        ] {
+            use_name(&mut used.text_styles, name);
+        }
+        for name in ["STANDARD", "ANNOTATIVE", &self.header.dimension_style_name] {
+            use_name(&mut used.dim_styles, name);
+        }
+        for name in ["STANDARD", &self.header.current_multiline_style] {
+            use_name(&mut used.mline_styles, name);
+        }
+        for name in [
+            &self.header.arrow_block_name,
+            &self.header.first_arrow_block_name,
+            &self.header.second_arrow_block_name,
+            &self.header.dimension_leader_block_name,
+        ] {
+            use_name(&mut used.blocks, name);
+        }
+
+        for ent in self
+            .entities()
+            .chain(self.blocks().flat_map(|b| b.entities.iter()))
+        {
+            use_name(&mut used.layers, &ent.common.layer);
+            use_name(&mut used.line_types, &ent.common.line_type_name);
+            if let Some(name) = ent.text_style_name() {
+                use_name(&mut used.text_styles, name);
+            }
+            if let Some(name) = ent.dimension_style_name() {
+                use_name(&mut used.dim_styles, name);
+            }
+            if let Some(name) = ent.block_name() {
+                use_name(&mut used.blocks, name);
+            }
+            for x_data in &ent.common.x_data {
+                for name in x_data.dimension_style_block_names() {
+                    use_name(&mut used.blocks, name);
+                }
+            }
+            match ent.specific {
+                EntityType::Attribute(ref att) => {
+                    use_name(&mut used.text_styles, &att.m_text.text_style_name)
+                }
+                EntityType::AttributeDefinition(ref att) => {
+                    use_name(&mut used.text_styles, &att.m_text.text_style_name)
+                }
+                EntityType::Insert(ref ins) => {
+                    for att in ins.attributes() {
+                        use_name(&mut used.text_styles, &att.text_style_name);
+                        use_name(&mut used.text_styles, &att.m_text.text_style_name);
+                    }
+                }
+                EntityType::MLine(ref mline) => use_name(&mut used.mline_styles, &mline.style_name),
+                _ => (),
             }
         }
-        for item in &self.__blocks {
-            if item.handle == handle {
-                return Some(DrawingItem::Block(item));
+        for block in self.blocks() {
+            use_name(&mut used.layers, &block.layer);
+        }
+        for layer in self.layers() {
+            use_name(&mut used.line_types, &layer.line_type_name);
+        }
+        for dim_style in self.dim_styles() {
+            use_name(&mut used.text_styles, &dim_style.dimension_text_style);
+            for name in [
+                &dim_style.arrow_block_name,
+                &dim_style.first_arrow_block_name,
+                &dim_style.second_arrow_block_name,
+                &dim_style.dimension_leader_block_name,
+            ] {
+                use_name(&mut used.blocks, name);
             }
         }
-        for item in &self.__block_records {
-            if item.handle == handle {
-                return Some(DrawingItem::BlockRecord(item));
+        for obj in self.objects() {
+            match obj.specific {
+                ObjectType::LayerFilter(ref f) => {
+                    for name in &f.layer_names {
+                        use_name(&mut used.layers, name);
+                    }
+                }
+                ObjectType::LayerIndex(ref i) => {
+                    for name in &i.layer_names {
+                        use_name(&mut used.layers, name);
+                    }
+                }
+                ObjectType::MLineStyle(ref style) => {
+                    for element in &style.elements {
+                        use_name(&mut used.line_types, &element.line_type);
+                    }
+                }
+                _ => (),
             }
         }
-        for item in &self.__dim_styles {
-            if item.handle == handle {
-                return Some(DrawingItem::DimStyle(item));
+
+        used
+    }
+    /// Collects the handles of every item that's referred to by another item or the header.
+    fn referenced_handles(&self) -> HashSet<Handle> {
+        let mut handles = vec![
+            self.header.solid_visual_style_pointer,
+            self.header.new_object_ploy_style_handle,
+            self.header.current_material_handle,
+            self.header.interference_object_visual_style_pointer,
+            self.header.interference_view_port_visual_style_pointer,
+        ];
+        for ent in self.entities() {
+            ent.collect_references(&mut handles);
+        }
+        for block in self.blocks() {
+            block.collect_references(&mut handles);
+        }
+        for obj in self.objects() {
+            // an object referring to itself doesn't keep it alive
+            let start = handles.len();
+            obj.collect_references(&mut handles);
+            let mut i = start;
+            while i < handles.len() {
+                if handles[i] == obj.common.handle {
+                    handles.swap_remove(i);
+                } else {
+                    i += 1;
+                }
             }
         }
-        for item in &self.__entities {
-            if item.common.handle == handle {
-                return Some(DrawingItem::Entity(item));
+        for item in self.app_ids() {
+            item.collect_references(&mut handles);
+        }
+        for item in self.block_records() {
+            item.collect_references(&mut handles);
+        }
+        for item in self.dim_styles() {
+            item.collect_references(&mut handles);
+            // the text style may be stored by handle
+            if let Ok(handle) = Handle::parse(&item.dimension_text_style, 0) {
+                handles.push(handle);
             }
         }
-        for item in &self.__layers {
-            if item.handle == handle {
-                return Some(DrawingItem::Layer(item));
+        for item in self.layers() {
+            item.collect_references(&mut handles);
+        }
+        for item in self.line_types() {
+            item.collect_references(&mut handles);
+        }
+        for item in self.styles() {
+            item.collect_references(&mut handles);
+        }
+        for item in self.ucss() {
+            item.collect_references(&mut handles);
+        }
+        for item in self.views() {
+            item.collect_references(&mut handles);
+        }
+        for item in self.view_ports() {
+            item.collect_references(&mut handles);
+        }
+
+        handles.into_iter().filter(|h| !h.is_empty()).collect()
+    }
+    /// Removes the entities using the named table item.  Non-entities using it are reset to the
+    /// default, e.g., blocks on a removed layer are moved to layer `0`.
+    fn remove_entities_using(&mut self, table: NamedTable, name: &str, report: &mut RemovalReport) {
+        let mut removed = vec![];
+        self.__entities.retain(|e| {
+            let uses = Drawing::entity_uses(e, table, name);
+            if uses {
+                removed.push(e.common.handle);
+            }
+
+            !uses
+        });
+        for block in self.__blocks.iter_mut() {
+            block.entities.retain(|e| {
+                let uses = Drawing::entity_uses(e, table, name);
+                if uses {
+                    removed.push(e.common.handle);
+                }
+
+                !uses
+            });
+            if table == NamedTable::Layer && block.layer.eq_ignore_ascii_case(name) {
+                block.layer = String::from("0");
+                report.updated.push(block.handle);
+            }
+        }
+
+        if table == NamedTable::LineType {
+            for layer in self.__layers.iter_mut() {
+                if layer.line_type_name.eq_ignore_ascii_case(name) {
+                    layer.line_type_name = String::from("CONTINUOUS");
+                    report.updated.push(layer.handle);
+                }
             }
         }
-        for item in &self.__line_types {
-            if item.handle == handle {
-                return Some(DrawingItem::LineType(item));
+
+        if !removed.is_empty() {
+            self.invalidate_handle_index();
+        }
+
+        report
+            .removed
+            .extend(removed.into_iter().filter(|h| !h.is_empty()));
+    }
+    /// Removes all pointers to the specified handle, recording the updated items.  Returns the handles
+    /// of the items that can't exist without it, e.g., the `DimensionAssoc` of a removed dimension.
+    fn remove_references_to(&mut self, handle: Handle, updated: &mut Vec<Handle>) -> Vec<Handle> {
+        let dependents = self
+            .objects()
+            .filter(|o| match o.specific {
+                ObjectType::DimensionAssoc(ref assoc) => assoc.__dimension_handle == handle,
+                _ => false,
+            })
+            .map(|o| o.common.handle)
+            .collect::<Vec<_>>();
+
+        for ent in self.__entities.iter_mut() {
+            if ent.remove_references_to(handle) {
+                updated.push(ent.common.handle);
             }
         }
-        for item in &self.__objects {
-            if item.common.handle == handle {
-                return Some(DrawingItem::Object(item));
+        for obj in self.__objects.iter_mut() {
+            if obj.remove_references_to(handle) {
+                updated.push(obj.common.handle);
             }
         }
-        for item in &self.__styles {
-            if item.handle == handle {
-                return Some(DrawingItem::Style(item));
+        for block in self.__blocks.iter_mut() {
+            if block.remove_references_to(handle) {
+                updated.push(block.handle);
             }
         }
-        for item in &self.__ucss {
-            if item.handle == handle {
-                return Some(DrawingItem::Ucs(item));
+        for item in self.__app_ids.iter_mut() {
+            if item.remove_references_to(handle) {
+                updated.push(item.handle);
             }
         }
-        for item in &self.__views {
-            if item.handle == handle {
-                return Some(DrawingItem::View(item));
+        for item in self.__block_records.iter_mut() {
+            if item.remove_references_to(handle) {
+                updated.push(item.handle);
             }
         }
-        for item in &self.__view_ports {
-            if item.handle == handle {
-                return Some(DrawingItem::ViewPort(item));
+        for item in self.__dim_styles.iter_mut() {
+            if item.remove_references_to(handle) {
+                updated.push(item.handle);
             }
         }
-
-        None
-    }
-    /// Gets a `DrawingItemMut` with the appropriate handle or `None`.
-    pub fn get_item_by_handle_mut(&'_ mut self, handle: u32) -> Option<DrawingItemMut<'_>> {
-        for item in &mut self.__app_ids {
-            if item.handle == handle {
-                return Some(DrawingItemMut::AppId(item));
+        for item in self.__layers.iter_mut() {
+            if item.remove_references_to(handle) {
+                updated.push(item.handle);
             }
         }
-        for item in &mut self.__blocks {
-            if item.handle == handle {
-                return Some(DrawingItemMut::Block(item));
+        for item in self.__line_types.iter_mut() {
+            if item.remove_references_to(handle) {
+                updated.push(item.handle);
             }
         }
-        for item in &mut self.__block_records {
-            if item.handle == handle {
-                return Some(DrawingItemMut::BlockRecord(item));
+        for item in self.__styles.iter_mut() {
+            if item.remove_references_to(handle) {
+                updated.push(item.handle);
             }
         }
-        for item in &mut self.__dim_styles {
-            if item.handle == handle {
-                return Some(DrawingItemMut::DimStyle(item));
+        for item in self.__ucss.iter_mut() {
+            if item.remove_references_to(handle) {
+                updated.push(item.handle);
             }
         }
-        for item in &mut self.__entities {
-            if item.common.handle == handle {
-                return Some(DrawingItemMut::Entity(item));
+        for item in self.__views.iter_mut() {
+            if item.remove_references_to(handle) {
+                updated.push(item.handle);
             }
         }
-        for item in &mut self.__layers {
-            if item.handle == handle {
-                return Some(DrawingItemMut::Layer(item));
+        for item in self.__view_ports.iter_mut() {
+            if item.remove_references_to(handle) {
+                updated.push(item.handle);
             }
         }
-        for item in &mut self.__line_types {
-            if item.handle == handle {
-                return Some(DrawingItemMut::LineType(item));
-            }
+
+        dependents
+    }
+    /// Appends the entities displayed by `insert` to `result`, transformed by `matrix`.  Returns
+    /// `false` if the block is missing or already being exploded.
+    fn explode_insert_into<'a>(
+        &'a self,
+        insert: &Insert,
+        properties: &EntityCommon,
+        matrix: &TransformationMatrix,
+        recursive: bool,
+        blocks_in_progress: &mut Vec<&'a str>,
+        result: &mut Vec<Entity>,
+    ) -> bool {
+        let block = match self.find_block(&insert.name) {
+            Some(block) => block,
+            None => return false,
+        };
+        if blocks_in_progress
+            .iter()
+            .any(|name| name.eq_ignore_ascii_case(&block.name))
+        {
+            return false;
         }
-        for item in &mut self.__objects {
-            if item.common.handle == handle {
-                return Some(DrawingItemMut::Object(item));
+
+        blocks_in_progress.push(&block.name);
+        for block_matrix in insert.block_transformations(&block.base_point) {
+            let transform = *matrix * block_matrix;
+            for block_entity in &block.entities {
+                let mut entity = block_entity.clone();
+                Drawing::resolve_block_properties(&mut entity.common, properties);
+                match entity.specific {
+                    // nested inserts that can't be exploded are kept
+                    EntityType::Insert(ref nested)
+                        if recursive
+                            && self.explode_insert_into(
+                                nested,
+                                &entity.common,
+                                &transform,
+                                recursive,
+                                blocks_in_progress,
+                                result,
+                            ) =>
+                    {
+                        continue;
+                    }
+                    EntityType::AttributeDefinition(ref att) => {
+                        // only constant attribute definitions are displayed by the insert
+                        if !att.get_is_constant() || att.get_is_invisible() {
+                            continue;
+                        }
+
+                        entity.specific = EntityType::Text(att.to_text());
+                    }
+                    _ => (),
+                }
+
+                Drawing::clear_entity_handles(&mut entity);
+                entity.transform(&transform);
+                result.push(entity);
             }
         }
-        for item in &mut self.__styles {
-            if item.handle == handle {
-                return Some(DrawingItemMut::Style(item));
+
+        blocks_in_progress.pop();
+
+        // attributes are already placed relative to the insert's container
+        for att in insert.attributes() {
+            if !att.get_is_invisible() {
+                let mut text = Entity {
+                    common: properties.clone(),
+                    specific: EntityType::Text(att.to_text()),
+                };
+                Drawing::clear_entity_handles(&mut text);
+                text.transform(matrix);
+                result.push(text);
             }
         }
-        for item in &mut self.__ucss {
-            if item.handle == handle {
-                return Some(DrawingItemMut::Ucs(item));
-            }
+
+        true
+    }
+    fn resolve_block_properties(common: &mut EntityCommon, insert: &EntityCommon) {
+        if common.layer == "0" {
+            common.layer = insert.layer.clone();
         }
-        for item in &mut self.__views {
-            if item.handle == handle {
-                return Some(DrawingItemMut::View(item));
-            }
+
+        if common.color.is_by_block() {
+            common.color = insert.color.clone();
+            common.color_24_bit = insert.color_24_bit;
+            common.color_name = insert.color_name.clone();
         }
-        for item in &mut self.__view_ports {
-            if item.handle == handle {
-                return Some(DrawingItemMut::ViewPort(item));
-            }
+
+        if common.line_type_name.eq_ignore_ascii_case("BYBLOCK") {
+            common.line_type_name = insert.line_type_name.clone();
         }
 
-        None
-    }
-    pub(crate) fn assign_and_get_handle(&mut self, item: &mut DrawingItemMut) -> u32 {
-        if item.get_handle() == 0 {
-            item.set_handle(self.header.next_available_handle);
-            self.header.next_available_handle += 1;
+        if LineWeight::from_raw_value(common.lineweight_enum_value).is_by_block() {
+            common.lineweight_enum_value = insert.lineweight_enum_value;
         }
 
-        item.get_handle()
+        common.is_in_paper_space = insert.is_in_paper_space;
     }
-}
+    fn clear_entity_handles(entity: &mut Entity) {
+        entity.common.handle = Handle::empty();
+        entity.common.__owner_handle = Handle::empty();
 
-// private implementation
-impl Drawing {
-    pub(crate) fn next_handle(&mut self) -> u32 {
-        let result = self.header.next_available_handle;
-        self.header.next_available_handle += 1;
-        result
+        // the copies can't share the original's extension dictionary or reactors
+        entity.common.extension_data_groups.retain(|g| {
+            g.application_name != "ACAD_XDICTIONARY" && g.application_name != "ACAD_REACTORS"
+        });
+        match entity.specific {
+            EntityType::Insert(ref mut ins) => {
+                for a in ins.__attributes_and_handles.iter_mut() {
+                    a.1 = None;
+                }
+            }
+            EntityType::Polyline(ref mut poly) => {
+                for v in poly.__vertices_and_handles.iter_mut() {
+                    v.1 = None;
+                }
+            }
+            _ => (),
+        }
     }
     fn remove_item<T>(collection: &mut Vec<T>, index: usize) -> Option<T> {
         if index < collection.len() {
@@ -657,6 +2199,7 @@ impl Drawing {
         self.ensure_layer_is_present_for_block(&block);
         self.ensure_line_type_is_present_for_block(&block);
         self.__blocks.push(block);
+        self.index_items_from(ItemLocation::Block(self.__blocks.len() - 1));
         self.__blocks.last().unwrap()
     }
     fn add_entity_no_handle_set(&mut self, entity: Entity) -> &Entity {
@@ -666,6 +2209,7 @@ impl Drawing {
         self.ensure_line_type_is_present(&entity.common.line_type_name);
         self.ensure_text_style_is_present_for_entity(&entity);
         self.__entities.push(entity);
+        self.index_items_from(ItemLocation::Entity(self.__entities.len() - 1));
         self.__entities.last().unwrap()
     }
     fn add_object_no_handle_set(&mut self, obj: Object) -> &Object {
@@ -674,11 +2218,13 @@ impl Drawing {
         self.ensure_text_style_is_present_for_object(&obj);
         self.ensure_view_is_present(&obj);
         self.__objects.push(obj);
+        self.index_items_from(ItemLocation::Object(self.__objects.len() - 1));
         self.__objects.last().unwrap()
     }
     pub(crate) fn add_app_id_no_handle_set(&mut self, app_id: AppId) -> &AppId {
         // TODO: ensure invariants
         self.__app_ids.push(app_id);
+        self.index_items_from(ItemLocation::AppId(self.__app_ids.len() - 1));
         self.__app_ids.last().unwrap()
     }
     pub(crate) fn add_block_record_no_handle_set(
@@ -687,41 +2233,49 @@ impl Drawing {
     ) -> &BlockRecord {
         // TODO: ensure invariants
         self.__block_records.push(block_record);
+        self.index_items_from(ItemLocation::BlockRecord(self.__block_records.len() - 1));
         self.__block_records.last().unwrap()
     }
     pub(crate) fn add_dim_style_no_handle_set(&mut self, dim_style: DimStyle) -> &DimStyle {
         // TODO: ensure invariants
         self.__dim_styles.push(dim_style);
+        self.index_items_from(ItemLocation::DimStyle(self.__dim_styles.len() - 1));
         self.__dim_styles.last().unwrap()
     }
     pub(crate) fn add_layer_no_handle_set(&mut self, layer: Layer) -> &Layer {
         self.ensure_line_type_is_present(&layer.line_type_name);
         self.__layers.push(layer);
+        self.index_items_from(ItemLocation::Layer(self.__layers.len() - 1));
         self.__layers.last().unwrap()
     }
     pub(crate) fn add_line_type_no_handle_set(&mut self, line_type: LineType) -> &LineType {
         // TODO: ensure invariants
         self.__line_types.push(line_type);
+        self.index_items_from(ItemLocation::LineType(self.__line_types.len() - 1));
         self.__line_types.last().unwrap()
     }
     pub(crate) fn add_style_no_handle_set(&mut self, style: Style) -> &Style {
         // TODO: ensure invariants
         self.__styles.push(style);
+        self.index_items_from(ItemLocation::Style(self.__styles.len() - 1));
         self.__styles.last().unwrap()
     }
     pub(crate) fn add_ucs_no_handle_set(&mut self, ucs: Ucs) -> &Ucs {
         // TODO: ensure invariants
         self.__ucss.push(ucs);
+        self.index_items_from(ItemLocation::Ucs(self.__ucss.len() - 1));
         self.__ucss.last().unwrap()
     }
     pub(crate) fn add_view_no_handle_set(&mut self, view: View) -> &View {
         // TODO: ensure invariants
         self.__views.push(view);
+        self.index_items_from(ItemLocation::View(self.__views.len() - 1));
         self.__views.last().unwrap()
     }
     pub(crate) fn add_view_port_no_handle_set(&mut self, view_port: ViewPort) -> &ViewPort {
         // TODO: ensure invariants
         self.__view_ports.push(view_port);
+        self.index_items_from(ItemLocation::ViewPort(self.__view_ports.len() - 1));
         self.__view_ports.last().unwrap()
     }
     fn ensure_app_id_is_present(&mut self, name: &str) {
@@ -755,19 +2309,7 @@ impl Drawing {
     }
     fn ensure_dimension_style_is_present_for_entity(&mut self, entity: &Entity) {
         // ensure corresponding dimension style is present
-        let dim_style_name = match &entity.specific {
-            EntityType::RotatedDimension(ref d) => Some(&d.dimension_base.dimension_style_name),
-            EntityType::RadialDimension(ref d) => Some(&d.dimension_base.dimension_style_name),
-            EntityType::DiameterDimension(ref d) => Some(&d.dimension_base.dimension_style_name),
-            EntityType::AngularThreePointDimension(ref d) => {
-                Some(&d.dimension_base.dimension_style_name)
-            }
-            EntityType::OrdinateDimension(ref d) => Some(&d.dimension_base.dimension_style_name),
-            EntityType::Leader(ref l) => Some(&l.dimension_style_name),
-            EntityType::Tolerance(ref t) => Some(&t.dimension_style_name),
-            _ => None,
-        };
-        if let Some(dim_style_name) = dim_style_name {
+        if let Some(dim_style_name) = entity.dimension_style_name() {
             self.ensure_dimension_style_is_present(&dim_style_name);
         }
     }
@@ -827,15 +2369,7 @@ impl Drawing {
         }
     }
     fn ensure_text_style_is_present_for_entity(&mut self, entity: &Entity) {
-        let text_style_name = match &entity.specific {
-            EntityType::ArcAlignedText(ref e) => Some(&e.text_style_name),
-            EntityType::Attribute(ref e) => Some(&e.text_style_name),
-            EntityType::AttributeDefinition(ref e) => Some(&e.text_style_name),
-            EntityType::MText(ref e) => Some(&e.text_style_name),
-            EntityType::Text(ref e) => Some(&e.text_style_name),
-            _ => None,
-        };
-        if let Some(text_style_name) = text_style_name {
+        if let Some(text_style_name) = entity.text_style_name() {
             self.ensure_text_style_is_present(&text_style_name);
         }
     }
@@ -976,10 +2510,63 @@ impl Drawing {
         }
         Ok(())
     }
-    fn read_sections<T>(drawing: &mut Drawing, iter: &mut CodePairPutBack<T>) -> DxfResult<()>
+    /// Writes the unknown sections that follow `preceding_section`, or that come before `HEADER` if
+    /// `None`.
+    pub(crate) fn write_unknown_sections<T>(
+        &self,
+        preceding_section: Option<&str>,
+        writer: &mut CodePairWriter<T>,
+    ) -> DxfResult<()>
+    where
+        T: Write + ?Sized,
+    {
+        for section in &self.unknown_sections {
+            let is_here = match (&section.position, preceding_section) {
+                (SectionPosition::BeforeHeader, None) => true,
+                (SectionPosition::After(name), Some(preceding_section)) => {
+                    name == preceding_section
+                }
+                _ => false,
+            };
+            if is_here {
+                section.write(writer)?;
+            }
+        }
+
+        Ok(())
+    }
+    /// Writes the unknown sections that go at the end or don't follow a supported section.
+    fn write_trailing_unknown_sections<T>(&self, writer: &mut CodePairWriter<T>) -> DxfResult<()>
+    where
+        T: Write + ?Sized,
+    {
+        for section in &self.unknown_sections {
+            match section.position {
+                SectionPosition::AtEnd => section.write(writer)?,
+                SectionPosition::After(ref name) if !SUPPORTED_SECTIONS.contains(&&**name) => {
+                    section.write(writer)?
+                }
+                _ => (),
+            }
+        }
+
+        Ok(())
+    }
+    /// Reads all sections, returning `true` if reading stopped before `0/EOF` because every section
+    /// requested by the filter had already been read.
+    fn read_sections<T>(
+        drawing: &mut Drawing,
+        iter: &mut CodePairPutBack<T>,
+        filter: &SectionFilter,
+    ) -> DxfResult<bool>
     where
         T: Read,
     {
+        let mut preceding_section = None;
+        let mut remaining_sections = SUPPORTED_SECTIONS
+            .iter()
+            .filter(|name| filter.includes(name))
+            .collect::<Vec<_>>();
         loop {
             match iter.next() {
                 Some(Ok(pair @ CodePair { code: 0, .. })) => match &*pair.assert_string()? {
@@ -994,7 +2581,14 @@ impl Drawing {
                             ..
                         })) => {
                             match &*s {
-                                "HEADER" => drawing.header = Header::read(iter)?,
+                                // the header is always read since it determines the text encoding
+                                "HEADER" => {
+                                    let header = Header::read(iter)?;
+                                    if filter.header {
+                                        drawing.header = header;
+                                    }
+                                }
+                                _ if !filter.includes(&s) => iter.skip_section()?,
                                 "CLASSES" => Class::read_classes(drawing, iter)?,
                                 "TABLES" => {
                                     drawing.read_section_item(iter, "TABLE", read_specific_table)?
@@ -1007,7 +2601,16 @@ impl Drawing {
                                 "THUMBNAILIMAGE" => {
                                     let _ = drawing.read_thumbnail(iter)?;
                                 }
-                                _ => Drawing::swallow_section(iter)?,
+                                _ => {
+                                    let section =
+                                        UnknownSection::read(&s, preceding_section, iter)?;
+                                    drawing.unknown_sections.push(section);
+                                }
+                            }
+
+                            if let Some(name) = SUPPORTED_SECTIONS.iter().find(|name| **name == s) {
+                                preceding_section = Some(name);
+                                remaining_sections.retain(|n| *n != name);
                             }
 
                             match iter.next() {
@@ -1025,6 +2628,10 @@ impl Drawing {
                                 Some(Err(e)) => return Err(e),
                                 None => return Err(DxfError::UnexpectedEndOfInput),
                             }
+
+                            if remaining_sections.is_empty() && !filter.unknown_sections {
+                                return Ok(true);
+                            }
                         }
                         Some(Ok(pair)) => {
                             return Err(DxfError::UnexpectedCodePair(
@@ -1053,36 +2660,17 @@ impl Drawing {
             }
         }
 
-        Ok(())
-    }
-    fn swallow_section<T>(iter: &mut CodePairPutBack<T>) -> DxfResult<()>
-    where
-        T: Read,
-    {
-        loop {
-            match iter.next() {
-                Some(Ok(pair)) => {
-                    if pair.code == 0 && pair.assert_string()? == "ENDSEC" {
-                        iter.put_back(Ok(pair));
-                        break;
-                    }
-                }
-                Some(Err(e)) => return Err(e),
-                None => break,
-            }
-        }
-
-        Ok(())
+        Ok(false)
     }
     fn read_entities<T>(&mut self, iter: &mut CodePairPutBack<T>) -> DxfResult<()>
     where
         T: Read,
     {
-        let mut iter = EntityIter { iter };
+        let mut iter = EntityIter::new(iter);
         let mut entities = vec![];
         iter.read_entities_into_vec(&mut entities)?;
         for e in entities {
-            if e.common.handle == 0 {
+            if e.common.handle.is_empty() {
                 self.add_entity(e);
             } else {
                 self.add_entity_no_handle_set(e);
@@ -1094,16 +2682,16 @@ impl Drawing {
     where
         T: Read,
     {
-        let iter = put_back(ObjectIter { iter });
-        for o in iter {
-            if o.common.handle == 0 {
+        let mut iter = ObjectIter::new(iter);
+        for o in put_back(&mut iter) {
+            if o.common.handle.is_empty() {
                 self.add_object(o);
             } else {
                 self.add_object_no_handle_set(o);
             }
         }
 
-        Ok(())
+        iter.take_error()
     }
     fn read_thumbnail<T>(&mut self, iter: &mut CodePairPutBack<T>) -> DxfResult<bool>
     where
@@ -1347,6 +2935,7 @@ mod tests {
     use crate::objects::*;
     use crate::tables::*;
     use crate::*;
+    use std::io::Cursor;
 
     #[test]
     fn default_layers_are_present() {
@@ -1388,10 +2977,10 @@ mod tests {
     fn block_handle_is_set_on_add() {
         let mut drawing = Drawing::new();
         let block = Block::default();
-        assert_eq!(0, block.handle);
+        assert!(block.handle.is_empty());
 
         let block = drawing.add_block(block);
-        assert_ne!(0, block.handle);
+        assert!(!block.handle.is_empty());
     }
 
     #[test]
@@ -1401,10 +2990,10 @@ mod tests {
             common: Default::default(),
             specific: EntityType::Line(Default::default()),
         };
-        assert_eq!(0, ent.common.handle);
+        assert!(ent.common.handle.is_empty());
 
         let ent = drawing.add_entity(ent);
-        assert_ne!(0, ent.common.handle);
+        assert!(!ent.common.handle.is_empty());
     }
 
     #[test]
@@ -1414,10 +3003,10 @@ mod tests {
             common: Default::default(),
             specific: ObjectType::PlaceHolder(Default::default()),
         };
-        assert_eq!(0, obj.common.handle);
+        assert!(obj.common.handle.is_empty());
 
         let obj = drawing.add_object(obj);
-        assert_ne!(0, obj.common.handle);
+        assert!(!obj.common.handle.is_empty());
     }
 
     #[test]
@@ -1425,10 +3014,10 @@ mod tests {
         let mut drawing = Drawing::new();
         drawing.clear();
         let layer = Layer::default();
-        assert_eq!(0, layer.handle);
+        assert!(layer.handle.is_empty());
 
         let layer = drawing.add_layer(layer);
-        assert_ne!(0, layer.handle);
+        assert!(!layer.handle.is_empty());
     }
 
     #[test]
@@ -1442,7 +3031,7 @@ mod tests {
             .as_str(),
         );
         let block = drawing.blocks().next().unwrap();
-        assert_ne!(0, block.handle);
+        assert!(!block.handle.is_empty());
     }
 
     #[test]
@@ -1455,7 +3044,7 @@ mod tests {
             .as_str(),
         );
         let line = drawing.entities().next().unwrap();
-        assert_ne!(0, line.common.handle);
+        assert!(!line.common.handle.is_empty());
     }
 
     #[test]
@@ -1477,7 +3066,7 @@ mod tests {
             .as_str(),
         );
         let obj = drawing.objects().next().unwrap();
-        assert_ne!(0, obj.common.handle);
+        assert!(!obj.common.handle.is_empty());
     }
 
     #[test]
@@ -1491,7 +3080,7 @@ mod tests {
             .as_str(),
         );
         let layer = drawing.layers().next().unwrap();
-        assert_ne!(0, layer.handle);
+        assert!(!layer.handle.is_empty());
     }
 
     #[test]
@@ -1505,7 +3094,7 @@ mod tests {
             .as_str(),
         );
         let block = drawing.blocks().next().unwrap();
-        assert_eq!(0x3333, block.handle);
+        assert_eq!(Handle(0x3333), block.handle);
     }
 
     #[test]
@@ -1519,7 +3108,7 @@ mod tests {
             .as_str(),
         );
         let line = drawing.entities().next().unwrap();
-        assert_eq!(0x3333, line.common.handle);
+        assert_eq!(Handle(0x3333), line.common.handle);
     }
 
     #[test]
@@ -1543,7 +3132,7 @@ mod tests {
             .as_str(),
         );
         let obj = drawing.objects().next().unwrap();
-        assert_eq!(0x3333, obj.common.handle);
+        assert_eq!(Handle(0x3333), obj.common.handle);
     }
 
     #[test]
@@ -1557,7 +3146,7 @@ mod tests {
             .as_str(),
         );
         let layer = drawing.layers().next().unwrap();
-        assert_eq!(0x3333, layer.handle);
+        assert_eq!(Handle(0x3333), layer.handle);
     }
 
     #[test]
@@ -1568,12 +3157,141 @@ mod tests {
             specific: EntityType::Line(Line::default()),
         });
         assert_eq!(1, drawing.entities().count());
-        assert_ne!(0, drawing.header.next_available_handle);
-        assert_ne!(1, drawing.header.next_available_handle);
+        assert!(!drawing.header.next_available_handle.is_empty());
+        assert_ne!(Handle(1), drawing.header.next_available_handle);
 
         drawing.clear();
         assert_eq!(0, drawing.entities().count());
-        assert_eq!(1, drawing.header.next_available_handle);
+        assert_eq!(Handle(1), drawing.header.next_available_handle);
+    }
+
+    fn line_handles(drawing: &mut Drawing, count: usize) -> Vec<Handle> {
+        (0..count)
+            .map(|_| {
+                drawing
+                    .add_entity(Entity::new(EntityType::Line(Line::default())))
+                    .common
+                    .handle
+            })
+            .collect()
+    }
+
+    #[test]
+    fn get_item_by_handle_after_add() {
+        let mut drawing = Drawing::new();
+        let handles = line_handles(&mut drawing, 3);
+        let layer_handle = drawing.add_layer(Layer::default()).handle;
+        for handle in &handles {
+            match drawing.get_item_by_handle(*handle) {
+                Some(DrawingItem::Entity(ent)) => assert_eq!(*handle, ent.common.handle),
+                _ => panic!("expected an entity"),
+            }
+        }
+        match drawing.get_item_by_handle(layer_handle) {
+            Some(DrawingItem::Layer(layer)) => assert_eq!(layer_handle, layer.handle),
+            _ => panic!("expected a layer"),
+        }
+        assert!(drawing.contains_handle(handles[0]));
+        assert!(!drawing.contains_handle(Handle(0xFFFF)));
+        assert!(!drawing.contains_handle(Handle::empty()));
+    }
+
+    #[test]
+    fn get_item_by_handle_after_remove() {
+        let mut drawing = Drawing::new();
+        let handles = line_handles(&mut drawing, 3);
+        drawing.remove_entity(0).unwrap();
+        assert!(!drawing.contains_handle(handles[0]));
+        match drawing.get_item_by_handle(handles[2]) {
+            Some(DrawingItem::Entity(ent)) => assert_eq!(handles[2], ent.common.handle),
+            _ => panic!("expected an entity"),
+        }
+        match drawing.get_item_by_handle_mut(handles[1]) {
+            Some(DrawingItemMut::Entity(ent)) => assert_eq!(handles[1], ent.common.handle),
+            _ => panic!("expected an entity"),
+        }
+    }
+
+    #[test]
+    fn get_item_by_handle_after_normalize() {
+        let mut drawing = Drawing::new();
+        let z_handle = drawing
+            .add_layer(Layer {
+                name: String::from("z"),
+                ..Default::default()
+            })
+            .handle;
+        let a_handle = drawing
+            .add_layer(Layer {
+                name: String::from("a"),
+                ..Default::default()
+            })
+            .handle;
+        drawing.normalize(); // re-sorts the layers
+        for (handle, name) in &[(z_handle, "z"), (a_handle, "a")] {
+            match drawing.get_item_by_handle(*handle) {
+                Some(DrawingItem::Layer(layer)) => assert_eq!(*name, layer.name),
+                _ => panic!("expected a layer"),
+            }
+        }
+    }
+
+    #[test]
+    fn get_item_by_handle_after_handle_is_changed() {
+        let mut drawing = Drawing::new();
+        let handles = line_handles(&mut drawing, 2);
+        drawing.entities_mut().next().unwrap().common.handle = Handle(0xABCD);
+        assert!(!drawing.contains_handle(handles[0]));
+        assert!(drawing.contains_handle(Handle(0xABCD)));
+        assert!(drawing.contains_handle(handles[1]));
+    }
+
+    #[test]
+    fn get_item_by_handle_after_handle_is_changed_through_get_item_by_handle_mut() {
+        let mut drawing = Drawing::new();
+        let handles = line_handles(&mut drawing, 2);
+        match drawing.get_item_by_handle_mut(handles[0]) {
+            Some(DrawingItemMut::Entity(entity)) => entity.common.handle = Handle(0xABCD),
+            _ => panic!("expected an entity"),
+        }
+        assert!(!drawing.contains_handle(handles[0]));
+        assert!(drawing.contains_handle(Handle(0xABCD)));
+        assert!(drawing.contains_handle(handles[1]));
+        drawing.remove_entity(0);
+        assert!(!drawing.contains_handle(Handle(0xABCD)));
+        assert!(drawing.contains_handle(handles[1]));
+    }
+
+    #[test]
+    fn get_items_by_handle_in_order() {
+        let mut drawing = Drawing::new();
+        let handles = line_handles(&mut drawing, 2);
+        let items = drawing.get_items_by_handle(vec![handles[1], Handle(0xFFFF), handles[0]]);
+        assert_eq!(3, items.len());
+        assert_eq!(Some(handles[1]), items[0].as_ref().map(|i| i.get_handle()));
+        assert!(items[1].is_none());
+        assert_eq!(Some(handles[0]), items[2].as_ref().map(|i| i.get_handle()));
+
+        let entities = drawing.get_entities_by_handle(vec![handles[0], Handle(0xFFFF)]);
+        assert_eq!(1, entities.len());
+        assert_eq!(handles[0], entities[0].common.handle);
+    }
+
+    #[test]
+    fn get_item_by_handle_after_read() {
+        let drawing = from_section(
+            "ENTITIES",
+            ["  0", "LINE", "  5", "A1", "  0", "CIRCLE", "  5", "A2"]
+                .join("\r\n")
+                .as_str(),
+        );
+        match drawing.get_item_by_handle(Handle(0xA2)) {
+            Some(DrawingItem::Entity(Entity {
+                specific: EntityType::Circle(_),
+                ..
+            })) => (),
+            _ => panic!("expected a circle"),
+        }
     }
 
     #[test]
@@ -1707,6 +3425,29 @@ mod tests {
         assert_eq!(1, dim_styles.len());
     }
 
+    #[test]
+    fn dim_style_is_added_with_arc_and_large_radial_dimensions() {
+        let mut drawing = Drawing::new();
+        drawing.add_entity(Entity::new(EntityType::ArcDimension(ArcDimension {
+            dimension_base: DimensionBase {
+                dimension_style_name: String::from("arc-dim-style"),
+                ..Default::default()
+            },
+            ..Default::default()
+        })));
+        drawing.add_entity(Entity::new(EntityType::LargeRadialDimension(
+            LargeRadialDimension {
+                dimension_base: DimensionBase {
+                    dimension_style_name: String::from("jogged-dim-style"),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        )));
+        assert!(drawing.dim_styles().any(|d| d.name == "arc-dim-style"));
+        assert!(drawing.dim_styles().any(|d| d.name == "jogged-dim-style"));
+    }
+
     #[test]
     fn dim_style_is_not_added_with_entity_if_already_present() {
         let mut drawing = Drawing::new();
@@ -2302,4 +4043,174 @@ mod tests {
             .collect::<Vec<_>>();
         assert_eq!(1, views.len());
     }
+
+    #[test]
+    fn unknown_section_is_written_in_original_position() {
+        let drawing = parse_drawing(
+            vec![
+                "  0",
+                "SECTION",
+                "  2",
+                "ENTITIES",
+                "  0",
+                "ENDSEC",
+                "  0",
+                "SECTION",
+                "  2",
+                "VENDOR_SECTION",
+                "  1",
+                "vendor data",
+                "  0",
+                "ENDSEC",
+                "  0",
+                "SECTION",
+                "  2",
+                "OBJECTS",
+                "  0",
+                "ENDSEC",
+                "  0",
+                "EOF",
+            ]
+            .join("\r\n")
+            .as_str(),
+        );
+        assert_eq!(1, drawing.unknown_sections.len());
+        let section = &drawing.unknown_sections[0];
+        assert_eq!("VENDOR_SECTION", section.name);
+        assert_eq!(
+            SectionPosition::After(String::from("ENTITIES")),
+            section.position
+        );
+        assert_eq!(
+            vec![CodePair::new_str(1, "vendor data")],
+            section.code_pairs
+        );
+        assert_contains(
+            &drawing,
+            [
+                "  2",
+                "ENTITIES",
+                "  0",
+                "ENDSEC",
+                "  0",
+                "SECTION",
+                "  2",
+                "VENDOR_SECTION",
+                "  1",
+                "vendor data",
+                "  0",
+                "ENDSEC",
+                "  0",
+                "SECTION",
+                "  2",
+                "OBJECTS",
+            ]
+            .join("\r\n"),
+        );
+    }
+
+    #[test]
+    fn unknown_section_before_header_round_trips() {
+        let drawing = parse_drawing(
+            [
+                "  0",
+                "SECTION",
+                "  2",
+                "VENDOR_SECTION",
+                "  1",
+                "vendor data",
+                "  0",
+                "ENDSEC",
+                "  0",
+                "SECTION",
+                "  2",
+                "HEADER",
+                "  0",
+                "ENDSEC",
+                "  0",
+                "EOF",
+            ]
+            .join("\r\n")
+            .as_str(),
+        );
+        assert_eq!(
+            SectionPosition::BeforeHeader,
+            drawing.unknown_sections[0].position
+        );
+
+        let mut buf = Cursor::new(vec![]);
+        drawing.save(&mut buf).ok().unwrap();
+        let contents = String::from_utf8(buf.into_inner()).ok().unwrap();
+        assert!(contents.starts_with(
+            &[
+                "  0",
+                "SECTION",
+                "  2",
+                "VENDOR_SECTION",
+                "  1",
+                "vendor data",
+                "  0",
+                "ENDSEC",
+                "  0",
+                "SECTION",
+                "  2",
+                "HEADER",
+            ]
+            .join("\r\n")
+        ));
+
+        let drawing = parse_drawing(&contents);
+        assert_eq!(1, drawing.unknown_sections.len());
+        assert_eq!(
+            SectionPosition::BeforeHeader,
+            drawing.unknown_sections[0].position
+        );
+    }
+
+    #[test]
+    fn unknown_section_after_unsupported_section_is_written_last() {
+        let mut drawing = Drawing::new();
+        let mut section = UnknownSection::new("VENDOR_SECTION");
+        section.position = SectionPosition::After(String::from("OTHER_VENDOR_SECTION"));
+        section.code_pairs.push(CodePair::new_str(1, "vendor data"));
+        drawing.unknown_sections.push(section);
+        assert_contains(
+            &drawing,
+            [
+                "  2",
+                "VENDOR_SECTION",
+                "  1",
+                "vendor data",
+                "  0",
+                "ENDSEC",
+                "  0",
+                "EOF",
+            ]
+            .join("\r\n"),
+        );
+    }
+
+    #[test]
+    fn new_unknown_section_is_written_last() {
+        let mut drawing = Drawing::new();
+        let mut section = UnknownSection::new("MYAPPDATA");
+        section.code_pairs.push(CodePair::new_str(1, "my data"));
+        drawing.unknown_sections.push(section);
+        let contents = to_test_string(&drawing);
+        assert!(contents.starts_with(&["  0", "SECTION", "  2", "HEADER"].join("\r\n")));
+        assert!(contents.ends_with(
+            &[
+                "  2",
+                "MYAPPDATA",
+                "  1",
+                "my data",
+                "  0",
+                "ENDSEC",
+                "  0",
+                "EOF",
+                "",
+            ]
+            .join("\r\n")
+        ));
+    }
 }
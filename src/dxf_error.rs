@@ -5,7 +5,7 @@ use std::num;
 
 use image;
 
-use crate::CodePair;
+use crate::{CodePair, Handle};
 
 #[derive(Debug)]
 pub enum DxfError {
@@ -25,6 +25,10 @@ pub enum DxfError {
     InvalidBinaryFile,
     MalformedString,
     WrongItemType,
+    ItemInUse(Handle, usize),
+    RequiredItem(String),
+    ItemNotFound(String),
+    DuplicateName(String),
 }
 
 impl From<io::Error> for DxfError {
@@ -39,6 +43,46 @@ impl From<::image::ImageError> for DxfError {
     }
 }
 
+// internal visibility only
+impl DxfError {
+    pub(crate) fn offset(&self) -> Option<usize> {
+        match *self {
+            DxfError::ParseFloatError(_, o)
+            | DxfError::ParseIntError(_, o)
+            | DxfError::ParseError(o)
+            | DxfError::UnexpectedCode(_, o)
+            | DxfError::UnexpectedByte(_, o)
+            | DxfError::UnexpectedEnumValue(o)
+            | DxfError::ExpectedTableType(o)
+            | DxfError::WrongValueType(o) => Some(o),
+            DxfError::UnexpectedCodePair(ref cp, _) => Some(cp.offset),
+            _ => None,
+        }
+    }
+    /// Returns `true` if the error only affected a single value and reading can continue.
+    pub(crate) fn is_value_error(&self) -> bool {
+        matches!(
+            *self,
+            DxfError::ParseFloatError(..)
+                | DxfError::ParseIntError(..)
+                | DxfError::ParseError(_)
+                | DxfError::UnexpectedEnumValue(_)
+                | DxfError::MalformedString
+        )
+    }
+    /// Returns `true` if the error only affected a single item and the next item can still be read.
+    pub(crate) fn is_item_error(&self) -> bool {
+        !matches!(
+            *self,
+            DxfError::IoError(_)
+                | DxfError::ImageError(_)
+                | DxfError::UnexpectedByte(..)
+                | DxfError::UnexpectedEndOfInput
+                | DxfError::InvalidBinaryFile
+        )
+    }
+}
+
 impl fmt::Display for DxfError {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
         match *self {
@@ -91,6 +135,20 @@ impl fmt::Display for DxfError {
             DxfError::InvalidBinaryFile => write!(formatter, "the binary file is invalid"),
             DxfError::MalformedString => write!(formatter, "the string is malformed"),
             DxfError::WrongItemType => write!(formatter, "the specified item type is not correct"),
+            DxfError::ItemInUse(h, count) => write!(
+                formatter,
+                "the item with handle {} is still used by {} entities",
+                h, count
+            ),
+            DxfError::RequiredItem(ref name) => {
+                write!(formatter, "the item '{}' is required by the drawing", name)
+            }
+            DxfError::ItemNotFound(ref name) => {
+                write!(formatter, "no item named '{}' was found", name)
+            }
+            DxfError::DuplicateName(ref name) => {
+                write!(formatter, "an item named '{}' already exists", name)
+            }
         }
     }
 }
@@ -0,0 +1,338 @@
+use std::io::{Seek, SeekFrom, Write};
+
+use crate::{CodePair, Drawing, DxfError, DxfResult, Handle};
+
+use crate::code_pair_writer::CodePairWriter;
+use crate::entities::*;
+use crate::enums::*;
+
+/// The placeholder written for `$HANDSEED` until all handles have been assigned.  Its width allows any
+/// final handle to be written over it.
+const HANDSEED_PLACEHOLDER: Handle = Handle(u64::MAX);
+
+/// Writes a DXF file incrementally, without holding all of its entities in memory.
+///
+/// The header, tables, and blocks of a template `Drawing` are written up front, entities are then
+/// written one at a time as they're given, and `finish()` writes the objects and the end of the file.
+/// Since the tables have already been written, any layers, line types, text styles, and dimension
+/// styles referenced by the streamed entities must already be present in the template `Drawing`;
+/// `write_entity()` fails with `DxfError::ItemNotFound` otherwise.
+///
+/// ```
+/// # fn main() -> dxf::DxfResult<()> {
+/// use dxf::entities::*;
+/// # let mut file = std::io::Cursor::new(vec![]);
+/// let mut writer = dxf::DxfStreamWriter::new(&mut file, dxf::Drawing::new())?;
+/// for i in 0..10 {
+///     let x = f64::from(i);
+///     writer.write_entity(Entity::new(EntityType::Line(Line::new(
+///         dxf::Point::new(x, 0.0, 0.0),
+///         dxf::Point::new(x, 1.0, 0.0),
+///     ))))?;
+/// }
+/// writer.finish()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct DxfStreamWriter<'a, T>
+where
+    T: Write + Seek + ?Sized + 'a,
+{
+    drawing: Drawing,
+    writer: CodePairWriter<'a, T>,
+    write_handles: bool,
+    handle_seed_position: Option<u64>,
+}
+
+// public implementation
+impl<'a, T: Write + Seek + ?Sized> DxfStreamWriter<'a, T> {
+    /// Begins writing the `drawing` as ASCII to anything that implements the `Write` and `Seek` traits.
+    pub fn new(writer: &'a mut T, drawing: Drawing) -> DxfResult<Self> {
+        DxfStreamWriter::new_internal(writer, drawing, true)
+    }
+    /// Begins writing the `drawing` as binary to anything that implements the `Write` and `Seek`
+    /// traits.
+    pub fn new_binary(writer: &'a mut T, drawing: Drawing) -> DxfResult<Self> {
+        DxfStreamWriter::new_internal(writer, drawing, false)
+    }
+    /// Writes the `Entity` to the `ENTITIES` section, assigning it the next available handle.
+    /// Returns the assigned handle.
+    ///
+    /// Fails with `DxfError::ItemNotFound` without writing anything if the entity references a
+    /// layer, line type, text style, or dimension style that isn't in the template `Drawing`.
+    pub fn write_entity(&mut self, mut entity: Entity) -> DxfResult<Handle> {
+        self.check_table_references(&entity)?;
+        self.drawing.assign_entity_handles(&mut entity);
+        entity.write(
+            self.drawing.header.version,
+            self.write_handles,
+            &mut self.writer,
+        )?;
+        Ok(entity.common.handle)
+    }
+    /// Writes the remaining sections and the end of the file and updates `$HANDSEED`.
+    pub fn finish(mut self) -> DxfResult<()> {
+        self.writer
+            .write_code_pair(&CodePair::new_str(0, "ENDSEC"))?;
+        self.drawing
+            .write_sections_after_entities(&mut self.writer)?;
+        if let Some(position) = self.handle_seed_position {
+            let inner = self.writer.inner_writer();
+            inner.seek(SeekFrom::Start(position))?;
+            inner.write_fmt(format_args!(
+                "{:016X}",
+                self.drawing.header.next_available_handle.0
+            ))?;
+            inner.seek(SeekFrom::End(0))?;
+        }
+
+        self.writer.inner_writer().flush()?;
+        Ok(())
+    }
+}
+
+// private implementation
+impl<'a, T: Write + Seek + ?Sized> DxfStreamWriter<'a, T> {
+    fn check_table_references(&self, entity: &Entity) -> DxfResult<()> {
+        fn check<'b>(name: &str, mut names: impl Iterator<Item = &'b String>) -> DxfResult<()> {
+            if name.is_empty() || names.any(|n| n.eq_ignore_ascii_case(name)) {
+                Ok(())
+            } else {
+                Err(DxfError::ItemNotFound(String::from(name)))
+            }
+        }
+
+        let drawing = &self.drawing;
+        check(&entity.common.layer, drawing.layers().map(|l| &l.name))?;
+        check(
+            &entity.common.line_type_name,
+            drawing.line_types().map(|l| &l.name),
+        )?;
+        if let Some(name) = entity.text_style_name() {
+            check(name, drawing.styles().map(|s| &s.name))?;
+        }
+
+        if let Some(name) = entity.dimension_style_name() {
+            check(name, drawing.dim_styles().map(|d| &d.name))?;
+        }
+
+        Ok(())
+    }
+    fn new_internal(writer: &'a mut T, mut drawing: Drawing, as_ascii: bool) -> DxfResult<Self> {
+        let version = drawing.header.version;
+        let string_encoding = drawing.header.string_encoding();
+        let write_handles = version >= AcadVersion::R13 || drawing.header.handles_enabled;
+
+        // the header is written with a placeholder `$HANDSEED` that's replaced once all entities have
+        // been written
+        let next_available_handle = drawing.header.next_available_handle;
+        drawing.header.next_available_handle = HANDSEED_PLACEHOLDER;
+        let mut header_bytes = vec![];
+        {
+            let mut header_writer =
+                CodePairWriter::new(&mut header_bytes, as_ascii, string_encoding, version);
+            header_writer.write_prelude()?;
+            drawing.write_unknown_sections(None, &mut header_writer)?;
+            drawing.header.write(&mut header_writer)?;
+        }
+        drawing.header.next_available_handle = next_available_handle;
+
+        let start_position = writer.stream_position()?;
+        let handle_seed_position =
+            find_handle_seed(&header_bytes).map(|offset| start_position + offset as u64);
+        writer.write_all(&header_bytes)?;
+
+        let mut writer = CodePairWriter::new(writer, as_ascii, string_encoding, version);
+        drawing.write_sections_before_entities(write_handles, &mut writer)?;
+        writer.write_code_pair(&CodePair::new_str(0, "SECTION"))?;
+        writer.write_code_pair(&CodePair::new_str(2, "ENTITIES"))?;
+        for entity in drawing.entities() {
+            entity.write(version, write_handles, &mut writer)?;
+        }
+
+        Ok(DxfStreamWriter {
+            drawing,
+            writer,
+            write_handles,
+            handle_seed_position,
+        })
+    }
+}
+
+/// Returns the offset of the `$HANDSEED` placeholder value.
+fn find_handle_seed(header_bytes: &[u8]) -> Option<usize> {
+    fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+        haystack.windows(needle.len()).position(|w| w == needle)
+    }
+
+    let variable_offset = find(header_bytes, b"$HANDSEED")?;
+    let placeholder = HANDSEED_PLACEHOLDER.as_string();
+    let value_offset = find(&header_bytes[variable_offset..], placeholder.as_bytes())?;
+    Some(variable_offset + value_offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::*;
+    use crate::enums::*;
+    use crate::helper_functions::tests::*;
+    use crate::*;
+    use std::io::Cursor;
+
+    const ALL_VERSIONS: [AcadVersion; 21] = [
+        AcadVersion::Version_1_0,
+        AcadVersion::Version_1_2,
+        AcadVersion::Version_1_40,
+        AcadVersion::Version_2_05,
+        AcadVersion::Version_2_10,
+        AcadVersion::Version_2_21,
+        AcadVersion::Version_2_22,
+        AcadVersion::Version_2_5,
+        AcadVersion::Version_2_6,
+        AcadVersion::R9,
+        AcadVersion::R10,
+        AcadVersion::R11,
+        AcadVersion::R12,
+        AcadVersion::R13,
+        AcadVersion::R14,
+        AcadVersion::R2000,
+        AcadVersion::R2004,
+        AcadVersion::R2007,
+        AcadVersion::R2010,
+        AcadVersion::R2013,
+        AcadVersion::R2018,
+    ];
+
+    fn line(x: f64) -> Entity {
+        Entity::new(EntityType::Line(Line::new(
+            Point::new(x, 0.0, 0.0),
+            Point::new(x, 1.0, 0.0),
+        )))
+    }
+
+    fn stream_lines_to_bytes(drawing: Drawing, count: usize, as_ascii: bool) -> Vec<u8> {
+        let mut buf = Cursor::new(vec![]);
+        {
+            let mut writer = if as_ascii {
+                DxfStreamWriter::new(&mut buf, drawing).unwrap()
+            } else {
+                DxfStreamWriter::new_binary(&mut buf, drawing).unwrap()
+            };
+            for i in 0..count {
+                writer.write_entity(line(i as f64)).unwrap();
+            }
+            writer.finish().unwrap();
+        }
+
+        buf.into_inner()
+    }
+
+    fn stream_lines(drawing: Drawing, count: usize, as_ascii: bool) -> Drawing {
+        let bytes = stream_lines_to_bytes(drawing, count, as_ascii);
+        unwrap_drawing(Drawing::load(&mut bytes.as_slice()))
+    }
+
+    #[test]
+    fn stream_entities_for_all_versions() {
+        for version in &ALL_VERSIONS {
+            for as_ascii in &[true, false] {
+                let mut drawing = Drawing::new();
+                drawing.header.version = *version;
+                let drawing = stream_lines(drawing, 5, *as_ascii);
+                assert_eq!(version.to_string(), drawing.header.version.to_string());
+                let entities = drawing.entities().collect::<Vec<_>>();
+                assert_eq!(5, entities.len());
+                assert_eq!(Point::new(4.0, 0.0, 0.0), line_p1(entities[4]));
+            }
+        }
+    }
+
+    fn line_p1(entity: &Entity) -> Point {
+        match entity.specific {
+            EntityType::Line(ref line) => line.p1.clone(),
+            _ => panic!("expected a line"),
+        }
+    }
+
+    #[test]
+    fn streamed_entities_are_assigned_handles_below_handle_seed() {
+        let mut drawing = Drawing::new();
+        drawing.header.version = AcadVersion::R2000;
+        let first_handle = drawing.header.next_available_handle;
+        let bytes = stream_lines_to_bytes(drawing, 3, true);
+        let text = String::from_utf8(bytes).unwrap();
+        let expected_seed = format!("$HANDSEED\r\n  5\r\n{:016X}\r\n", first_handle.0 + 3);
+        assert!(text.contains(&expected_seed));
+
+        let drawing = unwrap_drawing(Drawing::load(&mut text.as_bytes()));
+        let handles = drawing
+            .entities()
+            .map(|e| e.common.handle)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                first_handle,
+                Handle(first_handle.0 + 1),
+                Handle(first_handle.0 + 2)
+            ],
+            handles
+        );
+    }
+
+    #[test]
+    fn template_entities_are_written_first() {
+        let mut drawing = Drawing::new();
+        drawing.add_entity(line(-1.0));
+        let drawing = stream_lines(drawing, 2, true);
+        let entities = drawing.entities().collect::<Vec<_>>();
+        assert_eq!(3, entities.len());
+        assert_eq!(Point::new(-1.0, 0.0, 0.0), line_p1(entities[0]));
+        assert_eq!(Point::new(1.0, 0.0, 0.0), line_p1(entities[2]));
+    }
+
+    #[test]
+    fn entities_referencing_missing_table_items_are_rejected() {
+        let mut drawing = Drawing::new();
+        drawing.add_layer(tables::Layer {
+            name: String::from("walls"),
+            ..Default::default()
+        });
+        let mut buf = Cursor::new(vec![]);
+        {
+            let mut writer = DxfStreamWriter::new(&mut buf, drawing).unwrap();
+            let mut on_new_layer = line(0.0);
+            on_new_layer.common.layer = String::from("newlayer");
+            match writer.write_entity(on_new_layer) {
+                Err(DxfError::ItemNotFound(name)) => assert_eq!("newlayer", name),
+                _ => panic!("expected an error"),
+            }
+
+            let mut dashed = line(0.0);
+            dashed.common.line_type_name = String::from("DASHED");
+            match writer.write_entity(dashed) {
+                Err(DxfError::ItemNotFound(name)) => assert_eq!("DASHED", name),
+                _ => panic!("expected an error"),
+            }
+
+            let text = Entity::new(EntityType::Text(Text {
+                text_style_name: String::from("missing-style"),
+                ..Default::default()
+            }));
+            match writer.write_entity(text) {
+                Err(DxfError::ItemNotFound(name)) => assert_eq!("missing-style", name),
+                _ => panic!("expected an error"),
+            }
+
+            // names are case-insensitive
+            let mut on_walls = line(1.0);
+            on_walls.common.layer = String::from("WALLS");
+            writer.write_entity(on_walls).unwrap();
+            writer.finish().unwrap();
+        }
+
+        let drawing = unwrap_drawing(Drawing::load(&mut buf.into_inner().as_slice()));
+        let entities = drawing.entities().collect::<Vec<_>>();
+        assert_eq!(1, entities.len());
+        assert_eq!("WALLS", entities[0].common.layer);
+    }
+}
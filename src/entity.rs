@@ -3,15 +3,91 @@
 use enum_primitive::FromPrimitive;
 use std::io::{Read, Write};
 
-use crate::{CodePair, Color, DxfError, DxfResult, Point, Vector};
+use crate::{
+    CodePair, CodePairValue, Color, DxfError, DxfResult, Handle, HatchBoundaryPath,
+    HatchGradientColor, HatchPatternDefinitionLine, MLeaderContextData, Point, TableCell,
+    TableCellRange, TransformationMatrix, Vector,
+};
 
 use crate::code_pair_put_back::CodePairPutBack;
 use crate::code_pair_writer::CodePairWriter;
 use crate::entities::*;
 use crate::enums::*;
+use crate::extension_data;
 use crate::helper_functions::*;
+use crate::x_data;
 use crate::Drawing;
 
+// Copies the text properties shared by `Attribute` and `AttributeDefinition` into a new `Text`.
+macro_rules! text_from_attribute {
+    ($att: expr) => {
+        Text {
+            thickness: $att.thickness,
+            location: $att.location.clone(),
+            text_height: $att.text_height,
+            value: $att.value.clone(),
+            rotation: $att.rotation,
+            relative_x_scale_factor: $att.relative_x_scale_factor,
+            oblique_angle: $att.oblique_angle,
+            text_style_name: $att.text_style_name.clone(),
+            text_generation_flags: $att.text_generation_flags,
+            horizontal_text_justification: $att.horizontal_text_justification,
+            second_alignment_point: $att.second_alignment_point.clone(),
+            normal: $att.normal.clone(),
+            vertical_text_justification: $att.vertical_text_justification,
+        }
+    };
+}
+
+//------------------------------------------------------------------------------
+//                                                                     AcadTable
+//------------------------------------------------------------------------------
+impl AcadTable {
+    pub fn row_count(&self) -> usize {
+        self.row_heights.len()
+    }
+    pub fn column_count(&self) -> usize {
+        self.column_widths.len()
+    }
+    /// Gets the cell at the specified zero-based row and column.
+    pub fn cell(&self, row: usize, column: usize) -> Option<&TableCell> {
+        if column >= self.column_count() {
+            return None;
+        }
+        self.cells.get(row * self.column_count() + column)
+    }
+    /// Gets the cell at the specified zero-based row and column.
+    pub fn cell_mut(&mut self, row: usize, column: usize) -> Option<&mut TableCell> {
+        if column >= self.column_count() {
+            return None;
+        }
+        let column_count = self.column_count();
+        self.cells.get_mut(row * column_count + column)
+    }
+    /// Gets the ranges covered by merged cells, anchored at each merged cell's top-left corner.
+    pub fn merged_ranges(&self) -> Vec<TableCellRange> {
+        let column_count = self.column_count();
+        if column_count == 0 {
+            return vec![];
+        }
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(|(_, cell)| cell.merged_row_count > 1 || cell.merged_column_count > 1)
+            .map(|(i, cell)| {
+                let top_row = i / column_count;
+                let left_column = i % column_count;
+                TableCellRange {
+                    top_row,
+                    left_column,
+                    bottom_row: top_row + cell.merged_row_count.max(1) as usize - 1,
+                    right_column: left_column + cell.merged_column_count.max(1) as usize - 1,
+                }
+            })
+            .collect()
+    }
+}
+
 //------------------------------------------------------------------------------
 //                                                                           Arc
 //------------------------------------------------------------------------------
@@ -27,6 +103,26 @@ impl Arc {
     }
 }
 
+//------------------------------------------------------------------------------
+//                                                                     Attribute
+//------------------------------------------------------------------------------
+impl Attribute {
+    /// Returns a `Text` that displays the attribute's value.
+    pub fn to_text(&self) -> Text {
+        text_from_attribute!(self)
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                           AttributeDefinition
+//------------------------------------------------------------------------------
+impl AttributeDefinition {
+    /// Returns a `Text` that displays the attribute definition's default value.
+    pub fn to_text(&self) -> Text {
+        text_from_attribute!(self)
+    }
+}
+
 //------------------------------------------------------------------------------
 //                                                                        Circle
 //------------------------------------------------------------------------------
@@ -64,6 +160,26 @@ impl DimensionBase {
         }
         val
     }
+    fn write_as<T>(
+        &self,
+        dimension_type: DimensionType,
+        version: AcadVersion,
+        writer: &mut CodePairWriter<T>,
+    ) -> DxfResult<()>
+    where
+        T: Write + ?Sized,
+    {
+        // the type flags always have to agree with the dimension actually being written
+        if self.dimension_type == dimension_type {
+            self.write(version, writer)
+        } else {
+            let base = DimensionBase {
+                dimension_type,
+                ..self.clone()
+            };
+            base.write(version, writer)
+        }
+    }
 }
 
 //------------------------------------------------------------------------------
@@ -98,7 +214,33 @@ impl Insert {
     }
     pub fn add_attribute(&mut self, drawing: &mut Drawing, att: Attribute) {
         let att_handle = drawing.next_handle();
-        self.__attributes_and_handles.push((att, att_handle));
+        self.__attributes_and_handles.push((att, Some(att_handle)));
+    }
+    /// Returns the transformation from the coordinates of a block with the specified `base_point`
+    /// to WCS for every instance of the insert's column/row array.
+    pub fn block_transformations(&self, base_point: &Point) -> Vec<TransformationMatrix> {
+        let placement = TransformationMatrix::from_ocs(&self.extrusion_direction)
+            * TransformationMatrix::translation(&self.location.to_vector())
+            * TransformationMatrix::rotation_about_z(self.rotation);
+        let scale = TransformationMatrix::scale(
+            self.x_scale_factor,
+            self.y_scale_factor,
+            self.z_scale_factor,
+        ) * TransformationMatrix::translation(&-base_point.to_vector());
+        let mut transformations = vec![];
+        for row in 0..self.row_count.max(1) {
+            for column in 0..self.column_count.max(1) {
+                let offset = Vector::new(
+                    f64::from(column) * self.column_spacing,
+                    f64::from(row) * self.row_spacing,
+                    0.0,
+                );
+                transformations
+                    .push(placement * TransformationMatrix::translation(&offset) * scale);
+            }
+        }
+
+        transformations
     }
 }
 
@@ -130,6 +272,35 @@ pub struct LwPolylineVertex {
     pub bulge: f64,
 }
 
+//------------------------------------------------------------------------------
+//                                                         MeshSubEntityOverride
+//------------------------------------------------------------------------------
+/// Represents the overridden properties of a single sub-entity of a `Mesh`.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct MeshSubEntityOverride {
+    pub sub_entity_marker: i32,
+    pub properties: Vec<MeshPropertyOverride>,
+}
+
+/// Represents a single overridden property of a `Mesh` sub-entity.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct MeshPropertyOverride {
+    pub property_type: MeshPropertyType,
+    /// The raw code pairs that make up the property's value.
+    pub values: Vec<CodePair>,
+}
+
+impl MeshPropertyOverride {
+    pub fn new(property_type: MeshPropertyType) -> Self {
+        MeshPropertyOverride {
+            property_type,
+            values: vec![],
+        }
+    }
+}
+
 //------------------------------------------------------------------------------
 //                                                                    ModelPoint
 //------------------------------------------------------------------------------
@@ -146,6 +317,11 @@ impl ModelPoint {
 //                                                                      Polyline
 //------------------------------------------------------------------------------
 impl Polyline {
+    /// Returns `true` if the polyline's `normal` defines an OCS.  3D polylines and meshes are always
+    /// in WCS.
+    pub(crate) fn ocs_normal_is_used(&self) -> bool {
+        !(self.get_is_3d_polyline() || self.get_is_3d_polygon_mesh() || self.get_is_polyface_mesh())
+    }
     pub fn vertices(&self) -> impl Iterator<Item = &Vertex> {
         self.__vertices_and_handles.iter().map(|v| &v.0)
     }
@@ -154,7 +330,8 @@ impl Polyline {
     }
     pub fn add_vertex(&mut self, drawing: &mut Drawing, vertex: Vertex) {
         let vertex_handle = drawing.next_handle();
-        self.__vertices_and_handles.push((vertex, vertex_handle));
+        self.__vertices_and_handles
+            .push((vertex, Some(vertex_handle)));
     }
 }
 
@@ -219,6 +396,38 @@ impl Trace {
     }
 }
 
+//------------------------------------------------------------------------------
+//                                                                 UnknownEntity
+//------------------------------------------------------------------------------
+/// Represents an entity of a type that isn't otherwise supported, e.g., one defined by a third-party
+/// application.  The entity-specific code pairs are kept as-is and written back unchanged.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct UnknownEntity {
+    pub type_string: String,
+    /// The raw code pairs following the common entity values, including any subclass markers.
+    pub code_pairs: Vec<CodePair>,
+}
+
+impl UnknownEntity {
+    pub fn new(type_string: &str) -> Self {
+        UnknownEntity {
+            type_string: String::from(type_string),
+            code_pairs: vec![],
+        }
+    }
+    /// Returns the subclass markers (code 100) of the entity-specific data.
+    pub fn subclass_markers(&self) -> impl Iterator<Item = &str> {
+        self.code_pairs
+            .iter()
+            .filter(|pair| pair.code == 100)
+            .filter_map(|pair| match pair.value {
+                CodePairValue::Str(ref s) => Some(s.as_str()),
+                _ => None,
+            })
+    }
+}
+
 //------------------------------------------------------------------------------
 //                                                                        Vertex
 //------------------------------------------------------------------------------
@@ -235,6 +444,94 @@ impl Vertex {
 //                                                                    EntityType
 //------------------------------------------------------------------------------
 impl EntityType {
+    fn new_dimension(dimension_type: DimensionType, dimension_base: &DimensionBase) -> EntityType {
+        let dimension_base = DimensionBase {
+            dimension_type,
+            ..dimension_base.clone()
+        };
+        match dimension_type {
+            DimensionType::RotatedHorizontalOrVertical => {
+                EntityType::RotatedDimension(RotatedDimension {
+                    dimension_base,
+                    ..Default::default()
+                })
+            }
+            DimensionType::Aligned => EntityType::AlignedDimension(AlignedDimension {
+                dimension_base,
+                ..Default::default()
+            }),
+            DimensionType::Angular => {
+                EntityType::AngularTwoLineDimension(AngularTwoLineDimension {
+                    dimension_base,
+                    ..Default::default()
+                })
+            }
+            DimensionType::Diameter => EntityType::DiameterDimension(DiameterDimension {
+                dimension_base,
+                ..Default::default()
+            }),
+            DimensionType::Radius => EntityType::RadialDimension(RadialDimension {
+                dimension_base,
+                ..Default::default()
+            }),
+            DimensionType::AngularThreePoint => {
+                EntityType::AngularThreePointDimension(AngularThreePointDimension {
+                    dimension_base,
+                    ..Default::default()
+                })
+            }
+            DimensionType::Ordinate => EntityType::OrdinateDimension(OrdinateDimension {
+                dimension_base,
+                ..Default::default()
+            }),
+        }
+    }
+    fn new_dimension_for_subclass(
+        subclass: &str,
+        dimension_base: &DimensionBase,
+    ) -> Option<EntityType> {
+        match subclass {
+            // aligned dimensions are told apart from rotated ones once the entity is read
+            "AcDbAlignedDimension" => Some(EntityType::new_dimension(
+                DimensionType::RotatedHorizontalOrVertical,
+                dimension_base,
+            )),
+            "AcDb2LineAngularDimension" => Some(EntityType::new_dimension(
+                DimensionType::Angular,
+                dimension_base,
+            )),
+            "AcDbRadialDimension" => Some(EntityType::new_dimension(
+                DimensionType::Radius,
+                dimension_base,
+            )),
+            "AcDbDiametricDimension" => Some(EntityType::new_dimension(
+                DimensionType::Diameter,
+                dimension_base,
+            )),
+            "AcDb3PointAngularDimension" => Some(EntityType::new_dimension(
+                DimensionType::AngularThreePoint,
+                dimension_base,
+            )),
+            "AcDbOrdinateDimension" => Some(EntityType::new_dimension(
+                DimensionType::Ordinate,
+                dimension_base,
+            )),
+            "AcDbArcDimension" => Some(EntityType::ArcDimension(ArcDimension {
+                dimension_base: dimension_base.clone(),
+                ..Default::default()
+            })),
+            "AcDbRadialDimensionLarge" => {
+                Some(EntityType::LargeRadialDimension(LargeRadialDimension {
+                    dimension_base: dimension_base.clone(),
+                    ..Default::default()
+                }))
+            }
+            _ => None, // unexpected dimension type
+        }
+    }
+    fn is_dimension_specific_code(code: i32) -> bool {
+        matches!(code, 12..=16 | 22..=26 | 32..=36 | 40 | 50 | 52)
+    }
     fn apply_dimension_code_pair(&mut self, pair: &CodePair) -> DxfResult<bool> {
         match *self {
             EntityType::RotatedDimension(ref mut dim) => match pair.code {
@@ -275,6 +572,41 @@ impl EntityType {
                     return Ok(false);
                 }
             },
+            EntityType::AlignedDimension(ref mut dim) => match pair.code {
+                12 => {
+                    dim.insertion_point.x = pair.assert_f64()?;
+                }
+                22 => {
+                    dim.insertion_point.y = pair.assert_f64()?;
+                }
+                32 => {
+                    dim.insertion_point.z = pair.assert_f64()?;
+                }
+                13 => {
+                    dim.definition_point_2.x = pair.assert_f64()?;
+                }
+                23 => {
+                    dim.definition_point_2.y = pair.assert_f64()?;
+                }
+                33 => {
+                    dim.definition_point_2.z = pair.assert_f64()?;
+                }
+                14 => {
+                    dim.definition_point_3.x = pair.assert_f64()?;
+                }
+                24 => {
+                    dim.definition_point_3.y = pair.assert_f64()?;
+                }
+                34 => {
+                    dim.definition_point_3.z = pair.assert_f64()?;
+                }
+                52 => {
+                    dim.extension_line_angle = pair.assert_f64()?;
+                }
+                _ => {
+                    return Ok(false);
+                }
+            },
             EntityType::RadialDimension(ref mut dim) => match pair.code {
                 15 => {
                     dim.definition_point_2.x = pair.assert_f64()?;
@@ -309,6 +641,47 @@ impl EntityType {
                     return Ok(false);
                 }
             },
+            EntityType::AngularTwoLineDimension(ref mut dim) => match pair.code {
+                13 => {
+                    dim.definition_point_2.x = pair.assert_f64()?;
+                }
+                23 => {
+                    dim.definition_point_2.y = pair.assert_f64()?;
+                }
+                33 => {
+                    dim.definition_point_2.z = pair.assert_f64()?;
+                }
+                14 => {
+                    dim.definition_point_3.x = pair.assert_f64()?;
+                }
+                24 => {
+                    dim.definition_point_3.y = pair.assert_f64()?;
+                }
+                34 => {
+                    dim.definition_point_3.z = pair.assert_f64()?;
+                }
+                15 => {
+                    dim.definition_point_4.x = pair.assert_f64()?;
+                }
+                25 => {
+                    dim.definition_point_4.y = pair.assert_f64()?;
+                }
+                35 => {
+                    dim.definition_point_4.z = pair.assert_f64()?;
+                }
+                16 => {
+                    dim.definition_point_5.x = pair.assert_f64()?;
+                }
+                26 => {
+                    dim.definition_point_5.y = pair.assert_f64()?;
+                }
+                36 => {
+                    dim.definition_point_5.z = pair.assert_f64()?;
+                }
+                _ => {
+                    return Ok(false);
+                }
+            },
             EntityType::AngularThreePointDimension(ref mut dim) => match pair.code {
                 13 => {
                     dim.definition_point_2.x = pair.assert_f64()?;
@@ -350,6 +723,103 @@ impl EntityType {
                     return Ok(false);
                 }
             },
+            EntityType::ArcDimension(ref mut dim) => match pair.code {
+                13 => {
+                    dim.definition_point_2.x = pair.assert_f64()?;
+                }
+                23 => {
+                    dim.definition_point_2.y = pair.assert_f64()?;
+                }
+                33 => {
+                    dim.definition_point_2.z = pair.assert_f64()?;
+                }
+                14 => {
+                    dim.definition_point_3.x = pair.assert_f64()?;
+                }
+                24 => {
+                    dim.definition_point_3.y = pair.assert_f64()?;
+                }
+                34 => {
+                    dim.definition_point_3.z = pair.assert_f64()?;
+                }
+                15 => {
+                    dim.arc_center_point.x = pair.assert_f64()?;
+                }
+                25 => {
+                    dim.arc_center_point.y = pair.assert_f64()?;
+                }
+                35 => {
+                    dim.arc_center_point.z = pair.assert_f64()?;
+                }
+                40 => {
+                    dim.arc_start_parameter = pair.assert_f64()?;
+                }
+                41 => {
+                    dim.arc_end_parameter = pair.assert_f64()?;
+                }
+                70 => {
+                    dim.is_partial = as_bool(pair.assert_i16()?);
+                }
+                71 => {
+                    dim.has_leader = as_bool(pair.assert_i16()?);
+                }
+                16 => {
+                    dim.leader_point_1.x = pair.assert_f64()?;
+                }
+                26 => {
+                    dim.leader_point_1.y = pair.assert_f64()?;
+                }
+                36 => {
+                    dim.leader_point_1.z = pair.assert_f64()?;
+                }
+                17 => {
+                    dim.leader_point_2.x = pair.assert_f64()?;
+                }
+                27 => {
+                    dim.leader_point_2.y = pair.assert_f64()?;
+                }
+                37 => {
+                    dim.leader_point_2.z = pair.assert_f64()?;
+                }
+                _ => {
+                    return Ok(false);
+                }
+            },
+            EntityType::LargeRadialDimension(ref mut dim) => match pair.code {
+                13 => {
+                    dim.chord_point.x = pair.assert_f64()?;
+                }
+                23 => {
+                    dim.chord_point.y = pair.assert_f64()?;
+                }
+                33 => {
+                    dim.chord_point.z = pair.assert_f64()?;
+                }
+                14 => {
+                    dim.override_center.x = pair.assert_f64()?;
+                }
+                24 => {
+                    dim.override_center.y = pair.assert_f64()?;
+                }
+                34 => {
+                    dim.override_center.z = pair.assert_f64()?;
+                }
+                15 => {
+                    dim.jog_point.x = pair.assert_f64()?;
+                }
+                25 => {
+                    dim.jog_point.y = pair.assert_f64()?;
+                }
+                35 => {
+                    dim.jog_point.z = pair.assert_f64()?;
+                }
+                40 => {
+                    dim.jog_angle = pair.assert_f64()?;
+                }
+                _ => {
+                    return Ok(false);
+                }
+            },
             EntityType::OrdinateDimension(ref mut dim) => match pair.code {
                 13 => {
                     dim.definition_point_2.x = pair.assert_f64()?;
@@ -391,42 +861,439 @@ impl EntityCommon {
     }
 }
 
-//------------------------------------------------------------------------------
-//                                                                        Entity
-//------------------------------------------------------------------------------
-impl Entity {
-    /// Creates a new `Entity` with the default common values.
-    pub fn new(specific: EntityType) -> Self {
-        Entity {
-            common: Default::default(),
-            specific,
+// Applies a pair from the `AcDbModelerGeometry` and `AcDbSurface` subclasses shared by all surface
+// entities; evaluates to `true` if the pair was consumed.
+macro_rules! apply_surface_code_pair {
+    ($surface: expr, $subclass: expr, $pair: expr) => {
+        match ($subclass, $pair.code) {
+            ("AcDbModelerGeometry", 70) => {
+                $surface.format_version_number = $pair.assert_i16()?;
+                true
+            }
+            ("AcDbModelerGeometry", 1) => {
+                $surface.custom_data.push($pair.assert_string()?);
+                true
+            }
+            ("AcDbModelerGeometry", 3) => {
+                $surface.custom_data2.push($pair.assert_string()?);
+                true
+            }
+            ("AcDbSurface", 71) => {
+                $surface.u_isoline_count = $pair.assert_i16()?;
+                true
+            }
+            ("AcDbSurface", 72) => {
+                $surface.v_isoline_count = $pair.assert_i16()?;
+                true
+            }
+            _ => false,
         }
-    }
-    /// Ensures all entity values are valid.
-    pub fn normalize(&mut self) {
-        self.common.normalize();
-        // no entity-specific values to set
-    }
-    pub(crate) fn read<I>(iter: &mut CodePairPutBack<I>) -> DxfResult<Option<Entity>>
-    where
-        I: Read,
-    {
-        'new_entity: loop {
-            match iter.next() {
-                // first code pair must be 0/entity-type
-                Some(Ok(pair @ CodePair { code: 0, .. })) => {
-                    let type_string = pair.assert_string()?;
-                    if type_string == "ENDSEC" || type_string == "ENDBLK" {
+    };
+}
+
+// Applies a sweep option pair shared by `ExtrudedSurface` and `SweptSurface`; evaluates to `true` if
+// the pair was consumed.
+macro_rules! apply_sweep_options_code_pair {
+    ($surface: expr, $pair: expr, $iter: expr) => {
+        match $pair.code {
+            42 => {
+                $surface.draft_angle = $pair.assert_f64()?;
+                true
+            }
+            43 => {
+                $surface.draft_start_distance = $pair.assert_f64()?;
+                true
+            }
+            44 => {
+                $surface.draft_end_distance = $pair.assert_f64()?;
+                true
+            }
+            45 => {
+                $surface.twist_angle = $pair.assert_f64()?;
+                true
+            }
+            48 => {
+                $surface.scale_factor = $pair.assert_f64()?;
+                true
+            }
+            49 => {
+                $surface.align_angle = $pair.assert_f64()?;
+                true
+            }
+            46 => {
+                $surface.sweep_transformation_matrix =
+                    Entity::read_transformation_matrix(&$pair, $iter)?;
+                true
+            }
+            47 => {
+                $surface.path_transformation_matrix =
+                    Entity::read_transformation_matrix(&$pair, $iter)?;
+                true
+            }
+            290 => {
+                $surface.is_solid = $pair.assert_bool()?;
+                true
+            }
+            70 => {
+                $surface.sweep_alignment =
+                    enum_from_number!(SweepAlignment, NoAlignment, from_i16, $pair.assert_i16()?);
+                true
+            }
+            292 => {
+                $surface.align_start = $pair.assert_bool()?;
+                true
+            }
+            293 => {
+                $surface.bank = $pair.assert_bool()?;
+                true
+            }
+            294 => {
+                $surface.base_point_set = $pair.assert_bool()?;
+                true
+            }
+            295 => {
+                $surface.sweep_entity_transform_computed = $pair.assert_bool()?;
+                true
+            }
+            296 => {
+                $surface.path_entity_transform_computed = $pair.assert_bool()?;
+                true
+            }
+            11 => {
+                $surface.twist_reference_vector.x = $pair.assert_f64()?;
+                true
+            }
+            21 => {
+                $surface.twist_reference_vector.y = $pair.assert_f64()?;
+                true
+            }
+            31 => {
+                $surface.twist_reference_vector.z = $pair.assert_f64()?;
+                true
+            }
+            _ => false,
+        }
+    };
+}
+
+// Writes the `AcDbModelerGeometry` and `AcDbSurface` subclasses shared by all surface entities.
+macro_rules! write_surface_code_pairs {
+    ($surface: expr, $writer: expr) => {
+        $writer.write_code_pair(&CodePair::new_str(100, "AcDbModelerGeometry"))?;
+        $writer.write_code_pair(&CodePair::new_i16(70, $surface.format_version_number))?;
+        for data in &$surface.custom_data {
+            $writer.write_code_pair(&CodePair::new_string(1, data))?;
+        }
+        for data in &$surface.custom_data2 {
+            $writer.write_code_pair(&CodePair::new_string(3, data))?;
+        }
+        $writer.write_code_pair(&CodePair::new_str(100, "AcDbSurface"))?;
+        $writer.write_code_pair(&CodePair::new_i16(71, $surface.u_isoline_count))?;
+        $writer.write_code_pair(&CodePair::new_i16(72, $surface.v_isoline_count))?;
+    };
+}
+
+// Writes the sweep options shared by `ExtrudedSurface` and `SweptSurface`.
+macro_rules! write_sweep_options_code_pairs {
+    ($surface: expr, $writer: expr) => {
+        $writer.write_code_pair(&CodePair::new_f64(42, $surface.draft_angle))?;
+        $writer.write_code_pair(&CodePair::new_f64(43, $surface.draft_start_distance))?;
+        $writer.write_code_pair(&CodePair::new_f64(44, $surface.draft_end_distance))?;
+        $writer.write_code_pair(&CodePair::new_f64(45, $surface.twist_angle))?;
+        $writer.write_code_pair(&CodePair::new_f64(48, $surface.scale_factor))?;
+        $writer.write_code_pair(&CodePair::new_f64(49, $surface.align_angle))?;
+        Entity::write_transformation_matrix(46, &$surface.sweep_transformation_matrix, $writer)?;
+        Entity::write_transformation_matrix(47, &$surface.path_transformation_matrix, $writer)?;
+        $writer.write_code_pair(&CodePair::new_bool(290, $surface.is_solid))?;
+        $writer.write_code_pair(&CodePair::new_i16(70, $surface.sweep_alignment as i16))?;
+        $writer.write_code_pair(&CodePair::new_bool(292, $surface.align_start))?;
+        $writer.write_code_pair(&CodePair::new_bool(293, $surface.bank))?;
+        $writer.write_code_pair(&CodePair::new_bool(294, $surface.base_point_set))?;
+        $writer.write_code_pair(&CodePair::new_bool(
+            295,
+            $surface.sweep_entity_transform_computed,
+        ))?;
+        $writer.write_code_pair(&CodePair::new_bool(
+            296,
+            $surface.path_entity_transform_computed,
+        ))?;
+        $writer.write_code_pair(&CodePair::new_f64(11, $surface.twist_reference_vector.x))?;
+        $writer.write_code_pair(&CodePair::new_f64(21, $surface.twist_reference_vector.y))?;
+        $writer.write_code_pair(&CodePair::new_f64(31, $surface.twist_reference_vector.z))?;
+    };
+}
+
+//------------------------------------------------------------------------------
+//                                                                        Entity
+//------------------------------------------------------------------------------
+impl Entity {
+    /// Creates a new `Entity` with the default common values.
+    pub fn new(specific: EntityType) -> Self {
+        Entity {
+            common: Default::default(),
+            specific,
+        }
+    }
+    /// Ensures all entity values are valid.
+    pub fn normalize(&mut self) {
+        self.common.normalize();
+        // no entity-specific values to set
+    }
+    /// The normal that defines this entity's Object Coordinate System (OCS), or `None` if the
+    /// entity's coordinates are in the World Coordinate System (WCS).
+    pub fn ocs_normal(&self) -> Option<&Vector> {
+        match self.specific {
+            EntityType::Arc(ref e) => Some(&e.normal),
+            EntityType::Attribute(ref e) => Some(&e.normal),
+            EntityType::AttributeDefinition(ref e) => Some(&e.normal),
+            EntityType::Circle(ref e) => Some(&e.normal),
+            EntityType::Hatch(ref e) => Some(&e.extrusion_direction),
+            EntityType::Insert(ref e) => Some(&e.extrusion_direction),
+            EntityType::LwPolyline(ref e) => Some(&e.extrusion_direction),
+            EntityType::Polyline(ref e) if e.ocs_normal_is_used() => Some(&e.normal),
+            EntityType::Shape(ref e) => Some(&e.extrusion_direction),
+            EntityType::Solid(ref e) => Some(&e.extrusion_direction),
+            EntityType::Text(ref e) => Some(&e.normal),
+            EntityType::Trace(ref e) => Some(&e.extrusion_direction),
+            _ => None,
+        }
+    }
+    /// Returns the matrix that converts points in this entity's Object Coordinate System (OCS) to
+    /// the World Coordinate System (WCS).  This is the identity matrix for entities that aren't
+    /// defined in an OCS.
+    pub fn ocs_to_wcs(&self) -> TransformationMatrix {
+        match self.ocs_normal() {
+            Some(normal) => TransformationMatrix::from_ocs(normal),
+            None => TransformationMatrix::identity(),
+        }
+    }
+    /// Converts `point` from this entity's Object Coordinate System (OCS) to the World Coordinate
+    /// System (WCS).
+    ///
+    /// The point is expected to be one of the entity's own coordinates, e.g., the center of a
+    /// `Circle` or a vertex of an `LwPolyline` with `common.elevation` as its Z value.
+    ///
+    /// ```
+    /// # use dxf::*;
+    /// # use dxf::entities::*;
+    /// let mut circle = Circle::new(Point::new(1.0, 2.0, 3.0), 1.0);
+    /// circle.normal = Vector::new(0.0, 0.0, -1.0);
+    /// let circle = Entity::new(EntityType::Circle(circle));
+    /// assert_eq!(Point::new(-1.0, 2.0, -3.0), circle.to_wcs(&Point::new(1.0, 2.0, 3.0)));
+    /// ```
+    pub fn to_wcs(&self, point: &Point) -> Point {
+        self.ocs_to_wcs().transform_point(point)
+    }
+    /// Converts `point` from the World Coordinate System (WCS) to this entity's Object Coordinate
+    /// System (OCS).
+    pub fn to_ocs(&self, point: &Point) -> Point {
+        match self.ocs_normal() {
+            Some(normal) => TransformationMatrix::to_ocs(normal).transform_point(point),
+            None => point.clone(),
+        }
+    }
+    /// The name of the text style used by this entity, if any.
+    pub(crate) fn text_style_name(&self) -> Option<&String> {
+        match self.specific {
+            EntityType::ArcAlignedText(ref e) => Some(&e.text_style_name),
+            EntityType::Attribute(ref e) => Some(&e.text_style_name),
+            EntityType::AttributeDefinition(ref e) => Some(&e.text_style_name),
+            EntityType::MText(ref e) => Some(&e.text_style_name),
+            EntityType::RText(ref e) => Some(&e.text_style),
+            EntityType::Text(ref e) => Some(&e.text_style_name),
+            _ => None,
+        }
+    }
+    pub(crate) fn text_style_name_mut(&mut self) -> Option<&mut String> {
+        match self.specific {
+            EntityType::ArcAlignedText(ref mut e) => Some(&mut e.text_style_name),
+            EntityType::Attribute(ref mut e) => Some(&mut e.text_style_name),
+            EntityType::AttributeDefinition(ref mut e) => Some(&mut e.text_style_name),
+            EntityType::MText(ref mut e) => Some(&mut e.text_style_name),
+            EntityType::RText(ref mut e) => Some(&mut e.text_style),
+            EntityType::Text(ref mut e) => Some(&mut e.text_style_name),
+            _ => None,
+        }
+    }
+    /// The name of the dimension style used by this entity, if any.
+    pub(crate) fn dimension_style_name(&self) -> Option<&String> {
+        if let Some(base) = self.dimension_base() {
+            return Some(&base.dimension_style_name);
+        }
+
+        match self.specific {
+            EntityType::Leader(ref l) => Some(&l.dimension_style_name),
+            EntityType::Tolerance(ref t) => Some(&t.dimension_style_name),
+            _ => None,
+        }
+    }
+    pub(crate) fn dimension_style_name_mut(&mut self) -> Option<&mut String> {
+        match self.specific {
+            EntityType::Leader(ref mut l) => return Some(&mut l.dimension_style_name),
+            EntityType::Tolerance(ref mut t) => return Some(&mut t.dimension_style_name),
+            _ => (),
+        }
+
+        self.dimension_base_mut()
+            .map(|base| &mut base.dimension_style_name)
+    }
+    /// The name of the block referenced by this entity, if any.
+    pub(crate) fn block_name(&self) -> Option<&String> {
+        match self.specific {
+            EntityType::Insert(ref ins) => return Some(&ins.name),
+            EntityType::AcadTable(ref table) => return Some(&table.block_name),
+            _ => (),
+        }
+
+        self.dimension_base().map(|base| &base.block_name)
+    }
+    pub(crate) fn block_name_mut(&mut self) -> Option<&mut String> {
+        match self.specific {
+            EntityType::Insert(ref mut ins) => return Some(&mut ins.name),
+            EntityType::AcadTable(ref mut table) => return Some(&mut table.block_name),
+            _ => (),
+        }
+
+        self.dimension_base_mut().map(|base| &mut base.block_name)
+    }
+    pub(crate) fn dimension_base(&self) -> Option<&DimensionBase> {
+        match self.specific {
+            EntityType::RotatedDimension(ref d) => Some(&d.dimension_base),
+            EntityType::AlignedDimension(ref d) => Some(&d.dimension_base),
+            EntityType::RadialDimension(ref d) => Some(&d.dimension_base),
+            EntityType::DiameterDimension(ref d) => Some(&d.dimension_base),
+            EntityType::AngularTwoLineDimension(ref d) => Some(&d.dimension_base),
+            EntityType::AngularThreePointDimension(ref d) => Some(&d.dimension_base),
+            EntityType::OrdinateDimension(ref d) => Some(&d.dimension_base),
+            EntityType::ArcDimension(ref d) => Some(&d.dimension_base),
+            EntityType::LargeRadialDimension(ref d) => Some(&d.dimension_base),
+            _ => None,
+        }
+    }
+    pub(crate) fn dimension_base_mut(&mut self) -> Option<&mut DimensionBase> {
+        match self.specific {
+            EntityType::RotatedDimension(ref mut d) => Some(&mut d.dimension_base),
+            EntityType::AlignedDimension(ref mut d) => Some(&mut d.dimension_base),
+            EntityType::RadialDimension(ref mut d) => Some(&mut d.dimension_base),
+            EntityType::DiameterDimension(ref mut d) => Some(&mut d.dimension_base),
+            EntityType::AngularTwoLineDimension(ref mut d) => Some(&mut d.dimension_base),
+            EntityType::AngularThreePointDimension(ref mut d) => Some(&mut d.dimension_base),
+            EntityType::OrdinateDimension(ref mut d) => Some(&mut d.dimension_base),
+            EntityType::ArcDimension(ref mut d) => Some(&mut d.dimension_base),
+            EntityType::LargeRadialDimension(ref mut d) => Some(&mut d.dimension_base),
+            _ => None,
+        }
+    }
+    /// Removes any pointers to the item with the specified handle.  Returns `true` if anything was
+    /// removed.
+    pub(crate) fn remove_references_to(&mut self, handle: Handle) -> bool {
+        let mut removed = self.common.remove_pointers_to(handle);
+        for group in self.common.extension_data_groups.iter_mut() {
+            removed |= group.remove_handle(handle);
+        }
+
+        removed |= self.specific.remove_pointers_to(handle);
+        match self.specific {
+            EntityType::Hatch(ref mut hatch) => {
+                for path in hatch.boundary_paths.iter_mut() {
+                    let count = path.source_boundary_object_handles.len();
+                    path.source_boundary_object_handles.retain(|&h| h != handle);
+                    removed |= path.source_boundary_object_handles.len() != count;
+                }
+            }
+            EntityType::Insert(ref mut ins) => {
+                for (att, _) in ins.__attributes_and_handles.iter_mut() {
+                    removed |= att.remove_pointers_to(handle);
+                }
+            }
+            EntityType::MLeader(ref mut mleader) => {
+                let context = &mut mleader.context_data;
+                for h in [
+                    &mut context.text_style_handle,
+                    &mut context.block_content_handle,
+                ] {
+                    if *h == handle {
+                        *h = Handle::empty();
+                        removed = true;
+                    }
+                }
+            }
+            EntityType::AcadTable(ref mut table) => {
+                for cell in table.cells.iter_mut() {
+                    for h in [&mut cell.field_handle, &mut cell.block_handle] {
+                        if *h == handle {
+                            *h = Handle::empty();
+                            removed = true;
+                        }
+                    }
+
+                    let count = cell.block_attributes.len();
+                    cell.block_attributes.retain(|(h, _)| *h != handle);
+                    removed |= cell.block_attributes.len() != count;
+                }
+            }
+            _ => (),
+        }
+
+        removed
+    }
+    /// Collects the handles of the items this entity refers to.
+    pub(crate) fn collect_references(&self, handles: &mut Vec<Handle>) {
+        self.common.collect_pointers(handles);
+        for group in &self.common.extension_data_groups {
+            group.collect_handles(handles);
+        }
+        for x_data in &self.common.x_data {
+            x_data.collect_handles(handles);
+        }
+
+        self.specific.collect_pointers(handles);
+        match self.specific {
+            EntityType::Hatch(ref hatch) => {
+                for path in &hatch.boundary_paths {
+                    handles.extend(path.source_boundary_object_handles.iter().cloned());
+                }
+            }
+            EntityType::Insert(ref ins) => {
+                for att in ins.attributes() {
+                    att.collect_pointers(handles);
+                }
+            }
+            EntityType::MLeader(ref mleader) => {
+                handles.push(mleader.context_data.text_style_handle);
+                handles.push(mleader.context_data.block_content_handle);
+            }
+            EntityType::AcadTable(ref table) => {
+                for cell in &table.cells {
+                    handles.push(cell.field_handle);
+                    handles.push(cell.block_handle);
+                    handles.extend(cell.block_attributes.iter().map(|(h, _)| *h));
+                }
+            }
+            _ => (),
+        }
+    }
+    pub(crate) fn read<I>(iter: &mut CodePairPutBack<I>) -> DxfResult<Option<Entity>>
+    where
+        I: Read,
+    {
+        'new_entity: loop {
+            match iter.next() {
+                // first code pair must be 0/entity-type
+                Some(Ok(pair @ CodePair { code: 0, .. })) => {
+                    let type_string = pair.assert_string()?;
+                    if type_string == "ENDSEC" || type_string == "ENDBLK" {
                         iter.put_back(Ok(pair));
                         return Ok(None);
                     }
 
                     match &*type_string {
-                        "DIMENSION" => {
+                        "DIMENSION" | "ARC_DIMENSION" | "LARGE_RADIAL_DIMENSION" => {
                             // dimensions require special handling
                             let mut common = EntityCommon::default();
                             let mut dimension_entity: Option<EntityType> = None;
                             let mut dimension_base = DimensionBase::default();
+                            let mut has_rotated_dimension_subclass = false;
                             loop {
                                 match iter.next() {
                                     Some(Ok(pair @ CodePair { code: 0, .. })) => {
@@ -437,7 +1304,12 @@ impl Entity {
                                     Some(Ok(pair)) => {
                                         match dimension_entity {
                                             Some(ref mut dim) => {
-                                                if !dim.apply_dimension_code_pair(&pair)? {
+                                                if pair.code == 100
+                                                    && pair.assert_string()?
+                                                        == "AcDbRotatedDimension"
+                                                {
+                                                    has_rotated_dimension_subclass = true;
+                                                } else if !dim.apply_dimension_code_pair(&pair)? {
                                                     common.apply_individual_pair(&pair, iter)?;
                                                 }
                                             }
@@ -537,60 +1409,23 @@ impl Entity {
                                                         );
                                                     }
                                                     100 => {
-                                                        match &*pair.assert_string()? {
-                                                            "AcDbAlignedDimension" => {
-                                                                dimension_entity = Some(
-                                                                    EntityType::RotatedDimension(
-                                                                        RotatedDimension {
-                                                                            dimension_base:
-                                                                                dimension_base
-                                                                                    .clone(),
-                                                                            ..Default::default()
-                                                                        },
-                                                                    ),
-                                                                );
-                                                            }
-                                                            "AcDbRadialDimension" => {
-                                                                dimension_entity = Some(
-                                                                    EntityType::RadialDimension(
-                                                                        RadialDimension {
-                                                                            dimension_base:
-                                                                                dimension_base
-                                                                                    .clone(),
-                                                                            ..Default::default()
-                                                                        },
-                                                                    ),
-                                                                );
-                                                            }
-                                                            "AcDbDiametricDimension" => {
-                                                                dimension_entity = Some(
-                                                                    EntityType::DiameterDimension(
-                                                                        DiameterDimension {
-                                                                            dimension_base:
-                                                                                dimension_base
-                                                                                    .clone(),
-                                                                            ..Default::default()
-                                                                        },
-                                                                    ),
-                                                                );
-                                                            }
-                                                            "AcDb3PointAngularDimension" => {
-                                                                dimension_entity = Some(EntityType::AngularThreePointDimension(AngularThreePointDimension { dimension_base: dimension_base.clone(), .. Default::default() }));
-                                                            }
-                                                            "AcDbOrdinateDimension" => {
-                                                                dimension_entity = Some(
-                                                                    EntityType::OrdinateDimension(
-                                                                        OrdinateDimension {
-                                                                            dimension_base:
-                                                                                dimension_base
-                                                                                    .clone(),
-                                                                            ..Default::default()
-                                                                        },
-                                                                    ),
-                                                                );
-                                                            }
-                                                            _ => {} // unexpected dimension type
-                                                        }
+                                                        dimension_entity = EntityType::new_dimension_for_subclass(
+                                                            &pair.assert_string()?,
+                                                            &dimension_base,
+                                                        );
+                                                    }
+                                                    code if EntityType::is_dimension_specific_code(
+                                                        code,
+                                                    ) =>
+                                                    {
+                                                        // no subclass markers (e.g., R12); fall back
+                                                        // to the type flags
+                                                        let mut dim = EntityType::new_dimension(
+                                                            dimension_base.dimension_type,
+                                                            &dimension_base,
+                                                        );
+                                                        dim.apply_dimension_code_pair(&pair)?;
+                                                        dimension_entity = Some(dim);
                                                     }
                                                     _ => {
                                                         common
@@ -606,6 +1441,25 @@ impl Entity {
                             }
 
                             match dimension_entity {
+                                Some(EntityType::RotatedDimension(dim))
+                                    if dimension_base.dimension_type == DimensionType::Aligned
+                                        && !has_rotated_dimension_subclass =>
+                                {
+                                    // aligned dimensions share their subclass with rotated ones
+                                    return Ok(Some(Entity {
+                                        common,
+                                        specific: EntityType::AlignedDimension(AlignedDimension {
+                                            dimension_base: DimensionBase {
+                                                dimension_type: DimensionType::Aligned,
+                                                ..dim.dimension_base
+                                            },
+                                            insertion_point: dim.insertion_point,
+                                            definition_point_2: dim.definition_point_2,
+                                            definition_point_3: dim.definition_point_3,
+                                            extension_line_angle: dim.extension_line_angle,
+                                        }),
+                                    }));
+                                }
                                 Some(dim) => {
                                     return Ok(Some(Entity {
                                         common,
@@ -618,47 +1472,31 @@ impl Entity {
                             }
                         }
                         _ => {
-                            match EntityType::from_type_string(&type_string) {
-                                Some(e) => {
-                                    let mut entity = Entity::new(e);
-                                    if !entity.apply_custom_reader(iter)? {
-                                        // no custom reader, use the auto-generated one
-                                        loop {
-                                            match iter.next() {
-                                                Some(Ok(pair @ CodePair { code: 0, .. })) => {
-                                                    // new entity or ENDSEC
-                                                    iter.put_back(Ok(pair));
-                                                    break;
-                                                }
-                                                Some(Ok(pair)) => {
-                                                    entity.apply_code_pair(&pair, iter)?
-                                                }
-                                                Some(Err(e)) => return Err(e),
-                                                None => return Err(DxfError::UnexpectedEndOfInput),
-                                            }
-                                        }
-
-                                        entity.post_parse()?;
-                                    }
-
-                                    return Ok(Some(entity));
-                                }
-                                None => {
-                                    // swallow unsupported entity
-                                    loop {
-                                        match iter.next() {
-                                            Some(Ok(pair @ CodePair { code: 0, .. })) => {
-                                                // found another entity or ENDSEC
-                                                iter.put_back(Ok(pair));
-                                                break;
-                                            }
-                                            Some(Ok(_)) => (), // part of the unsupported entity
-                                            Some(Err(e)) => return Err(e),
-                                            None => return Err(DxfError::UnexpectedEndOfInput),
+                            // unsupported entities are kept as raw code pairs
+                            let specific = EntityType::from_type_string(&type_string)
+                                .unwrap_or_else(|| {
+                                    EntityType::Unknown(UnknownEntity::new(&type_string))
+                                });
+                            let mut entity = Entity::new(specific);
+                            if !entity.apply_custom_reader(iter)? {
+                                // no custom reader, use the auto-generated one
+                                loop {
+                                    match iter.next() {
+                                        Some(Ok(pair @ CodePair { code: 0, .. })) => {
+                                            // new entity or ENDSEC
+                                            iter.put_back(Ok(pair));
+                                            break;
                                         }
+                                        Some(Ok(pair)) => entity.apply_code_pair(&pair, iter)?,
+                                        Some(Err(e)) => return Err(e),
+                                        None => return Err(DxfError::UnexpectedEndOfInput),
                                     }
                                 }
+
+                                entity.post_parse()?;
                             }
+
+                            return Ok(Some(entity));
                         }
                     }
                 }
@@ -802,21 +1640,124 @@ impl Entity {
         I: Read,
     {
         match self.specific {
+            EntityType::AcadTable(ref mut table) => {
+                Entity::apply_custom_reader_acadtable(&mut self.common, table, iter)
+            }
             EntityType::Attribute(ref mut att) => {
                 Entity::apply_custom_reader_attribute(&mut self.common, att, iter)
             }
             EntityType::AttributeDefinition(ref mut att) => {
                 Entity::apply_custom_reader_attributedefinition(&mut self.common, att, iter)
             }
+            EntityType::ExtrudedSurface(ref mut surface) => {
+                Entity::apply_custom_reader_extrudedsurface(&mut self.common, surface, iter)
+            }
+            EntityType::Hatch(ref mut hatch) => {
+                Entity::apply_custom_reader_hatch(&mut self.common, hatch, iter)
+            }
+            EntityType::LoftedSurface(ref mut surface) => {
+                Entity::apply_custom_reader_loftedsurface(&mut self.common, surface, iter)
+            }
             EntityType::LwPolyline(ref mut poly) => {
                 Entity::apply_custom_reader_lwpolyline(&mut self.common, poly, iter)
             }
+            EntityType::Mesh(ref mut mesh) => {
+                Entity::apply_custom_reader_mesh(&mut self.common, mesh, iter)
+            }
+            EntityType::MLeader(ref mut mleader) => {
+                Entity::apply_custom_reader_mleader(&mut self.common, mleader, iter)
+            }
             EntityType::MText(ref mut mtext) => {
                 Entity::apply_custom_reader_mtext(&mut self.common, mtext, iter)
             }
+            EntityType::RevolvedSurface(ref mut surface) => {
+                Entity::apply_custom_reader_revolvedsurface(&mut self.common, surface, iter)
+            }
+            EntityType::SweptSurface(ref mut surface) => {
+                Entity::apply_custom_reader_sweptsurface(&mut self.common, surface, iter)
+            }
+            EntityType::Unknown(ref mut unknown) => {
+                Entity::apply_custom_reader_unknown(&mut self.common, unknown, iter)
+            }
             _ => Ok(false), // no custom reader
         }
     }
+    fn apply_custom_reader_acadtable<I>(
+        common: &mut EntityCommon,
+        table: &mut AcadTable,
+        iter: &mut CodePairPutBack<I>,
+    ) -> DxfResult<bool>
+    where
+        I: Read,
+    {
+        loop {
+            let pair = next_pair!(iter);
+            match pair.code {
+                2 => {
+                    table.block_name = pair.assert_string()?;
+                }
+                10 => {
+                    table.insertion_point.x = pair.assert_f64()?;
+                }
+                20 => {
+                    table.insertion_point.y = pair.assert_f64()?;
+                }
+                30 => {
+                    table.insertion_point.z = pair.assert_f64()?;
+                }
+                280 => {
+                    table.version = pair.assert_i16()?;
+                }
+                342 => {
+                    table.__table_style_handle = pair.as_handle()?;
+                }
+                343 => {
+                    table.__block_record_handle = pair.as_handle()?;
+                }
+                11 => {
+                    table.horizontal_direction.x = pair.assert_f64()?;
+                }
+                21 => {
+                    table.horizontal_direction.y = pair.assert_f64()?;
+                }
+                31 => {
+                    table.horizontal_direction.z = pair.assert_f64()?;
+                }
+                90 => {
+                    table.table_value_flags = pair.assert_i32()?;
+                }
+                91 => (), // row count; we just read as many as we're given
+                92 => (), // column count; we just read as many as we're given
+                93 => {
+                    table.override_flags = pair.assert_i32()?;
+                }
+                94 => {
+                    table.border_color_override_flags = pair.assert_i32()?;
+                }
+                95 => {
+                    table.border_lineweight_override_flags = pair.assert_i32()?;
+                }
+                96 => {
+                    table.border_visibility_override_flags = pair.assert_i32()?;
+                }
+                141 => {
+                    table.row_heights.push(pair.assert_f64()?);
+                }
+                142 => {
+                    table.column_widths.push(pair.assert_f64()?);
+                }
+                171 => {
+                    iter.put_back(Ok(pair)); // let the TableCell reader parse this
+                    while let Some(cell) = TableCell::read(iter)? {
+                        table.cells.push(cell);
+                    }
+                }
+                _ => {
+                    common.apply_individual_pair(&pair, iter)?;
+                }
+            }
+        }
+    }
     fn apply_custom_reader_attribute<I>(
         common: &mut EntityCommon,
         att: &mut Attribute,
@@ -1122,56 +2063,47 @@ impl Entity {
             }
         }
     }
-    fn apply_custom_reader_lwpolyline<I>(
+    fn apply_custom_reader_extrudedsurface<I>(
         common: &mut EntityCommon,
-        poly: &mut LwPolyline,
+        surface: &mut ExtrudedSurface,
         iter: &mut CodePairPutBack<I>,
     ) -> DxfResult<bool>
     where
         I: Read,
     {
+        let mut subclass = String::from("AcDbModelerGeometry");
         loop {
             let pair = next_pair!(iter);
+            if pair.code == 100 {
+                subclass = pair.assert_string()?;
+                continue;
+            }
+            if apply_surface_code_pair!(surface, subclass.as_str(), pair) {
+                continue;
+            }
+            if subclass != "AcDbExtrudedSurface" {
+                common.apply_individual_pair(&pair, iter)?;
+                continue;
+            }
+            if apply_sweep_options_code_pair!(surface, pair, iter) {
+                continue;
+            }
             match pair.code {
-                // vertex-specific pairs
+                90 => {
+                    surface.class_id = pair.assert_i32()?;
+                }
                 10 => {
-                    // start a new vertex
-                    poly.vertices.push(LwPolylineVertex::default());
-                    vec_last!(poly.vertices).x = pair.assert_f64()?;
+                    surface.sweep_vector.x = pair.assert_f64()?;
                 }
                 20 => {
-                    vec_last!(poly.vertices).y = pair.assert_f64()?;
-                }
-                40 => {
-                    vec_last!(poly.vertices).starting_width = pair.assert_f64()?;
-                }
-                41 => {
-                    vec_last!(poly.vertices).ending_width = pair.assert_f64()?;
-                }
-                42 => {
-                    vec_last!(poly.vertices).bulge = pair.assert_f64()?;
-                }
-                91 => {
-                    vec_last!(poly.vertices).id = pair.assert_i32()?;
-                }
-                // other pairs
-                39 => {
-                    poly.thickness = pair.assert_f64()?;
-                }
-                43 => {
-                    poly.constant_width = pair.assert_f64()?;
+                    surface.sweep_vector.y = pair.assert_f64()?;
                 }
-                70 => {
-                    poly.flags = i32::from(pair.assert_i16()?);
-                }
-                210 => {
-                    poly.extrusion_direction.x = pair.assert_f64()?;
-                }
-                220 => {
-                    poly.extrusion_direction.y = pair.assert_f64()?;
+                30 => {
+                    surface.sweep_vector.z = pair.assert_f64()?;
                 }
-                230 => {
-                    poly.extrusion_direction.z = pair.assert_f64()?;
+                40 => {
+                    surface.extruded_entity_transformation_matrix =
+                        Entity::read_transformation_matrix(&pair, iter)?;
                 }
                 _ => {
                     common.apply_individual_pair(&pair, iter)?;
@@ -1179,139 +2111,261 @@ impl Entity {
             }
         }
     }
-    fn apply_custom_reader_mtext<I>(
+    fn apply_custom_reader_hatch<I>(
         common: &mut EntityCommon,
-        mtext: &mut MText,
+        hatch: &mut Hatch,
         iter: &mut CodePairPutBack<I>,
     ) -> DxfResult<bool>
     where
         I: Read,
     {
-        let mut reading_column_data = false;
-        let mut read_column_count = false;
+        let mut reading_seed_points = false;
         loop {
             let pair = next_pair!(iter);
             match pair.code {
                 10 => {
-                    mtext.insertion_point.x = pair.assert_f64()?;
+                    if reading_seed_points {
+                        hatch
+                            .seed_points
+                            .push(Point::new(pair.assert_f64()?, 0.0, 0.0));
+                    } else {
+                        hatch.elevation_point.x = pair.assert_f64()?;
+                    }
                 }
                 20 => {
-                    mtext.insertion_point.y = pair.assert_f64()?;
+                    if reading_seed_points {
+                        vec_last!(hatch.seed_points).y = pair.assert_f64()?;
+                    } else {
+                        hatch.elevation_point.y = pair.assert_f64()?;
+                    }
                 }
                 30 => {
-                    mtext.insertion_point.z = pair.assert_f64()?;
+                    hatch.elevation_point.z = pair.assert_f64()?;
                 }
-                40 => {
-                    mtext.initial_text_height = pair.assert_f64()?;
+                210 => {
+                    hatch.extrusion_direction.x = pair.assert_f64()?;
                 }
-                41 => {
-                    mtext.reference_rectangle_width = pair.assert_f64()?;
+                220 => {
+                    hatch.extrusion_direction.y = pair.assert_f64()?;
+                }
+                230 => {
+                    hatch.extrusion_direction.z = pair.assert_f64()?;
+                }
+                2 => {
+                    hatch.pattern_name = pair.assert_string()?;
+                }
+                70 => {
+                    hatch.is_solid_fill = as_bool(pair.assert_i16()?);
                 }
                 71 => {
-                    mtext.attachment_point =
-                        enum_from_number!(AttachmentPoint, TopLeft, from_i16, pair.assert_i16()?);
+                    hatch.is_associative = as_bool(pair.assert_i16()?);
                 }
-                72 => {
-                    mtext.drawing_direction = enum_from_number!(
-                        DrawingDirection,
-                        LeftToRight,
+                91 => {
+                    // boundary path count; we just read as many as we're given
+                    while let Some(path) = HatchBoundaryPath::read(iter)? {
+                        hatch.boundary_paths.push(path);
+                    }
+                }
+                75 => {
+                    hatch.hatch_style =
+                        enum_from_number!(HatchStyle, OddParity, from_i16, pair.assert_i16()?);
+                }
+                76 => {
+                    hatch.pattern_type = enum_from_number!(
+                        HatchPatternType,
+                        Predefined,
                         from_i16,
                         pair.assert_i16()?
                     );
                 }
-                3 => {
-                    mtext.extended_text.push(pair.assert_string()?);
+                52 => {
+                    hatch.pattern_angle = pair.assert_f64()?;
                 }
-                1 => {
-                    mtext.text = pair.assert_string()?;
+                41 => {
+                    hatch.pattern_scale = pair.assert_f64()?;
                 }
-                7 => {
-                    mtext.text_style_name = pair.assert_string()?;
+                77 => {
+                    hatch.is_pattern_double = as_bool(pair.assert_i16()?);
                 }
-                210 => {
-                    mtext.extrusion_direction.x = pair.assert_f64()?;
+                78 => {
+                    // pattern definition line count; we just read as many as we're given
+                    while let Some(line) = HatchPatternDefinitionLine::read(iter)? {
+                        hatch.pattern_definition_lines.push(line);
+                    }
                 }
-                220 => {
-                    mtext.extrusion_direction.y = pair.assert_f64()?;
+                47 => {
+                    hatch.pixel_size = pair.assert_f64()?;
                 }
-                230 => {
-                    mtext.extrusion_direction.z = pair.assert_f64()?;
+                98 => {
+                    // seed point count; we just read as many as we're given
+                    reading_seed_points = true;
                 }
-                11 => {
-                    mtext.x_axis_direction.x = pair.assert_f64()?;
+                450 => {
+                    hatch.is_gradient = pair.assert_i64()? != 0;
                 }
-                21 => {
-                    mtext.x_axis_direction.y = pair.assert_f64()?;
+                451 => (), // reserved
+                452 => {
+                    hatch.is_single_color_gradient = pair.assert_i64()? != 0;
                 }
-                31 => {
-                    mtext.x_axis_direction.z = pair.assert_f64()?;
+                453 => (), // gradient color count; we just read as many as we're given
+                460 => {
+                    hatch.gradient_angle = pair.assert_f64()?;
                 }
-                42 => {
-                    mtext.horizontal_width = pair.assert_f64()?;
+                461 => {
+                    hatch.gradient_shift = pair.assert_f64()?;
                 }
-                43 => {
-                    mtext.vertical_height = pair.assert_f64()?;
+                462 => {
+                    hatch.gradient_color_tint = pair.assert_f64()?;
                 }
-                50 => {
-                    if reading_column_data {
-                        if read_column_count {
-                            mtext.column_heights.push(pair.assert_f64()?);
-                        } else {
-                            mtext.column_count = pair.assert_f64()? as i32;
-                            read_column_count = true;
-                        }
-                    } else {
-                        mtext.rotation_angle = pair.assert_f64()?;
-                    }
+                463 => {
+                    hatch.gradient_colors.push(HatchGradientColor {
+                        value: pair.assert_f64()?,
+                        ..Default::default()
+                    });
                 }
-                73 => {
-                    mtext.line_spacing_style = enum_from_number!(
-                        MTextLineSpacingStyle,
-                        AtLeast,
+                63 => {
+                    vec_last!(hatch.gradient_colors).color =
+                        Color::from_raw_value(pair.assert_i16()?);
+                }
+                421 => {
+                    vec_last!(hatch.gradient_colors).true_color = pair.assert_i32()?;
+                }
+                470 => {
+                    hatch.gradient_name = pair.assert_string()?;
+                }
+                _ => {
+                    common.apply_individual_pair(&pair, iter)?;
+                }
+            }
+        }
+    }
+    fn apply_custom_reader_loftedsurface<I>(
+        common: &mut EntityCommon,
+        surface: &mut LoftedSurface,
+        iter: &mut CodePairPutBack<I>,
+    ) -> DxfResult<bool>
+    where
+        I: Read,
+    {
+        let mut subclass = String::from("AcDbModelerGeometry");
+        loop {
+            let pair = next_pair!(iter);
+            if pair.code == 100 {
+                subclass = pair.assert_string()?;
+                continue;
+            }
+            if apply_surface_code_pair!(surface, subclass.as_str(), pair) {
+                continue;
+            }
+            if subclass != "AcDbLoftedSurface" {
+                common.apply_individual_pair(&pair, iter)?;
+                continue;
+            }
+            match pair.code {
+                40 => {
+                    surface.transformation_matrix =
+                        Entity::read_transformation_matrix(&pair, iter)?;
+                }
+                70 => {
+                    surface.plane_normal_lofting_type = enum_from_number!(
+                        LoftedObjectNormalMode,
+                        SmoothFit,
                         from_i16,
                         pair.assert_i16()?
                     );
                 }
+                41 => {
+                    surface.start_draft_angle = pair.assert_f64()?;
+                }
+                42 => {
+                    surface.end_draft_angle = pair.assert_f64()?;
+                }
+                43 => {
+                    surface.start_draft_magnitude = pair.assert_f64()?;
+                }
                 44 => {
-                    mtext.line_spacing_factor = pair.assert_f64()?;
+                    surface.end_draft_magnitude = pair.assert_f64()?;
                 }
-                90 => {
-                    mtext.background_fill_setting =
-                        enum_from_number!(BackgroundFillSetting, Off, from_i32, pair.assert_i32()?);
+                290 => {
+                    surface.arc_length_parameterization = pair.assert_bool()?;
                 }
-                420 => {
-                    mtext.background_color_rgb = pair.assert_i32()?;
+                291 => {
+                    surface.no_twist = pair.assert_bool()?;
                 }
-                430 => {
-                    mtext.background_color_name = pair.assert_string()?;
+                292 => {
+                    surface.align_direction = pair.assert_bool()?;
                 }
-                45 => {
-                    mtext.fill_box_scale = pair.assert_f64()?;
+                293 => {
+                    surface.create_simple_surfaces = pair.assert_bool()?;
                 }
-                63 => {
-                    mtext.background_fill_color = Color::from_raw_value(pair.assert_i16()?);
+                294 => {
+                    surface.create_closed_surface = pair.assert_bool()?;
                 }
-                441 => {
-                    mtext.background_fill_color_transparency = pair.assert_i32()?;
+                295 => {
+                    surface.is_solid = pair.assert_bool()?;
                 }
-                75 => {
-                    mtext.column_type = pair.assert_i16()?;
-                    reading_column_data = true;
+                296 => {
+                    surface.create_ruled_surface = pair.assert_bool()?;
                 }
-                76 => {
-                    mtext.column_count = i32::from(pair.assert_i16()?);
+                297 => {
+                    surface.virtual_guide = pair.assert_bool()?;
                 }
-                78 => {
-                    mtext.is_column_flow_reversed = as_bool(pair.assert_i16()?);
+                _ => {
+                    common.apply_individual_pair(&pair, iter)?;
                 }
-                79 => {
-                    mtext.is_column_auto_height = as_bool(pair.assert_i16()?);
+            }
+        }
+    }
+    fn apply_custom_reader_lwpolyline<I>(
+        common: &mut EntityCommon,
+        poly: &mut LwPolyline,
+        iter: &mut CodePairPutBack<I>,
+    ) -> DxfResult<bool>
+    where
+        I: Read,
+    {
+        loop {
+            let pair = next_pair!(iter);
+            match pair.code {
+                // vertex-specific pairs
+                10 => {
+                    // start a new vertex
+                    poly.vertices.push(LwPolylineVertex::default());
+                    vec_last!(poly.vertices).x = pair.assert_f64()?;
                 }
-                48 => {
-                    mtext.column_width = pair.assert_f64()?;
+                20 => {
+                    vec_last!(poly.vertices).y = pair.assert_f64()?;
                 }
-                49 => {
-                    mtext.column_gutter = pair.assert_f64()?;
+                40 => {
+                    vec_last!(poly.vertices).starting_width = pair.assert_f64()?;
+                }
+                41 => {
+                    vec_last!(poly.vertices).ending_width = pair.assert_f64()?;
+                }
+                42 => {
+                    vec_last!(poly.vertices).bulge = pair.assert_f64()?;
+                }
+                91 => {
+                    vec_last!(poly.vertices).id = pair.assert_i32()?;
+                }
+                // other pairs
+                39 => {
+                    poly.thickness = pair.assert_f64()?;
+                }
+                43 => {
+                    poly.constant_width = pair.assert_f64()?;
+                }
+                70 => {
+                    poly.flags = i32::from(pair.assert_i16()?);
+                }
+                210 => {
+                    poly.extrusion_direction.x = pair.assert_f64()?;
+                }
+                220 => {
+                    poly.extrusion_direction.y = pair.assert_f64()?;
+                }
+                230 => {
+                    poly.extrusion_direction.z = pair.assert_f64()?;
                 }
                 _ => {
                     common.apply_individual_pair(&pair, iter)?;
@@ -1319,453 +2373,2282 @@ impl Entity {
             }
         }
     }
-    pub(crate) fn write<T>(
-        &self,
-        version: AcadVersion,
-        write_handles: bool,
-        writer: &mut CodePairWriter<T>,
-    ) -> DxfResult<()>
+    fn apply_custom_reader_mesh<I>(
+        common: &mut EntityCommon,
+        mesh: &mut Mesh,
+        iter: &mut CodePairPutBack<I>,
+    ) -> DxfResult<bool>
     where
-        T: Write + ?Sized,
+        I: Read,
     {
-        if self.specific.is_supported_on_version(version) {
-            writer.write_code_pair(&CodePair::new_str(0, self.specific.to_type_string()))?;
-            self.common.write(version, write_handles, writer)?;
-            if !self.apply_custom_writer(version, writer)? {
-                self.specific.write(&self.common, version, writer)?;
-            }
-
-            self.post_write(version, write_handles, writer)?;
-            for x in &self.common.x_data {
-                x.write(version, writer)?;
+        let mut read_subdivision_level = false;
+        loop {
+            let pair = next_pair!(iter);
+            match pair.code {
+                71 => {
+                    mesh.version = pair.assert_i16()?;
+                }
+                72 => {
+                    mesh.is_blend_crease = as_bool(pair.assert_i16()?);
+                }
+                91 if !read_subdivision_level => {
+                    mesh.subdivision_level = pair.assert_i32()?;
+                    read_subdivision_level = true;
+                }
+                92 => (), // vertex count; we just read as many as we're given
+                10 => {
+                    mesh.vertices.push(Point::new(pair.assert_f64()?, 0.0, 0.0));
+                }
+                20 => {
+                    vec_last!(mesh.vertices).y = pair.assert_f64()?;
+                }
+                30 => {
+                    vec_last!(mesh.vertices).z = pair.assert_f64()?;
+                }
+                93 => {
+                    // face list size; each face is a vertex count followed by that many indices
+                    let mut remaining = pair.assert_i32()?;
+                    while remaining > 0 {
+                        let vertex_count = match Entity::read_mesh_value(iter)? {
+                            Some(vertex_count) => vertex_count,
+                            None => break,
+                        };
+                        let mut face = vec![];
+                        for _ in 0..vertex_count {
+                            match Entity::read_mesh_value(iter)? {
+                                Some(index) => face.push(index),
+                                None => break,
+                            }
+                        }
+                        remaining -= vertex_count + 1;
+                        mesh.faces.push(face);
+                    }
+                }
+                94 => {
+                    let edge_count = pair.assert_i32()?;
+                    for _ in 0..edge_count {
+                        let start = Entity::read_mesh_value(iter)?;
+                        let end = Entity::read_mesh_value(iter)?;
+                        match (start, end) {
+                            (Some(start), Some(end)) => mesh.edges.push((start, end)),
+                            _ => break,
+                        }
+                    }
+                }
+                95 => (), // edge crease count; we just read as many as we're given
+                140 => {
+                    mesh.edge_creases.push(pair.assert_f64()?);
+                }
+                90 => {
+                    let override_count = pair.assert_i32()?;
+                    for _ in 0..override_count {
+                        match Entity::read_mesh_sub_entity_override(iter)? {
+                            Some(o) => mesh.overrides.push(o),
+                            None => break,
+                        }
+                    }
+                }
+                _ => {
+                    common.apply_individual_pair(&pair, iter)?;
+                }
             }
         }
-
-        Ok(())
     }
-    fn apply_custom_writer<T>(
-        &self,
-        version: AcadVersion,
-        writer: &mut CodePairWriter<T>,
-    ) -> DxfResult<bool>
+    fn read_mesh_value<I>(iter: &mut CodePairPutBack<I>) -> DxfResult<Option<i32>>
     where
-        T: Write + ?Sized,
+        I: Read,
     {
-        match self.specific {
-            EntityType::RotatedDimension(ref dim) => {
-                Entity::apply_custom_writer_rotateddimension(dim, version, writer)?;
+        match iter.next() {
+            Some(Ok(pair @ CodePair { code: 90, .. })) => Ok(Some(pair.assert_i32()?)),
+            Some(Ok(pair)) => {
+                iter.put_back(Ok(pair));
+                Ok(None)
             }
-            EntityType::RadialDimension(ref dim) => {
-                Entity::apply_custom_writer_radialdimension(dim, version, writer)?;
+            Some(Err(e)) => Err(e),
+            None => Ok(None),
+        }
+    }
+    fn read_mesh_sub_entity_override<I>(
+        iter: &mut CodePairPutBack<I>,
+    ) -> DxfResult<Option<MeshSubEntityOverride>>
+    where
+        I: Read,
+    {
+        let mut sub_entity_override = MeshSubEntityOverride::default();
+        match iter.next() {
+            Some(Ok(pair @ CodePair { code: 91, .. })) => {
+                sub_entity_override.sub_entity_marker = pair.assert_i32()?;
             }
-            EntityType::DiameterDimension(ref dim) => {
-                Entity::apply_custom_writer_diameterdimension(dim, version, writer)?;
+            Some(Ok(pair)) => {
+                iter.put_back(Ok(pair));
+                return Ok(None);
             }
-            EntityType::AngularThreePointDimension(ref dim) => {
-                Entity::apply_custom_writer_angularthreepointdimension(dim, version, writer)?;
+            Some(Err(e)) => return Err(e),
+            None => return Ok(None),
+        }
+        let property_count = match iter.next() {
+            Some(Ok(pair @ CodePair { code: 92, .. })) => pair.assert_i32()?,
+            Some(Ok(pair)) => {
+                iter.put_back(Ok(pair));
+                return Ok(Some(sub_entity_override));
             }
-            EntityType::OrdinateDimension(ref dim) => {
-                Entity::apply_custom_writer_ordinatedimension(dim, version, writer)?;
+            Some(Err(e)) => return Err(e),
+            None => return Ok(Some(sub_entity_override)),
+        };
+        for _ in 0..property_count {
+            let property_type = match Entity::read_mesh_value(iter)? {
+                Some(property_type) => property_type,
+                None => break,
+            };
+            let mut property = MeshPropertyOverride::new(enum_from_number!(
+                MeshPropertyType,
+                Color,
+                from_i32,
+                property_type
+            ));
+            // the value runs until the next property or sub-entity, or the trailing XDATA
+            loop {
+                match iter.next() {
+                    Some(Ok(pair)) => match pair.code {
+                        0
+                        | 90
+                        | 91
+                        | extension_data::EXTENSION_DATA_GROUP
+                        | x_data::XDATA_APPLICATIONNAME => {
+                            iter.put_back(Ok(pair));
+                            break;
+                        }
+                        _ => property.values.push(pair),
+                    },
+                    Some(Err(e)) => return Err(e),
+                    None => break,
+                }
             }
-            EntityType::Polyline(ref poly) => {
-                Entity::apply_custom_writer_polyline(poly, version, writer)?;
-            }
-            EntityType::Vertex(ref v) => {
-                Entity::apply_custom_writer_vertex(v, version, writer)?;
-            }
-            _ => return Ok(false), // no custom writer
+            sub_entity_override.properties.push(property);
         }
-
-        Ok(true)
+        Ok(Some(sub_entity_override))
     }
-    fn apply_custom_writer_rotateddimension<T>(
-        dim: &RotatedDimension,
-        version: AcadVersion,
-        writer: &mut CodePairWriter<T>,
+    fn apply_custom_reader_mleader<I>(
+        common: &mut EntityCommon,
+        mleader: &mut MLeader,
+        iter: &mut CodePairPutBack<I>,
     ) -> DxfResult<bool>
     where
-        T: Write + ?Sized,
+        I: Read,
     {
-        dim.dimension_base.write(version, writer)?;
-        if version >= AcadVersion::R13 {
-            writer.write_code_pair(&CodePair::new_str(100, "AcDbAlignedDimension"))?;
-        }
-        writer.write_code_pair(&CodePair::new_f64(12, dim.insertion_point.x))?;
-        writer.write_code_pair(&CodePair::new_f64(22, dim.insertion_point.y))?;
-        writer.write_code_pair(&CodePair::new_f64(32, dim.insertion_point.z))?;
-        writer.write_code_pair(&CodePair::new_f64(13, dim.definition_point_2.x))?;
-        writer.write_code_pair(&CodePair::new_f64(23, dim.definition_point_2.y))?;
-        writer.write_code_pair(&CodePair::new_f64(33, dim.definition_point_2.z))?;
-        writer.write_code_pair(&CodePair::new_f64(14, dim.definition_point_3.x))?;
-        writer.write_code_pair(&CodePair::new_f64(24, dim.definition_point_3.y))?;
-        writer.write_code_pair(&CodePair::new_f64(34, dim.definition_point_3.z))?;
-        writer.write_code_pair(&CodePair::new_f64(50, dim.rotation_angle))?;
-        writer.write_code_pair(&CodePair::new_f64(52, dim.extension_line_angle))?;
-        if version >= AcadVersion::R13 {
-            writer.write_code_pair(&CodePair::new_str(100, "AcDbRotatedDimension"))?;
+        loop {
+            let pair = next_pair!(iter);
+            match pair.code {
+                270 => {
+                    mleader.version = pair.assert_i16()?;
+                }
+                300 => {
+                    // value should be "CONTEXT_DATA{", but it doesn't really matter
+                    mleader.context_data = MLeaderContextData::read(iter)?;
+                }
+                340 => {
+                    mleader.__leader_style_handle = pair.as_handle()?;
+                }
+                90 => {
+                    mleader.property_override_flags = pair.assert_i32()?;
+                }
+                170 => {
+                    mleader.leader_line_type = pair.assert_i16()?;
+                }
+                91 => {
+                    mleader.leader_line_color = pair.assert_i32()?;
+                }
+                341 => {
+                    mleader.__leader_line_type_style_handle = pair.as_handle()?;
+                }
+                171 => {
+                    mleader.leader_line_weight = pair.assert_i16()?;
+                }
+                290 => {
+                    mleader.enable_landing = pair.assert_bool()?;
+                }
+                291 => {
+                    mleader.enable_dogleg = pair.assert_bool()?;
+                }
+                41 => {
+                    mleader.dogleg_length = pair.assert_f64()?;
+                }
+                342 => {
+                    mleader.__arrowhead_handle = pair.as_handle()?;
+                }
+                42 => {
+                    mleader.arrowhead_size = pair.assert_f64()?;
+                }
+                172 => {
+                    mleader.content_type = pair.assert_i16()?;
+                }
+                343 => {
+                    mleader.__text_style_handle = pair.as_handle()?;
+                }
+                173 => {
+                    mleader.text_left_attachment_type = pair.assert_i16()?;
+                }
+                95 => {
+                    mleader.text_right_attachment_type = pair.assert_i32()?;
+                }
+                174 => {
+                    mleader.text_angle_type = pair.assert_i16()?;
+                }
+                175 => {
+                    mleader.text_alignment_type = pair.assert_i16()?;
+                }
+                92 => {
+                    mleader.text_color = pair.assert_i32()?;
+                }
+                292 => {
+                    mleader.enable_frame_text = pair.assert_bool()?;
+                }
+                344 => {
+                    mleader.__block_content_handle = pair.as_handle()?;
+                }
+                93 => {
+                    mleader.block_content_color = pair.assert_i32()?;
+                }
+                10 => {
+                    mleader.block_content_scale.x = pair.assert_f64()?;
+                }
+                20 => {
+                    mleader.block_content_scale.y = pair.assert_f64()?;
+                }
+                30 => {
+                    mleader.block_content_scale.z = pair.assert_f64()?;
+                }
+                43 => {
+                    mleader.block_content_rotation = pair.assert_f64()?;
+                }
+                176 => {
+                    mleader.block_content_connection_type = pair.assert_i16()?;
+                }
+                293 => {
+                    mleader.enable_annotation_scale = pair.assert_bool()?;
+                }
+                294 => {
+                    mleader.is_text_direction_negative = pair.assert_bool()?;
+                }
+                178 => {
+                    mleader.ipe_text_alignment = pair.assert_i16()?;
+                }
+                179 => {
+                    mleader.text_attachment_point = pair.assert_i16()?;
+                }
+                45 => {
+                    mleader.scale_factor = pair.assert_f64()?;
+                }
+                271 => {
+                    mleader.text_attachment_direction = pair.assert_i16()?;
+                }
+                272 => {
+                    mleader.text_bottom_attachment_direction = pair.assert_i16()?;
+                }
+                273 => {
+                    mleader.text_top_attachment_direction = pair.assert_i16()?;
+                }
+                _ => {
+                    common.apply_individual_pair(&pair, iter)?;
+                }
+            }
         }
-        Ok(true)
     }
-    fn apply_custom_writer_radialdimension<T>(
-        dim: &RadialDimension,
-        version: AcadVersion,
-        writer: &mut CodePairWriter<T>,
+    fn apply_custom_reader_mtext<I>(
+        common: &mut EntityCommon,
+        mtext: &mut MText,
+        iter: &mut CodePairPutBack<I>,
     ) -> DxfResult<bool>
     where
-        T: Write + ?Sized,
+        I: Read,
     {
-        dim.dimension_base.write(version, writer)?;
-        writer.write_code_pair(&CodePair::new_str(100, "AcDbRadialDimension"))?;
-        writer.write_code_pair(&CodePair::new_f64(15, dim.definition_point_2.x))?;
-        writer.write_code_pair(&CodePair::new_f64(25, dim.definition_point_2.y))?;
-        writer.write_code_pair(&CodePair::new_f64(35, dim.definition_point_2.z))?;
-        writer.write_code_pair(&CodePair::new_f64(40, dim.leader_length))?;
-        Ok(true)
+        let mut reading_column_data = false;
+        let mut read_column_count = false;
+        loop {
+            let pair = next_pair!(iter);
+            match pair.code {
+                10 => {
+                    mtext.insertion_point.x = pair.assert_f64()?;
+                }
+                20 => {
+                    mtext.insertion_point.y = pair.assert_f64()?;
+                }
+                30 => {
+                    mtext.insertion_point.z = pair.assert_f64()?;
+                }
+                40 => {
+                    mtext.initial_text_height = pair.assert_f64()?;
+                }
+                41 => {
+                    mtext.reference_rectangle_width = pair.assert_f64()?;
+                }
+                71 => {
+                    mtext.attachment_point =
+                        enum_from_number!(AttachmentPoint, TopLeft, from_i16, pair.assert_i16()?);
+                }
+                72 => {
+                    mtext.drawing_direction = enum_from_number!(
+                        DrawingDirection,
+                        LeftToRight,
+                        from_i16,
+                        pair.assert_i16()?
+                    );
+                }
+                3 => {
+                    mtext.extended_text.push(pair.assert_string()?);
+                }
+                1 => {
+                    mtext.text = pair.assert_string()?;
+                }
+                7 => {
+                    mtext.text_style_name = pair.assert_string()?;
+                }
+                210 => {
+                    mtext.extrusion_direction.x = pair.assert_f64()?;
+                }
+                220 => {
+                    mtext.extrusion_direction.y = pair.assert_f64()?;
+                }
+                230 => {
+                    mtext.extrusion_direction.z = pair.assert_f64()?;
+                }
+                11 => {
+                    mtext.x_axis_direction.x = pair.assert_f64()?;
+                }
+                21 => {
+                    mtext.x_axis_direction.y = pair.assert_f64()?;
+                }
+                31 => {
+                    mtext.x_axis_direction.z = pair.assert_f64()?;
+                }
+                42 => {
+                    mtext.horizontal_width = pair.assert_f64()?;
+                }
+                43 => {
+                    mtext.vertical_height = pair.assert_f64()?;
+                }
+                50 => {
+                    if reading_column_data {
+                        if read_column_count {
+                            mtext.column_heights.push(pair.assert_f64()?);
+                        } else {
+                            mtext.column_count = pair.assert_f64()? as i32;
+                            read_column_count = true;
+                        }
+                    } else {
+                        mtext.rotation_angle = pair.assert_f64()?;
+                    }
+                }
+                73 => {
+                    mtext.line_spacing_style = enum_from_number!(
+                        MTextLineSpacingStyle,
+                        AtLeast,
+                        from_i16,
+                        pair.assert_i16()?
+                    );
+                }
+                44 => {
+                    mtext.line_spacing_factor = pair.assert_f64()?;
+                }
+                90 => {
+                    mtext.background_fill_setting =
+                        enum_from_number!(BackgroundFillSetting, Off, from_i32, pair.assert_i32()?);
+                }
+                420 => {
+                    mtext.background_color_rgb = pair.assert_i32()?;
+                }
+                430 => {
+                    mtext.background_color_name = pair.assert_string()?;
+                }
+                45 => {
+                    mtext.fill_box_scale = pair.assert_f64()?;
+                }
+                63 => {
+                    mtext.background_fill_color = Color::from_raw_value(pair.assert_i16()?);
+                }
+                441 => {
+                    mtext.background_fill_color_transparency = pair.assert_i32()?;
+                }
+                75 => {
+                    mtext.column_type = pair.assert_i16()?;
+                    reading_column_data = true;
+                }
+                76 => {
+                    mtext.column_count = i32::from(pair.assert_i16()?);
+                }
+                78 => {
+                    mtext.is_column_flow_reversed = as_bool(pair.assert_i16()?);
+                }
+                79 => {
+                    mtext.is_column_auto_height = as_bool(pair.assert_i16()?);
+                }
+                48 => {
+                    mtext.column_width = pair.assert_f64()?;
+                }
+                49 => {
+                    mtext.column_gutter = pair.assert_f64()?;
+                }
+                _ => {
+                    common.apply_individual_pair(&pair, iter)?;
+                }
+            }
+        }
     }
-    fn apply_custom_writer_diameterdimension<T>(
-        dim: &DiameterDimension,
+    pub(crate) fn write<T>(
+        &self,
         version: AcadVersion,
+        write_handles: bool,
         writer: &mut CodePairWriter<T>,
-    ) -> DxfResult<bool>
+    ) -> DxfResult<()>
     where
         T: Write + ?Sized,
     {
-        dim.dimension_base.write(version, writer)?;
-        writer.write_code_pair(&CodePair::new_str(100, "AcDbDiametricDimension"))?;
-        writer.write_code_pair(&CodePair::new_f64(15, dim.definition_point_2.x))?;
-        writer.write_code_pair(&CodePair::new_f64(25, dim.definition_point_2.y))?;
-        writer.write_code_pair(&CodePair::new_f64(35, dim.definition_point_2.z))?;
-        writer.write_code_pair(&CodePair::new_f64(40, dim.leader_length))?;
-        Ok(true)
-    }
-    fn apply_custom_writer_angularthreepointdimension<T>(
-        dim: &AngularThreePointDimension,
-        version: AcadVersion,
-        writer: &mut CodePairWriter<T>,
-    ) -> DxfResult<bool>
-    where
+        if self.specific.is_supported_on_version(version) {
+            writer.write_code_pair(&CodePair::new_str(0, self.specific.to_type_string()))?;
+            self.common.write(version, write_handles, writer)?;
+            if !self.apply_custom_writer(version, writer)? {
+                self.specific.write(&self.common, version, writer)?;
+            }
+
+            self.post_write(version, write_handles, writer)?;
+            for x in &self.common.x_data {
+                x.write(version, writer)?;
+            }
+        }
+
+        Ok(())
+    }
+    fn apply_custom_reader_revolvedsurface<I>(
+        common: &mut EntityCommon,
+        surface: &mut RevolvedSurface,
+        iter: &mut CodePairPutBack<I>,
+    ) -> DxfResult<bool>
+    where
+        I: Read,
+    {
+        let mut subclass = String::from("AcDbModelerGeometry");
+        let mut read_revolve_entity_id = false;
+        loop {
+            let pair = next_pair!(iter);
+            if pair.code == 100 {
+                subclass = pair.assert_string()?;
+                continue;
+            }
+            if apply_surface_code_pair!(surface, subclass.as_str(), pair) {
+                continue;
+            }
+            if subclass != "AcDbRevolvedSurface" {
+                common.apply_individual_pair(&pair, iter)?;
+                continue;
+            }
+            match pair.code {
+                90 => {
+                    if read_revolve_entity_id {
+                        // revolve entity data size; we just read as many as we're given
+                    } else {
+                        surface.revolve_entity_id = pair.assert_i32()?;
+                        read_revolve_entity_id = true;
+                    }
+                }
+                310 => {
+                    surface.revolve_entity_data.push(pair.assert_string()?);
+                }
+                10 => {
+                    surface.axis_point.x = pair.assert_f64()?;
+                }
+                20 => {
+                    surface.axis_point.y = pair.assert_f64()?;
+                }
+                30 => {
+                    surface.axis_point.z = pair.assert_f64()?;
+                }
+                11 => {
+                    surface.axis_vector.x = pair.assert_f64()?;
+                }
+                21 => {
+                    surface.axis_vector.y = pair.assert_f64()?;
+                }
+                31 => {
+                    surface.axis_vector.z = pair.assert_f64()?;
+                }
+                40 => {
+                    surface.revolve_angle = pair.assert_f64()?;
+                }
+                41 => {
+                    surface.start_angle = pair.assert_f64()?;
+                }
+                42 => {
+                    surface.revolved_entity_transformation_matrix =
+                        Entity::read_transformation_matrix(&pair, iter)?;
+                }
+                43 => {
+                    surface.draft_angle = pair.assert_f64()?;
+                }
+                44 => {
+                    surface.start_draft_distance = pair.assert_f64()?;
+                }
+                45 => {
+                    surface.end_draft_distance = pair.assert_f64()?;
+                }
+                46 => {
+                    surface.twist_angle = pair.assert_f64()?;
+                }
+                290 => {
+                    surface.is_solid = pair.assert_bool()?;
+                }
+                291 => {
+                    surface.is_close_to_axis = pair.assert_bool()?;
+                }
+                _ => {
+                    common.apply_individual_pair(&pair, iter)?;
+                }
+            }
+        }
+    }
+    fn apply_custom_reader_sweptsurface<I>(
+        common: &mut EntityCommon,
+        surface: &mut SweptSurface,
+        iter: &mut CodePairPutBack<I>,
+    ) -> DxfResult<bool>
+    where
+        I: Read,
+    {
+        let mut subclass = String::from("AcDbModelerGeometry");
+        let mut code_90_count = 0;
+        loop {
+            let pair = next_pair!(iter);
+            if pair.code == 100 {
+                subclass = pair.assert_string()?;
+                continue;
+            }
+            if apply_surface_code_pair!(surface, subclass.as_str(), pair) {
+                continue;
+            }
+            if subclass != "AcDbSweptSurface" {
+                common.apply_individual_pair(&pair, iter)?;
+                continue;
+            }
+            if apply_sweep_options_code_pair!(surface, pair, iter) {
+                continue;
+            }
+            match pair.code {
+                90 => {
+                    // entity ids are each followed by their data size, which we don't need
+                    match code_90_count {
+                        0 => surface.sweep_entity_id = pair.assert_i32()?,
+                        2 => surface.path_entity_id = pair.assert_i32()?,
+                        _ => (),
+                    }
+                    code_90_count += 1;
+                }
+                310 => {
+                    if code_90_count <= 2 {
+                        surface.sweep_entity_data.push(pair.assert_string()?);
+                    } else {
+                        surface.path_entity_data.push(pair.assert_string()?);
+                    }
+                }
+                40 => {
+                    surface.sweep_entity_transformation_matrix =
+                        Entity::read_transformation_matrix(&pair, iter)?;
+                }
+                41 => {
+                    surface.path_entity_transformation_matrix =
+                        Entity::read_transformation_matrix(&pair, iter)?;
+                }
+                _ => {
+                    common.apply_individual_pair(&pair, iter)?;
+                }
+            }
+        }
+    }
+    fn apply_custom_reader_unknown<I>(
+        common: &mut EntityCommon,
+        unknown: &mut UnknownEntity,
+        iter: &mut CodePairPutBack<I>,
+    ) -> DxfResult<bool>
+    where
+        I: Read,
+    {
+        // only the values before the first entity-specific subclass and the trailing XDATA are common
+        let mut reading_common = true;
+        loop {
+            let pair = next_pair!(iter);
+            if pair.code == 100 {
+                reading_common = pair.assert_string()? == "AcDbEntity";
+                if reading_common {
+                    continue; // written with the common values
+                }
+            }
+
+            if (reading_common || pair.code == x_data::XDATA_APPLICATIONNAME)
+                && common.apply_individual_pair(&pair, iter)?
+            {
+                continue;
+            }
+
+            unknown.code_pairs.push(pair);
+        }
+    }
+    fn read_transformation_matrix<I>(
+        first_pair: &CodePair,
+        iter: &mut CodePairPutBack<I>,
+    ) -> DxfResult<TransformationMatrix>
+    where
+        I: Read,
+    {
+        // the 16 values are written one after another with the same code
+        let mut values = vec![first_pair.assert_f64()?];
+        while values.len() < 16 {
+            match iter.next() {
+                Some(Ok(pair)) => {
+                    if pair.code == first_pair.code {
+                        values.push(pair.assert_f64()?);
+                    } else {
+                        iter.put_back(Ok(pair));
+                        break;
+                    }
+                }
+                Some(Err(e)) => return Err(e),
+                None => break,
+            }
+        }
+        Ok(TransformationMatrix::from_vec(&values))
+    }
+    fn apply_custom_writer<T>(
+        &self,
+        version: AcadVersion,
+        writer: &mut CodePairWriter<T>,
+    ) -> DxfResult<bool>
+    where
         T: Write + ?Sized,
     {
-        dim.dimension_base.write(version, writer)?;
-        writer.write_code_pair(&CodePair::new_str(100, "AcDb3PointAngularDimension"))?;
+        match self.specific {
+            EntityType::RotatedDimension(ref dim) => {
+                Entity::apply_custom_writer_rotateddimension(dim, version, writer)?;
+            }
+            EntityType::AlignedDimension(ref dim) => {
+                Entity::apply_custom_writer_aligneddimension(dim, version, writer)?;
+            }
+            EntityType::RadialDimension(ref dim) => {
+                Entity::apply_custom_writer_radialdimension(dim, version, writer)?;
+            }
+            EntityType::DiameterDimension(ref dim) => {
+                Entity::apply_custom_writer_diameterdimension(dim, version, writer)?;
+            }
+            EntityType::AngularTwoLineDimension(ref dim) => {
+                Entity::apply_custom_writer_angulartwolinedimension(dim, version, writer)?;
+            }
+            EntityType::AngularThreePointDimension(ref dim) => {
+                Entity::apply_custom_writer_angularthreepointdimension(dim, version, writer)?;
+            }
+            EntityType::OrdinateDimension(ref dim) => {
+                Entity::apply_custom_writer_ordinatedimension(dim, version, writer)?;
+            }
+            EntityType::ArcDimension(ref dim) => {
+                Entity::apply_custom_writer_arcdimension(dim, version, writer)?;
+            }
+            EntityType::LargeRadialDimension(ref dim) => {
+                Entity::apply_custom_writer_largeradialdimension(dim, version, writer)?;
+            }
+            EntityType::AcadTable(ref table) => {
+                Entity::apply_custom_writer_acadtable(table, writer)?;
+            }
+            EntityType::ExtrudedSurface(ref surface) => {
+                Entity::apply_custom_writer_extrudedsurface(surface, writer)?;
+            }
+            EntityType::Hatch(ref hatch) => {
+                Entity::apply_custom_writer_hatch(hatch, version, writer)?;
+            }
+            EntityType::LoftedSurface(ref surface) => {
+                Entity::apply_custom_writer_loftedsurface(surface, writer)?;
+            }
+            EntityType::Mesh(ref mesh) => {
+                Entity::apply_custom_writer_mesh(mesh, writer)?;
+            }
+            EntityType::MLeader(ref mleader) => {
+                Entity::apply_custom_writer_mleader(mleader, version, writer)?;
+            }
+            EntityType::Polyline(ref poly) => {
+                Entity::apply_custom_writer_polyline(poly, version, writer)?;
+            }
+            EntityType::RevolvedSurface(ref surface) => {
+                Entity::apply_custom_writer_revolvedsurface(surface, writer)?;
+            }
+            EntityType::SweptSurface(ref surface) => {
+                Entity::apply_custom_writer_sweptsurface(surface, writer)?;
+            }
+            EntityType::Unknown(ref unknown) => {
+                for pair in &unknown.code_pairs {
+                    writer.write_code_pair(pair)?;
+                }
+            }
+            EntityType::Vertex(ref v) => {
+                Entity::apply_custom_writer_vertex(v, version, writer)?;
+            }
+            _ => return Ok(false), // no custom writer
+        }
+
+        Ok(true)
+    }
+    fn apply_custom_writer_rotateddimension<T>(
+        dim: &RotatedDimension,
+        version: AcadVersion,
+        writer: &mut CodePairWriter<T>,
+    ) -> DxfResult<bool>
+    where
+        T: Write + ?Sized,
+    {
+        dim.dimension_base
+            .write_as(DimensionType::RotatedHorizontalOrVertical, version, writer)?;
+        if version >= AcadVersion::R13 {
+            writer.write_code_pair(&CodePair::new_str(100, "AcDbAlignedDimension"))?;
+        }
+        writer.write_code_pair(&CodePair::new_f64(12, dim.insertion_point.x))?;
+        writer.write_code_pair(&CodePair::new_f64(22, dim.insertion_point.y))?;
+        writer.write_code_pair(&CodePair::new_f64(32, dim.insertion_point.z))?;
         writer.write_code_pair(&CodePair::new_f64(13, dim.definition_point_2.x))?;
         writer.write_code_pair(&CodePair::new_f64(23, dim.definition_point_2.y))?;
         writer.write_code_pair(&CodePair::new_f64(33, dim.definition_point_2.z))?;
         writer.write_code_pair(&CodePair::new_f64(14, dim.definition_point_3.x))?;
         writer.write_code_pair(&CodePair::new_f64(24, dim.definition_point_3.y))?;
         writer.write_code_pair(&CodePair::new_f64(34, dim.definition_point_3.z))?;
-        writer.write_code_pair(&CodePair::new_f64(15, dim.definition_point_4.x))?;
-        writer.write_code_pair(&CodePair::new_f64(25, dim.definition_point_4.y))?;
-        writer.write_code_pair(&CodePair::new_f64(35, dim.definition_point_4.z))?;
-        writer.write_code_pair(&CodePair::new_f64(16, dim.definition_point_5.x))?;
-        writer.write_code_pair(&CodePair::new_f64(26, dim.definition_point_5.y))?;
-        writer.write_code_pair(&CodePair::new_f64(36, dim.definition_point_5.z))?;
+        writer.write_code_pair(&CodePair::new_f64(50, dim.rotation_angle))?;
+        writer.write_code_pair(&CodePair::new_f64(52, dim.extension_line_angle))?;
+        if version >= AcadVersion::R13 {
+            writer.write_code_pair(&CodePair::new_str(100, "AcDbRotatedDimension"))?;
+        }
         Ok(true)
     }
-    fn apply_custom_writer_ordinatedimension<T>(
-        dim: &OrdinateDimension,
+    fn apply_custom_writer_aligneddimension<T>(
+        dim: &AlignedDimension,
         version: AcadVersion,
         writer: &mut CodePairWriter<T>,
     ) -> DxfResult<bool>
     where
         T: Write + ?Sized,
     {
-        dim.dimension_base.write(version, writer)?;
-        writer.write_code_pair(&CodePair::new_str(100, "AcDbOrdinateDimension"))?;
+        dim.dimension_base
+            .write_as(DimensionType::Aligned, version, writer)?;
+        writer.write_code_pair(&CodePair::new_str(100, "AcDbAlignedDimension"))?;
+        writer.write_code_pair(&CodePair::new_f64(12, dim.insertion_point.x))?;
+        writer.write_code_pair(&CodePair::new_f64(22, dim.insertion_point.y))?;
+        writer.write_code_pair(&CodePair::new_f64(32, dim.insertion_point.z))?;
         writer.write_code_pair(&CodePair::new_f64(13, dim.definition_point_2.x))?;
         writer.write_code_pair(&CodePair::new_f64(23, dim.definition_point_2.y))?;
         writer.write_code_pair(&CodePair::new_f64(33, dim.definition_point_2.z))?;
         writer.write_code_pair(&CodePair::new_f64(14, dim.definition_point_3.x))?;
         writer.write_code_pair(&CodePair::new_f64(24, dim.definition_point_3.y))?;
         writer.write_code_pair(&CodePair::new_f64(34, dim.definition_point_3.z))?;
+        writer.write_code_pair(&CodePair::new_f64(52, dim.extension_line_angle))?;
         Ok(true)
     }
-    fn apply_custom_writer_polyline<T>(
-        poly: &Polyline,
+    fn apply_custom_writer_radialdimension<T>(
+        dim: &RadialDimension,
         version: AcadVersion,
         writer: &mut CodePairWriter<T>,
     ) -> DxfResult<bool>
     where
         T: Write + ?Sized,
     {
-        let subclass_marker = if poly.get_is_3d_polyline() || poly.get_is_3d_polygon_mesh() {
-            "AcDb3dPolyline"
-        } else {
-            "AcDb2dPolyline"
-        };
-        writer.write_code_pair(&CodePair::new_str(100, subclass_marker))?;
-        if version <= AcadVersion::R13 {
-            writer.write_code_pair(&CodePair::new_i16(66, as_i16(poly.contains_vertices)))?;
-        }
-        if version >= AcadVersion::R12 {
-            writer.write_code_pair(&CodePair::new_f64(10, poly.location.x))?;
-            writer.write_code_pair(&CodePair::new_f64(20, poly.location.y))?;
-            writer.write_code_pair(&CodePair::new_f64(30, poly.location.z))?;
-        }
-        if poly.thickness != 0.0 {
-            writer.write_code_pair(&CodePair::new_f64(39, poly.thickness))?;
-        }
-        if poly.flags != 0 {
-            writer.write_code_pair(&CodePair::new_i16(70, poly.flags as i16))?;
-        }
-        if poly.default_starting_width != 0.0 {
-            writer.write_code_pair(&CodePair::new_f64(40, poly.default_starting_width))?;
-        }
-        if poly.default_ending_width != 0.0 {
-            writer.write_code_pair(&CodePair::new_f64(41, poly.default_ending_width))?;
-        }
-        if poly.polygon_mesh_m_vertex_count != 0 {
-            writer.write_code_pair(&CodePair::new_i16(
-                71,
-                poly.polygon_mesh_m_vertex_count as i16,
-            ))?;
-        }
-        if poly.polygon_mesh_n_vertex_count != 0 {
-            writer.write_code_pair(&CodePair::new_i16(
-                72,
-                poly.polygon_mesh_n_vertex_count as i16,
-            ))?;
-        }
-        if poly.smooth_surface_m_density != 0 {
-            writer.write_code_pair(&CodePair::new_i16(73, poly.smooth_surface_m_density as i16))?;
-        }
-        if poly.smooth_surface_n_density != 0 {
-            writer.write_code_pair(&CodePair::new_i16(74, poly.smooth_surface_n_density as i16))?;
-        }
-        if poly.surface_type != PolylineCurvedAndSmoothSurfaceType::None {
-            writer.write_code_pair(&CodePair::new_i16(75, poly.surface_type as i16))?;
-        }
-        if poly.normal != Vector::z_axis() {
-            writer.write_code_pair(&CodePair::new_f64(210, poly.normal.x))?;
-            writer.write_code_pair(&CodePair::new_f64(220, poly.normal.y))?;
-            writer.write_code_pair(&CodePair::new_f64(230, poly.normal.z))?;
-        }
+        dim.dimension_base
+            .write_as(DimensionType::Radius, version, writer)?;
+        writer.write_code_pair(&CodePair::new_str(100, "AcDbRadialDimension"))?;
+        writer.write_code_pair(&CodePair::new_f64(15, dim.definition_point_2.x))?;
+        writer.write_code_pair(&CodePair::new_f64(25, dim.definition_point_2.y))?;
+        writer.write_code_pair(&CodePair::new_f64(35, dim.definition_point_2.z))?;
+        writer.write_code_pair(&CodePair::new_f64(40, dim.leader_length))?;
         Ok(true)
     }
-    fn apply_custom_writer_vertex<T>(
-        v: &Vertex,
+    fn apply_custom_writer_diameterdimension<T>(
+        dim: &DiameterDimension,
         version: AcadVersion,
         writer: &mut CodePairWriter<T>,
     ) -> DxfResult<bool>
     where
         T: Write + ?Sized,
     {
-        writer.write_code_pair(&CodePair::new_str(100, "AcDbVertex"))?;
-        let subclass_marker = if v.get_is_3d_polyline_vertex() || v.get_is_3d_polygon_mesh() {
-            "AcDb3dPolylineVertex"
-        } else {
-            "AcDb2dVertex"
-        };
-        writer.write_code_pair(&CodePair::new_str(100, subclass_marker))?;
-        writer.write_code_pair(&CodePair::new_f64(10, v.location.x))?;
-        writer.write_code_pair(&CodePair::new_f64(20, v.location.y))?;
-        writer.write_code_pair(&CodePair::new_f64(30, v.location.z))?;
-        if v.starting_width != 0.0 {
-            writer.write_code_pair(&CodePair::new_f64(40, v.starting_width))?;
+        dim.dimension_base
+            .write_as(DimensionType::Diameter, version, writer)?;
+        writer.write_code_pair(&CodePair::new_str(100, "AcDbDiametricDimension"))?;
+        writer.write_code_pair(&CodePair::new_f64(15, dim.definition_point_2.x))?;
+        writer.write_code_pair(&CodePair::new_f64(25, dim.definition_point_2.y))?;
+        writer.write_code_pair(&CodePair::new_f64(35, dim.definition_point_2.z))?;
+        writer.write_code_pair(&CodePair::new_f64(40, dim.leader_length))?;
+        Ok(true)
+    }
+    fn apply_custom_writer_angulartwolinedimension<T>(
+        dim: &AngularTwoLineDimension,
+        version: AcadVersion,
+        writer: &mut CodePairWriter<T>,
+    ) -> DxfResult<bool>
+    where
+        T: Write + ?Sized,
+    {
+        dim.dimension_base
+            .write_as(DimensionType::Angular, version, writer)?;
+        writer.write_code_pair(&CodePair::new_str(100, "AcDb2LineAngularDimension"))?;
+        writer.write_code_pair(&CodePair::new_f64(13, dim.definition_point_2.x))?;
+        writer.write_code_pair(&CodePair::new_f64(23, dim.definition_point_2.y))?;
+        writer.write_code_pair(&CodePair::new_f64(33, dim.definition_point_2.z))?;
+        writer.write_code_pair(&CodePair::new_f64(14, dim.definition_point_3.x))?;
+        writer.write_code_pair(&CodePair::new_f64(24, dim.definition_point_3.y))?;
+        writer.write_code_pair(&CodePair::new_f64(34, dim.definition_point_3.z))?;
+        writer.write_code_pair(&CodePair::new_f64(15, dim.definition_point_4.x))?;
+        writer.write_code_pair(&CodePair::new_f64(25, dim.definition_point_4.y))?;
+        writer.write_code_pair(&CodePair::new_f64(35, dim.definition_point_4.z))?;
+        writer.write_code_pair(&CodePair::new_f64(16, dim.definition_point_5.x))?;
+        writer.write_code_pair(&CodePair::new_f64(26, dim.definition_point_5.y))?;
+        writer.write_code_pair(&CodePair::new_f64(36, dim.definition_point_5.z))?;
+        Ok(true)
+    }
+    fn apply_custom_writer_angularthreepointdimension<T>(
+        dim: &AngularThreePointDimension,
+        version: AcadVersion,
+        writer: &mut CodePairWriter<T>,
+    ) -> DxfResult<bool>
+    where
+        T: Write + ?Sized,
+    {
+        dim.dimension_base
+            .write_as(DimensionType::AngularThreePoint, version, writer)?;
+        writer.write_code_pair(&CodePair::new_str(100, "AcDb3PointAngularDimension"))?;
+        writer.write_code_pair(&CodePair::new_f64(13, dim.definition_point_2.x))?;
+        writer.write_code_pair(&CodePair::new_f64(23, dim.definition_point_2.y))?;
+        writer.write_code_pair(&CodePair::new_f64(33, dim.definition_point_2.z))?;
+        writer.write_code_pair(&CodePair::new_f64(14, dim.definition_point_3.x))?;
+        writer.write_code_pair(&CodePair::new_f64(24, dim.definition_point_3.y))?;
+        writer.write_code_pair(&CodePair::new_f64(34, dim.definition_point_3.z))?;
+        writer.write_code_pair(&CodePair::new_f64(15, dim.definition_point_4.x))?;
+        writer.write_code_pair(&CodePair::new_f64(25, dim.definition_point_4.y))?;
+        writer.write_code_pair(&CodePair::new_f64(35, dim.definition_point_4.z))?;
+        writer.write_code_pair(&CodePair::new_f64(16, dim.definition_point_5.x))?;
+        writer.write_code_pair(&CodePair::new_f64(26, dim.definition_point_5.y))?;
+        writer.write_code_pair(&CodePair::new_f64(36, dim.definition_point_5.z))?;
+        Ok(true)
+    }
+    fn apply_custom_writer_ordinatedimension<T>(
+        dim: &OrdinateDimension,
+        version: AcadVersion,
+        writer: &mut CodePairWriter<T>,
+    ) -> DxfResult<bool>
+    where
+        T: Write + ?Sized,
+    {
+        dim.dimension_base
+            .write_as(DimensionType::Ordinate, version, writer)?;
+        writer.write_code_pair(&CodePair::new_str(100, "AcDbOrdinateDimension"))?;
+        writer.write_code_pair(&CodePair::new_f64(13, dim.definition_point_2.x))?;
+        writer.write_code_pair(&CodePair::new_f64(23, dim.definition_point_2.y))?;
+        writer.write_code_pair(&CodePair::new_f64(33, dim.definition_point_2.z))?;
+        writer.write_code_pair(&CodePair::new_f64(14, dim.definition_point_3.x))?;
+        writer.write_code_pair(&CodePair::new_f64(24, dim.definition_point_3.y))?;
+        writer.write_code_pair(&CodePair::new_f64(34, dim.definition_point_3.z))?;
+        Ok(true)
+    }
+    fn apply_custom_writer_arcdimension<T>(
+        dim: &ArcDimension,
+        version: AcadVersion,
+        writer: &mut CodePairWriter<T>,
+    ) -> DxfResult<bool>
+    where
+        T: Write + ?Sized,
+    {
+        dim.dimension_base.write(version, writer)?;
+        writer.write_code_pair(&CodePair::new_str(100, "AcDbArcDimension"))?;
+        writer.write_code_pair(&CodePair::new_f64(13, dim.definition_point_2.x))?;
+        writer.write_code_pair(&CodePair::new_f64(23, dim.definition_point_2.y))?;
+        writer.write_code_pair(&CodePair::new_f64(33, dim.definition_point_2.z))?;
+        writer.write_code_pair(&CodePair::new_f64(14, dim.definition_point_3.x))?;
+        writer.write_code_pair(&CodePair::new_f64(24, dim.definition_point_3.y))?;
+        writer.write_code_pair(&CodePair::new_f64(34, dim.definition_point_3.z))?;
+        writer.write_code_pair(&CodePair::new_f64(15, dim.arc_center_point.x))?;
+        writer.write_code_pair(&CodePair::new_f64(25, dim.arc_center_point.y))?;
+        writer.write_code_pair(&CodePair::new_f64(35, dim.arc_center_point.z))?;
+        writer.write_code_pair(&CodePair::new_f64(40, dim.arc_start_parameter))?;
+        writer.write_code_pair(&CodePair::new_f64(41, dim.arc_end_parameter))?;
+        writer.write_code_pair(&CodePair::new_i16(70, as_i16(dim.is_partial)))?;
+        writer.write_code_pair(&CodePair::new_i16(71, as_i16(dim.has_leader)))?;
+        writer.write_code_pair(&CodePair::new_f64(16, dim.leader_point_1.x))?;
+        writer.write_code_pair(&CodePair::new_f64(26, dim.leader_point_1.y))?;
+        writer.write_code_pair(&CodePair::new_f64(36, dim.leader_point_1.z))?;
+        writer.write_code_pair(&CodePair::new_f64(17, dim.leader_point_2.x))?;
+        writer.write_code_pair(&CodePair::new_f64(27, dim.leader_point_2.y))?;
+        writer.write_code_pair(&CodePair::new_f64(37, dim.leader_point_2.z))?;
+        Ok(true)
+    }
+    fn apply_custom_writer_largeradialdimension<T>(
+        dim: &LargeRadialDimension,
+        version: AcadVersion,
+        writer: &mut CodePairWriter<T>,
+    ) -> DxfResult<bool>
+    where
+        T: Write + ?Sized,
+    {
+        dim.dimension_base.write(version, writer)?;
+        writer.write_code_pair(&CodePair::new_str(100, "AcDbRadialDimensionLarge"))?;
+        writer.write_code_pair(&CodePair::new_f64(13, dim.chord_point.x))?;
+        writer.write_code_pair(&CodePair::new_f64(23, dim.chord_point.y))?;
+        writer.write_code_pair(&CodePair::new_f64(33, dim.chord_point.z))?;
+        writer.write_code_pair(&CodePair::new_f64(14, dim.override_center.x))?;
+        writer.write_code_pair(&CodePair::new_f64(24, dim.override_center.y))?;
+        writer.write_code_pair(&CodePair::new_f64(34, dim.override_center.z))?;
+        writer.write_code_pair(&CodePair::new_f64(15, dim.jog_point.x))?;
+        writer.write_code_pair(&CodePair::new_f64(25, dim.jog_point.y))?;
+        writer.write_code_pair(&CodePair::new_f64(35, dim.jog_point.z))?;
+        writer.write_code_pair(&CodePair::new_f64(40, dim.jog_angle))?;
+        Ok(true)
+    }
+    fn apply_custom_writer_acadtable<T>(
+        table: &AcadTable,
+        writer: &mut CodePairWriter<T>,
+    ) -> DxfResult<bool>
+    where
+        T: Write + ?Sized,
+    {
+        writer.write_code_pair(&CodePair::new_str(100, "AcDbBlockReference"))?;
+        writer.write_code_pair(&CodePair::new_string(2, &table.block_name))?;
+        writer.write_code_pair(&CodePair::new_f64(10, table.insertion_point.x))?;
+        writer.write_code_pair(&CodePair::new_f64(20, table.insertion_point.y))?;
+        writer.write_code_pair(&CodePair::new_f64(30, table.insertion_point.z))?;
+        writer.write_code_pair(&CodePair::new_str(100, "AcDbTable"))?;
+        writer.write_code_pair(&CodePair::new_i16(280, table.version))?;
+        writer.write_code_pair(&CodePair::new_string(
+            342,
+            &as_handle(table.__table_style_handle),
+        ))?;
+        writer.write_code_pair(&CodePair::new_string(
+            343,
+            &as_handle(table.__block_record_handle),
+        ))?;
+        writer.write_code_pair(&CodePair::new_f64(11, table.horizontal_direction.x))?;
+        writer.write_code_pair(&CodePair::new_f64(21, table.horizontal_direction.y))?;
+        writer.write_code_pair(&CodePair::new_f64(31, table.horizontal_direction.z))?;
+        writer.write_code_pair(&CodePair::new_i32(90, table.table_value_flags))?;
+        writer.write_code_pair(&CodePair::new_i32(91, table.row_heights.len() as i32))?;
+        writer.write_code_pair(&CodePair::new_i32(92, table.column_widths.len() as i32))?;
+        writer.write_code_pair(&CodePair::new_i32(93, table.override_flags))?;
+        writer.write_code_pair(&CodePair::new_i32(94, table.border_color_override_flags))?;
+        writer.write_code_pair(&CodePair::new_i32(
+            95,
+            table.border_lineweight_override_flags,
+        ))?;
+        writer.write_code_pair(&CodePair::new_i32(
+            96,
+            table.border_visibility_override_flags,
+        ))?;
+        for row_height in &table.row_heights {
+            writer.write_code_pair(&CodePair::new_f64(141, *row_height))?;
         }
-        if v.ending_width != 0.0 {
-            writer.write_code_pair(&CodePair::new_f64(41, v.ending_width))?;
+        for column_width in &table.column_widths {
+            writer.write_code_pair(&CodePair::new_f64(142, *column_width))?;
         }
-        if v.bulge != 0.0 {
-            writer.write_code_pair(&CodePair::new_f64(42, v.bulge))?;
+        for cell in &table.cells {
+            cell.write(writer)?;
         }
-        writer.write_code_pair(&CodePair::new_i16(70, v.flags as i16))?;
-        writer.write_code_pair(&CodePair::new_f64(50, v.curve_fit_tangent_direction))?;
-        if version >= AcadVersion::R13 {
-            if v.polyface_mesh_vertex_index1 != 0 {
-                writer.write_code_pair(&CodePair::new_i16(
-                    71,
-                    v.polyface_mesh_vertex_index1 as i16,
-                ))?;
+        Ok(true)
+    }
+    fn apply_custom_writer_extrudedsurface<T>(
+        surface: &ExtrudedSurface,
+        writer: &mut CodePairWriter<T>,
+    ) -> DxfResult<bool>
+    where
+        T: Write + ?Sized,
+    {
+        write_surface_code_pairs!(surface, writer);
+        writer.write_code_pair(&CodePair::new_str(100, "AcDbExtrudedSurface"))?;
+        writer.write_code_pair(&CodePair::new_i32(90, surface.class_id))?;
+        writer.write_code_pair(&CodePair::new_f64(10, surface.sweep_vector.x))?;
+        writer.write_code_pair(&CodePair::new_f64(20, surface.sweep_vector.y))?;
+        writer.write_code_pair(&CodePair::new_f64(30, surface.sweep_vector.z))?;
+        Entity::write_transformation_matrix(
+            40,
+            &surface.extruded_entity_transformation_matrix,
+            writer,
+        )?;
+        write_sweep_options_code_pairs!(surface, writer);
+        Ok(true)
+    }
+    fn apply_custom_writer_loftedsurface<T>(
+        surface: &LoftedSurface,
+        writer: &mut CodePairWriter<T>,
+    ) -> DxfResult<bool>
+    where
+        T: Write + ?Sized,
+    {
+        write_surface_code_pairs!(surface, writer);
+        writer.write_code_pair(&CodePair::new_str(100, "AcDbLoftedSurface"))?;
+        Entity::write_transformation_matrix(40, &surface.transformation_matrix, writer)?;
+        writer.write_code_pair(&CodePair::new_i16(
+            70,
+            surface.plane_normal_lofting_type as i16,
+        ))?;
+        writer.write_code_pair(&CodePair::new_f64(41, surface.start_draft_angle))?;
+        writer.write_code_pair(&CodePair::new_f64(42, surface.end_draft_angle))?;
+        writer.write_code_pair(&CodePair::new_f64(43, surface.start_draft_magnitude))?;
+        writer.write_code_pair(&CodePair::new_f64(44, surface.end_draft_magnitude))?;
+        writer.write_code_pair(&CodePair::new_bool(
+            290,
+            surface.arc_length_parameterization,
+        ))?;
+        writer.write_code_pair(&CodePair::new_bool(291, surface.no_twist))?;
+        writer.write_code_pair(&CodePair::new_bool(292, surface.align_direction))?;
+        writer.write_code_pair(&CodePair::new_bool(293, surface.create_simple_surfaces))?;
+        writer.write_code_pair(&CodePair::new_bool(294, surface.create_closed_surface))?;
+        writer.write_code_pair(&CodePair::new_bool(295, surface.is_solid))?;
+        writer.write_code_pair(&CodePair::new_bool(296, surface.create_ruled_surface))?;
+        writer.write_code_pair(&CodePair::new_bool(297, surface.virtual_guide))?;
+        Ok(true)
+    }
+    fn apply_custom_writer_hatch<T>(
+        hatch: &Hatch,
+        version: AcadVersion,
+        writer: &mut CodePairWriter<T>,
+    ) -> DxfResult<bool>
+    where
+        T: Write + ?Sized,
+    {
+        writer.write_code_pair(&CodePair::new_str(100, "AcDbHatch"))?;
+        writer.write_code_pair(&CodePair::new_f64(10, hatch.elevation_point.x))?;
+        writer.write_code_pair(&CodePair::new_f64(20, hatch.elevation_point.y))?;
+        writer.write_code_pair(&CodePair::new_f64(30, hatch.elevation_point.z))?;
+        writer.write_code_pair(&CodePair::new_f64(210, hatch.extrusion_direction.x))?;
+        writer.write_code_pair(&CodePair::new_f64(220, hatch.extrusion_direction.y))?;
+        writer.write_code_pair(&CodePair::new_f64(230, hatch.extrusion_direction.z))?;
+        writer.write_code_pair(&CodePair::new_string(2, &hatch.pattern_name))?;
+        writer.write_code_pair(&CodePair::new_i16(70, as_i16(hatch.is_solid_fill)))?;
+        writer.write_code_pair(&CodePair::new_i16(71, as_i16(hatch.is_associative)))?;
+        writer.write_code_pair(&CodePair::new_i32(91, hatch.boundary_paths.len() as i32))?;
+        for path in &hatch.boundary_paths {
+            path.write(version, writer)?;
+        }
+        writer.write_code_pair(&CodePair::new_i16(75, hatch.hatch_style as i16))?;
+        writer.write_code_pair(&CodePair::new_i16(76, hatch.pattern_type as i16))?;
+        if !hatch.is_solid_fill {
+            writer.write_code_pair(&CodePair::new_f64(52, hatch.pattern_angle))?;
+            writer.write_code_pair(&CodePair::new_f64(41, hatch.pattern_scale))?;
+            writer.write_code_pair(&CodePair::new_i16(77, as_i16(hatch.is_pattern_double)))?;
+            writer.write_code_pair(&CodePair::new_i16(
+                78,
+                hatch.pattern_definition_lines.len() as i16,
+            ))?;
+            for line in &hatch.pattern_definition_lines {
+                line.write(writer)?;
             }
-            if v.polyface_mesh_vertex_index2 != 0 {
+        }
+        writer.write_code_pair(&CodePair::new_f64(47, hatch.pixel_size))?;
+        writer.write_code_pair(&CodePair::new_i32(98, hatch.seed_points.len() as i32))?;
+        for seed_point in &hatch.seed_points {
+            writer.write_code_pair(&CodePair::new_f64(10, seed_point.x))?;
+            writer.write_code_pair(&CodePair::new_f64(20, seed_point.y))?;
+        }
+        if version >= AcadVersion::R2004 && hatch.is_gradient {
+            writer.write_code_pair(&CodePair::new_i64(450, 1))?;
+            writer.write_code_pair(&CodePair::new_i64(451, 0))?;
+            writer.write_code_pair(&CodePair::new_f64(460, hatch.gradient_angle))?;
+            writer.write_code_pair(&CodePair::new_f64(461, hatch.gradient_shift))?;
+            writer.write_code_pair(&CodePair::new_i64(
+                452,
+                i64::from(as_i16(hatch.is_single_color_gradient)),
+            ))?;
+            writer.write_code_pair(&CodePair::new_f64(462, hatch.gradient_color_tint))?;
+            writer.write_code_pair(&CodePair::new_i64(453, hatch.gradient_colors.len() as i64))?;
+            for gradient_color in &hatch.gradient_colors {
+                writer.write_code_pair(&CodePair::new_f64(463, gradient_color.value))?;
                 writer.write_code_pair(&CodePair::new_i16(
-                    72,
-                    v.polyface_mesh_vertex_index2 as i16,
+                    63,
+                    gradient_color.color.get_raw_value(),
                 ))?;
+                writer.write_code_pair(&CodePair::new_i32(421, gradient_color.true_color))?;
             }
-            if v.polyface_mesh_vertex_index3 != 0 {
-                writer.write_code_pair(&CodePair::new_i16(
-                    73,
-                    v.polyface_mesh_vertex_index3 as i16,
-                ))?;
+            writer.write_code_pair(&CodePair::new_string(470, &hatch.gradient_name))?;
+        }
+        Ok(true)
+    }
+    fn apply_custom_writer_mesh<T>(mesh: &Mesh, writer: &mut CodePairWriter<T>) -> DxfResult<bool>
+    where
+        T: Write + ?Sized,
+    {
+        writer.write_code_pair(&CodePair::new_str(100, "AcDbSubDMesh"))?;
+        writer.write_code_pair(&CodePair::new_i16(71, mesh.version))?;
+        writer.write_code_pair(&CodePair::new_i16(72, as_i16(mesh.is_blend_crease)))?;
+        writer.write_code_pair(&CodePair::new_i32(91, mesh.subdivision_level))?;
+        writer.write_code_pair(&CodePair::new_i32(92, mesh.vertices.len() as i32))?;
+        for vertex in &mesh.vertices {
+            writer.write_code_pair(&CodePair::new_f64(10, vertex.x))?;
+            writer.write_code_pair(&CodePair::new_f64(20, vertex.y))?;
+            writer.write_code_pair(&CodePair::new_f64(30, vertex.z))?;
+        }
+        let face_list_size: usize = mesh.faces.iter().map(|f| f.len() + 1).sum();
+        writer.write_code_pair(&CodePair::new_i32(93, face_list_size as i32))?;
+        for face in &mesh.faces {
+            writer.write_code_pair(&CodePair::new_i32(90, face.len() as i32))?;
+            for index in face {
+                writer.write_code_pair(&CodePair::new_i32(90, *index))?;
             }
-            if v.polyface_mesh_vertex_index4 != 0 {
-                writer.write_code_pair(&CodePair::new_i16(
-                    74,
-                    v.polyface_mesh_vertex_index4 as i16,
-                ))?;
+        }
+        writer.write_code_pair(&CodePair::new_i32(94, mesh.edges.len() as i32))?;
+        for (start, end) in &mesh.edges {
+            writer.write_code_pair(&CodePair::new_i32(90, *start))?;
+            writer.write_code_pair(&CodePair::new_i32(90, *end))?;
+        }
+        writer.write_code_pair(&CodePair::new_i32(95, mesh.edge_creases.len() as i32))?;
+        for crease in &mesh.edge_creases {
+            writer.write_code_pair(&CodePair::new_f64(140, *crease))?;
+        }
+        writer.write_code_pair(&CodePair::new_i32(90, mesh.overrides.len() as i32))?;
+        for sub_entity_override in &mesh.overrides {
+            writer.write_code_pair(&CodePair::new_i32(
+                91,
+                sub_entity_override.sub_entity_marker,
+            ))?;
+            writer.write_code_pair(&CodePair::new_i32(
+                92,
+                sub_entity_override.properties.len() as i32,
+            ))?;
+            for property in &sub_entity_override.properties {
+                writer.write_code_pair(&CodePair::new_i32(90, property.property_type as i32))?;
+                for value in &property.values {
+                    writer.write_code_pair(value)?;
+                }
             }
         }
+        Ok(true)
+    }
+    fn apply_custom_writer_mleader<T>(
+        mleader: &MLeader,
+        version: AcadVersion,
+        writer: &mut CodePairWriter<T>,
+    ) -> DxfResult<bool>
+    where
+        T: Write + ?Sized,
+    {
+        writer.write_code_pair(&CodePair::new_str(100, "AcDbMLeader"))?;
+        writer.write_code_pair(&CodePair::new_i16(270, mleader.version))?;
+        mleader.context_data.write(version, writer)?;
+        writer.write_code_pair(&CodePair::new_string(
+            340,
+            &as_handle(mleader.__leader_style_handle),
+        ))?;
+        writer.write_code_pair(&CodePair::new_i32(90, mleader.property_override_flags))?;
+        writer.write_code_pair(&CodePair::new_i16(170, mleader.leader_line_type))?;
+        writer.write_code_pair(&CodePair::new_i32(91, mleader.leader_line_color))?;
+        writer.write_code_pair(&CodePair::new_string(
+            341,
+            &as_handle(mleader.__leader_line_type_style_handle),
+        ))?;
+        writer.write_code_pair(&CodePair::new_i16(171, mleader.leader_line_weight))?;
+        writer.write_code_pair(&CodePair::new_bool(290, mleader.enable_landing))?;
+        writer.write_code_pair(&CodePair::new_bool(291, mleader.enable_dogleg))?;
+        writer.write_code_pair(&CodePair::new_f64(41, mleader.dogleg_length))?;
+        if !mleader.__arrowhead_handle.is_empty() {
+            writer.write_code_pair(&CodePair::new_string(
+                342,
+                &as_handle(mleader.__arrowhead_handle),
+            ))?;
+        }
+        writer.write_code_pair(&CodePair::new_f64(42, mleader.arrowhead_size))?;
+        writer.write_code_pair(&CodePair::new_i16(172, mleader.content_type))?;
+        writer.write_code_pair(&CodePair::new_string(
+            343,
+            &as_handle(mleader.__text_style_handle),
+        ))?;
+        writer.write_code_pair(&CodePair::new_i16(173, mleader.text_left_attachment_type))?;
+        writer.write_code_pair(&CodePair::new_i32(95, mleader.text_right_attachment_type))?;
+        writer.write_code_pair(&CodePair::new_i16(174, mleader.text_angle_type))?;
+        writer.write_code_pair(&CodePair::new_i16(175, mleader.text_alignment_type))?;
+        writer.write_code_pair(&CodePair::new_i32(92, mleader.text_color))?;
+        writer.write_code_pair(&CodePair::new_bool(292, mleader.enable_frame_text))?;
+        if !mleader.__block_content_handle.is_empty() {
+            writer.write_code_pair(&CodePair::new_string(
+                344,
+                &as_handle(mleader.__block_content_handle),
+            ))?;
+        }
+        writer.write_code_pair(&CodePair::new_i32(93, mleader.block_content_color))?;
+        writer.write_code_pair(&CodePair::new_f64(10, mleader.block_content_scale.x))?;
+        writer.write_code_pair(&CodePair::new_f64(20, mleader.block_content_scale.y))?;
+        writer.write_code_pair(&CodePair::new_f64(30, mleader.block_content_scale.z))?;
+        writer.write_code_pair(&CodePair::new_f64(43, mleader.block_content_rotation))?;
+        writer.write_code_pair(&CodePair::new_i16(
+            176,
+            mleader.block_content_connection_type,
+        ))?;
+        writer.write_code_pair(&CodePair::new_bool(293, mleader.enable_annotation_scale))?;
+        writer.write_code_pair(&CodePair::new_bool(294, mleader.is_text_direction_negative))?;
+        writer.write_code_pair(&CodePair::new_i16(178, mleader.ipe_text_alignment))?;
+        writer.write_code_pair(&CodePair::new_i16(179, mleader.text_attachment_point))?;
+        writer.write_code_pair(&CodePair::new_f64(45, mleader.scale_factor))?;
         if version >= AcadVersion::R2010 {
-            writer.write_code_pair(&CodePair::new_i32(91, v.identifier))?;
+            writer.write_code_pair(&CodePair::new_i16(271, mleader.text_attachment_direction))?;
+            writer.write_code_pair(&CodePair::new_i16(
+                272,
+                mleader.text_bottom_attachment_direction,
+            ))?;
+            writer.write_code_pair(&CodePair::new_i16(
+                273,
+                mleader.text_top_attachment_direction,
+            ))?;
         }
         Ok(true)
     }
-    fn post_write<T>(
-        &self,
-        version: AcadVersion,
-        write_handles: bool,
-        writer: &mut CodePairWriter<T>,
-    ) -> DxfResult<()>
-    where
-        T: Write + ?Sized,
-    {
-        match self.specific {
-            EntityType::Attribute(ref att) => {
-                self.write_attribute_m_text(att.m_text.clone(), version, write_handles, writer)?
-            }
-            EntityType::AttributeDefinition(ref att) => {
-                self.write_attribute_m_text(att.m_text.clone(), version, write_handles, writer)?
-            }
-            EntityType::Insert(ref ins) => {
-                for (a, att_handle) in &ins.__attributes_and_handles {
-                    let a = Entity {
-                        common: EntityCommon {
-                            handle: *att_handle,
+    fn apply_custom_writer_polyline<T>(
+        poly: &Polyline,
+        version: AcadVersion,
+        writer: &mut CodePairWriter<T>,
+    ) -> DxfResult<bool>
+    where
+        T: Write + ?Sized,
+    {
+        let subclass_marker = if poly.get_is_3d_polyline() || poly.get_is_3d_polygon_mesh() {
+            "AcDb3dPolyline"
+        } else {
+            "AcDb2dPolyline"
+        };
+        writer.write_code_pair(&CodePair::new_str(100, subclass_marker))?;
+        if version <= AcadVersion::R13 {
+            writer.write_code_pair(&CodePair::new_i16(66, as_i16(poly.contains_vertices)))?;
+        }
+        if version >= AcadVersion::R12 {
+            writer.write_code_pair(&CodePair::new_f64(10, poly.location.x))?;
+            writer.write_code_pair(&CodePair::new_f64(20, poly.location.y))?;
+            writer.write_code_pair(&CodePair::new_f64(30, poly.location.z))?;
+        }
+        if poly.thickness != 0.0 {
+            writer.write_code_pair(&CodePair::new_f64(39, poly.thickness))?;
+        }
+        if poly.flags != 0 {
+            writer.write_code_pair(&CodePair::new_i16(70, poly.flags as i16))?;
+        }
+        if poly.default_starting_width != 0.0 {
+            writer.write_code_pair(&CodePair::new_f64(40, poly.default_starting_width))?;
+        }
+        if poly.default_ending_width != 0.0 {
+            writer.write_code_pair(&CodePair::new_f64(41, poly.default_ending_width))?;
+        }
+        if poly.polygon_mesh_m_vertex_count != 0 {
+            writer.write_code_pair(&CodePair::new_i16(
+                71,
+                poly.polygon_mesh_m_vertex_count as i16,
+            ))?;
+        }
+        if poly.polygon_mesh_n_vertex_count != 0 {
+            writer.write_code_pair(&CodePair::new_i16(
+                72,
+                poly.polygon_mesh_n_vertex_count as i16,
+            ))?;
+        }
+        if poly.smooth_surface_m_density != 0 {
+            writer.write_code_pair(&CodePair::new_i16(73, poly.smooth_surface_m_density as i16))?;
+        }
+        if poly.smooth_surface_n_density != 0 {
+            writer.write_code_pair(&CodePair::new_i16(74, poly.smooth_surface_n_density as i16))?;
+        }
+        if poly.surface_type != PolylineCurvedAndSmoothSurfaceType::None {
+            writer.write_code_pair(&CodePair::new_i16(75, poly.surface_type as i16))?;
+        }
+        if poly.normal != Vector::z_axis() {
+            writer.write_code_pair(&CodePair::new_f64(210, poly.normal.x))?;
+            writer.write_code_pair(&CodePair::new_f64(220, poly.normal.y))?;
+            writer.write_code_pair(&CodePair::new_f64(230, poly.normal.z))?;
+        }
+        Ok(true)
+    }
+    fn apply_custom_writer_revolvedsurface<T>(
+        surface: &RevolvedSurface,
+        writer: &mut CodePairWriter<T>,
+    ) -> DxfResult<bool>
+    where
+        T: Write + ?Sized,
+    {
+        write_surface_code_pairs!(surface, writer);
+        writer.write_code_pair(&CodePair::new_str(100, "AcDbRevolvedSurface"))?;
+        writer.write_code_pair(&CodePair::new_i32(90, surface.revolve_entity_id))?;
+        Entity::write_binary_data(&surface.revolve_entity_data, writer)?;
+        writer.write_code_pair(&CodePair::new_f64(10, surface.axis_point.x))?;
+        writer.write_code_pair(&CodePair::new_f64(20, surface.axis_point.y))?;
+        writer.write_code_pair(&CodePair::new_f64(30, surface.axis_point.z))?;
+        writer.write_code_pair(&CodePair::new_f64(11, surface.axis_vector.x))?;
+        writer.write_code_pair(&CodePair::new_f64(21, surface.axis_vector.y))?;
+        writer.write_code_pair(&CodePair::new_f64(31, surface.axis_vector.z))?;
+        writer.write_code_pair(&CodePair::new_f64(40, surface.revolve_angle))?;
+        writer.write_code_pair(&CodePair::new_f64(41, surface.start_angle))?;
+        Entity::write_transformation_matrix(
+            42,
+            &surface.revolved_entity_transformation_matrix,
+            writer,
+        )?;
+        writer.write_code_pair(&CodePair::new_f64(43, surface.draft_angle))?;
+        writer.write_code_pair(&CodePair::new_f64(44, surface.start_draft_distance))?;
+        writer.write_code_pair(&CodePair::new_f64(45, surface.end_draft_distance))?;
+        writer.write_code_pair(&CodePair::new_f64(46, surface.twist_angle))?;
+        writer.write_code_pair(&CodePair::new_bool(290, surface.is_solid))?;
+        writer.write_code_pair(&CodePair::new_bool(291, surface.is_close_to_axis))?;
+        Ok(true)
+    }
+    fn apply_custom_writer_sweptsurface<T>(
+        surface: &SweptSurface,
+        writer: &mut CodePairWriter<T>,
+    ) -> DxfResult<bool>
+    where
+        T: Write + ?Sized,
+    {
+        write_surface_code_pairs!(surface, writer);
+        writer.write_code_pair(&CodePair::new_str(100, "AcDbSweptSurface"))?;
+        writer.write_code_pair(&CodePair::new_i32(90, surface.sweep_entity_id))?;
+        Entity::write_binary_data(&surface.sweep_entity_data, writer)?;
+        writer.write_code_pair(&CodePair::new_i32(90, surface.path_entity_id))?;
+        Entity::write_binary_data(&surface.path_entity_data, writer)?;
+        Entity::write_transformation_matrix(
+            40,
+            &surface.sweep_entity_transformation_matrix,
+            writer,
+        )?;
+        Entity::write_transformation_matrix(
+            41,
+            &surface.path_entity_transformation_matrix,
+            writer,
+        )?;
+        write_sweep_options_code_pairs!(surface, writer);
+        Ok(true)
+    }
+    fn write_transformation_matrix<T>(
+        code: i32,
+        matrix: &TransformationMatrix,
+        writer: &mut CodePairWriter<T>,
+    ) -> DxfResult<()>
+    where
+        T: Write + ?Sized,
+    {
+        for value in matrix.get_values() {
+            writer.write_code_pair(&CodePair::new_f64(code, value))?;
+        }
+        Ok(())
+    }
+    fn write_binary_data<T>(data: &[String], writer: &mut CodePairWriter<T>) -> DxfResult<()>
+    where
+        T: Write + ?Sized,
+    {
+        // the data size is the number of bytes represented by the hex strings
+        let size: usize = data.iter().map(|d| d.len() / 2).sum();
+        writer.write_code_pair(&CodePair::new_i32(90, size as i32))?;
+        for d in data {
+            writer.write_code_pair(&CodePair::new_string(310, d))?;
+        }
+        Ok(())
+    }
+    fn apply_custom_writer_vertex<T>(
+        v: &Vertex,
+        version: AcadVersion,
+        writer: &mut CodePairWriter<T>,
+    ) -> DxfResult<bool>
+    where
+        T: Write + ?Sized,
+    {
+        writer.write_code_pair(&CodePair::new_str(100, "AcDbVertex"))?;
+        let subclass_marker = if v.get_is_3d_polyline_vertex() || v.get_is_3d_polygon_mesh() {
+            "AcDb3dPolylineVertex"
+        } else {
+            "AcDb2dVertex"
+        };
+        writer.write_code_pair(&CodePair::new_str(100, subclass_marker))?;
+        writer.write_code_pair(&CodePair::new_f64(10, v.location.x))?;
+        writer.write_code_pair(&CodePair::new_f64(20, v.location.y))?;
+        writer.write_code_pair(&CodePair::new_f64(30, v.location.z))?;
+        if v.starting_width != 0.0 {
+            writer.write_code_pair(&CodePair::new_f64(40, v.starting_width))?;
+        }
+        if v.ending_width != 0.0 {
+            writer.write_code_pair(&CodePair::new_f64(41, v.ending_width))?;
+        }
+        if v.bulge != 0.0 {
+            writer.write_code_pair(&CodePair::new_f64(42, v.bulge))?;
+        }
+        writer.write_code_pair(&CodePair::new_i16(70, v.flags as i16))?;
+        writer.write_code_pair(&CodePair::new_f64(50, v.curve_fit_tangent_direction))?;
+        if version >= AcadVersion::R13 {
+            if v.polyface_mesh_vertex_index1 != 0 {
+                writer.write_code_pair(&CodePair::new_i16(
+                    71,
+                    v.polyface_mesh_vertex_index1 as i16,
+                ))?;
+            }
+            if v.polyface_mesh_vertex_index2 != 0 {
+                writer.write_code_pair(&CodePair::new_i16(
+                    72,
+                    v.polyface_mesh_vertex_index2 as i16,
+                ))?;
+            }
+            if v.polyface_mesh_vertex_index3 != 0 {
+                writer.write_code_pair(&CodePair::new_i16(
+                    73,
+                    v.polyface_mesh_vertex_index3 as i16,
+                ))?;
+            }
+            if v.polyface_mesh_vertex_index4 != 0 {
+                writer.write_code_pair(&CodePair::new_i16(
+                    74,
+                    v.polyface_mesh_vertex_index4 as i16,
+                ))?;
+            }
+        }
+        if version >= AcadVersion::R2010 {
+            writer.write_code_pair(&CodePair::new_i32(91, v.identifier))?;
+        }
+        Ok(true)
+    }
+    fn post_write<T>(
+        &self,
+        version: AcadVersion,
+        write_handles: bool,
+        writer: &mut CodePairWriter<T>,
+    ) -> DxfResult<()>
+    where
+        T: Write + ?Sized,
+    {
+        match self.specific {
+            EntityType::Attribute(ref att) => {
+                self.write_attribute_m_text(att.m_text.clone(), version, write_handles, writer)?
+            }
+            EntityType::AttributeDefinition(ref att) => {
+                self.write_attribute_m_text(att.m_text.clone(), version, write_handles, writer)?
+            }
+            EntityType::Insert(ref ins) => {
+                for (a, att_handle) in &ins.__attributes_and_handles {
+                    let a = Entity {
+                        common: EntityCommon {
+                            handle: att_handle.unwrap_or_default(),
+                            ..Default::default()
+                        },
+                        specific: EntityType::Attribute(a.clone()),
+                    };
+                    a.write(version, write_handles, writer)?;
+                }
+                Entity::write_seqend(version, write_handles, writer)?;
+            }
+            EntityType::Polyline(ref poly) => {
+                for (v, vertex_handle) in &poly.__vertices_and_handles {
+                    let mut v = v.clone();
+                    v.set_is_3d_polyline_vertex(poly.get_is_3d_polyline());
+                    v.set_is_3d_polygon_mesh(poly.get_is_3d_polygon_mesh());
+                    let v = Entity {
+                        common: EntityCommon {
+                            handle: vertex_handle.unwrap_or_default(),
+                            ..Default::default()
+                        },
+                        specific: EntityType::Vertex(v),
+                    };
+                    v.write(version, write_handles, writer)?;
+                }
+                Entity::write_seqend(version, write_handles, writer)?;
+            }
+            _ => (),
+        }
+
+        Ok(())
+    }
+    fn write_attribute_m_text<T>(
+        &self,
+        m_text: MText,
+        version: AcadVersion,
+        write_handles: bool,
+        writer: &mut CodePairWriter<T>,
+    ) -> DxfResult<()>
+    where
+        T: Write + ?Sized,
+    {
+        let m_text_common = EntityCommon {
+            handle: Handle::empty(), // TODO: set handle
+            __owner_handle: self.common.handle,
+            is_in_paper_space: self.common.is_in_paper_space,
+            layer: self.common.layer.clone(),
+            ..Default::default()
+        };
+        let m_text = Entity {
+            common: m_text_common,
+            specific: EntityType::MText(m_text),
+        };
+        m_text.write(version, write_handles, writer)?;
+        Ok(())
+    }
+    fn write_seqend<T>(
+        version: AcadVersion,
+        write_handles: bool,
+        writer: &mut CodePairWriter<T>,
+    ) -> DxfResult<()>
+    where
+        T: Write + ?Sized,
+    {
+        let seqend = Entity {
+            common: Default::default(),
+            specific: EntityType::Seqend(Default::default()),
+        };
+        seqend.write(version, write_handles, writer)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::*;
+    use crate::enums::*;
+    use crate::helper_functions::tests::*;
+    use crate::objects::*;
+    use crate::*;
+
+    fn read_entity(entity_type: &str, body: String) -> Entity {
+        let drawing = from_section(
+            "ENTITIES",
+            vec!["0", entity_type, body.as_str()].join("\r\n").as_str(),
+        );
+        let entities = drawing.entities().collect::<Vec<_>>();
+        assert_eq!(1, entities.len());
+        entities[0].clone()
+    }
+
+    #[test]
+    fn read_empty_entities_section() {
+        let drawing = parse_drawing(
+            vec!["0", "SECTION", "2", "ENTITIES", "0", "ENDSEC", "0", "EOF"]
+                .join("\r\n")
+                .as_str(),
+        );
+        let entities = drawing.entities().collect::<Vec<_>>();
+        assert_eq!(0, entities.len());
+    }
+
+    #[test]
+    fn read_unsupported_entity() {
+        let drawing = parse_drawing(
+            vec![
+                "0",
+                "SECTION",
+                "2",
+                "ENTITIES",
+                "0",
+                "UNSUPPORTED_ENTITY",
+                "1",
+                "unsupported string",
+                "0",
+                "ENDSEC",
+                "0",
+                "EOF",
+            ]
+            .join("\r\n")
+            .as_str(),
+        );
+        let entities = drawing.entities().collect::<Vec<_>>();
+        assert_eq!(1, entities.len());
+        match entities[0].specific {
+            EntityType::Unknown(ref unknown) => {
+                assert_eq!("UNSUPPORTED_ENTITY", unknown.type_string);
+                assert_eq!(
+                    vec![CodePair::new_str(1, "unsupported string")],
+                    unknown.code_pairs
+                );
+            }
+            _ => panic!("expected an unknown entity"),
+        }
+    }
+
+    #[test]
+    fn read_unsupported_entity_with_subclass_markers() {
+        let ent = read_entity(
+            "CUSTOM_ENTITY",
+            [
+                "5",
+                "A1",
+                "100",
+                "AcDbEntity",
+                "8",
+                "some-layer",
+                "100",
+                "AcDbCustomEntity",
+                "8",
+                "not-a-layer",
+                "40",
+                "1.5",
+                "1001",
+                "APP",
+                "1000",
+                "xdata",
+            ]
+            .join("\r\n"),
+        );
+        assert_eq!(Handle(0xA1), ent.common.handle);
+        assert_eq!("some-layer", ent.common.layer);
+        assert_eq!(1, ent.common.x_data.len());
+        match ent.specific {
+            EntityType::Unknown(ref unknown) => {
+                assert_eq!("CUSTOM_ENTITY", unknown.type_string);
+                assert_eq!(
+                    vec!["AcDbCustomEntity"],
+                    unknown.subclass_markers().collect::<Vec<_>>()
+                );
+                assert_eq!(
+                    vec![
+                        CodePair::new_str(100, "AcDbCustomEntity"),
+                        CodePair::new_str(8, "not-a-layer"),
+                        CodePair::new_f64(40, 1.5),
+                    ],
+                    unknown.code_pairs
+                );
+            }
+            _ => panic!("expected an unknown entity"),
+        }
+    }
+
+    #[test]
+    fn write_unsupported_entity() {
+        let mut unknown = UnknownEntity::new("CUSTOM_ENTITY");
+        unknown.code_pairs = vec![
+            CodePair::new_str(100, "AcDbCustomEntity"),
+            CodePair::new_f64(40, 1.5),
+        ];
+        let mut drawing = Drawing::new();
+        drawing.header.version = AcadVersion::R2000;
+        drawing.add_entity(Entity::new(EntityType::Unknown(unknown)));
+        assert_contains(
+            &drawing,
+            [
+                "100",
+                "AcDbEntity",
+                "  8",
+                "0",
+                "370",
+                "     0",
+                "100",
+                "AcDbCustomEntity",
+                " 40",
+                "1.5",
+                "  0",
+                "ENDSEC",
+            ]
+            .join("\r\n"),
+        );
+    }
+
+    #[test]
+    fn unsupported_entity_is_not_written_on_r12() {
+        let mut unknown = UnknownEntity::new("CUSTOM_ENTITY");
+        unknown
+            .code_pairs
+            .push(CodePair::new_str(100, "AcDbCustomEntity"));
+        let mut drawing = Drawing::new();
+        drawing.header.version = AcadVersion::R12;
+        drawing.add_entity(Entity::new(EntityType::Unknown(unknown)));
+        assert_not_contains(&drawing, String::from("CUSTOM_ENTITY"));
+        assert_not_contains(&drawing, String::from("AcDbCustomEntity"));
+    }
+
+    #[test]
+    fn read_unsupported_entity_between_supported_entities() {
+        let drawing = parse_drawing(
+            vec![
+                "0",
+                "SECTION",
+                "2",
+                "ENTITIES",
+                "0",
+                "LINE",
+                "0",
+                "UNSUPPORTED_ENTITY",
+                "1",
+                "unsupported string",
+                "0",
+                "CIRCLE",
+                "0",
+                "ENDSEC",
+                "0",
+                "EOF",
+            ]
+            .join("\r\n")
+            .as_str(),
+        );
+        let entities = drawing.entities().collect::<Vec<_>>();
+        assert_eq!(3, entities.len());
+        match entities[0].specific {
+            EntityType::Line(_) => (),
+            _ => panic!("expected a line"),
+        }
+        match entities[1].specific {
+            EntityType::Unknown(ref unknown) => {
+                assert_eq!("UNSUPPORTED_ENTITY", unknown.type_string)
+            }
+            _ => panic!("expected an unknown entity"),
+        }
+        match entities[2].specific {
+            EntityType::Circle(_) => (),
+            _ => panic!("expected a circle"),
+        }
+    }
+
+    #[test]
+    fn read_entity_with_no_values() {
+        let drawing = parse_drawing(
+            vec![
+                "0", "SECTION", "2", "ENTITIES", "0", "LINE", "0", "ENDSEC", "0", "EOF",
+            ]
+            .join("\r\n")
+            .as_str(),
+        );
+        let entities = drawing.entities().collect::<Vec<_>>();
+        assert_eq!(1, entities.len());
+        match entities[0].specific {
+            EntityType::Line(_) => (),
+            _ => panic!("expected a line"),
+        }
+    }
+
+    #[test]
+    fn read_common_entity_fields() {
+        let ent = read_entity("LINE", vec!["8", "layer"].join("\r\n"));
+        assert_eq!("layer", ent.common.layer);
+    }
+
+    #[test]
+    fn read_line() {
+        let ent = read_entity(
+            "LINE",
+            vec![
+                "10", "1.1", // p1
+                "20", "2.2", "30", "3.3", "11", "4.4", // p2
+                "21", "5.5", "31", "6.6",
+            ]
+            .join("\r\n"),
+        );
+        match ent.specific {
+            EntityType::Line(ref line) => {
+                assert_eq!(Point::new(1.1, 2.2, 3.3), line.p1);
+                assert_eq!(Point::new(4.4, 5.5, 6.6), line.p2);
+            }
+            _ => panic!("expected a line"),
+        }
+    }
+
+    #[test]
+    fn write_common_entity_fields() {
+        let mut drawing = Drawing::new();
+        let mut ent = Entity {
+            common: Default::default(),
+            specific: EntityType::Line(Default::default()),
+        };
+        ent.common.layer = "some-layer".to_owned();
+        drawing.add_entity(ent);
+        assert_contains(
+            &drawing,
+            vec![
+                "  0",
+                "LINE",
+                "  5",
+                "10",
+                "100",
+                "AcDbEntity",
+                "  8",
+                "some-layer",
+            ]
+            .join("\r\n"),
+        );
+    }
+
+    #[test]
+    fn write_specific_entity_fields() {
+        let mut drawing = Drawing::new();
+        let line = Line {
+            p1: Point::new(1.1, 2.2, 3.3),
+            p2: Point::new(4.4, 5.5, 6.6),
+            ..Default::default()
+        };
+        drawing.add_entity(Entity::new(EntityType::Line(line)));
+        assert_contains(
+            &drawing,
+            vec![
+                "100", "AcDbLine", " 10", "1.1", " 20", "2.2", " 30", "3.3", " 11", "4.4", " 21",
+                "5.5", " 31", "6.6",
+            ]
+            .join("\r\n"),
+        );
+    }
+
+    #[test]
+    fn read_multiple_entities() {
+        let drawing = from_section(
+            "ENTITIES",
+            vec![
+                "0", "CIRCLE", "10", "1.1", // center
+                "20", "2.2", "30", "3.3", "40", "4.4", // radius
+                "0", "LINE", "10", "5.5", // p1
+                "20", "6.6", "30", "7.7", "11", "8.8", // p2
+                "21", "9.9", "31", "10.1",
+            ]
+            .join("\r\n")
+            .as_str(),
+        );
+        let entities = drawing.entities().collect::<Vec<_>>();
+        assert_eq!(2, entities.len());
+
+        // verify circle
+        match entities[0].specific {
+            EntityType::Circle(ref circle) => {
+                assert_eq!(Point::new(1.1, 2.2, 3.3), circle.center);
+                assert!(approx_eq!(f64, 4.4, circle.radius));
+            }
+            _ => panic!("expected a line"),
+        }
+
+        // verify line
+        match entities[1].specific {
+            EntityType::Line(ref line) => {
+                assert_eq!(Point::new(5.5, 6.6, 7.7), line.p1);
+                assert_eq!(Point::new(8.8, 9.9, 10.1), line.p2);
+            }
+            _ => panic!("expected a line"),
+        }
+    }
+
+    #[test]
+    fn read_field_with_multiples_common() {
+        let ent = read_entity("LINE", vec!["310", "one", "310", "two"].join("\r\n"));
+        assert_eq!(vec!["one", "two"], ent.common.preview_image_data);
+    }
+
+    #[test]
+    fn write_field_with_multiples_common() {
+        let mut drawing = Drawing::new();
+        drawing.header.version = AcadVersion::R2000;
+        drawing.add_entity(Entity {
+            common: EntityCommon {
+                preview_image_data: vec![String::from("one"), String::from("two")],
+                ..Default::default()
+            },
+            specific: EntityType::Line(Default::default()),
+        });
+        assert_contains(&drawing, vec!["310", "one", "310", "two"].join("\r\n"));
+    }
+
+    #[test]
+    fn read_field_with_multiples_specific() {
+        let ent = read_entity(
+            "3DSOLID",
+            vec!["1", "one-1", "1", "one-2", "3", "three-1", "3", "three-2"].join("\r\n"),
+        );
+        match ent.specific {
+            EntityType::Solid3D(ref solid3d) => {
+                assert_eq!(vec!["one-1", "one-2"], solid3d.custom_data);
+                assert_eq!(vec!["three-1", "three-2"], solid3d.custom_data2);
+            }
+            _ => panic!("expected a 3DSOLID"),
+        }
+    }
+
+    #[test]
+    fn write_field_with_multiples_specific() {
+        let mut drawing = Drawing::new();
+        drawing.header.version = AcadVersion::R13; // 3DSOLID is only supported on R13+
+        drawing.add_entity(Entity {
+            common: Default::default(),
+            specific: EntityType::Solid3D(Solid3D {
+                custom_data: vec![String::from("one-1"), String::from("one-2")],
+                custom_data2: vec![String::from("three-1"), String::from("three-2")],
+                ..Default::default()
+            }),
+        });
+        assert_contains(
+            &drawing,
+            vec![
+                "  1", "one-1", "  1", "one-2", "  3", "three-1", "  3", "three-2",
+            ]
+            .join("\r\n"),
+        );
+    }
+
+    #[test]
+    fn read_entity_with_post_parse() {
+        let ent = read_entity(
+            "IMAGE",
+            vec![
+                "14", "1.1", // clipping_vertices[0]
+                "24", "2.2", "14", "3.3", // clipping_vertices[1]
+                "24", "4.4", "14", "5.5", // clipping_vertices[2]
+                "24", "6.6",
+            ]
+            .join("\r\n"),
+        );
+        match ent.specific {
+            EntityType::Image(ref image) => {
+                assert_eq!(3, image.clipping_vertices.len());
+                assert_eq!(Point::new(1.1, 2.2, 0.0), image.clipping_vertices[0]);
+                assert_eq!(Point::new(3.3, 4.4, 0.0), image.clipping_vertices[1]);
+                assert_eq!(Point::new(5.5, 6.6, 0.0), image.clipping_vertices[2]);
+            }
+            _ => panic!("expected an IMAGE"),
+        }
+    }
+
+    #[test]
+    fn write_entity_with_write_order() {
+        let mut drawing = Drawing::new();
+        drawing.header.version = AcadVersion::R14; // IMAGE is only supported on R14+
+        drawing.add_entity(Entity {
+            common: Default::default(),
+            specific: EntityType::Image(Image {
+                clipping_vertices: vec![
+                    Point::new(1.1, 2.2, 0.0),
+                    Point::new(3.3, 4.4, 0.0),
+                    Point::new(5.5, 6.6, 0.0),
+                ],
+                ..Default::default()
+            }),
+        });
+        assert_contains(
+            &drawing,
+            vec![
+                " 91",
+                "        3",
+                " 14",
+                "1.1",
+                " 24",
+                "2.2",
+                " 14",
+                "3.3",
+                " 24",
+                "4.4",
+                " 14",
+                "5.5",
+                " 24",
+                "6.6",
+            ]
+            .join("\r\n"),
+        );
+    }
+
+    #[test]
+    fn read_entity_with_custom_reader_mtext() {
+        let ent = read_entity(
+            "MTEXT",
+            vec![
+                "50", "1.1", // rotation angle
+                "75", "7", // column type
+                "50", "3", // column count
+                "50", "10", // column values
+                "50", "20", "50", "30",
+            ]
+            .join("\r\n"),
+        );
+        match ent.specific {
+            EntityType::MText(ref mtext) => {
+                assert!(approx_eq!(f64, 1.1, mtext.rotation_angle));
+                assert_eq!(7, mtext.column_type);
+                assert_eq!(3, mtext.column_count);
+                assert_eq!(3, mtext.column_heights.len());
+                assert!(approx_eq!(f64, 10.0, mtext.column_heights[0]));
+                assert!(approx_eq!(f64, 20.0, mtext.column_heights[1]));
+                assert!(approx_eq!(f64, 30.0, mtext.column_heights[2]));
+            }
+            _ => panic!("expected an MTEXT"),
+        }
+    }
+
+    #[test]
+    fn read_entity_after_entity_with_custom_reader() {
+        let drawing = from_section(
+            "ENTITIES",
+            vec![
+                "  0", "MTEXT", // has a custom reader
+                "  0", "LINE", // uses the auto-generated reader
+            ]
+            .join("\r\n")
+            .as_str(),
+        );
+        let entities = drawing.entities().collect::<Vec<_>>();
+        assert_eq!(2, entities.len());
+        match entities[0].specific {
+            EntityType::MText(_) => {}
+            _ => panic!("expected an mtext"),
+        }
+        match entities[1].specific {
+            EntityType::Line(_) => {}
+            _ => panic!("expected a line"),
+        }
+    }
+
+    #[test]
+    fn read_entity_with_flags() {
+        let ent = read_entity("IMAGE", vec!["70", "5"].join("\r\n"));
+        match ent.specific {
+            EntityType::Image(ref image) => {
+                assert!(image.get_show_image());
+                assert!(image.get_use_clipping_boundary());
+            }
+            _ => panic!("expected an IMAGE"),
+        }
+    }
+
+    #[test]
+    fn write_entity_with_flags() {
+        let mut drawing = Drawing::new();
+        drawing.header.version = AcadVersion::R14; // IMAGE is only supported on R14+
+        let mut image = Image::default();
+        assert_eq!(0, image.display_options_flags);
+        image.set_show_image(true);
+        image.set_use_clipping_boundary(true);
+        drawing.add_entity(Entity {
+            common: Default::default(),
+            specific: EntityType::Image(image),
+        });
+        assert_contains(
+            &drawing,
+            vec![
+                " 70", "     5", // flags
+                "280", "     1", // sentinels to make sure we're not reading a header value
+                "281", "    50",
+            ]
+            .join("\r\n"),
+        );
+    }
+
+    #[test]
+    fn read_entity_with_handle_and_pointer() {
+        let ent = read_entity(
+            "3DSOLID",
+            vec![
+                "5", "A1", // handle
+                "330", "A2", // owner handle
+                "350", "A3", // history_object pointer
+            ]
+            .join("\r\n"),
+        );
+        assert_eq!(Handle(0xa1), ent.common.handle);
+        assert_eq!(Handle(0xa2), ent.common.__owner_handle);
+        match ent.specific {
+            EntityType::Solid3D(ref solid) => {
+                assert_eq!(Handle(0xa3), solid.__history_object_handle)
+            }
+            _ => panic!("expected a 3DSOLID entity"),
+        }
+    }
+
+    #[test]
+    fn write_entity_with_handle_and_pointer() {
+        let mut drawing = Drawing::new();
+        drawing.header.version = AcadVersion::R2000;
+        drawing.add_entity(Entity {
+            common: EntityCommon {
+                __owner_handle: Handle(0xa2),
+                ..Default::default()
+            },
+            specific: EntityType::Line(Default::default()),
+        });
+        assert_contains(
+            &drawing,
+            vec!["  0", "LINE", "  5", "10", "330", "A2"].join("\r\n"),
+        );
+    }
+
+    #[test]
+    fn round_trip_entity_with_64_bit_handle() {
+        let mut drawing = from_section(
+            "ENTITIES",
+            ["  0", "LINE", "  5", "123456789ABCD"]
+                .join("\r\n")
+                .as_str(),
+        );
+        let line = drawing.entities().next().unwrap();
+        assert_eq!(Handle(0x1_2345_6789_ABCD), line.common.handle);
+
+        drawing.header.version = AcadVersion::R2000;
+        assert_contains(
+            &drawing,
+            ["  0", "LINE", "  5", "123456789ABCD"].join("\r\n"),
+        );
+    }
+
+    #[test]
+    fn write_version_specific_entity() {
+        let mut drawing = Drawing::new();
+        drawing.add_entity(Entity {
+            common: Default::default(),
+            specific: EntityType::Solid3D(Default::default()),
+        });
+
+        // 3DSOLID not supported in R12 and below
+        drawing.header.version = AcadVersion::R12;
+        assert_contains(
+            &drawing,
+            vec!["  0", "SECTION", "  2", "ENTITIES", "  0", "ENDSEC"].join("\r\n"),
+        );
+
+        // but it is in R13 and above
+        drawing.header.version = AcadVersion::R13;
+        assert_contains(
+            &drawing,
+            vec!["  0", "SECTION", "  2", "ENTITIES", "  0", "3DSOLID"].join("\r\n"),
+        );
+    }
+
+    #[test]
+    fn read_polyline() {
+        let drawing = from_section(
+            "ENTITIES",
+            vec![
+                "  0", "POLYLINE", // polyline sentinel
+                "  0", "VERTEX", // vertex 1
+                " 10", "1.1", " 20", "2.1", " 30", "3.1", "  0", "VERTEX", // vertex 2
+                " 10", "1.2", " 20", "2.2", " 30", "3.2", "  0", "VERTEX", // vertex 3
+                " 10", "1.3", " 20", "2.3", " 30", "3.3", "  0", "SEQEND", // end sequence
+            ]
+            .join("\r\n")
+            .as_str(),
+        );
+        let entities = drawing.entities().collect::<Vec<_>>();
+        assert_eq!(1, entities.len());
+        match entities[0].specific {
+            EntityType::Polyline(ref poly) => {
+                assert_eq!(
+                    vec![
+                        Vertex {
+                            location: Point::new(1.1, 2.1, 3.1),
                             ..Default::default()
                         },
-                        specific: EntityType::Attribute(a.clone()),
-                    };
-                    a.write(version, write_handles, writer)?;
-                }
-                Entity::write_seqend(version, write_handles, writer)?;
+                        Vertex {
+                            location: Point::new(1.2, 2.2, 3.2),
+                            ..Default::default()
+                        },
+                        Vertex {
+                            location: Point::new(1.3, 2.3, 3.3),
+                            ..Default::default()
+                        },
+                    ],
+                    poly.vertices().cloned().collect::<Vec<_>>()
+                );
             }
+            _ => panic!("expected a POLYLINE"),
+        }
+    }
+
+    #[test]
+    fn read_polyline_without_seqend() {
+        let drawing = from_section(
+            "ENTITIES",
+            vec![
+                "  0", "POLYLINE", // polyline sentinel
+                "  0", "VERTEX", // vertex 1
+                " 10", "1.1", " 20", "2.1", " 30", "3.1", "  0", "VERTEX", // vertex 2
+                " 10", "1.2", " 20", "2.2", " 30", "3.2", "  0", "VERTEX", // vertex 3
+                " 10", "1.3", " 20", "2.3", " 30", "3.3",
+                // no end sequence
+            ]
+            .join("\r\n")
+            .as_str(),
+        );
+        let entities = drawing.entities().collect::<Vec<_>>();
+        assert_eq!(1, entities.len());
+        match entities[0].specific {
             EntityType::Polyline(ref poly) => {
-                for (v, vertex_handle) in &poly.__vertices_and_handles {
-                    let mut v = v.clone();
-                    v.set_is_3d_polyline_vertex(poly.get_is_3d_polyline());
-                    v.set_is_3d_polygon_mesh(poly.get_is_3d_polygon_mesh());
-                    let v = Entity {
-                        common: EntityCommon {
-                            handle: *vertex_handle,
+                assert_eq!(
+                    vec![
+                        Vertex {
+                            location: Point::new(1.1, 2.1, 3.1),
                             ..Default::default()
                         },
-                        specific: EntityType::Vertex(v),
-                    };
-                    v.write(version, write_handles, writer)?;
-                }
-                Entity::write_seqend(version, write_handles, writer)?;
+                        Vertex {
+                            location: Point::new(1.2, 2.2, 3.2),
+                            ..Default::default()
+                        },
+                        Vertex {
+                            location: Point::new(1.3, 2.3, 3.3),
+                            ..Default::default()
+                        },
+                    ],
+                    poly.vertices().cloned().collect::<Vec<_>>()
+                );
             }
-            _ => (),
+            _ => panic!("expected a POLYLINE"),
         }
-
-        Ok(())
-    }
-    fn write_attribute_m_text<T>(
-        &self,
-        m_text: MText,
-        version: AcadVersion,
-        write_handles: bool,
-        writer: &mut CodePairWriter<T>,
-    ) -> DxfResult<()>
-    where
-        T: Write + ?Sized,
-    {
-        let m_text_common = EntityCommon {
-            handle: 0, // TODO: set handle
-            __owner_handle: self.common.handle,
-            is_in_paper_space: self.common.is_in_paper_space,
-            layer: self.common.layer.clone(),
-            ..Default::default()
-        };
-        let m_text = Entity {
-            common: m_text_common,
-            specific: EntityType::MText(m_text),
-        };
-        m_text.write(version, write_handles, writer)?;
-        Ok(())
-    }
-    fn write_seqend<T>(
-        version: AcadVersion,
-        write_handles: bool,
-        writer: &mut CodePairWriter<T>,
-    ) -> DxfResult<()>
-    where
-        T: Write + ?Sized,
-    {
-        let seqend = Entity {
-            common: Default::default(),
-            specific: EntityType::Seqend(Default::default()),
-        };
-        seqend.write(version, write_handles, writer)?;
-        Ok(())
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::entities::*;
-    use crate::enums::*;
-    use crate::helper_functions::tests::*;
-    use crate::objects::*;
-    use crate::*;
 
-    fn read_entity(entity_type: &str, body: String) -> Entity {
+    #[test]
+    fn read_empty_polyline() {
         let drawing = from_section(
             "ENTITIES",
-            vec!["0", entity_type, body.as_str()].join("\r\n").as_str(),
+            vec!["0", "POLYLINE", "0", "SEQEND"].join("\r\n").as_str(),
         );
         let entities = drawing.entities().collect::<Vec<_>>();
         assert_eq!(1, entities.len());
-        entities[0].clone()
+        match entities[0].specific {
+            EntityType::Polyline(ref poly) => assert_eq!(0, poly.vertices().count()),
+            _ => panic!("expected a POLYLINE"),
+        }
     }
 
     #[test]
-    fn read_empty_entities_section() {
-        let drawing = parse_drawing(
-            vec!["0", "SECTION", "2", "ENTITIES", "0", "ENDSEC", "0", "EOF"]
-                .join("\r\n")
-                .as_str(),
-        );
+    fn read_empty_polyline_without_seqend() {
+        let drawing = from_section("ENTITIES", vec!["0", "POLYLINE"].join("\r\n").as_str());
         let entities = drawing.entities().collect::<Vec<_>>();
-        assert_eq!(0, entities.len());
+        assert_eq!(1, entities.len());
+        match entities[0].specific {
+            EntityType::Polyline(ref poly) => assert_eq!(0, poly.vertices().count()),
+            _ => panic!("expected a POLYLINE"),
+        }
     }
 
     #[test]
-    fn read_unsupported_entity() {
-        let drawing = parse_drawing(
+    fn read_polyline_with_trailing_entity() {
+        let drawing = from_section(
+            "ENTITIES",
             vec![
-                "0",
-                "SECTION",
-                "2",
-                "ENTITIES",
-                "0",
-                "UNSUPPORTED_ENTITY",
-                "1",
-                "unsupported string",
-                "0",
-                "ENDSEC",
-                "0",
-                "EOF",
+                "  0", "POLYLINE", // polyline sentinel
+                "  0", "VERTEX", // vertex 1
+                " 10", "1.1", " 20", "2.1", " 30", "3.1", "  0", "VERTEX", // vertex 2
+                " 10", "1.2", " 20", "2.2", " 30", "3.2", "  0", "VERTEX", // vertex 3
+                " 10", "1.3", " 20", "2.3", " 30", "3.3", "  0", "SEQEND", // end sequence
+                "  0", "LINE", // trailing entity
             ]
             .join("\r\n")
             .as_str(),
         );
         let entities = drawing.entities().collect::<Vec<_>>();
-        assert_eq!(0, entities.len());
+        assert_eq!(2, entities.len());
+        match entities[0].specific {
+            EntityType::Polyline(ref poly) => {
+                assert_eq!(
+                    vec![
+                        Vertex {
+                            location: Point::new(1.1, 2.1, 3.1),
+                            ..Default::default()
+                        },
+                        Vertex {
+                            location: Point::new(1.2, 2.2, 3.2),
+                            ..Default::default()
+                        },
+                        Vertex {
+                            location: Point::new(1.3, 2.3, 3.3),
+                            ..Default::default()
+                        },
+                    ],
+                    poly.vertices().cloned().collect::<Vec<_>>()
+                );
+            }
+            _ => panic!("expected a POLYLINE"),
+        }
+
+        match entities[1].specific {
+            EntityType::Line(_) => (),
+            _ => panic!("expected a LINE"),
+        }
     }
 
-    #[test]
-    fn read_unsupported_entity_between_supported_entities() {
-        let drawing = parse_drawing(
+    #[test]
+    fn read_polyline_without_seqend_with_trailing_entity() {
+        let drawing = from_section(
+            "ENTITIES",
             vec![
-                "0",
-                "SECTION",
-                "2",
-                "ENTITIES",
-                "0",
-                "LINE",
-                "0",
-                "UNSUPPORTED_ENTITY",
-                "1",
-                "unsupported string",
-                "0",
-                "CIRCLE",
-                "0",
-                "ENDSEC",
-                "0",
-                "EOF",
+                "  0", "POLYLINE", // polyline sentinel
+                "  0", "VERTEX", // vertex 1
+                " 10", "1.1", " 20", "2.1", " 30", "3.1", "  0", "VERTEX", // vertex 2
+                " 10", "1.2", " 20", "2.2", " 30", "3.2", "  0", "VERTEX", // vertex 3
+                " 10", "1.3", " 20", "2.3", " 30", "3.3", // no end sequence
+                "  0", "LINE", // trailing entity
             ]
             .join("\r\n")
             .as_str(),
@@ -1773,1000 +4656,2028 @@ mod tests {
         let entities = drawing.entities().collect::<Vec<_>>();
         assert_eq!(2, entities.len());
         match entities[0].specific {
-            EntityType::Line(_) => (),
-            _ => panic!("expected a line"),
+            EntityType::Polyline(ref poly) => {
+                assert_eq!(
+                    vec![
+                        Vertex {
+                            location: Point::new(1.1, 2.1, 3.1),
+                            ..Default::default()
+                        },
+                        Vertex {
+                            location: Point::new(1.2, 2.2, 3.2),
+                            ..Default::default()
+                        },
+                        Vertex {
+                            location: Point::new(1.3, 2.3, 3.3),
+                            ..Default::default()
+                        },
+                    ],
+                    poly.vertices().cloned().collect::<Vec<_>>()
+                );
+            }
+            _ => panic!("expected a POLYLINE"),
         }
+
         match entities[1].specific {
-            EntityType::Circle(_) => (),
-            _ => panic!("expected a circle"),
+            EntityType::Line(_) => (),
+            _ => panic!("expected a LINE"),
         }
     }
 
     #[test]
-    fn read_entity_with_no_values() {
-        let drawing = parse_drawing(
-            vec![
-                "0", "SECTION", "2", "ENTITIES", "0", "LINE", "0", "ENDSEC", "0", "EOF",
-            ]
-            .join("\r\n")
-            .as_str(),
+    fn read_empty_polyline_with_trailing_entity() {
+        let drawing = from_section(
+            "ENTITIES",
+            vec!["0", "POLYLINE", "0", "SEQEND", "0", "LINE"]
+                .join("\r\n")
+                .as_str(),
         );
         let entities = drawing.entities().collect::<Vec<_>>();
-        assert_eq!(1, entities.len());
+        assert_eq!(2, entities.len());
         match entities[0].specific {
+            EntityType::Polyline(ref poly) => assert_eq!(0, poly.vertices().count()),
+            _ => panic!("expected a POLYLINE"),
+        }
+        match entities[1].specific {
             EntityType::Line(_) => (),
-            _ => panic!("expected a line"),
+            _ => panic!("expected a LINE"),
         }
     }
 
     #[test]
-    fn read_common_entity_fields() {
-        let ent = read_entity("LINE", vec!["8", "layer"].join("\r\n"));
-        assert_eq!("layer", ent.common.layer);
-    }
-
-    #[test]
-    fn read_line() {
-        let ent = read_entity(
-            "LINE",
-            vec![
-                "10", "1.1", // p1
-                "20", "2.2", "30", "3.3", "11", "4.4", // p2
-                "21", "5.5", "31", "6.6",
-            ]
-            .join("\r\n"),
+    fn read_empty_polyline_without_seqend_with_trailing_entity() {
+        let drawing = from_section(
+            "ENTITIES",
+            vec!["0", "POLYLINE", "0", "LINE"].join("\r\n").as_str(),
         );
-        match ent.specific {
-            EntityType::Line(ref line) => {
-                assert_eq!(Point::new(1.1, 2.2, 3.3), line.p1);
-                assert_eq!(Point::new(4.4, 5.5, 6.6), line.p2);
-            }
-            _ => panic!("expected a line"),
+        let entities = drawing.entities().collect::<Vec<_>>();
+        assert_eq!(2, entities.len());
+        match entities[0].specific {
+            EntityType::Polyline(ref poly) => assert_eq!(0, poly.vertices().count()),
+            _ => panic!("expected a POLYLINE"),
+        }
+        match entities[1].specific {
+            EntityType::Line(_) => (),
+            _ => panic!("expected a LINE"),
         }
     }
 
     #[test]
-    fn write_common_entity_fields() {
+    fn write_2d_polyline() {
         let mut drawing = Drawing::new();
-        let mut ent = Entity {
+        let mut poly = Polyline::default();
+        poly.add_vertex(
+            &mut drawing,
+            Vertex {
+                location: Point::new(1.1, 2.1, 3.1),
+                ..Default::default()
+            },
+        );
+        poly.add_vertex(
+            &mut drawing,
+            Vertex {
+                location: Point::new(1.2, 2.2, 3.2),
+                ..Default::default()
+            },
+        );
+        poly.add_vertex(
+            &mut drawing,
+            Vertex {
+                location: Point::new(1.3, 2.3, 3.3),
+                ..Default::default()
+            },
+        );
+        drawing.add_entity(Entity {
             common: Default::default(),
-            specific: EntityType::Line(Default::default()),
-        };
-        ent.common.layer = "some-layer".to_owned();
-        drawing.add_entity(ent);
+            specific: EntityType::Polyline(poly),
+        });
         assert_contains(
             &drawing,
             vec![
                 "  0",
-                "LINE",
+                "POLYLINE", // polyline
+                "  5",
+                "13",
+                "100",
+                "AcDbEntity",
+                "  8",
+                "0",
+                "100",
+                "AcDb2dPolyline",
+                " 66",
+                "     1",
+                " 10",
+                "0.0",
+                " 20",
+                "0.0",
+                " 30",
+                "0.0",
+                "  0",
+                "VERTEX", // vertex 1
                 "  5",
                 "10",
                 "100",
                 "AcDbEntity",
                 "  8",
-                "some-layer",
-            ]
-            .join("\r\n"),
-        );
-    }
-
-    #[test]
-    fn write_specific_entity_fields() {
-        let mut drawing = Drawing::new();
-        let line = Line {
-            p1: Point::new(1.1, 2.2, 3.3),
-            p2: Point::new(4.4, 5.5, 6.6),
-            ..Default::default()
-        };
-        drawing.add_entity(Entity::new(EntityType::Line(line)));
-        assert_contains(
-            &drawing,
-            vec![
-                "100", "AcDbLine", " 10", "1.1", " 20", "2.2", " 30", "3.3", " 11", "4.4", " 21",
-                "5.5", " 31", "6.6",
-            ]
-            .join("\r\n"),
-        );
-    }
-
-    #[test]
-    fn read_multiple_entities() {
-        let drawing = from_section(
-            "ENTITIES",
-            vec![
-                "0", "CIRCLE", "10", "1.1", // center
-                "20", "2.2", "30", "3.3", "40", "4.4", // radius
-                "0", "LINE", "10", "5.5", // p1
-                "20", "6.6", "30", "7.7", "11", "8.8", // p2
-                "21", "9.9", "31", "10.1",
+                "0",
+                "100",
+                "AcDbVertex",
+                "100",
+                "AcDb2dVertex",
+                " 10",
+                "1.1",
+                " 20",
+                "2.1",
+                " 30",
+                "3.1",
+                " 70",
+                "     0",
+                " 50",
+                "0.0",
+                "  0",
+                "VERTEX", // vertex 2
+                "  5",
+                "11",
+                "100",
+                "AcDbEntity",
+                "  8",
+                "0",
+                "100",
+                "AcDbVertex",
+                "100",
+                "AcDb2dVertex",
+                " 10",
+                "1.2",
+                " 20",
+                "2.2",
+                " 30",
+                "3.2",
+                " 70",
+                "     0",
+                " 50",
+                "0.0",
+                "  0",
+                "VERTEX", // vertex 3
+                "  5",
+                "12",
+                "100",
+                "AcDbEntity",
+                "  8",
+                "0",
+                "100",
+                "AcDbVertex",
+                "100",
+                "AcDb2dVertex",
+                " 10",
+                "1.3",
+                " 20",
+                "2.3",
+                " 30",
+                "3.3",
+                " 70",
+                "     0",
+                " 50",
+                "0.0",
+                "  0",
+                "SEQEND", // end sequence
             ]
-            .join("\r\n")
-            .as_str(),
+            .join("\r\n"),
         );
-        let entities = drawing.entities().collect::<Vec<_>>();
-        assert_eq!(2, entities.len());
-
-        // verify circle
-        match entities[0].specific {
-            EntityType::Circle(ref circle) => {
-                assert_eq!(Point::new(1.1, 2.2, 3.3), circle.center);
-                assert!(approx_eq!(f64, 4.4, circle.radius));
-            }
-            _ => panic!("expected a line"),
-        }
-
-        // verify line
-        match entities[1].specific {
-            EntityType::Line(ref line) => {
-                assert_eq!(Point::new(5.5, 6.6, 7.7), line.p1);
-                assert_eq!(Point::new(8.8, 9.9, 10.1), line.p2);
-            }
-            _ => panic!("expected a line"),
-        }
-    }
-
-    #[test]
-    fn read_field_with_multiples_common() {
-        let ent = read_entity("LINE", vec!["310", "one", "310", "two"].join("\r\n"));
-        assert_eq!(vec!["one", "two"], ent.common.preview_image_data);
     }
 
     #[test]
-    fn write_field_with_multiples_common() {
+    fn write_3d_polyline() {
         let mut drawing = Drawing::new();
-        drawing.header.version = AcadVersion::R2000;
-        drawing.add_entity(Entity {
-            common: EntityCommon {
-                preview_image_data: vec![String::from("one"), String::from("two")],
+        let mut poly = Polyline::default();
+        poly.add_vertex(
+            &mut drawing,
+            Vertex {
+                location: Point::new(1.1, 2.1, 3.1),
                 ..Default::default()
             },
-            specific: EntityType::Line(Default::default()),
-        });
-        assert_contains(&drawing, vec!["310", "one", "310", "two"].join("\r\n"));
-    }
-
-    #[test]
-    fn read_field_with_multiples_specific() {
-        let ent = read_entity(
-            "3DSOLID",
-            vec!["1", "one-1", "1", "one-2", "3", "three-1", "3", "three-2"].join("\r\n"),
         );
-        match ent.specific {
-            EntityType::Solid3D(ref solid3d) => {
-                assert_eq!(vec!["one-1", "one-2"], solid3d.custom_data);
-                assert_eq!(vec!["three-1", "three-2"], solid3d.custom_data2);
-            }
-            _ => panic!("expected a 3DSOLID"),
-        }
-    }
-
-    #[test]
-    fn write_field_with_multiples_specific() {
-        let mut drawing = Drawing::new();
-        drawing.header.version = AcadVersion::R13; // 3DSOLID is only supported on R13+
+        poly.set_is_3d_polyline(true);
         drawing.add_entity(Entity {
             common: Default::default(),
-            specific: EntityType::Solid3D(Solid3D {
-                custom_data: vec![String::from("one-1"), String::from("one-2")],
-                custom_data2: vec![String::from("three-1"), String::from("three-2")],
-                ..Default::default()
-            }),
+            specific: EntityType::Polyline(poly),
         });
         assert_contains(
             &drawing,
             vec![
-                "  1", "one-1", "  1", "one-2", "  3", "three-1", "  3", "three-2",
+                "  0",
+                "POLYLINE", // polyline
+                "  5",
+                "11",
+                "100",
+                "AcDbEntity",
+                "  8",
+                "0",
+                "100",
+                "AcDb3dPolyline", // 3d = true
+                " 66",
+                "     1",
+                " 10",
+                "0.0",
+                " 20",
+                "0.0",
+                " 30",
+                "0.0",
+                " 70",
+                "     8", // 3d = true
+                "  0",
+                "VERTEX", // vertex 1
+                "  5",
+                "10",
+                "100",
+                "AcDbEntity",
+                "  8",
+                "0",
+                "100",
+                "AcDbVertex",
+                "100",
+                "AcDb3dPolylineVertex", // 3d = true
+                " 10",
+                "1.1",
+                " 20",
+                "2.1",
+                " 30",
+                "3.1",
+                " 70",
+                "    32", // 3d = true
             ]
             .join("\r\n"),
         );
     }
 
     #[test]
-    fn read_entity_with_post_parse() {
-        let ent = read_entity(
-            "IMAGE",
-            vec![
-                "14", "1.1", // clipping_vertices[0]
-                "24", "2.2", "14", "3.3", // clipping_vertices[1]
-                "24", "4.4", "14", "5.5", // clipping_vertices[2]
-                "24", "6.6",
-            ]
-            .join("\r\n"),
+    fn read_lw_polyline_with_no_vertices() {
+        let drawing = from_section(
+            "ENTITIES",
+            vec!["0", "LWPOLYLINE", "43", "43.0"].join("\r\n").as_str(),
         );
-        match ent.specific {
-            EntityType::Image(ref image) => {
-                assert_eq!(3, image.clipping_vertices.len());
-                assert_eq!(Point::new(1.1, 2.2, 0.0), image.clipping_vertices[0]);
-                assert_eq!(Point::new(3.3, 4.4, 0.0), image.clipping_vertices[1]);
-                assert_eq!(Point::new(5.5, 6.6, 0.0), image.clipping_vertices[2]);
+        let entities = drawing.entities().collect::<Vec<_>>();
+        assert_eq!(1, entities.len());
+        match entities[0].specific {
+            EntityType::LwPolyline(ref poly) => {
+                assert!(approx_eq!(f64, 43.0, poly.constant_width));
+                assert_eq!(0, poly.vertices.len());
             }
-            _ => panic!("expected an IMAGE"),
+            _ => panic!("expected an LWPOLYLINE"),
         }
     }
 
     #[test]
-    fn write_entity_with_write_order() {
-        let mut drawing = Drawing::new();
-        drawing.header.version = AcadVersion::R14; // IMAGE is only supported on R14+
-        drawing.add_entity(Entity {
-            common: Default::default(),
-            specific: EntityType::Image(Image {
-                clipping_vertices: vec![
-                    Point::new(1.1, 2.2, 0.0),
-                    Point::new(3.3, 4.4, 0.0),
-                    Point::new(5.5, 6.6, 0.0),
-                ],
-                ..Default::default()
-            }),
-        });
-        assert_contains(
-            &drawing,
+    fn read_lw_polyline_with_one_vertex() {
+        let drawing = from_section(
+            "ENTITIES",
             vec![
-                " 91",
-                "        3",
-                " 14",
+                "0",
+                "LWPOLYLINE",
+                "43",
+                "43.0",
+                // vertex 1
+                "10",
                 "1.1",
-                " 24",
-                "2.2",
-                " 14",
-                "3.3",
-                " 24",
-                "4.4",
-                " 14",
-                "5.5",
-                " 24",
-                "6.6",
-            ]
-            .join("\r\n"),
-        );
-    }
-
-    #[test]
-    fn read_entity_with_custom_reader_mtext() {
-        let ent = read_entity(
-            "MTEXT",
-            vec![
-                "50", "1.1", // rotation angle
-                "75", "7", // column type
-                "50", "3", // column count
-                "50", "10", // column values
-                "50", "20", "50", "30",
+                "20",
+                "2.1",
+                "40",
+                "40.1",
+                "41",
+                "41.1",
+                "42",
+                "42.1",
+                "91",
+                "91",
             ]
-            .join("\r\n"),
+            .join("\r\n")
+            .as_str(),
         );
-        match ent.specific {
-            EntityType::MText(ref mtext) => {
-                assert!(approx_eq!(f64, 1.1, mtext.rotation_angle));
-                assert_eq!(7, mtext.column_type);
-                assert_eq!(3, mtext.column_count);
-                assert_eq!(3, mtext.column_heights.len());
-                assert!(approx_eq!(f64, 10.0, mtext.column_heights[0]));
-                assert!(approx_eq!(f64, 20.0, mtext.column_heights[1]));
-                assert!(approx_eq!(f64, 30.0, mtext.column_heights[2]));
+        let entities = drawing.entities().collect::<Vec<_>>();
+        assert_eq!(1, entities.len());
+        match entities[0].specific {
+            EntityType::LwPolyline(ref poly) => {
+                assert!(approx_eq!(f64, 43.0, poly.constant_width));
+                assert_eq!(1, poly.vertices.len());
+                // vertex 1
+                assert!(approx_eq!(f64, 1.1, poly.vertices[0].x));
+                assert!(approx_eq!(f64, 2.1, poly.vertices[0].y));
+                assert!(approx_eq!(f64, 40.1, poly.vertices[0].starting_width));
+                assert!(approx_eq!(f64, 41.1, poly.vertices[0].ending_width));
+                assert!(approx_eq!(f64, 42.1, poly.vertices[0].bulge));
+                assert_eq!(91, poly.vertices[0].id);
             }
-            _ => panic!("expected an MTEXT"),
+            _ => panic!("expected an LWPOLYLINE"),
         }
     }
 
     #[test]
-    fn read_entity_after_entity_with_custom_reader() {
+    fn read_lw_polyline_with_multiple_vertices() {
         let drawing = from_section(
             "ENTITIES",
             vec![
-                "  0", "MTEXT", // has a custom reader
-                "  0", "LINE", // uses the auto-generated reader
+                "0",
+                "LWPOLYLINE",
+                "43",
+                "43.0",
+                // vertex 1
+                "10",
+                "1.1",
+                "20",
+                "2.1",
+                "40",
+                "40.1",
+                "41",
+                "41.1",
+                "42",
+                "42.1",
+                "91",
+                "91",
+                // vertex 2
+                "10",
+                "1.2",
+                "20",
+                "2.2",
+                "40",
+                "40.2",
+                "41",
+                "41.2",
+                "42",
+                "42.2",
+                "91",
+                "92",
             ]
             .join("\r\n")
             .as_str(),
         );
         let entities = drawing.entities().collect::<Vec<_>>();
-        assert_eq!(2, entities.len());
+        assert_eq!(1, entities.len());
         match entities[0].specific {
-            EntityType::MText(_) => {}
-            _ => panic!("expected an mtext"),
-        }
-        match entities[1].specific {
-            EntityType::Line(_) => {}
-            _ => panic!("expected a line"),
-        }
-    }
-
-    #[test]
-    fn read_entity_with_flags() {
-        let ent = read_entity("IMAGE", vec!["70", "5"].join("\r\n"));
-        match ent.specific {
-            EntityType::Image(ref image) => {
-                assert!(image.get_show_image());
-                assert!(image.get_use_clipping_boundary());
+            EntityType::LwPolyline(ref poly) => {
+                assert!(approx_eq!(f64, 43.0, poly.constant_width));
+                assert_eq!(2, poly.vertices.len());
+                // vertex 1
+                assert!(approx_eq!(f64, 1.1, poly.vertices[0].x));
+                assert!(approx_eq!(f64, 2.1, poly.vertices[0].y));
+                assert!(approx_eq!(f64, 40.1, poly.vertices[0].starting_width));
+                assert!(approx_eq!(f64, 41.1, poly.vertices[0].ending_width));
+                assert!(approx_eq!(f64, 42.1, poly.vertices[0].bulge));
+                assert_eq!(91, poly.vertices[0].id);
+                // vertex 2
+                assert!(approx_eq!(f64, 1.2, poly.vertices[1].x));
+                assert!(approx_eq!(f64, 2.2, poly.vertices[1].y));
+                assert!(approx_eq!(f64, 40.2, poly.vertices[1].starting_width));
+                assert!(approx_eq!(f64, 41.2, poly.vertices[1].ending_width));
+                assert!(approx_eq!(f64, 42.2, poly.vertices[1].bulge));
+                assert_eq!(92, poly.vertices[1].id);
             }
-            _ => panic!("expected an IMAGE"),
+            _ => panic!("expected an LWPOLYLINE"),
         }
     }
 
     #[test]
-    fn write_entity_with_flags() {
+    fn write_lw_polyline() {
         let mut drawing = Drawing::new();
-        drawing.header.version = AcadVersion::R14; // IMAGE is only supported on R14+
-        let mut image = Image::default();
-        assert_eq!(0, image.display_options_flags);
-        image.set_show_image(true);
-        image.set_use_clipping_boundary(true);
-        drawing.add_entity(Entity {
-            common: Default::default(),
-            specific: EntityType::Image(image),
+        drawing.header.version = AcadVersion::R2013;
+        let mut poly = LwPolyline::default();
+        poly.constant_width = 43.0;
+        poly.vertices.push(LwPolylineVertex {
+            x: 1.1,
+            y: 2.1,
+            ..Default::default()
+        });
+        poly.vertices.push(LwPolylineVertex {
+            x: 1.2,
+            y: 2.2,
+            starting_width: 40.2,
+            ending_width: 41.2,
+            bulge: 42.2,
+            id: 92,
         });
+        drawing.add_entity(Entity::new(EntityType::LwPolyline(poly)));
         assert_contains(
             &drawing,
             vec![
-                " 70", "     5", // flags
-                "280", "     1", // sentinels to make sure we're not reading a header value
-                "281", "    50",
+                "100",
+                "AcDbPolyline",
+                " 90",
+                "        2",
+                " 70",
+                "     0",
+                " 43",
+                "43.0",
+                // vertex 1
+                " 10",
+                "1.1",
+                " 20",
+                "2.1",
+                " 91",
+                "        0",
+                // vertex 2
+                " 10",
+                "1.2",
+                " 20",
+                "2.2",
+                " 91",
+                "       92",
+                " 40",
+                "40.2",
+                " 41",
+                "41.2",
+                " 42",
+                "42.2",
             ]
             .join("\r\n"),
         );
     }
 
     #[test]
-    fn read_entity_with_handle_and_pointer() {
+    fn read_hatch_with_polyline_boundary_path() {
         let ent = read_entity(
-            "3DSOLID",
+            "HATCH",
             vec![
-                "5", "A1", // handle
-                "330", "A2", // owner handle
-                "350", "A3", // history_object pointer
+                "100",
+                "AcDbHatch",
+                " 10",
+                "0.0",
+                " 20",
+                "0.0",
+                " 30",
+                "3.0",
+                "  2",
+                "SOLID",
+                " 70",
+                "1",
+                " 71",
+                "1",
+                " 91",
+                "1",
+                // boundary path
+                " 92",
+                "3",
+                " 72",
+                "1",
+                " 73",
+                "1",
+                " 93",
+                "2",
+                " 10",
+                "1.0",
+                " 20",
+                "2.0",
+                " 42",
+                "0.5",
+                " 10",
+                "3.0",
+                " 20",
+                "4.0",
+                " 42",
+                "0.0",
+                " 97",
+                "1",
+                "330",
+                "A1",
+                // back to the hatch
+                " 75",
+                "1",
+                " 76",
+                "1",
+                " 98",
+                "2",
+                " 10",
+                "5.0",
+                " 20",
+                "6.0",
+                " 10",
+                "7.0",
+                " 20",
+                "8.0",
             ]
             .join("\r\n"),
         );
-        assert_eq!(0xa1, ent.common.handle);
-        assert_eq!(0xa2, ent.common.__owner_handle);
         match ent.specific {
-            EntityType::Solid3D(ref solid) => assert_eq!(0xa3, solid.__history_object_handle),
-            _ => panic!("expected a 3DSOLID entity"),
-        }
-    }
-
-    #[test]
-    fn write_entity_with_handle_and_pointer() {
-        let mut drawing = Drawing::new();
-        drawing.header.version = AcadVersion::R2000;
-        drawing.add_entity(Entity {
-            common: EntityCommon {
-                __owner_handle: 0xa2,
-                ..Default::default()
-            },
-            specific: EntityType::Line(Default::default()),
-        });
-        assert_contains(
-            &drawing,
-            vec!["  0", "LINE", "  5", "10", "330", "A2"].join("\r\n"),
-        );
-    }
-
-    #[test]
-    fn write_version_specific_entity() {
-        let mut drawing = Drawing::new();
-        drawing.add_entity(Entity {
-            common: Default::default(),
-            specific: EntityType::Solid3D(Default::default()),
-        });
-
-        // 3DSOLID not supported in R12 and below
-        drawing.header.version = AcadVersion::R12;
-        assert_contains(
-            &drawing,
-            vec!["  0", "SECTION", "  2", "ENTITIES", "  0", "ENDSEC"].join("\r\n"),
-        );
-
-        // but it is in R13 and above
-        drawing.header.version = AcadVersion::R13;
-        assert_contains(
-            &drawing,
-            vec!["  0", "SECTION", "  2", "ENTITIES", "  0", "3DSOLID"].join("\r\n"),
-        );
-    }
-
-    #[test]
-    fn read_polyline() {
-        let drawing = from_section(
-            "ENTITIES",
-            vec![
-                "  0", "POLYLINE", // polyline sentinel
-                "  0", "VERTEX", // vertex 1
-                " 10", "1.1", " 20", "2.1", " 30", "3.1", "  0", "VERTEX", // vertex 2
-                " 10", "1.2", " 20", "2.2", " 30", "3.2", "  0", "VERTEX", // vertex 3
-                " 10", "1.3", " 20", "2.3", " 30", "3.3", "  0", "SEQEND", // end sequence
-            ]
-            .join("\r\n")
-            .as_str(),
-        );
-        let entities = drawing.entities().collect::<Vec<_>>();
-        assert_eq!(1, entities.len());
-        match entities[0].specific {
-            EntityType::Polyline(ref poly) => {
+            EntityType::Hatch(ref hatch) => {
+                assert_eq!(Point::new(0.0, 0.0, 3.0), hatch.elevation_point);
+                assert_eq!("SOLID", hatch.pattern_name);
+                assert!(hatch.is_solid_fill);
+                assert!(hatch.is_associative);
+                assert_eq!(1, hatch.boundary_paths.len());
+                let path = &hatch.boundary_paths[0];
+                assert!(path.get_is_external());
+                assert!(path.get_is_polyline());
+                assert!(path.is_closed);
                 assert_eq!(
                     vec![
-                        Vertex {
-                            location: Point::new(1.1, 2.1, 3.1),
-                            ..Default::default()
-                        },
-                        Vertex {
-                            location: Point::new(1.2, 2.2, 3.2),
-                            ..Default::default()
-                        },
-                        Vertex {
-                            location: Point::new(1.3, 2.3, 3.3),
-                            ..Default::default()
-                        },
+                        HatchPolylineVertex::new(1.0, 2.0, 0.5),
+                        HatchPolylineVertex::new(3.0, 4.0, 0.0),
                     ],
-                    poly.vertices().cloned().collect::<Vec<_>>()
+                    path.polyline_vertices
+                );
+                assert_eq!(vec![Handle(0xA1)], path.source_boundary_object_handles);
+                assert_eq!(HatchStyle::OutermostAreaOnly, hatch.hatch_style);
+                assert_eq!(HatchPatternType::Predefined, hatch.pattern_type);
+                assert_eq!(
+                    vec![Point::new(5.0, 6.0, 0.0), Point::new(7.0, 8.0, 0.0)],
+                    hatch.seed_points
                 );
             }
-            _ => panic!("expected a POLYLINE"),
+            _ => panic!("expected a HATCH"),
         }
     }
 
     #[test]
-    fn read_polyline_without_seqend() {
-        let drawing = from_section(
-            "ENTITIES",
+    fn read_hatch_with_edge_boundary_path_and_pattern_lines() {
+        let ent = read_entity(
+            "HATCH",
             vec![
-                "  0", "POLYLINE", // polyline sentinel
-                "  0", "VERTEX", // vertex 1
-                " 10", "1.1", " 20", "2.1", " 30", "3.1", "  0", "VERTEX", // vertex 2
-                " 10", "1.2", " 20", "2.2", " 30", "3.2", "  0", "VERTEX", // vertex 3
-                " 10", "1.3", " 20", "2.3", " 30", "3.3",
-                // no end sequence
+                "100",
+                "AcDbHatch",
+                "  2",
+                "ANSI31",
+                " 70",
+                "0",
+                " 71",
+                "0",
+                " 91",
+                "1",
+                // boundary path
+                " 92",
+                "1",
+                " 93",
+                "4",
+                // line
+                " 72",
+                "1",
+                " 10",
+                "1.0",
+                " 20",
+                "2.0",
+                " 11",
+                "3.0",
+                " 21",
+                "4.0",
+                // arc
+                " 72",
+                "2",
+                " 10",
+                "5.0",
+                " 20",
+                "6.0",
+                " 40",
+                "7.0",
+                " 50",
+                "8.0",
+                " 51",
+                "9.0",
+                " 73",
+                "0",
+                // ellipse
+                " 72",
+                "3",
+                " 10",
+                "1.0",
+                " 20",
+                "2.0",
+                " 11",
+                "3.0",
+                " 21",
+                "4.0",
+                " 40",
+                "0.5",
+                " 50",
+                "6.0",
+                " 51",
+                "7.0",
+                " 73",
+                "1",
+                // spline
+                " 72",
+                "4",
+                " 94",
+                "3",
+                " 73",
+                "1",
+                " 74",
+                "0",
+                " 95",
+                "2",
+                " 96",
+                "2",
+                " 40",
+                "0.0",
+                " 40",
+                "1.0",
+                " 10",
+                "1.0",
+                " 20",
+                "2.0",
+                " 42",
+                "0.25",
+                " 10",
+                "3.0",
+                " 20",
+                "4.0",
+                " 42",
+                "0.75",
+                " 97",
+                "1",
+                " 11",
+                "5.0",
+                " 21",
+                "6.0",
+                " 12",
+                "1.0",
+                " 22",
+                "0.0",
+                " 13",
+                "0.0",
+                " 23",
+                "1.0",
+                " 97",
+                "0",
+                // back to the hatch
+                " 75",
+                "0",
+                " 76",
+                "1",
+                " 52",
+                "45.0",
+                " 41",
+                "2.0",
+                " 77",
+                "0",
+                " 78",
+                "2",
+                // pattern line 1
+                " 53",
+                "45.0",
+                " 43",
+                "1.0",
+                " 44",
+                "2.0",
+                " 45",
+                "3.0",
+                " 46",
+                "4.0",
+                " 79",
+                "2",
+                " 49",
+                "5.0",
+                " 49",
+                "-6.0",
+                // pattern line 2
+                " 53",
+                "135.0",
+                " 43",
+                "7.0",
+                " 44",
+                "8.0",
+                " 45",
+                "9.0",
+                " 46",
+                "10.0",
+                " 79",
+                "0",
+                // back to the hatch
+                " 47",
+                "0.5",
+                " 98",
+                "0",
             ]
-            .join("\r\n")
-            .as_str(),
+            .join("\r\n"),
         );
-        let entities = drawing.entities().collect::<Vec<_>>();
-        assert_eq!(1, entities.len());
-        match entities[0].specific {
-            EntityType::Polyline(ref poly) => {
+        match ent.specific {
+            EntityType::Hatch(ref hatch) => {
+                assert!(!hatch.is_solid_fill);
+                assert_eq!(1, hatch.boundary_paths.len());
+                let path = &hatch.boundary_paths[0];
+                assert!(!path.get_is_polyline());
                 assert_eq!(
                     vec![
-                        Vertex {
-                            location: Point::new(1.1, 2.1, 3.1),
-                            ..Default::default()
+                        HatchBoundaryPathEdge::Line {
+                            start: Point::new(1.0, 2.0, 0.0),
+                            end: Point::new(3.0, 4.0, 0.0),
                         },
-                        Vertex {
-                            location: Point::new(1.2, 2.2, 3.2),
-                            ..Default::default()
+                        HatchBoundaryPathEdge::Arc {
+                            center: Point::new(5.0, 6.0, 0.0),
+                            radius: 7.0,
+                            start_angle: 8.0,
+                            end_angle: 9.0,
+                            is_counter_clockwise: false,
                         },
-                        Vertex {
-                            location: Point::new(1.3, 2.3, 3.3),
-                            ..Default::default()
+                        HatchBoundaryPathEdge::Ellipse {
+                            center: Point::new(1.0, 2.0, 0.0),
+                            major_axis: Vector::new(3.0, 4.0, 0.0),
+                            minor_axis_ratio: 0.5,
+                            start_angle: 6.0,
+                            end_angle: 7.0,
+                            is_counter_clockwise: true,
+                        },
+                        HatchBoundaryPathEdge::Spline {
+                            degree: 3,
+                            is_rational: true,
+                            is_periodic: false,
+                            knot_values: vec![0.0, 1.0],
+                            control_points: vec![
+                                Point::new(1.0, 2.0, 0.0),
+                                Point::new(3.0, 4.0, 0.0),
+                            ],
+                            weights: vec![0.25, 0.75],
+                            fit_points: vec![Point::new(5.0, 6.0, 0.0)],
+                            start_tangent: Vector::new(1.0, 0.0, 0.0),
+                            end_tangent: Vector::new(0.0, 1.0, 0.0),
                         },
                     ],
-                    poly.vertices().cloned().collect::<Vec<_>>()
+                    path.edges
                 );
-            }
-            _ => panic!("expected a POLYLINE"),
-        }
-    }
-
-    #[test]
-    fn read_empty_polyline() {
-        let drawing = from_section(
-            "ENTITIES",
-            vec!["0", "POLYLINE", "0", "SEQEND"].join("\r\n").as_str(),
-        );
-        let entities = drawing.entities().collect::<Vec<_>>();
-        assert_eq!(1, entities.len());
-        match entities[0].specific {
-            EntityType::Polyline(ref poly) => assert_eq!(0, poly.vertices().count()),
-            _ => panic!("expected a POLYLINE"),
-        }
-    }
-
-    #[test]
-    fn read_empty_polyline_without_seqend() {
-        let drawing = from_section("ENTITIES", vec!["0", "POLYLINE"].join("\r\n").as_str());
-        let entities = drawing.entities().collect::<Vec<_>>();
-        assert_eq!(1, entities.len());
-        match entities[0].specific {
-            EntityType::Polyline(ref poly) => assert_eq!(0, poly.vertices().count()),
-            _ => panic!("expected a POLYLINE"),
-        }
-    }
-
-    #[test]
-    fn read_polyline_with_trailing_entity() {
-        let drawing = from_section(
-            "ENTITIES",
-            vec![
-                "  0", "POLYLINE", // polyline sentinel
-                "  0", "VERTEX", // vertex 1
-                " 10", "1.1", " 20", "2.1", " 30", "3.1", "  0", "VERTEX", // vertex 2
-                " 10", "1.2", " 20", "2.2", " 30", "3.2", "  0", "VERTEX", // vertex 3
-                " 10", "1.3", " 20", "2.3", " 30", "3.3", "  0", "SEQEND", // end sequence
-                "  0", "LINE", // trailing entity
-            ]
-            .join("\r\n")
-            .as_str(),
-        );
-        let entities = drawing.entities().collect::<Vec<_>>();
-        assert_eq!(2, entities.len());
-        match entities[0].specific {
-            EntityType::Polyline(ref poly) => {
+                assert!(approx_eq!(f64, 45.0, hatch.pattern_angle));
+                assert!(approx_eq!(f64, 2.0, hatch.pattern_scale));
                 assert_eq!(
                     vec![
-                        Vertex {
-                            location: Point::new(1.1, 2.1, 3.1),
-                            ..Default::default()
-                        },
-                        Vertex {
-                            location: Point::new(1.2, 2.2, 3.2),
-                            ..Default::default()
+                        HatchPatternDefinitionLine {
+                            angle: 45.0,
+                            base_point: Point::new(1.0, 2.0, 0.0),
+                            offset: Vector::new(3.0, 4.0, 0.0),
+                            dash_lengths: vec![5.0, -6.0],
                         },
-                        Vertex {
-                            location: Point::new(1.3, 2.3, 3.3),
-                            ..Default::default()
+                        HatchPatternDefinitionLine {
+                            angle: 135.0,
+                            base_point: Point::new(7.0, 8.0, 0.0),
+                            offset: Vector::new(9.0, 10.0, 0.0),
+                            dash_lengths: vec![],
                         },
                     ],
-                    poly.vertices().cloned().collect::<Vec<_>>()
+                    hatch.pattern_definition_lines
                 );
+                assert!(approx_eq!(f64, 0.5, hatch.pixel_size));
             }
-            _ => panic!("expected a POLYLINE"),
-        }
-
-        match entities[1].specific {
-            EntityType::Line(_) => (),
-            _ => panic!("expected a LINE"),
+            _ => panic!("expected a HATCH"),
         }
     }
 
     #[test]
-    fn read_polyline_without_seqend_with_trailing_entity() {
-        let drawing = from_section(
-            "ENTITIES",
+    fn read_hatch_with_gradient() {
+        let ent = read_entity(
+            "HATCH",
             vec![
-                "  0", "POLYLINE", // polyline sentinel
-                "  0", "VERTEX", // vertex 1
-                " 10", "1.1", " 20", "2.1", " 30", "3.1", "  0", "VERTEX", // vertex 2
-                " 10", "1.2", " 20", "2.2", " 30", "3.2", "  0", "VERTEX", // vertex 3
-                " 10", "1.3", " 20", "2.3", " 30", "3.3", // no end sequence
-                "  0", "LINE", // trailing entity
+                "100",
+                "AcDbHatch",
+                " 91",
+                "0",
+                " 75",
+                "0",
+                " 76",
+                "1",
+                " 98",
+                "0",
+                "450",
+                "1",
+                "451",
+                "0",
+                "460",
+                "1.5",
+                "461",
+                "0.25",
+                "452",
+                "1",
+                "462",
+                "0.5",
+                "453",
+                "2",
+                "463",
+                "0.0",
+                " 63",
+                "5",
+                "421",
+                "255",
+                "463",
+                "1.0",
+                " 63",
+                "2",
+                "421",
+                "16776960",
+                "470",
+                "LINEAR",
             ]
-            .join("\r\n")
-            .as_str(),
+            .join("\r\n"),
         );
-        let entities = drawing.entities().collect::<Vec<_>>();
-        assert_eq!(2, entities.len());
-        match entities[0].specific {
-            EntityType::Polyline(ref poly) => {
+        match ent.specific {
+            EntityType::Hatch(ref hatch) => {
+                assert!(hatch.is_gradient);
+                assert!(hatch.is_single_color_gradient);
+                assert!(approx_eq!(f64, 1.5, hatch.gradient_angle));
+                assert!(approx_eq!(f64, 0.25, hatch.gradient_shift));
+                assert!(approx_eq!(f64, 0.5, hatch.gradient_color_tint));
                 assert_eq!(
                     vec![
-                        Vertex {
-                            location: Point::new(1.1, 2.1, 3.1),
-                            ..Default::default()
-                        },
-                        Vertex {
-                            location: Point::new(1.2, 2.2, 3.2),
-                            ..Default::default()
+                        HatchGradientColor {
+                            value: 0.0,
+                            color: Color::from_index(5),
+                            true_color: 255,
                         },
-                        Vertex {
-                            location: Point::new(1.3, 2.3, 3.3),
-                            ..Default::default()
+                        HatchGradientColor {
+                            value: 1.0,
+                            color: Color::from_index(2),
+                            true_color: 16_776_960,
                         },
                     ],
-                    poly.vertices().cloned().collect::<Vec<_>>()
+                    hatch.gradient_colors
                 );
+                assert_eq!("LINEAR", hatch.gradient_name);
             }
-            _ => panic!("expected a POLYLINE"),
-        }
-
-        match entities[1].specific {
-            EntityType::Line(_) => (),
-            _ => panic!("expected a LINE"),
+            _ => panic!("expected a HATCH"),
         }
     }
 
     #[test]
-    fn read_empty_polyline_with_trailing_entity() {
-        let drawing = from_section(
-            "ENTITIES",
-            vec!["0", "POLYLINE", "0", "SEQEND", "0", "LINE"]
-                .join("\r\n")
-                .as_str(),
+    fn write_hatch() {
+        let mut drawing = Drawing::new();
+        drawing.header.version = AcadVersion::R2010;
+        let mut path = HatchBoundaryPath::default();
+        path.set_is_polyline(true);
+        path.polyline_vertices
+            .push(HatchPolylineVertex::new(1.0, 2.0, 0.0));
+        path.polyline_vertices
+            .push(HatchPolylineVertex::new(3.0, 4.0, 0.5));
+        let hatch = Hatch {
+            boundary_paths: vec![path],
+            seed_points: vec![Point::new(5.0, 6.0, 0.0)],
+            ..Default::default()
+        };
+        drawing.add_entity(Entity::new(EntityType::Hatch(hatch)));
+        assert_contains(
+            &drawing,
+            vec![
+                "100",
+                "AcDbHatch",
+                " 10",
+                "0.0",
+                " 20",
+                "0.0",
+                " 30",
+                "0.0",
+                "210",
+                "0.0",
+                "220",
+                "0.0",
+                "230",
+                "1.0",
+                "  2",
+                "SOLID",
+                " 70",
+                "     1",
+                " 71",
+                "     0",
+                " 91",
+                "        1",
+                " 92",
+                "        2",
+                " 72",
+                "     1",
+                " 73",
+                "     1",
+                " 93",
+                "        2",
+                " 10",
+                "1.0",
+                " 20",
+                "2.0",
+                " 42",
+                "0.0",
+                " 10",
+                "3.0",
+                " 20",
+                "4.0",
+                " 42",
+                "0.5",
+                " 97",
+                "        0",
+                " 75",
+                "     0",
+                " 76",
+                "     1",
+                " 47",
+                "0.0",
+                " 98",
+                "        1",
+                " 10",
+                "5.0",
+                " 20",
+                "6.0",
+            ]
+            .join("\r\n"),
         );
-        let entities = drawing.entities().collect::<Vec<_>>();
-        assert_eq!(2, entities.len());
-        match entities[0].specific {
-            EntityType::Polyline(ref poly) => assert_eq!(0, poly.vertices().count()),
-            _ => panic!("expected a POLYLINE"),
-        }
-        match entities[1].specific {
-            EntityType::Line(_) => (),
-            _ => panic!("expected a LINE"),
-        }
     }
 
     #[test]
-    fn read_empty_polyline_without_seqend_with_trailing_entity() {
-        let drawing = from_section(
-            "ENTITIES",
-            vec!["0", "POLYLINE", "0", "LINE"].join("\r\n").as_str(),
-        );
-        let entities = drawing.entities().collect::<Vec<_>>();
-        assert_eq!(2, entities.len());
-        match entities[0].specific {
-            EntityType::Polyline(ref poly) => assert_eq!(0, poly.vertices().count()),
-            _ => panic!("expected a POLYLINE"),
-        }
-        match entities[1].specific {
-            EntityType::Line(_) => (),
-            _ => panic!("expected a LINE"),
+    fn write_hatch_is_not_written_before_r14() {
+        let mut drawing = Drawing::new();
+        drawing.header.version = AcadVersion::R13;
+        drawing.add_entity(Entity::new(EntityType::Hatch(Hatch::default())));
+        assert_not_contains(&drawing, ["  0", "HATCH"].join("\r\n"));
+    }
+
+    #[test]
+    fn round_trip_hatch() {
+        let mut edge_path = HatchBoundaryPath::default();
+        edge_path.set_is_external(true);
+        edge_path.edges.push(HatchBoundaryPathEdge::Line {
+            start: Point::new(1.0, 2.0, 0.0),
+            end: Point::new(3.0, 4.0, 0.0),
+        });
+        edge_path.edges.push(HatchBoundaryPathEdge::Arc {
+            center: Point::new(5.0, 6.0, 0.0),
+            radius: 7.0,
+            start_angle: 8.0,
+            end_angle: 9.0,
+            is_counter_clockwise: false,
+        });
+        edge_path.edges.push(HatchBoundaryPathEdge::Ellipse {
+            center: Point::new(1.0, 2.0, 0.0),
+            major_axis: Vector::new(3.0, 4.0, 0.0),
+            minor_axis_ratio: 0.5,
+            start_angle: 6.0,
+            end_angle: 7.0,
+            is_counter_clockwise: true,
+        });
+        edge_path.edges.push(HatchBoundaryPathEdge::Spline {
+            degree: 3,
+            is_rational: true,
+            is_periodic: false,
+            knot_values: vec![0.0, 0.0, 1.0, 1.0],
+            control_points: vec![Point::new(1.0, 2.0, 0.0), Point::new(3.0, 4.0, 0.0)],
+            weights: vec![0.25, 0.75],
+            fit_points: vec![],
+            start_tangent: Vector::zero(),
+            end_tangent: Vector::zero(),
+        });
+        edge_path.source_boundary_object_handles.push(Handle(0xA1));
+        let mut polyline_path = HatchBoundaryPath::default();
+        polyline_path.set_is_polyline(true);
+        polyline_path
+            .polyline_vertices
+            .push(HatchPolylineVertex::new(1.0, 2.0, 0.5));
+        polyline_path
+            .polyline_vertices
+            .push(HatchPolylineVertex::new(3.0, 4.0, 0.0));
+        let hatch = Hatch {
+            elevation_point: Point::new(0.0, 0.0, 1.5),
+            pattern_name: String::from("ANSI31"),
+            is_solid_fill: false,
+            is_associative: true,
+            boundary_paths: vec![edge_path, polyline_path],
+            hatch_style: HatchStyle::EntireArea,
+            pattern_angle: 45.0,
+            pattern_scale: 2.0,
+            pattern_definition_lines: vec![HatchPatternDefinitionLine {
+                angle: 45.0,
+                base_point: Point::new(1.0, 2.0, 0.0),
+                offset: Vector::new(3.0, 4.0, 0.0),
+                dash_lengths: vec![5.0, -6.0],
+            }],
+            pixel_size: 0.5,
+            seed_points: vec![Point::new(5.0, 6.0, 0.0)],
+            ..Default::default()
+        };
+        let hatch = EntityType::Hatch(hatch);
+
+        for &version in &[
+            AcadVersion::R14,
+            AcadVersion::R2000,
+            AcadVersion::R2004,
+            AcadVersion::R2007,
+            AcadVersion::R2010,
+            AcadVersion::R2013,
+            AcadVersion::R2018,
+        ] {
+            let mut drawing = Drawing::new();
+            drawing.header.version = version;
+            drawing.add_entity(Entity::new(hatch.clone()));
+            let drawing = parse_drawing(&to_test_string(&drawing));
+            let entities = drawing.entities().collect::<Vec<_>>();
+            assert_eq!(1, entities.len());
+            assert_eq!(hatch, entities[0].specific);
         }
     }
 
     #[test]
-    fn write_2d_polyline() {
-        let mut drawing = Drawing::new();
-        let mut poly = Polyline::default();
-        poly.add_vertex(
-            &mut drawing,
-            Vertex {
-                location: Point::new(1.1, 2.1, 3.1),
-                ..Default::default()
-            },
-        );
-        poly.add_vertex(
-            &mut drawing,
-            Vertex {
-                location: Point::new(1.2, 2.2, 3.2),
-                ..Default::default()
-            },
-        );
-        poly.add_vertex(
-            &mut drawing,
-            Vertex {
-                location: Point::new(1.3, 2.3, 3.3),
-                ..Default::default()
-            },
-        );
-        drawing.add_entity(Entity {
-            common: Default::default(),
-            specific: EntityType::Polyline(poly),
+    fn round_trip_hatch_with_gradient_and_spline_fit_points() {
+        let mut path = HatchBoundaryPath::default();
+        path.edges.push(HatchBoundaryPathEdge::Spline {
+            degree: 3,
+            is_rational: false,
+            is_periodic: false,
+            knot_values: vec![0.0, 0.0, 1.0, 1.0],
+            control_points: vec![Point::new(1.0, 2.0, 0.0), Point::new(3.0, 4.0, 0.0)],
+            weights: vec![],
+            fit_points: vec![Point::new(1.0, 2.0, 0.0), Point::new(3.0, 4.0, 0.0)],
+            start_tangent: Vector::x_axis(),
+            end_tangent: Vector::y_axis(),
         });
-        assert_contains(
-            &drawing,
+        let hatch = Hatch {
+            boundary_paths: vec![path],
+            is_gradient: true,
+            gradient_angle: 1.5,
+            gradient_colors: vec![
+                HatchGradientColor {
+                    value: 0.0,
+                    color: Color::from_index(5),
+                    true_color: 255,
+                },
+                HatchGradientColor {
+                    value: 1.0,
+                    color: Color::from_index(2),
+                    true_color: 16_776_960,
+                },
+            ],
+            gradient_name: String::from("LINEAR"),
+            ..Default::default()
+        };
+        let hatch = EntityType::Hatch(hatch);
+
+        let mut drawing = Drawing::new();
+        drawing.header.version = AcadVersion::R2018;
+        drawing.add_entity(Entity::new(hatch.clone()));
+        let drawing = parse_drawing(&to_test_string(&drawing));
+        let entities = drawing.entities().collect::<Vec<_>>();
+        assert_eq!(1, entities.len());
+        assert_eq!(hatch, entities[0].specific);
+    }
+
+    #[test]
+    fn read_mleader() {
+        let ent = read_entity(
+            "MULTILEADER",
             vec![
-                "  0",
-                "POLYLINE", // polyline
-                "  5",
-                "13",
-                "100",
-                "AcDbEntity",
-                "  8",
-                "0",
                 "100",
-                "AcDb2dPolyline",
-                " 66",
-                "     1",
+                "AcDbMLeader",
+                "270",
+                "2",
+                "300",
+                "CONTEXT_DATA{",
+                " 40",
+                "1.0",
                 " 10",
-                "0.0",
+                "1.0",
                 " 20",
-                "0.0",
+                "2.0",
                 " 30",
+                "3.0",
+                " 41",
+                "0.25",
+                "290",
+                "1",
+                "304",
+                "leader text",
+                " 11",
                 "0.0",
-                "  0",
-                "VERTEX", // vertex 1
-                "  5",
-                "10",
-                "100",
-                "AcDbEntity",
-                "  8",
+                " 21",
+                "0.0",
+                " 31",
+                "1.0",
+                "340",
+                "A1",
+                " 12",
+                "4.0",
+                " 22",
+                "5.0",
+                " 32",
+                "0.0",
+                "296",
                 "0",
-                "100",
-                "AcDbVertex",
-                "100",
-                "AcDb2dVertex",
+                "110",
+                "0.0",
+                "120",
+                "0.0",
+                "130",
+                "0.0",
+                "302",
+                "LEADER{",
+                "290",
+                "1",
+                "291",
+                "1",
                 " 10",
-                "1.1",
+                "6.0",
                 " 20",
-                "2.1",
+                "7.0",
                 " 30",
-                "3.1",
-                " 70",
-                "     0",
-                " 50",
                 "0.0",
-                "  0",
-                "VERTEX", // vertex 2
-                "  5",
-                "11",
-                "100",
-                "AcDbEntity",
-                "  8",
+                " 11",
+                "1.0",
+                " 21",
+                "0.0",
+                " 31",
+                "0.0",
+                " 90",
                 "0",
-                "100",
-                "AcDbVertex",
-                "100",
-                "AcDb2dVertex",
+                " 40",
+                "0.36",
+                "304",
+                "LEADER_LINE{",
                 " 10",
-                "1.2",
+                "8.0",
                 " 20",
-                "2.2",
+                "9.0",
                 " 30",
-                "3.2",
-                " 70",
-                "     0",
-                " 50",
                 "0.0",
-                "  0",
-                "VERTEX", // vertex 3
-                "  5",
-                "12",
-                "100",
-                "AcDbEntity",
-                "  8",
-                "0",
-                "100",
-                "AcDbVertex",
-                "100",
-                "AcDb2dVertex",
                 " 10",
-                "1.3",
+                "10.0",
                 " 20",
-                "2.3",
+                "11.0",
                 " 30",
-                "3.3",
-                " 70",
-                "     0",
-                " 50",
                 "0.0",
-                "  0",
-                "SEQEND", // end sequence
+                " 91",
+                "0",
+                "305",
+                "}",
+                "271",
+                "0",
+                "303",
+                "}",
+                "272",
+                "9",
+                "273",
+                "9",
+                "301",
+                "}",
+                "340",
+                "B2",
+                " 90",
+                "0",
+                "172",
+                "2",
+                "343",
+                "C3",
+                " 45",
+                "2.0",
             ]
             .join("\r\n"),
         );
+        match ent.specific {
+            EntityType::MLeader(ref mleader) => {
+                assert_eq!(2, mleader.version);
+                let context = &mleader.context_data;
+                assert_eq!(Point::new(1.0, 2.0, 3.0), context.content_base_point);
+                assert!(approx_eq!(f64, 0.25, context.text_height));
+                assert!(context.has_text_contents);
+                assert_eq!("leader text", context.text_contents);
+                assert_eq!(Handle(0xA1), context.text_style_handle);
+                assert_eq!(Point::new(4.0, 5.0, 0.0), context.text_location);
+                assert!(!context.has_block_contents);
+                assert_eq!(1, context.leaders.len());
+                let leader = &context.leaders[0];
+                assert_eq!(Point::new(6.0, 7.0, 0.0), leader.connection_point);
+                assert_eq!(Vector::x_axis(), leader.direction);
+                assert!(approx_eq!(f64, 0.36, leader.landing_distance));
+                assert_eq!(1, leader.lines.len());
+                assert_eq!(
+                    vec![Point::new(8.0, 9.0, 0.0), Point::new(10.0, 11.0, 0.0)],
+                    leader.lines[0].vertices
+                );
+                assert_eq!(Handle(0xB2), mleader.__leader_style_handle);
+                assert_eq!(2, mleader.content_type);
+                assert_eq!(Handle(0xC3), mleader.__text_style_handle);
+                assert!(approx_eq!(f64, 2.0, mleader.scale_factor));
+            }
+            _ => panic!("expected an MLEADER"),
+        }
     }
 
     #[test]
-    fn write_3d_polyline() {
+    fn write_mleader() {
         let mut drawing = Drawing::new();
-        let mut poly = Polyline::default();
-        poly.add_vertex(
-            &mut drawing,
-            Vertex {
-                location: Point::new(1.1, 2.1, 3.1),
-                ..Default::default()
-            },
-        );
-        poly.set_is_3d_polyline(true);
-        drawing.add_entity(Entity {
-            common: Default::default(),
-            specific: EntityType::Polyline(poly),
-        });
+        drawing.header.version = AcadVersion::R2010;
+        let mut line = MLeaderLine::default();
+        line.vertices.push(Point::new(1.0, 2.0, 3.0));
+        let mut leader = MLeaderRoot::default();
+        leader.lines.push(line);
+        let mut mleader = MLeader::default();
+        mleader.context_data.leaders.push(leader);
+        drawing.add_entity(Entity::new(EntityType::MLeader(mleader)));
         assert_contains(
             &drawing,
             vec![
-                "  0",
-                "POLYLINE", // polyline
-                "  5",
-                "11",
-                "100",
-                "AcDbEntity",
-                "  8",
-                "0",
-                "100",
-                "AcDb3dPolyline", // 3d = true
-                " 66",
-                "     1",
+                "302",
+                "LEADER{",
+                "290",
+                "1",
+                "291",
+                "1",
                 " 10",
                 "0.0",
                 " 20",
                 "0.0",
                 " 30",
                 "0.0",
-                " 70",
-                "     8", // 3d = true
-                "  0",
-                "VERTEX", // vertex 1
-                "  5",
-                "10",
+                " 11",
+                "1.0",
+                " 21",
+                "0.0",
+                " 31",
+                "0.0",
+                " 90",
+                "        0",
+                " 40",
+                "0.0",
+                "304",
+                "LEADER_LINE{",
+                " 10",
+                "1.0",
+                " 20",
+                "2.0",
+                " 30",
+                "3.0",
+                " 91",
+                "        0",
+                "305",
+                "}",
+                "271",
+                "     0",
+                "303",
+                "}",
+            ]
+            .join("\r\n"),
+        );
+        assert_contains(
+            &drawing,
+            ["273", "     9", "301", "}", "340", "0"].join("\r\n"),
+        );
+    }
+
+    #[test]
+    fn round_trip_mleader() {
+        let mut line = MLeaderLine::default();
+        line.vertices.push(Point::new(1.0, 2.0, 0.0));
+        line.vertices.push(Point::new(3.0, 4.0, 0.0));
+        line.break_point_indices.push(0);
+        line.break_start_points.push(Point::new(1.5, 2.5, 0.0));
+        line.break_end_points.push(Point::new(2.0, 3.0, 0.0));
+        let leader = MLeaderRoot {
+            connection_point: Point::new(5.0, 6.0, 0.0),
+            landing_distance: 0.36,
+            lines: vec![line],
+            ..Default::default()
+        };
+        let context_data = MLeaderContextData {
+            has_block_contents: true,
+            block_content_handle: Handle(0xA1),
+            block_content_position: Point::new(7.0, 8.0, 0.0),
+            leaders: vec![leader],
+            ..Default::default()
+        };
+        let mleader = EntityType::MLeader(MLeader {
+            context_data,
+            content_type: 1,
+            ..Default::default()
+        });
+
+        for &version in &[AcadVersion::R2010, AcadVersion::R2018] {
+            let mut drawing = Drawing::new();
+            drawing.header.version = version;
+            drawing.add_entity(Entity::new(mleader.clone()));
+            let drawing = parse_drawing(&to_test_string(&drawing));
+            let entities = drawing.entities().collect::<Vec<_>>();
+            assert_eq!(1, entities.len());
+            assert_eq!(mleader, entities[0].specific);
+        }
+    }
+
+    #[test]
+    fn read_acad_table() {
+        let ent = read_entity(
+            "ACAD_TABLE",
+            vec![
                 "100",
-                "AcDbEntity",
-                "  8",
-                "0",
+                "AcDbBlockReference",
+                "  2",
+                "*T1",
+                " 10",
+                "1.0",
+                " 20",
+                "2.0",
+                " 30",
+                "0.0",
                 "100",
-                "AcDbVertex",
+                "AcDbTable",
+                "342",
+                "A1",
+                "343",
+                "A2",
+                " 91",
+                "        2",
+                " 92",
+                "        2",
+                "141",
+                "0.5",
+                "141",
+                "0.75",
+                "142",
+                "3.0",
+                "142",
+                "4.0",
+                "171",
+                "     1",
+                "173",
+                "     1",
+                "175",
+                "     2",
+                "176",
+                "     1",
+                "  1",
+                "title",
+                "140",
+                "0.25",
+                "171",
+                "     1",
+                "173",
+                "     1",
+                "175",
+                "     0",
+                "176",
+                "     0",
+                "171",
+                "     1",
+                "301",
+                "CELL_VALUE",
+                " 90",
+                "        4",
+                "302",
+                "from value",
+                "304",
+                "ACVALUE_END",
+                "171",
+                "     2",
+                "340",
+                "B1",
+                "144",
+                "2.0",
+                "179",
+                "     1",
+                "331",
+                "B2",
+                "300",
+                "attribute value",
+            ]
+            .join("\r\n"),
+        );
+        match ent.specific {
+            EntityType::AcadTable(ref table) => {
+                assert_eq!("*T1", table.block_name);
+                assert_eq!(Point::new(1.0, 2.0, 0.0), table.insertion_point);
+                assert_eq!(Handle(0xA1), table.__table_style_handle);
+                assert_eq!(Handle(0xA2), table.__block_record_handle);
+                assert_eq!(vec![0.5, 0.75], table.row_heights);
+                assert_eq!(vec![3.0, 4.0], table.column_widths);
+                assert_eq!(4, table.cells.len());
+
+                let title = table.cell(0, 0).unwrap();
+                assert_eq!(TableCellType::Text, title.cell_type);
+                assert_eq!("title", title.text);
+                assert_eq!(Some(0.25), title.text_height);
+                assert_eq!(None, title.text_style_name);
+
+                assert_eq!("from value", table.cell(1, 0).unwrap().text);
+
+                let block = table.cell(1, 1).unwrap();
+                assert_eq!(TableCellType::Block, block.cell_type);
+                assert_eq!(Handle(0xB1), block.block_handle);
+                assert!(approx_eq!(f64, 2.0, block.block_scale));
+                assert_eq!(
+                    vec![(Handle(0xB2), String::from("attribute value"))],
+                    block.block_attributes
+                );
+
+                assert_eq!(
+                    vec![TableCellRange {
+                        top_row: 0,
+                        left_column: 0,
+                        bottom_row: 0,
+                        right_column: 1,
+                    }],
+                    table.merged_ranges()
+                );
+            }
+            _ => panic!("expected an ACAD_TABLE"),
+        }
+    }
+
+    #[test]
+    fn write_acad_table() {
+        let mut drawing = Drawing::new();
+        drawing.header.version = AcadVersion::R2004;
+        let mut table = AcadTable::default();
+        table.row_heights.push(0.5);
+        table.column_widths.push(3.0);
+        table.cells.push(TableCell::new_text("cell text"));
+        drawing.add_entity(Entity::new(EntityType::AcadTable(table)));
+        assert_contains(
+            &drawing,
+            vec![
+                " 91",
+                "        1",
+                " 92",
+                "        1",
+                " 93",
+                "        0",
+                " 94",
+                "        0",
+                " 95",
+                "        0",
+                " 96",
+                "        0",
+                "141",
+                "0.5",
+                "142",
+                "3.0",
+                "171",
+                "     1",
+                "172",
+                "     0",
+                "173",
+                "     0",
+                "174",
+                "     0",
+                "175",
+                "     1",
+                "176",
+                "     1",
+                " 91",
+                "        0",
+                "178",
+                "     0",
+                "145",
+                "0.0",
+                "  1",
+                "cell text",
+            ]
+            .join("\r\n"),
+        );
+    }
+
+    #[test]
+    fn write_acad_table_is_not_written_before_r2004() {
+        let mut drawing = Drawing::new();
+        drawing.header.version = AcadVersion::R2000;
+        drawing.add_entity(Entity::new(EntityType::AcadTable(AcadTable::default())));
+        assert_not_contains(&drawing, String::from("ACAD_TABLE"));
+    }
+
+    #[test]
+    fn round_trip_acad_table() {
+        let title = TableCell {
+            is_merged: true,
+            merged_column_count: 2,
+            text_style_name: Some(String::from("title-style")),
+            fill_color: Some(Color::from_index(3)),
+            is_top_border_visible: Some(false),
+            left_border_lineweight: Some(25),
+            ..TableCell::new_text("title")
+        };
+        let hidden = TableCell {
+            is_merged: true,
+            merged_column_count: 0,
+            merged_row_count: 0,
+            ..Default::default()
+        };
+        let block = TableCell {
+            block_attributes: vec![(Handle(0xB2), String::from("attribute value"))],
+            ..TableCell::new_block(Handle(0xB1))
+        };
+        let table = EntityType::AcadTable(AcadTable {
+            block_name: String::from("*T1"),
+            row_heights: vec![0.5, 0.75],
+            column_widths: vec![3.0, 4.0],
+            cells: vec![title, hidden, TableCell::new_text("data"), block],
+            ..Default::default()
+        });
+
+        for &version in &[AcadVersion::R2004, AcadVersion::R2018] {
+            let mut drawing = Drawing::new();
+            drawing.header.version = version;
+            drawing.add_entity(Entity::new(table.clone()));
+            let drawing = parse_drawing(&to_test_string(&drawing));
+            let entities = drawing.entities().collect::<Vec<_>>();
+            assert_eq!(1, entities.len());
+            assert_eq!(table, entities[0].specific);
+        }
+    }
+
+    #[test]
+    fn read_viewport() {
+        let ent = read_entity(
+            "VIEWPORT",
+            vec![
                 "100",
-                "AcDb3dPolylineVertex", // 3d = true
+                "AcDbViewport",
                 " 10",
-                "1.1",
+                "1.0",
                 " 20",
-                "2.1",
+                "2.0",
                 " 30",
-                "3.1",
-                " 70",
-                "    32", // 3d = true
+                "0.0",
+                " 40",
+                "3.0",
+                " 41",
+                "4.0",
+                " 68",
+                "     1",
+                " 69",
+                "     2",
+                " 17",
+                "5.0",
+                " 27",
+                "6.0",
+                " 37",
+                "0.0",
+                " 45",
+                "7.0",
+                " 51",
+                "0.5",
+                "331",
+                "A1",
+                "331",
+                "A2",
+                " 90",
+                "    32864",
+                "340",
+                "B1",
+                "110",
+                "8.0",
+                "120",
+                "0.0",
+                "130",
+                "0.0",
             ]
             .join("\r\n"),
         );
+        match ent.specific {
+            EntityType::Viewport(ref vp) => {
+                assert_eq!(Point::new(1.0, 2.0, 0.0), vp.center);
+                assert!(approx_eq!(f64, 3.0, vp.width));
+                assert!(approx_eq!(f64, 4.0, vp.height));
+                assert_eq!(1, vp.status);
+                assert_eq!(2, vp.viewport_id);
+                assert_eq!(Point::new(5.0, 6.0, 0.0), vp.view_target_point);
+                assert!(approx_eq!(f64, 7.0, vp.view_height));
+                assert!(approx_eq!(f64, 0.5, vp.twist_angle));
+                assert_eq!(vec![Handle(0xA1), Handle(0xA2)], vp.__frozen_layers_handle);
+                assert!(vp.get_is_ucs_icon_visible());
+                assert!(vp.get_is_ucs_icon_at_origin());
+                assert!(!vp.get_is_perspective_mode_on());
+                assert_eq!(Handle(0xB1), vp.__clipping_boundary_handle);
+                assert_eq!(Point::new(8.0, 0.0, 0.0), vp.ucs_origin);
+            }
+            _ => panic!("expected a VIEWPORT"),
+        }
     }
 
     #[test]
-    fn read_lw_polyline_with_no_vertices() {
-        let drawing = from_section(
-            "ENTITIES",
-            vec!["0", "LWPOLYLINE", "43", "43.0"].join("\r\n").as_str(),
+    fn write_viewport_r12() {
+        let mut drawing = Drawing::new();
+        drawing.header.version = AcadVersion::R12;
+        drawing.add_entity(Entity::new(EntityType::Viewport(Viewport {
+            width: 3.0,
+            height: 4.0,
+            ..Default::default()
+        })));
+        assert_contains(
+            &drawing,
+            [
+                " 40", "3.0", // width
+                " 41", "4.0", // height
+                " 68", "     1", // status
+                " 69", "     1", // id
+                "  0", "ENDSEC",
+            ]
+            .join("\r\n"),
         );
-        let entities = drawing.entities().collect::<Vec<_>>();
-        assert_eq!(1, entities.len());
-        match entities[0].specific {
-            EntityType::LwPolyline(ref poly) => {
-                assert!(approx_eq!(f64, 43.0, poly.constant_width));
-                assert_eq!(0, poly.vertices.len());
-            }
-            _ => panic!("expected an LWPOLYLINE"),
+    }
+
+    #[test]
+    fn round_trip_viewport() {
+        let mut viewport = Viewport {
+            center: Point::new(1.0, 2.0, 0.0),
+            view_target_point: Point::new(3.0, 4.0, 5.0),
+            view_height: 6.0,
+            twist_angle: 0.25,
+            __frozen_layers_handle: vec![Handle(0xA1), Handle(0xA2)],
+            __clipping_boundary_handle: Handle(0xB1),
+            ucs_origin: Point::new(7.0, 8.0, 9.0),
+            ..Default::default()
+        };
+        viewport.set_is_non_rectangular_clipping_on(true);
+        viewport.set_is_grid_mode_on(true);
+        let viewport = EntityType::Viewport(viewport);
+
+        for &version in &[AcadVersion::R2000, AcadVersion::R2018] {
+            let mut drawing = Drawing::new();
+            drawing.header.version = version;
+            drawing.add_entity(Entity::new(viewport.clone()));
+            let drawing = parse_drawing(&to_test_string(&drawing));
+            let entities = drawing.entities().collect::<Vec<_>>();
+            assert_eq!(1, entities.len());
+            assert_eq!(viewport, entities[0].specific);
         }
     }
 
     #[test]
-    fn read_lw_polyline_with_one_vertex() {
-        let drawing = from_section(
-            "ENTITIES",
+    fn read_mesh() {
+        let ent = read_entity(
+            "MESH",
             vec![
-                "0",
-                "LWPOLYLINE",
-                "43",
-                "43.0",
-                // vertex 1
-                "10",
-                "1.1",
-                "20",
-                "2.1",
-                "40",
-                "40.1",
-                "41",
-                "41.1",
-                "42",
-                "42.1",
-                "91",
-                "91",
+                "100",
+                "AcDbSubDMesh",
+                " 71",
+                "     2",
+                " 72",
+                "     1",
+                " 91",
+                "        3",
+                " 92",
+                "        3",
+                " 10",
+                "0.0",
+                " 20",
+                "0.0",
+                " 30",
+                "0.0",
+                " 10",
+                "1.0",
+                " 20",
+                "0.0",
+                " 30",
+                "0.0",
+                " 10",
+                "0.0",
+                " 20",
+                "1.0",
+                " 30",
+                "0.0",
+                " 93",
+                "        4",
+                " 90",
+                "        3",
+                " 90",
+                "        0",
+                " 90",
+                "        1",
+                " 90",
+                "        2",
+                " 94",
+                "        2",
+                " 90",
+                "        0",
+                " 90",
+                "        1",
+                " 90",
+                "        1",
+                " 90",
+                "        2",
+                " 95",
+                "        2",
+                "140",
+                "0.5",
+                "140",
+                "1.5",
+                " 90",
+                "        1",
+                " 91",
+                "        7",
+                " 92",
+                "        1",
+                " 90",
+                "        0",
+                " 63",
+                "     5",
             ]
-            .join("\r\n")
-            .as_str(),
+            .join("\r\n"),
         );
-        let entities = drawing.entities().collect::<Vec<_>>();
-        assert_eq!(1, entities.len());
-        match entities[0].specific {
-            EntityType::LwPolyline(ref poly) => {
-                assert!(approx_eq!(f64, 43.0, poly.constant_width));
-                assert_eq!(1, poly.vertices.len());
-                // vertex 1
-                assert!(approx_eq!(f64, 1.1, poly.vertices[0].x));
-                assert!(approx_eq!(f64, 2.1, poly.vertices[0].y));
-                assert!(approx_eq!(f64, 40.1, poly.vertices[0].starting_width));
-                assert!(approx_eq!(f64, 41.1, poly.vertices[0].ending_width));
-                assert!(approx_eq!(f64, 42.1, poly.vertices[0].bulge));
-                assert_eq!(91, poly.vertices[0].id);
+        match ent.specific {
+            EntityType::Mesh(ref mesh) => {
+                assert_eq!(2, mesh.version);
+                assert!(mesh.is_blend_crease);
+                assert_eq!(3, mesh.subdivision_level);
+                assert_eq!(
+                    vec![
+                        Point::origin(),
+                        Point::new(1.0, 0.0, 0.0),
+                        Point::new(0.0, 1.0, 0.0),
+                    ],
+                    mesh.vertices
+                );
+                assert_eq!(vec![vec![0, 1, 2]], mesh.faces);
+                assert_eq!(vec![(0, 1), (1, 2)], mesh.edges);
+                assert_eq!(vec![0.5, 1.5], mesh.edge_creases);
+                assert_eq!(1, mesh.overrides.len());
+                assert_eq!(7, mesh.overrides[0].sub_entity_marker);
+                assert_eq!(1, mesh.overrides[0].properties.len());
+                let property = &mesh.overrides[0].properties[0];
+                assert_eq!(MeshPropertyType::Color, property.property_type);
+                assert_eq!(vec![CodePair::new_i16(63, 5)], property.values);
             }
-            _ => panic!("expected an LWPOLYLINE"),
+            _ => panic!("expected a MESH"),
         }
     }
 
     #[test]
-    fn read_lw_polyline_with_multiple_vertices() {
-        let drawing = from_section(
-            "ENTITIES",
+    fn read_mesh_with_overrides_followed_by_x_data() {
+        let ent = read_entity(
+            "MESH",
             vec![
-                "0",
-                "LWPOLYLINE",
-                "43",
-                "43.0",
-                // vertex 1
-                "10",
-                "1.1",
-                "20",
-                "2.1",
-                "40",
-                "40.1",
-                "41",
-                "41.1",
-                "42",
-                "42.1",
-                "91",
-                "91",
-                // vertex 2
-                "10",
-                "1.2",
-                "20",
-                "2.2",
-                "40",
-                "40.2",
-                "41",
-                "41.2",
-                "42",
-                "42.2",
-                "91",
-                "92",
+                "100",
+                "AcDbSubDMesh",
+                " 90",
+                "        1",
+                " 91",
+                "        7",
+                " 92",
+                "        1",
+                " 90",
+                "        0",
+                " 62",
+                "     5",
+                "1001",
+                "MYAPP",
+                "1000",
+                "hello",
             ]
-            .join("\r\n")
-            .as_str(),
+            .join("\r\n"),
         );
-        let entities = drawing.entities().collect::<Vec<_>>();
-        assert_eq!(1, entities.len());
-        match entities[0].specific {
-            EntityType::LwPolyline(ref poly) => {
-                assert!(approx_eq!(f64, 43.0, poly.constant_width));
-                assert_eq!(2, poly.vertices.len());
-                // vertex 1
-                assert!(approx_eq!(f64, 1.1, poly.vertices[0].x));
-                assert!(approx_eq!(f64, 2.1, poly.vertices[0].y));
-                assert!(approx_eq!(f64, 40.1, poly.vertices[0].starting_width));
-                assert!(approx_eq!(f64, 41.1, poly.vertices[0].ending_width));
-                assert!(approx_eq!(f64, 42.1, poly.vertices[0].bulge));
-                assert_eq!(91, poly.vertices[0].id);
-                // vertex 2
-                assert!(approx_eq!(f64, 1.2, poly.vertices[1].x));
-                assert!(approx_eq!(f64, 2.2, poly.vertices[1].y));
-                assert!(approx_eq!(f64, 40.2, poly.vertices[1].starting_width));
-                assert!(approx_eq!(f64, 41.2, poly.vertices[1].ending_width));
-                assert!(approx_eq!(f64, 42.2, poly.vertices[1].bulge));
-                assert_eq!(92, poly.vertices[1].id);
+        match ent.specific {
+            EntityType::Mesh(ref mesh) => {
+                assert_eq!(1, mesh.overrides.len());
+                assert_eq!(
+                    vec![CodePair::new_i16(62, 5)],
+                    mesh.overrides[0].properties[0].values
+                );
             }
-            _ => panic!("expected an LWPOLYLINE"),
+            _ => panic!("expected a MESH"),
         }
+        assert_eq!(1, ent.common.x_data.len());
+        assert_eq!("MYAPP", ent.common.x_data[0].application_name);
+        assert_eq!(
+            vec![XDataItem::Str(String::from("hello"))],
+            ent.common.x_data[0].items
+        );
     }
 
     #[test]
-    fn write_lw_polyline() {
+    fn write_mesh() {
         let mut drawing = Drawing::new();
-        drawing.header.version = AcadVersion::R2013;
-        let mut poly = LwPolyline::default();
-        poly.constant_width = 43.0;
-        poly.vertices.push(LwPolylineVertex {
-            x: 1.1,
-            y: 2.1,
+        drawing.header.version = AcadVersion::R2010;
+        let mesh = Mesh {
+            vertices: vec![
+                Point::origin(),
+                Point::new(1.0, 0.0, 0.0),
+                Point::new(0.0, 1.0, 0.0),
+            ],
+            faces: vec![vec![0, 1, 2]],
             ..Default::default()
-        });
-        poly.vertices.push(LwPolylineVertex {
-            x: 1.2,
-            y: 2.2,
-            starting_width: 40.2,
-            ending_width: 41.2,
-            bulge: 42.2,
-            id: 92,
-        });
-        drawing.add_entity(Entity::new(EntityType::LwPolyline(poly)));
+        };
+        drawing.add_entity(Entity::new(EntityType::Mesh(mesh)));
         assert_contains(
             &drawing,
             vec![
+                " 93",
+                "        4",
+                " 90",
+                "        3",
+                " 90",
+                "        0",
+                " 90",
+                "        1",
+                " 90",
+                "        2",
+                " 94",
+                "        0",
+                " 95",
+                "        0",
+                " 90",
+                "        0",
+            ]
+            .join("\r\n"),
+        );
+    }
+
+    #[test]
+    fn round_trip_mesh() {
+        let mut property = MeshPropertyOverride::new(MeshPropertyType::Material);
+        property.values.push(CodePair::new_str(347, "A1"));
+        let mesh = EntityType::Mesh(Mesh {
+            subdivision_level: 1,
+            vertices: vec![
+                Point::origin(),
+                Point::new(1.0, 0.0, 0.0),
+                Point::new(1.0, 1.0, 0.0),
+                Point::new(0.0, 1.0, 0.0),
+            ],
+            faces: vec![vec![0, 1, 2, 3], vec![0, 2, 3]],
+            edges: vec![(0, 1), (1, 2), (2, 3), (3, 0)],
+            edge_creases: vec![0.0, 1.0, 0.0, 1.0],
+            overrides: vec![MeshSubEntityOverride {
+                sub_entity_marker: 3,
+                properties: vec![property],
+            }],
+            ..Default::default()
+        });
+
+        for &version in &[AcadVersion::R2010, AcadVersion::R2018] {
+            let mut drawing = Drawing::new();
+            drawing.header.version = version;
+            drawing.add_entity(Entity::new(mesh.clone()));
+            let drawing = parse_drawing(&to_test_string(&drawing));
+            let entities = drawing.entities().collect::<Vec<_>>();
+            assert_eq!(1, entities.len());
+            assert_eq!(mesh, entities[0].specific);
+        }
+    }
+
+    #[test]
+    fn read_extruded_surface() {
+        let mut body = vec![
+            "100",
+            "AcDbModelerGeometry",
+            " 70",
+            "     1",
+            "  1",
+            "acis data",
+            "100",
+            "AcDbSurface",
+            " 71",
+            "     2",
+            " 72",
+            "     3",
+            "100",
+            "AcDbExtrudedSurface",
+            " 90",
+            "        7",
+            " 10",
+            "1.0",
+            " 20",
+            "2.0",
+            " 30",
+            "3.0",
+        ];
+        let matrix_values = [
+            "2.0", "0.0", "0.0", "0.0", "0.0", "2.0", "0.0", "0.0", "0.0", "0.0", "2.0", "0.0",
+            "4.0", "5.0", "6.0", "1.0",
+        ];
+        for value in &matrix_values {
+            body.push(" 40");
+            body.push(value);
+        }
+        body.extend_from_slice(&[" 42", "0.5", " 70", "     2", "290", "     1"]);
+        let ent = read_entity("EXTRUDEDSURFACE", body.join("\r\n"));
+        match ent.specific {
+            EntityType::ExtrudedSurface(ref surface) => {
+                assert_eq!(1, surface.format_version_number);
+                assert_eq!(vec![String::from("acis data")], surface.custom_data);
+                assert_eq!(2, surface.u_isoline_count);
+                assert_eq!(3, surface.v_isoline_count);
+                assert_eq!(7, surface.class_id);
+                assert_eq!(Vector::new(1.0, 2.0, 3.0), surface.sweep_vector);
+                let matrix = &surface.extruded_entity_transformation_matrix;
+                assert_eq!(2.0, matrix.m11);
+                assert_eq!(2.0, matrix.m22);
+                assert_eq!(4.0, matrix.m41);
+                assert_eq!(6.0, matrix.m43);
+                assert_eq!(0.5, surface.draft_angle);
+                assert_eq!(
+                    SweepAlignment::TranslateSweepEntityToPath,
+                    surface.sweep_alignment
+                );
+                assert!(surface.is_solid);
+            }
+            _ => panic!("expected an EXTRUDEDSURFACE"),
+        }
+    }
+
+    #[test]
+    fn read_swept_surface_entity_data() {
+        let ent = read_entity(
+            "SWEPTSURFACE",
+            [
                 "100",
-                "AcDbPolyline",
+                "AcDbModelerGeometry",
+                " 70",
+                "     1",
+                "100",
+                "AcDbSurface",
+                " 71",
+                "     0",
+                " 72",
+                "     0",
+                "100",
+                "AcDbSweptSurface",
+                " 90",
+                "        1",
                 " 90",
                 "        2",
-                " 70",
-                "     0",
-                " 43",
-                "43.0",
-                // vertex 1
-                " 10",
-                "1.1",
-                " 20",
-                "2.1",
-                " 91",
-                "        0",
-                // vertex 2
-                " 10",
-                "1.2",
-                " 20",
-                "2.2",
-                " 91",
-                "       92",
-                " 40",
-                "40.2",
-                " 41",
-                "41.2",
-                " 42",
-                "42.2",
+                "310",
+                "AABB",
+                " 90",
+                "        2",
+                " 90",
+                "        3",
+                "310",
+                "CCDDEE",
+                " 48",
+                "2.0",
             ]
             .join("\r\n"),
         );
+        match ent.specific {
+            EntityType::SweptSurface(ref surface) => {
+                assert_eq!(1, surface.sweep_entity_id);
+                assert_eq!(vec![String::from("AABB")], surface.sweep_entity_data);
+                assert_eq!(2, surface.path_entity_id);
+                assert_eq!(vec![String::from("CCDDEE")], surface.path_entity_data);
+                assert_eq!(2.0, surface.scale_factor);
+            }
+            _ => panic!("expected a SWEPTSURFACE"),
+        }
+    }
+
+    #[test]
+    fn write_surfaces_not_written_before_r2007() {
+        let mut drawing = Drawing::new();
+        drawing.header.version = AcadVersion::R2004;
+        drawing.add_entity(Entity::new(EntityType::PlaneSurface(Default::default())));
+        drawing.add_entity(Entity::new(EntityType::LoftedSurface(Default::default())));
+        let contents = to_test_string(&drawing);
+        assert!(!contents.contains("PLANESURFACE"));
+        assert!(!contents.contains("LOFTEDSURFACE"));
+    }
+
+    #[test]
+    fn round_trip_surfaces() {
+        let mut matrix = TransformationMatrix::identity();
+        matrix.m41 = 3.0;
+        matrix.m42 = 4.0;
+        let surfaces = vec![
+            EntityType::ExtrudedSurface(ExtrudedSurface {
+                custom_data: vec![String::from("acis 1"), String::from("acis 2")],
+                u_isoline_count: 4,
+                sweep_vector: Vector::new(0.0, 0.0, 5.0),
+                extruded_entity_transformation_matrix: matrix,
+                twist_angle: 0.25,
+                sweep_alignment: SweepAlignment::AlignSweepEntityToPath,
+                bank: true,
+                twist_reference_vector: Vector::x_axis(),
+                ..Default::default()
+            }),
+            EntityType::LoftedSurface(LoftedSurface {
+                transformation_matrix: matrix,
+                plane_normal_lofting_type: LoftedObjectNormalMode::AllCrossSections,
+                start_draft_angle: 0.5,
+                create_closed_surface: true,
+                no_twist: false,
+                ..Default::default()
+            }),
+            EntityType::NurbSurface(NurbSurface {
+                custom_data2: vec![String::from("more acis")],
+                v_isoline_count: 6,
+                ..Default::default()
+            }),
+            EntityType::PlaneSurface(PlaneSurface {
+                u_isoline_count: 1,
+                ..Default::default()
+            }),
+            EntityType::RevolvedSurface(RevolvedSurface {
+                revolve_entity_id: 9,
+                revolve_entity_data: vec![String::from("0102"), String::from("0304")],
+                axis_point: Point::new(1.0, 2.0, 3.0),
+                axis_vector: Vector::z_axis(),
+                revolve_angle: 3.0,
+                revolved_entity_transformation_matrix: matrix,
+                is_close_to_axis: true,
+                ..Default::default()
+            }),
+            EntityType::SweptSurface(SweptSurface {
+                sweep_entity_id: 1,
+                sweep_entity_data: vec![String::from("0A0B")],
+                path_entity_id: 2,
+                path_entity_data: vec![String::from("0C"), String::from("0D")],
+                path_entity_transformation_matrix: matrix,
+                scale_factor: 2.0,
+                path_transformation_matrix: matrix,
+                ..Default::default()
+            }),
+        ];
+
+        for &version in &[AcadVersion::R2007, AcadVersion::R2018] {
+            let mut drawing = Drawing::new();
+            drawing.header.version = version;
+            for surface in &surfaces {
+                drawing.add_entity(Entity::new(surface.clone()));
+            }
+            let drawing = parse_drawing(&to_test_string(&drawing));
+            let entities = drawing
+                .entities()
+                .map(|e| e.specific.clone())
+                .collect::<Vec<_>>();
+            assert_eq!(surfaces, entities);
+        }
     }
 
     #[test]
@@ -2803,6 +6714,396 @@ mod tests {
         }
     }
 
+    #[test]
+    fn read_aligned_dimension() {
+        let ent = read_entity(
+            "DIMENSION",
+            [
+                "100",
+                "AcDbDimension",
+                " 70",
+                "    33",
+                "100",
+                "AcDbAlignedDimension",
+                " 13",
+                "1.0",
+                " 23",
+                "2.0",
+                " 33",
+                "0.0",
+                " 14",
+                "3.0",
+                " 24",
+                "4.0",
+                " 34",
+                "0.0",
+                " 52",
+                "0.5",
+            ]
+            .join("\r\n"),
+        );
+        match ent.specific {
+            EntityType::AlignedDimension(ref dim) => {
+                assert_eq!(DimensionType::Aligned, dim.dimension_base.dimension_type);
+                assert!(
+                    dim.dimension_base
+                        .is_block_reference_referenced_by_this_block_only
+                );
+                assert_eq!(Point::new(1.0, 2.0, 0.0), dim.definition_point_2);
+                assert_eq!(Point::new(3.0, 4.0, 0.0), dim.definition_point_3);
+                assert_eq!(0.5, dim.extension_line_angle);
+            }
+            _ => panic!("expected an aligned dimension"),
+        }
+    }
+
+    #[test]
+    fn read_rotated_dimension_with_aligned_type_flags() {
+        // the rotated subclass marker wins over the type flags
+        let ent = read_entity(
+            "DIMENSION",
+            [
+                "100",
+                "AcDbDimension",
+                " 70",
+                "     1",
+                "100",
+                "AcDbAlignedDimension",
+                " 50",
+                "45.0",
+                "100",
+                "AcDbRotatedDimension",
+            ]
+            .join("\r\n"),
+        );
+        match ent.specific {
+            EntityType::RotatedDimension(ref dim) => {
+                assert_eq!(
+                    DimensionType::RotatedHorizontalOrVertical,
+                    dim.dimension_base.dimension_type
+                );
+                assert_eq!(45.0, dim.rotation_angle);
+            }
+            _ => panic!("expected a rotated dimension"),
+        }
+    }
+
+    #[test]
+    fn read_angular_two_line_dimension() {
+        let ent = read_entity(
+            "DIMENSION",
+            [
+                "100",
+                "AcDbDimension",
+                " 70",
+                "     2",
+                "100",
+                "AcDb2LineAngularDimension",
+                " 13",
+                "1.0",
+                " 23",
+                "0.0",
+                " 33",
+                "0.0",
+                " 14",
+                "2.0",
+                " 24",
+                "0.0",
+                " 34",
+                "0.0",
+                " 15",
+                "0.0",
+                " 25",
+                "1.0",
+                " 35",
+                "0.0",
+                " 16",
+                "3.0",
+                " 26",
+                "3.0",
+                " 36",
+                "0.0",
+            ]
+            .join("\r\n"),
+        );
+        match ent.specific {
+            EntityType::AngularTwoLineDimension(ref dim) => {
+                assert_eq!(DimensionType::Angular, dim.dimension_base.dimension_type);
+                assert_eq!(Point::new(1.0, 0.0, 0.0), dim.definition_point_2);
+                assert_eq!(Point::new(2.0, 0.0, 0.0), dim.definition_point_3);
+                assert_eq!(Point::new(0.0, 1.0, 0.0), dim.definition_point_4);
+                assert_eq!(Point::new(3.0, 3.0, 0.0), dim.definition_point_5);
+            }
+            _ => panic!("expected an angular two-line dimension"),
+        }
+    }
+
+    #[test]
+    fn read_dimension_without_subclass_markers() {
+        let ent = read_entity(
+            "DIMENSION",
+            [
+                "  2", "*D1", " 70", "     2", " 13", "1.0", " 23", "2.0", " 33", "3.0",
+            ]
+            .join("\r\n"),
+        );
+        match ent.specific {
+            EntityType::AngularTwoLineDimension(ref dim) => {
+                assert_eq!("*D1", dim.dimension_base.block_name);
+                assert_eq!(Point::new(1.0, 2.0, 3.0), dim.definition_point_2);
+            }
+            _ => panic!("expected an angular two-line dimension"),
+        }
+    }
+
+    #[test]
+    fn write_dimension_type_matches_entity() {
+        let mut drawing = Drawing::new();
+        drawing.header.version = AcadVersion::R2000;
+        drawing.add_entity(Entity::new(EntityType::AngularTwoLineDimension(
+            Default::default(),
+        )));
+        assert_contains(
+            &drawing,
+            [
+                " 31", "0.0", // text_mid_point.z
+                " 70", "     2",
+            ]
+            .join("\r\n"),
+        );
+    }
+
+    #[test]
+    fn round_trip_all_dimension_types() {
+        let base = DimensionBase {
+            block_name: String::from("*D1"),
+            definition_point_1: Point::new(1.0, 2.0, 3.0),
+            ..Default::default()
+        };
+        let dimensions = vec![
+            EntityType::RotatedDimension(RotatedDimension {
+                dimension_base: base.clone(),
+                rotation_angle: 30.0,
+                ..Default::default()
+            }),
+            EntityType::AlignedDimension(AlignedDimension {
+                dimension_base: base.clone(),
+                definition_point_2: Point::new(4.0, 5.0, 0.0),
+                extension_line_angle: 15.0,
+                ..Default::default()
+            }),
+            EntityType::AngularTwoLineDimension(AngularTwoLineDimension {
+                dimension_base: base.clone(),
+                definition_point_5: Point::new(6.0, 7.0, 0.0),
+                ..Default::default()
+            }),
+            EntityType::DiameterDimension(DiameterDimension {
+                dimension_base: base.clone(),
+                leader_length: 2.0,
+                ..Default::default()
+            }),
+            EntityType::RadialDimension(RadialDimension {
+                dimension_base: base.clone(),
+                leader_length: 3.0,
+                ..Default::default()
+            }),
+            EntityType::AngularThreePointDimension(AngularThreePointDimension {
+                dimension_base: base.clone(),
+                definition_point_4: Point::new(8.0, 9.0, 0.0),
+                ..Default::default()
+            }),
+            EntityType::OrdinateDimension(OrdinateDimension {
+                dimension_base: base,
+                definition_point_3: Point::new(1.0, 1.0, 0.0),
+                ..Default::default()
+            }),
+        ];
+
+        for &version in &[AcadVersion::R12, AcadVersion::R2000, AcadVersion::R2018] {
+            let mut drawing = Drawing::new();
+            drawing.header.version = version;
+            for dim in &dimensions {
+                drawing.add_entity(Entity::new(dim.clone()));
+            }
+            let drawing = parse_drawing(&to_test_string(&drawing));
+            let entities = drawing.entities().collect::<Vec<_>>();
+            assert_eq!(dimensions.len(), entities.len());
+            for (expected, actual) in dimensions.iter().zip(entities.iter()) {
+                match (expected, &actual.specific) {
+                    (EntityType::RotatedDimension(_), EntityType::RotatedDimension(_))
+                    | (EntityType::AlignedDimension(_), EntityType::AlignedDimension(_))
+                    | (
+                        EntityType::AngularTwoLineDimension(_),
+                        EntityType::AngularTwoLineDimension(_),
+                    )
+                    | (EntityType::DiameterDimension(_), EntityType::DiameterDimension(_))
+                    | (EntityType::RadialDimension(_), EntityType::RadialDimension(_))
+                    | (
+                        EntityType::AngularThreePointDimension(_),
+                        EntityType::AngularThreePointDimension(_),
+                    )
+                    | (EntityType::OrdinateDimension(_), EntityType::OrdinateDimension(_)) => (),
+                    _ => panic!(
+                        "expected {:?} but got {:?} for {:?}",
+                        expected, actual.specific, version
+                    ),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn read_arc_dimension() {
+        let ent = read_entity(
+            "ARC_DIMENSION",
+            [
+                "100",
+                "AcDbDimension",
+                "  3",
+                "arc-style",
+                "100",
+                "AcDbArcDimension",
+                " 13",
+                "1.0",
+                " 23",
+                "0.0",
+                " 33",
+                "0.0",
+                " 14",
+                "0.0",
+                " 24",
+                "1.0",
+                " 34",
+                "0.0",
+                " 15",
+                "0.0",
+                " 25",
+                "0.0",
+                " 35",
+                "0.0",
+                " 40",
+                "0.0",
+                " 41",
+                "1.25",
+                " 70",
+                "     1",
+                " 71",
+                "     1",
+                " 16",
+                "2.0",
+                " 26",
+                "2.0",
+                " 36",
+                "0.0",
+            ]
+            .join("\r\n"),
+        );
+        match ent.specific {
+            EntityType::ArcDimension(ref dim) => {
+                assert_eq!("arc-style", dim.dimension_base.dimension_style_name);
+                assert_eq!(Point::new(1.0, 0.0, 0.0), dim.definition_point_2);
+                assert_eq!(Point::new(0.0, 1.0, 0.0), dim.definition_point_3);
+                assert_eq!(Point::origin(), dim.arc_center_point);
+                assert_eq!(1.25, dim.arc_end_parameter);
+                assert!(dim.is_partial);
+                assert!(dim.has_leader);
+                assert_eq!(Point::new(2.0, 2.0, 0.0), dim.leader_point_1);
+            }
+            _ => panic!("expected an arc dimension"),
+        }
+    }
+
+    #[test]
+    fn read_large_radial_dimension() {
+        let ent = read_entity(
+            "LARGE_RADIAL_DIMENSION",
+            [
+                "100",
+                "AcDbDimension",
+                "100",
+                "AcDbRadialDimensionLarge",
+                " 13",
+                "1.0",
+                " 23",
+                "2.0",
+                " 33",
+                "0.0",
+                " 14",
+                "3.0",
+                " 24",
+                "4.0",
+                " 34",
+                "0.0",
+                " 15",
+                "5.0",
+                " 25",
+                "6.0",
+                " 35",
+                "0.0",
+                " 40",
+                "0.785",
+            ]
+            .join("\r\n"),
+        );
+        match ent.specific {
+            EntityType::LargeRadialDimension(ref dim) => {
+                assert_eq!(Point::new(1.0, 2.0, 0.0), dim.chord_point);
+                assert_eq!(Point::new(3.0, 4.0, 0.0), dim.override_center);
+                assert_eq!(Point::new(5.0, 6.0, 0.0), dim.jog_point);
+                assert_eq!(0.785, dim.jog_angle);
+            }
+            _ => panic!("expected a large radial dimension"),
+        }
+    }
+
+    #[test]
+    fn write_arc_dimension_is_not_written_before_r2004() {
+        let mut drawing = Drawing::new();
+        drawing.header.version = AcadVersion::R2000;
+        drawing.add_entity(Entity::new(EntityType::ArcDimension(Default::default())));
+        drawing.add_entity(Entity::new(EntityType::LargeRadialDimension(
+            Default::default(),
+        )));
+        let contents = to_test_string(&drawing);
+        assert!(!contents.contains("ARC_DIMENSION"));
+        assert!(!contents.contains("LARGE_RADIAL_DIMENSION"));
+    }
+
+    #[test]
+    fn round_trip_arc_and_large_radial_dimensions() {
+        let dimensions = vec![
+            EntityType::ArcDimension(ArcDimension {
+                definition_point_2: Point::new(1.0, 0.0, 0.0),
+                definition_point_3: Point::new(0.0, 1.0, 0.0),
+                arc_start_parameter: 0.25,
+                arc_end_parameter: 1.25,
+                is_partial: true,
+                leader_point_2: Point::new(3.0, 4.0, 0.0),
+                ..Default::default()
+            }),
+            EntityType::LargeRadialDimension(LargeRadialDimension {
+                chord_point: Point::new(1.0, 1.0, 0.0),
+                jog_point: Point::new(2.0, 3.0, 0.0),
+                jog_angle: 0.5,
+                ..Default::default()
+            }),
+        ];
+
+        for &version in &[AcadVersion::R2004, AcadVersion::R2018] {
+            let mut drawing = Drawing::new();
+            drawing.header.version = version;
+            for dim in &dimensions {
+                drawing.add_entity(Entity::new(dim.clone()));
+            }
+            let drawing = parse_drawing(&to_test_string(&drawing));
+            let entities = drawing
+                .entities()
+                .map(|e| e.specific.clone())
+                .collect::<Vec<_>>();
+            assert_eq!(dimensions, entities);
+        }
+    }
+
     #[test]
     fn read_entity_after_unsupported_dimension() {
         let drawing = from_section(
@@ -3131,24 +7432,27 @@ mod tests {
     fn read_all_types() {
         for (type_string, subclass, expected_type, _) in all_types::get_all_entity_types() {
             println!("parsing {}/{}", type_string, subclass);
-            let ent = read_entity(
-                type_string,
-                vec![
-                    "100",
-                    subclass,
-                    "102",
-                    "{IXMILIA", // read extension data
-                    "  1",
-                    "some string",
-                    "102",
-                    "}",
-                    "1001",
-                    "IXMILIA", // read x data
-                    "1040",
-                    "1.1",
-                ]
-                .join("\r\n"),
-            );
+            // aligned and rotated dimensions share a subclass and are told apart by their type flags
+            let type_flags: &[&str] = match expected_type {
+                EntityType::AlignedDimension(_) => &[" 70", "     1"],
+                _ => &[],
+            };
+            let mut body = type_flags.to_vec();
+            body.extend_from_slice(&[
+                "100",
+                subclass,
+                "102",
+                "{IXMILIA", // read extension data
+                "  1",
+                "some string",
+                "102",
+                "}",
+                "1001",
+                "IXMILIA", // read x data
+                "1040",
+                "1.1",
+            ]);
+            let ent = read_entity(type_string, body.join("\r\n"));
 
             // validate specific
             assert_eq!(expected_type, ent.specific);
@@ -3256,4 +7560,50 @@ mod tests {
         assert_eq!("STANDARD", dim_styles[1].name);
         assert_eq!("style name", dim_styles[2].name);
     }
+
+    #[test]
+    fn ocs_coordinates_to_wcs() {
+        // read from a file so the mirrored normal comes from the extrusion codes
+        let arc = read_entity(
+            "ARC",
+            vec![
+                "10", "1.0", "20", "2.0", "30", "3.0", "40", "1.0", "210", "0.0", "220", "0.0",
+                "230", "-1.0",
+            ]
+            .join("\r\n"),
+        );
+        assert_eq!(Some(&Vector::new(0.0, 0.0, -1.0)), arc.ocs_normal());
+        assert_eq!(
+            Point::new(-1.0, 2.0, -3.0),
+            arc.to_wcs(&Point::new(1.0, 2.0, 3.0))
+        );
+        // a counter-clockwise arc in the mirrored OCS is clockwise in WCS
+        assert_eq!(
+            Point::new(-2.0, 2.0, -3.0),
+            arc.to_wcs(&Point::new(2.0, 2.0, 3.0))
+        );
+        assert_eq!(
+            Point::new(1.0, 2.0, 3.0),
+            arc.to_ocs(&Point::new(-1.0, 2.0, -3.0))
+        );
+    }
+
+    #[test]
+    fn wcs_entities_have_no_ocs() {
+        let line = Entity::new(EntityType::Line(Line::default()));
+        assert_eq!(None, line.ocs_normal());
+        assert_eq!(TransformationMatrix::identity(), line.ocs_to_wcs());
+
+        let mut poly = Polyline {
+            normal: Vector::new(0.0, 0.0, -1.0),
+            ..Default::default()
+        };
+        poly.set_is_3d_polyline(true);
+        let poly = Entity::new(EntityType::Polyline(poly));
+        assert_eq!(None, poly.ocs_normal());
+        assert_eq!(
+            Point::new(1.0, 2.0, 3.0),
+            poly.to_wcs(&Point::new(1.0, 2.0, 3.0))
+        );
+    }
 }
@@ -1,30 +1,55 @@
 use std::io::Read;
 
 use crate::code_pair_put_back::CodePairPutBack;
-use crate::drawing::AUTO_REPLACE_HANDLE;
 use crate::entities::*;
-use crate::DxfResult;
+use crate::{DiagnosticAction, DxfError, DxfResult};
 
 use itertools::{put_back, PutBack};
 
 pub(crate) struct EntityIter<'a, T: 'a + Read> {
-    pub iter: &'a mut CodePairPutBack<T>,
+    iter: &'a mut CodePairPutBack<T>,
+    error: Option<DxfError>,
 }
 
 impl<'a, I: 'a + Read> Iterator for EntityIter<'a, I> {
     type Item = Entity;
 
     fn next(&mut self) -> Option<Entity> {
-        match Entity::read(self.iter) {
-            Ok(Some(e)) => Some(e),
-            Ok(None) | Err(_) => None,
+        loop {
+            let position = self.iter.read_position();
+            match Entity::read(self.iter) {
+                Ok(Some(e)) => return Some(e),
+                Ok(None) => return None,
+                Err(e) if self.iter.is_lenient() && e.is_item_error() => {
+                    self.iter.add_diagnostic(&e, DiagnosticAction::SkippedItem);
+                    if self.iter.read_position() == position {
+                        // nothing was consumed; drop the offending pair so we don't try it again
+                        let _ = self.iter.next();
+                    }
+                    if let Err(e) = self.iter.skip_to_next_item() {
+                        self.error = Some(e);
+                        return None;
+                    }
+                }
+                Err(e) => {
+                    self.error = Some(e);
+                    return None;
+                }
+            }
         }
     }
 }
 
 impl<'a, I: 'a + Read> EntityIter<'a, I> {
+    pub(crate) fn new(iter: &'a mut CodePairPutBack<I>) -> Self {
+        EntityIter { iter, error: None }
+    }
     pub(crate) fn read_entities_into_vec(&mut self, entities: &mut Vec<Entity>) -> DxfResult<()> {
-        collect_entities(self, entities)
+        collect_entities(self, entities)?;
+        match self.error.take() {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
     }
 }
 
@@ -32,140 +57,145 @@ pub(crate) fn collect_entities<I>(iter: &mut I, entities: &mut Vec<Entity>) -> D
 where
     I: Iterator<Item = Entity>,
 {
-    fn swallow_seqend<I>(iter: &mut PutBack<I>) -> DxfResult<()>
-    where
-        I: Iterator<Item = Entity>,
-    {
-        match iter.next() {
-            Some(Entity {
-                specific: EntityType::Seqend(_),
-                ..
-            }) => (),
-            Some(ent) => iter.put_back(ent),
-            None => (),
-        }
-
-        Ok(())
-    }
-
-    fn get_mtext<I>(iter: &mut PutBack<I>) -> DxfResult<Option<MText>>
-    where
-        I: Iterator<Item = Entity>,
-    {
-        let m_text = match iter.next() {
-            Some(Entity {
-                specific: EntityType::MText(m),
-                ..
-            }) => Some(m),
-            Some(ent) => {
-                iter.put_back(ent);
-                None
-            }
-            None => None,
-        };
-
-        Ok(m_text)
+    let mut iter = put_back(iter);
+    while let Some(entity) = next_grouped_entity(&mut iter)? {
+        entities.push(entity);
     }
 
-    let mut iter = put_back(iter);
-    loop {
-        match iter.next() {
-            Some(Entity {
-                ref common,
-                specific: EntityType::Attribute(ref att),
-            }) => {
-                let mut att = att.clone(); // 27 fields
-                match get_mtext(&mut iter) {
-                    Ok(Some(m_text)) => att.m_text = m_text,
-                    Ok(None) => (),
-                    Err(e) => return Err(e),
-                }
+    Ok(())
+}
 
-                entities.push(Entity {
-                    common: common.clone(), // 18 fields
-                    specific: EntityType::Attribute(att),
-                });
+/// Reads the next entity, gathering any trailing entities that belong to it, e.g., `VERTEX`es after a
+/// `POLYLINE`.
+pub(crate) fn next_grouped_entity<I>(iter: &mut PutBack<I>) -> DxfResult<Option<Entity>>
+where
+    I: Iterator<Item = Entity>,
+{
+    match iter.next() {
+        Some(Entity {
+            ref common,
+            specific: EntityType::Attribute(ref att),
+        }) => {
+            let mut att = att.clone(); // 27 fields
+            match get_mtext(iter) {
+                Ok(Some(m_text)) => att.m_text = m_text,
+                Ok(None) => (),
+                Err(e) => return Err(e),
             }
-            Some(Entity {
-                ref common,
-                specific: EntityType::AttributeDefinition(ref att),
-            }) => {
-                let mut att = att.clone(); // 27 fields
-                match get_mtext(&mut iter) {
-                    Ok(Some(m_text)) => att.m_text = m_text,
-                    Ok(None) => (),
-                    Err(e) => return Err(e),
-                }
 
-                entities.push(Entity {
-                    common: common.clone(), // 18 fields
-                    specific: EntityType::AttributeDefinition(att),
-                });
+            Ok(Some(Entity {
+                common: common.clone(), // 18 fields
+                specific: EntityType::Attribute(att),
+            }))
+        }
+        Some(Entity {
+            ref common,
+            specific: EntityType::AttributeDefinition(ref att),
+        }) => {
+            let mut att = att.clone(); // 27 fields
+            match get_mtext(iter) {
+                Ok(Some(m_text)) => att.m_text = m_text,
+                Ok(None) => (),
+                Err(e) => return Err(e),
             }
-            Some(Entity {
-                ref common,
-                specific: EntityType::Insert(ref ins),
-            }) if ins.__has_attributes => {
-                let mut ins = ins.clone(); // 12 fields
-                loop {
-                    match iter.next() {
-                        Some(Entity {
-                            specific: EntityType::Attribute(att),
-                            ..
-                        }) => ins
-                            .__attributes_and_handles
-                            .push((att, AUTO_REPLACE_HANDLE)),
-                        Some(ent) => {
-                            // stop gathering on any non-ATTRIBUTE
-                            iter.put_back(ent);
-                            break;
-                        }
-                        None => break,
+
+            Ok(Some(Entity {
+                common: common.clone(), // 18 fields
+                specific: EntityType::AttributeDefinition(att),
+            }))
+        }
+        Some(Entity {
+            ref common,
+            specific: EntityType::Insert(ref ins),
+        }) if ins.__has_attributes => {
+            let mut ins = ins.clone(); // 12 fields
+            loop {
+                match iter.next() {
+                    Some(Entity {
+                        specific: EntityType::Attribute(att),
+                        ..
+                    }) => ins.__attributes_and_handles.push((att, None)),
+                    Some(ent) => {
+                        // stop gathering on any non-ATTRIBUTE
+                        iter.put_back(ent);
+                        break;
                     }
+                    None => break,
                 }
+            }
 
-                swallow_seqend(&mut iter)?;
+            swallow_seqend(iter)?;
 
-                // and finally keep the INSERT
-                entities.push(Entity {
-                    common: common.clone(), // 18 fields
-                    specific: EntityType::Insert(ins),
-                });
-            }
-            Some(Entity {
-                common,
-                specific: EntityType::Polyline(poly),
-            }) => {
-                let mut poly = poly.clone(); // 13 fields
-                loop {
-                    match iter.next() {
-                        Some(Entity {
-                            specific: EntityType::Vertex(vertex),
-                            ..
-                        }) => poly
-                            .__vertices_and_handles
-                            .push((vertex, AUTO_REPLACE_HANDLE)),
-                        Some(ent) => {
-                            // stop gathering on any non-VERTEX
-                            iter.put_back(ent);
-                            break;
-                        }
-                        None => break,
+            // and finally keep the INSERT
+            Ok(Some(Entity {
+                common: common.clone(), // 18 fields
+                specific: EntityType::Insert(ins),
+            }))
+        }
+        Some(Entity {
+            common,
+            specific: EntityType::Polyline(poly),
+        }) => {
+            let mut poly = poly.clone(); // 13 fields
+            loop {
+                match iter.next() {
+                    Some(Entity {
+                        specific: EntityType::Vertex(vertex),
+                        ..
+                    }) => poly.__vertices_and_handles.push((vertex, None)),
+                    Some(ent) => {
+                        // stop gathering on any non-VERTEX
+                        iter.put_back(ent);
+                        break;
                     }
+                    None => break,
                 }
+            }
 
-                swallow_seqend(&mut iter)?;
+            swallow_seqend(iter)?;
 
-                // and finally keep the POLYLINE
-                entities.push(Entity {
-                    common: common.clone(), // 18 fields
-                    specific: EntityType::Polyline(poly),
-                });
-            }
-            Some(entity) => entities.push(entity),
-            None => break,
+            // and finally keep the POLYLINE
+            Ok(Some(Entity {
+                common: common.clone(), // 18 fields
+                specific: EntityType::Polyline(poly),
+            }))
         }
+        Some(entity) => Ok(Some(entity)),
+        None => Ok(None),
+    }
+}
+
+fn swallow_seqend<I>(iter: &mut PutBack<I>) -> DxfResult<()>
+where
+    I: Iterator<Item = Entity>,
+{
+    match iter.next() {
+        Some(Entity {
+            specific: EntityType::Seqend(_),
+            ..
+        }) => (),
+        Some(ent) => iter.put_back(ent),
+        None => (),
     }
 
     Ok(())
 }
+
+fn get_mtext<I>(iter: &mut PutBack<I>) -> DxfResult<Option<MText>>
+where
+    I: Iterator<Item = Entity>,
+{
+    let m_text = match iter.next() {
+        Some(Entity {
+            specific: EntityType::MText(m),
+            ..
+        }) => Some(m),
+        Some(ent) => {
+            iter.put_back(ent);
+            None
+        }
+        None => None,
+    };
+
+    Ok(m_text)
+}
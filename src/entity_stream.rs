@@ -0,0 +1,277 @@
+use std::io::Read;
+
+use encoding_rs::Encoding;
+use itertools::put_back;
+
+use crate::{CodePair, CodePairValue, DxfError, DxfResult, Header};
+
+use crate::code_pair_put_back::CodePairPutBack;
+use crate::code_pair_reader::new_code_pair_iter;
+use crate::entities::*;
+use crate::entity_iter::next_grouped_entity;
+
+/// Reads the entities of the `ENTITIES` section one at a time, without building a `Drawing`.  Only a
+/// single entity is kept in memory at a time, so arbitrarily large files can be processed.
+///
+/// ```
+/// # fn main() -> dxf::DxfResult<()> {
+/// # let mut file = std::io::Cursor::new("0\r\nSECTION\r\n2\r\nENTITIES\r\n0\r\nLINE\r\n0\r\nENDSEC\r\n0\r\nEOF\r\n");
+/// let stream = dxf::EntityStream::new(&mut file)?;
+/// for entity in stream {
+///     if let dxf::entities::EntityType::Line(ref line) = entity?.specific {
+///         println!("line from {:?}", line.p1);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct EntityStream<T: Read> {
+    entities: RawEntityIter<T>,
+    pending: Option<Entity>,
+}
+
+// public implementation
+impl<T: Read> EntityStream<T> {
+    /// Creates an `EntityStream` from anything that implements the `Read` trait.
+    pub fn new(reader: T) -> DxfResult<Self> {
+        EntityStream::new_internal(reader, encoding_rs::WINDOWS_1252, true)
+    }
+    /// Creates an `EntityStream` from anything that implements the `Read` trait using the specified
+    /// text encoding.  `$DWGCODEPAGE` is ignored.
+    pub fn new_with_encoding(reader: T, encoding: &'static Encoding) -> DxfResult<Self> {
+        EntityStream::new_internal(reader, encoding, false)
+    }
+    /// The drawing's header.  Only populated once the first entity has been requested.
+    pub fn header(&self) -> &Header {
+        &self.entities.header
+    }
+}
+
+impl<T: Read> EntityStream<T> {
+    fn new_internal(
+        reader: T,
+        encoding: &'static Encoding,
+        use_code_page: bool,
+    ) -> DxfResult<Self> {
+        let mut iter = new_code_pair_iter(reader, encoding)?;
+        iter.set_use_code_page(use_code_page);
+        Ok(EntityStream {
+            entities: RawEntityIter {
+                iter: CodePairPutBack::from_code_pair_iter(iter),
+                header: Header::default(),
+                state: StreamState::FindingEntities,
+                error: None,
+            },
+            pending: None,
+        })
+    }
+}
+
+impl<T: Read> Iterator for EntityStream<T> {
+    type Item = DxfResult<Entity>;
+
+    fn next(&mut self) -> Option<DxfResult<Entity>> {
+        let mut iter = put_back(&mut self.entities);
+        if let Some(entity) = self.pending.take() {
+            iter.put_back(entity);
+        }
+
+        let result = next_grouped_entity(&mut iter);
+        self.pending = iter.into_parts().0;
+        if let Some(e) = self.entities.error.take() {
+            return Some(Err(e));
+        }
+
+        match result {
+            Ok(Some(entity)) => Some(Ok(entity)),
+            Ok(None) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+#[derive(PartialEq)]
+enum StreamState {
+    FindingEntities,
+    ReadingEntities,
+    Done,
+}
+
+struct RawEntityIter<T: Read> {
+    iter: CodePairPutBack<T>,
+    header: Header,
+    state: StreamState,
+    error: Option<DxfError>,
+}
+
+impl<T: Read> RawEntityIter<T> {
+    fn find_entities_section(&mut self) -> DxfResult<()> {
+        loop {
+            match self.iter.next() {
+                Some(Ok(CodePair {
+                    code: 0,
+                    value: CodePairValue::Str(ref s),
+                    ..
+                })) if s == "SECTION" => match self.iter.next() {
+                    Some(Ok(CodePair {
+                        code: 2,
+                        value: CodePairValue::Str(ref s),
+                        ..
+                    })) => match &**s {
+                        "HEADER" => self.header = Header::read(&mut self.iter)?,
+                        "ENTITIES" => {
+                            self.state = StreamState::ReadingEntities;
+                            return Ok(());
+                        }
+                        _ => self.skip_section()?,
+                    },
+                    Some(Ok(pair)) => {
+                        return Err(DxfError::UnexpectedCodePair(
+                            pair,
+                            String::from("expected 2/<section-name>"),
+                        ))
+                    }
+                    Some(Err(e)) => return Err(e),
+                    None => return Err(DxfError::UnexpectedEndOfInput),
+                },
+                Some(Ok(_)) => (), // e.g., 0/ENDSEC
+                Some(Err(e)) => return Err(e),
+                None => {
+                    // no entities section
+                    self.state = StreamState::Done;
+                    return Ok(());
+                }
+            }
+        }
+    }
+    fn skip_section(&mut self) -> DxfResult<()> {
+        loop {
+            match self.iter.next() {
+                Some(Ok(CodePair {
+                    code: 0,
+                    value: CodePairValue::Str(ref s),
+                    ..
+                })) if s == "ENDSEC" => return Ok(()),
+                Some(Ok(_)) => (),
+                Some(Err(e)) => return Err(e),
+                None => return Ok(()),
+            }
+        }
+    }
+}
+
+impl<T: Read> Iterator for RawEntityIter<T> {
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Entity> {
+        if self.state == StreamState::FindingEntities {
+            if let Err(e) = self.find_entities_section() {
+                self.error = Some(e);
+                self.state = StreamState::Done;
+            }
+        }
+
+        if self.state != StreamState::ReadingEntities {
+            return None;
+        }
+
+        match Entity::read(&mut self.iter) {
+            Ok(Some(entity)) => Some(entity),
+            Ok(None) => {
+                self.state = StreamState::Done;
+                None
+            }
+            Err(e) => {
+                self.error = Some(e);
+                self.state = StreamState::Done;
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::*;
+    use crate::enums::*;
+    use crate::*;
+
+    fn stream_entities(text: &str) -> Vec<DxfResult<Entity>> {
+        let mut bytes = text.as_bytes();
+        EntityStream::new(&mut bytes).unwrap().collect()
+    }
+
+    #[test]
+    fn stream_entities_after_other_sections() {
+        let text = [
+            "0", "SECTION", "2", "HEADER", "9", "$ACADVER", "1", "AC1015", "0", "ENDSEC", "0",
+            "SECTION", "2", "TABLES", "0", "TABLE", "2", "LAYER", "0", "ENDTAB", "0", "ENDSEC",
+            "0", "SECTION", "2", "ENTITIES", "0", "LINE", "0", "CIRCLE", "0", "ENDSEC", "0",
+            "SECTION", "2", "OBJECTS", "0", "ENDSEC", "0", "EOF",
+        ]
+        .join("\r\n");
+        let mut bytes = text.as_bytes();
+        let mut stream = EntityStream::new(&mut bytes).unwrap();
+        match stream.next().unwrap().unwrap().specific {
+            EntityType::Line(_) => (),
+            _ => panic!("expected a line"),
+        }
+        assert_eq!(AcadVersion::R2000, stream.header().version);
+        match stream.next().unwrap().unwrap().specific {
+            EntityType::Circle(_) => (),
+            _ => panic!("expected a circle"),
+        }
+        assert!(stream.next().is_none());
+    }
+
+    #[test]
+    fn stream_polyline_with_vertices() {
+        let entities = stream_entities(
+            &[
+                "0", "SECTION", "2", "ENTITIES", "0", "POLYLINE", "0", "VERTEX", "10", "1.0", "0",
+                "VERTEX", "10", "2.0", "0", "SEQEND", "0", "LINE", "0", "ENDSEC", "0", "EOF",
+            ]
+            .join("\r\n"),
+        );
+        assert_eq!(2, entities.len());
+        match entities[0].as_ref().unwrap().specific {
+            EntityType::Polyline(ref poly) => {
+                let vertices = poly.vertices().collect::<Vec<_>>();
+                assert_eq!(2, vertices.len());
+                assert_eq!(Point::new(2.0, 0.0, 0.0), vertices[1].location);
+            }
+            _ => panic!("expected a polyline"),
+        }
+        match entities[1].as_ref().unwrap().specific {
+            EntityType::Line(_) => (),
+            _ => panic!("expected a line"),
+        }
+    }
+
+    #[test]
+    fn stream_without_entities_section() {
+        let entities = stream_entities(
+            &["0", "SECTION", "2", "OBJECTS", "0", "ENDSEC", "0", "EOF"].join("\r\n"),
+        );
+        assert!(entities.is_empty());
+    }
+
+    #[test]
+    fn stream_reports_errors() {
+        let entities = stream_entities(
+            &[
+                "0",
+                "SECTION",
+                "2",
+                "ENTITIES",
+                "0",
+                "LINE",
+                "10",
+                "not-a-number",
+            ]
+            .join("\r\n"),
+        );
+        assert_eq!(1, entities.len());
+        assert!(entities[0].is_err());
+    }
+}
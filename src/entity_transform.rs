@@ -0,0 +1,1058 @@
+use std::f64::consts::PI;
+
+use crate::entities::*;
+use crate::{HatchBoundaryPathEdge, Point, TransformationMatrix, Vector};
+
+// transforms the shared geometry of `Text`, `Attribute`, and `AttributeDefinition` and returns the
+// `PlaneTransform` that was used
+macro_rules! transform_text {
+    ($text: expr, $matrix: expr) => {{
+        let plane = PlaneTransform::new($matrix, &$text.normal);
+        $text.location = plane.point(&$text.location);
+        $text.second_alignment_point = plane.point(&$text.second_alignment_point);
+        let (rotation, x_scale, y_scale) = plane.axes($text.rotation);
+        $text.rotation = rotation;
+        $text.text_height *= y_scale.abs();
+        if y_scale != 0.0 {
+            $text.relative_x_scale_factor *= x_scale / y_scale.abs();
+        }
+
+        if plane.is_mirrored() {
+            // mirroring across the baseline keeps the text running in the same direction
+            let upside_down = $text.get_is_text_upside_down();
+            $text.set_is_text_upside_down(!upside_down);
+        }
+
+        $text.thickness *= plane.thickness_scale;
+        $text.normal = plane.normal.clone();
+        plane
+    }};
+}
+
+macro_rules! transform_underlay {
+    ($underlay: expr, $matrix: expr) => {{
+        let plane = PlaneTransform::new($matrix, &$underlay.normal);
+        $underlay.insertion_point = $matrix.transform_point(&$underlay.insertion_point);
+        let (rotation, x_scale, y_scale) = plane.axes($underlay.rotation_angle);
+        $underlay.rotation_angle = rotation;
+        $underlay.x_scale *= x_scale;
+        $underlay.y_scale *= y_scale;
+        $underlay.z_scale *= plane.thickness_scale;
+        $underlay.normal = plane.normal;
+    }};
+}
+
+//------------------------------------------------------------------------------
+//                                                                        Entity
+//------------------------------------------------------------------------------
+impl Entity {
+    /// Applies `matrix` to the geometry of the entity.
+    ///
+    /// Points, direction vectors, and normals are transformed as appropriate for each entity type;
+    /// entities defined in an Object Coordinate System (OCS) are moved to the OCS of the
+    /// transformed plane.  Radii, text heights, and widths are scaled, and mirroring flips
+    /// `LwPolyline` bulges and arc directions, turns text upside down in its own plane, and
+    /// negates the Y scale of inserts.  Under a non-uniform scale, arcs and circles become
+    /// ellipses while other curved geometry, e.g., polyline bulges, is only approximated.
+    ///
+    /// ACIS-based entities, proxies, and `MLeader` context data are left unchanged and the
+    /// anonymous blocks of dimensions aren't regenerated.
+    ///
+    /// ```
+    /// # use dxf::*;
+    /// # use dxf::entities::*;
+    /// let mut line = Entity::new(EntityType::Line(Line::new(Point::origin(), Point::new(1.0, 0.0, 0.0))));
+    /// line.transform(&TransformationMatrix::rotation_about_z(90.0));
+    /// match line.specific {
+    ///     EntityType::Line(ref line) => assert!(line.p2.distance_to(&Point::new(0.0, 1.0, 0.0)) < 1e-12),
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn transform(&mut self, matrix: &TransformationMatrix) {
+        let m = matrix;
+        let mut replacement = None;
+        match self.specific {
+            EntityType::Arc(ref mut arc) => {
+                let plane = PlaneTransform::new(m, &arc.normal);
+                if plane.is_uniform() {
+                    arc.center = plane.point(&arc.center);
+                    arc.radius *= plane.scale();
+                    let (start, end) = plane.arc_angles(arc.start_angle, arc.end_angle);
+                    arc.start_angle = start;
+                    arc.end_angle = end;
+                    arc.thickness *= plane.thickness_scale;
+                    arc.normal = plane.normal;
+                } else {
+                    replacement = Some(EntityType::Ellipse(ellipse_from_arc(
+                        m,
+                        &arc.normal,
+                        &arc.center,
+                        arc.radius,
+                        arc.start_angle.to_radians(),
+                        arc.end_angle.to_radians(),
+                    )));
+                }
+            }
+            EntityType::ArcAlignedText(ref mut text) => {
+                let plane = PlaneTransform::new(m, &text.extrusion_direction);
+                let scale = plane.scale();
+                text.center_point = plane.point(&text.center_point);
+                text.arc_radius *= scale;
+                text.text_height *= scale;
+                text.character_spacing *= scale;
+                text.offset_from_arc *= scale;
+                text.right_offset *= scale;
+                text.left_offset *= scale;
+                let (start, end) = plane.arc_angles(text.start_angle, text.end_angle);
+                text.start_angle = start;
+                text.end_angle = end;
+                text.extrusion_direction = plane.normal;
+            }
+            EntityType::Attribute(ref mut att) => {
+                let plane = transform_text!(att, m);
+                att.alignment_point = plane.point(&att.alignment_point);
+                transform_m_text(&mut att.m_text, m);
+            }
+            EntityType::AttributeDefinition(ref mut att) => {
+                let plane = transform_text!(att, m);
+                att.alignment_point = plane.point(&att.alignment_point);
+                transform_m_text(&mut att.m_text, m);
+            }
+            EntityType::Circle(ref mut circle) => {
+                let plane = PlaneTransform::new(m, &circle.normal);
+                if plane.is_uniform() {
+                    circle.center = plane.point(&circle.center);
+                    circle.radius *= plane.scale();
+                    circle.thickness *= plane.thickness_scale;
+                    circle.normal = plane.normal;
+                } else {
+                    replacement = Some(EntityType::Ellipse(ellipse_from_arc(
+                        m,
+                        &circle.normal,
+                        &circle.center,
+                        circle.radius,
+                        0.0,
+                        2.0 * PI,
+                    )));
+                }
+            }
+            EntityType::Ellipse(ref mut el) => {
+                let minor_axis = el.normal.normalize().cross(&el.major_axis) * el.minor_axis_ratio;
+                *el = ellipse_from_conjugates(
+                    m.transform_point(&el.center),
+                    &m.transform_vector(&el.major_axis),
+                    &m.transform_vector(&minor_axis),
+                    el.start_parameter,
+                    el.end_parameter,
+                );
+            }
+            EntityType::Face3D(ref mut face) => {
+                face.first_corner = m.transform_point(&face.first_corner);
+                face.second_corner = m.transform_point(&face.second_corner);
+                face.third_corner = m.transform_point(&face.third_corner);
+                face.fourth_corner = m.transform_point(&face.fourth_corner);
+            }
+            EntityType::Hatch(ref mut hatch) => transform_hatch(hatch, m),
+            EntityType::Helix(ref mut helix) => {
+                let axis_length = helix.axis_vector.length();
+                helix.axis_base_point = m.transform_point(&helix.axis_base_point);
+                helix.start_point = m.transform_point(&helix.start_point);
+                helix.axis_vector = m.transform_vector(&helix.axis_vector);
+                if axis_length != 0.0 {
+                    helix.turn_height *= helix.axis_vector.length() / axis_length;
+                }
+
+                helix.radius *= uniform_scale(m);
+                if m.determinant() < 0.0 {
+                    helix.is_right_handed = !helix.is_right_handed;
+                }
+            }
+            EntityType::Image(ref mut image) => {
+                image.location = m.transform_point(&image.location);
+                image.u_vector = m.transform_vector(&image.u_vector);
+                image.v_vector = m.transform_vector(&image.v_vector);
+            }
+            EntityType::Insert(ref mut ins) => {
+                let plane = PlaneTransform::new(m, &ins.extrusion_direction);
+                ins.location = plane.point(&ins.location);
+                let (rotation, x_scale, y_scale) = plane.axes(ins.rotation);
+                ins.rotation = rotation;
+                ins.x_scale_factor *= x_scale;
+                ins.y_scale_factor *= y_scale;
+                ins.z_scale_factor *= plane.thickness_scale;
+                ins.column_spacing *= x_scale;
+                ins.row_spacing *= y_scale;
+                ins.extrusion_direction = plane.normal;
+                for att in ins.attributes_mut() {
+                    let plane = transform_text!(att, m);
+                    att.alignment_point = plane.point(&att.alignment_point);
+                    transform_m_text(&mut att.m_text, m);
+                }
+            }
+            EntityType::Leader(ref mut leader) => {
+                let scale = uniform_scale(m);
+                transform_points(&mut leader.vertices, m);
+                leader.text_annotation_height *= scale;
+                leader.text_annotation_width *= scale;
+                leader.normal = transform_normal(m, &leader.normal);
+                leader.right = m.transform_vector(&leader.right).normalize();
+                leader.block_offset = m.transform_vector(&leader.block_offset);
+                leader.annotation_offset = m.transform_vector(&leader.annotation_offset);
+            }
+            EntityType::Light(ref mut light) => {
+                light.position = m.transform_point(&light.position);
+                light.target_location = m.transform_point(&light.target_location);
+            }
+            EntityType::Line(ref mut line) => {
+                line.p1 = m.transform_point(&line.p1);
+                line.p2 = m.transform_point(&line.p2);
+                line.thickness *= thickness_scale(m, &line.extrusion_direction);
+                line.extrusion_direction = transform_normal(m, &line.extrusion_direction);
+            }
+            EntityType::LwPolyline(ref mut poly) => {
+                let plane = PlaneTransform::new(m, &poly.extrusion_direction);
+                let scale = plane.scale();
+                let elevation = self.common.elevation;
+                for v in poly.vertices.iter_mut() {
+                    let p = plane.point(&Point::new(v.x, v.y, elevation));
+                    v.x = p.x;
+                    v.y = p.y;
+                    v.starting_width *= scale;
+                    v.ending_width *= scale;
+                    if plane.is_mirrored() {
+                        v.bulge = -v.bulge;
+                    }
+                }
+
+                self.common.elevation = plane.point(&Point::new(0.0, 0.0, elevation)).z;
+                poly.constant_width *= scale;
+                poly.thickness *= plane.thickness_scale;
+                poly.extrusion_direction = plane.normal;
+            }
+            EntityType::Mesh(ref mut mesh) => transform_points(&mut mesh.vertices, m),
+            EntityType::MLine(ref mut mline) => {
+                mline.start_point = m.transform_point(&mline.start_point);
+                transform_points(&mut mline.vertices, m);
+                for v in mline
+                    .segment_directions
+                    .iter_mut()
+                    .chain(mline.miter_directions.iter_mut())
+                {
+                    *v = m.transform_vector(v).normalize();
+                }
+
+                mline.scale_factor *= uniform_scale(m);
+                mline.normal = transform_normal(m, &mline.normal);
+            }
+            EntityType::ModelPoint(ref mut point) => {
+                let plane = PlaneTransform::new(m, &point.extrusion_direction);
+                point.location = m.transform_point(&point.location);
+                point.angle = plane.angle(point.angle);
+                point.thickness *= plane.thickness_scale;
+                point.extrusion_direction = plane.normal;
+            }
+            EntityType::MText(ref mut mtext) => transform_m_text(mtext, m),
+            EntityType::Ole2Frame(ref mut frame) => {
+                frame.upper_left_corner = m.transform_point(&frame.upper_left_corner);
+                frame.lower_right_corner = m.transform_point(&frame.lower_right_corner);
+            }
+            EntityType::Polyline(ref mut poly) => {
+                if poly.ocs_normal_is_used() {
+                    let plane = PlaneTransform::new(m, &poly.normal);
+                    let scale = plane.scale();
+                    let elevation = poly.location.z;
+                    for v in poly.vertices_mut() {
+                        let p = plane.point(&Point::new(v.location.x, v.location.y, elevation));
+                        v.location = Point::new(p.x, p.y, v.location.z - elevation + p.z);
+                        v.starting_width *= scale;
+                        v.ending_width *= scale;
+                        if plane.is_mirrored() {
+                            v.bulge = -v.bulge;
+                        }
+                    }
+
+                    poly.location = plane.point(&poly.location);
+                    poly.default_starting_width *= scale;
+                    poly.default_ending_width *= scale;
+                    poly.thickness *= plane.thickness_scale;
+                    poly.normal = plane.normal;
+                } else {
+                    for v in poly.vertices_mut() {
+                        // polyface mesh face records don't have a location
+                        if !v.get_is_polyface_mesh_vertex() || v.get_is_3d_polygon_mesh() {
+                            v.location = m.transform_point(&v.location);
+                        }
+                    }
+                }
+            }
+            EntityType::Ray(ref mut ray) => {
+                ray.start_point = m.transform_point(&ray.start_point);
+                ray.unit_direction_vector =
+                    m.transform_vector(&ray.unit_direction_vector).normalize();
+            }
+            EntityType::RText(ref mut text) => {
+                let plane = PlaneTransform::new(m, &text.extrusion_direction);
+                text.insertion_point = m.transform_point(&text.insertion_point);
+                text.rotation_angle = plane.angle(text.rotation_angle);
+                text.text_height *= plane.scale();
+                text.extrusion_direction = plane.normal;
+            }
+            EntityType::Section(ref mut section) => {
+                let scale = uniform_scale(m);
+                transform_points(&mut section.vertices, m);
+                transform_points(&mut section.back_line_vertices, m);
+                section.vertical_direction =
+                    m.transform_vector(&section.vertical_direction).normalize();
+                section.top_height *= scale;
+                section.bottom_height *= scale;
+            }
+            EntityType::Shape(ref mut shape) => {
+                let plane = PlaneTransform::new(m, &shape.extrusion_direction);
+                shape.location = plane.point(&shape.location);
+                let (rotation, x_scale, y_scale) = plane.axes(shape.rotation_angle);
+                shape.rotation_angle = rotation;
+                shape.size *= y_scale.abs();
+                if y_scale != 0.0 {
+                    shape.relative_x_scale_factor *= x_scale / y_scale.abs();
+                }
+
+                shape.thickness *= plane.thickness_scale;
+                shape.extrusion_direction = plane.normal;
+            }
+            EntityType::Solid(ref mut solid) => {
+                let plane = PlaneTransform::new(m, &solid.extrusion_direction);
+                solid.first_corner = plane.point(&solid.first_corner);
+                solid.second_corner = plane.point(&solid.second_corner);
+                solid.third_corner = plane.point(&solid.third_corner);
+                solid.fourth_corner = plane.point(&solid.fourth_corner);
+                solid.thickness *= plane.thickness_scale;
+                solid.extrusion_direction = plane.normal;
+            }
+            EntityType::Spline(ref mut spline) => {
+                transform_points(&mut spline.control_points, m);
+                transform_points(&mut spline.fit_points, m);
+                // the tangents are stored as points but are directions
+                spline.start_tangent = m
+                    .transform_vector(&spline.start_tangent.to_vector())
+                    .to_point();
+                spline.end_tangent = m
+                    .transform_vector(&spline.end_tangent.to_vector())
+                    .to_point();
+                if !spline.normal.is_zero() {
+                    spline.normal = transform_normal(m, &spline.normal);
+                }
+            }
+            EntityType::AcadTable(ref mut table) => {
+                let scale = uniform_scale(m);
+                table.insertion_point = m.transform_point(&table.insertion_point);
+                table.horizontal_direction =
+                    m.transform_vector(&table.horizontal_direction).normalize();
+                for size in table
+                    .row_heights
+                    .iter_mut()
+                    .chain(table.column_widths.iter_mut())
+                {
+                    *size *= scale;
+                }
+            }
+            EntityType::Text(ref mut text) => {
+                transform_text!(text, m);
+            }
+            EntityType::Tolerance(ref mut tol) => {
+                tol.insertion_point = m.transform_point(&tol.insertion_point);
+                tol.direction_vector = m.transform_vector(&tol.direction_vector);
+                tol.extrusion_direction = transform_normal(m, &tol.extrusion_direction);
+            }
+            EntityType::Trace(ref mut trace) => {
+                let plane = PlaneTransform::new(m, &trace.extrusion_direction);
+                trace.first_corner = plane.point(&trace.first_corner);
+                trace.second_corner = plane.point(&trace.second_corner);
+                trace.third_corner = plane.point(&trace.third_corner);
+                trace.fourth_corner = plane.point(&trace.fourth_corner);
+                trace.thickness *= plane.thickness_scale;
+                trace.extrusion_direction = plane.normal;
+            }
+            EntityType::DgnUnderlay(ref mut u) => transform_underlay!(u, m),
+            EntityType::DwfUnderlay(ref mut u) => transform_underlay!(u, m),
+            EntityType::PdfUnderlay(ref mut u) => transform_underlay!(u, m),
+            EntityType::Viewport(ref mut vp) => {
+                vp.center = m.transform_point(&vp.center);
+                vp.width *= m.transform_vector(&Vector::x_axis()).length();
+                vp.height *= m.transform_vector(&Vector::y_axis()).length();
+            }
+            EntityType::Wipeout(ref mut wipeout) => {
+                wipeout.location = m.transform_point(&wipeout.location);
+                wipeout.u_vector = m.transform_vector(&wipeout.u_vector);
+                wipeout.v_vector = m.transform_vector(&wipeout.v_vector);
+            }
+            EntityType::XLine(ref mut xline) => {
+                xline.first_point = m.transform_point(&xline.first_point);
+                xline.unit_direction_vector =
+                    m.transform_vector(&xline.unit_direction_vector).normalize();
+            }
+            _ => self.transform_dimension(m),
+        }
+
+        if let Some(specific) = replacement {
+            self.specific = specific;
+        }
+    }
+    fn transform_dimension(&mut self, m: &TransformationMatrix) {
+        let plane = match self.dimension_base_mut() {
+            Some(base) => {
+                let plane = PlaneTransform::new(m, &base.normal);
+                base.definition_point_1 = m.transform_point(&base.definition_point_1);
+                base.text_mid_point = plane.point(&base.text_mid_point);
+                base.text_rotation_angle = plane.angle(base.text_rotation_angle);
+                base.horizontal_direction_angle = plane.angle(base.horizontal_direction_angle);
+                base.normal = plane.normal.clone();
+                plane
+            }
+            None => return,
+        };
+        match self.specific {
+            EntityType::RotatedDimension(ref mut d) => {
+                d.insertion_point = plane.point(&d.insertion_point);
+                d.definition_point_2 = m.transform_point(&d.definition_point_2);
+                d.definition_point_3 = m.transform_point(&d.definition_point_3);
+                d.rotation_angle = plane.angle(d.rotation_angle);
+            }
+            EntityType::AlignedDimension(ref mut d) => {
+                d.insertion_point = plane.point(&d.insertion_point);
+                d.definition_point_2 = m.transform_point(&d.definition_point_2);
+                d.definition_point_3 = m.transform_point(&d.definition_point_3);
+            }
+            EntityType::RadialDimension(ref mut d) => {
+                d.definition_point_2 = m.transform_point(&d.definition_point_2);
+                d.leader_length *= plane.scale();
+            }
+            EntityType::DiameterDimension(ref mut d) => {
+                d.definition_point_2 = m.transform_point(&d.definition_point_2);
+                d.leader_length *= plane.scale();
+            }
+            EntityType::AngularTwoLineDimension(ref mut d) => {
+                d.definition_point_2 = m.transform_point(&d.definition_point_2);
+                d.definition_point_3 = m.transform_point(&d.definition_point_3);
+                d.definition_point_4 = m.transform_point(&d.definition_point_4);
+                d.definition_point_5 = m.transform_point(&d.definition_point_5);
+            }
+            EntityType::AngularThreePointDimension(ref mut d) => {
+                d.definition_point_2 = m.transform_point(&d.definition_point_2);
+                d.definition_point_3 = m.transform_point(&d.definition_point_3);
+                d.definition_point_4 = m.transform_point(&d.definition_point_4);
+                d.definition_point_5 = m.transform_point(&d.definition_point_5);
+            }
+            EntityType::OrdinateDimension(ref mut d) => {
+                d.definition_point_2 = m.transform_point(&d.definition_point_2);
+                d.definition_point_3 = m.transform_point(&d.definition_point_3);
+            }
+            EntityType::ArcDimension(ref mut d) => {
+                d.definition_point_2 = m.transform_point(&d.definition_point_2);
+                d.definition_point_3 = m.transform_point(&d.definition_point_3);
+                d.arc_center_point = m.transform_point(&d.arc_center_point);
+                d.leader_point_1 = m.transform_point(&d.leader_point_1);
+                d.leader_point_2 = m.transform_point(&d.leader_point_2);
+            }
+            EntityType::LargeRadialDimension(ref mut d) => {
+                d.chord_point = m.transform_point(&d.chord_point);
+                d.override_center = m.transform_point(&d.override_center);
+                d.jog_point = m.transform_point(&d.jog_point);
+                d.jog_angle = plane.angle(d.jog_angle);
+            }
+            _ => (),
+        }
+    }
+}
+
+// maps the coordinates of a planar entity from its Object Coordinate System (OCS) to the OCS of the
+// transformed plane
+struct PlaneTransform {
+    // from the old OCS to the new OCS
+    matrix: TransformationMatrix,
+    // the transformed normal
+    normal: Vector,
+    // the scale along the normal, used for thickness
+    thickness_scale: f64,
+}
+
+impl PlaneTransform {
+    fn new(matrix: &TransformationMatrix, normal: &Vector) -> Self {
+        let new_normal = transform_normal(matrix, normal);
+        PlaneTransform {
+            matrix: TransformationMatrix::to_ocs(&new_normal)
+                * *matrix
+                * TransformationMatrix::from_ocs(normal),
+            thickness_scale: thickness_scale(matrix, normal),
+            normal: new_normal,
+        }
+    }
+    fn point(&self, point: &Point) -> Point {
+        self.matrix.transform_point(point)
+    }
+    fn vector(&self, vector: &Vector) -> Vector {
+        self.matrix.transform_vector(vector)
+    }
+    fn direction(&self, angle: f64) -> Vector {
+        let angle = angle.to_radians();
+        self.vector(&Vector::new(angle.cos(), angle.sin(), 0.0))
+    }
+    // the in-plane determinant; negative when the plane is mirrored
+    fn determinant(&self) -> f64 {
+        let x = self.vector(&Vector::x_axis());
+        let y = self.vector(&Vector::y_axis());
+        x.x * y.y - x.y * y.x
+    }
+    fn is_mirrored(&self) -> bool {
+        self.determinant() < 0.0
+    }
+    fn is_uniform(&self) -> bool {
+        let x = self.vector(&Vector::x_axis());
+        let y = self.vector(&Vector::y_axis());
+        let (x_length, y_length) = (x.length(), y.length());
+        let tolerance = 1e-9 * x_length.max(y_length);
+        (x_length - y_length).abs() <= tolerance && x.dot(&y).abs() <= tolerance * x_length
+    }
+    fn scale(&self) -> f64 {
+        self.determinant().abs().sqrt()
+    }
+    // the new angle, in degrees, of the direction at `angle` degrees
+    fn angle(&self, angle: f64) -> f64 {
+        let direction = self.direction(angle);
+        normalize_angle(direction.y.atan2(direction.x).to_degrees())
+    }
+    // the new start and end angle, in degrees, of a counter-clockwise arc
+    fn arc_angles(&self, start: f64, end: f64) -> (f64, f64) {
+        if self.is_mirrored() {
+            (self.angle(end), self.angle(start))
+        } else {
+            (self.angle(start), self.angle(end))
+        }
+    }
+    // the new rotation of a local coordinate system rotated by `rotation` degrees, and the scales
+    // along its X and Y axes; the Y scale is negative when the plane is mirrored
+    fn axes(&self, rotation: f64) -> (f64, f64, f64) {
+        let x = self.direction(rotation);
+        let y = self.direction(rotation + 90.0);
+        let x_scale = x.length();
+        let y_scale = if x_scale == 0.0 {
+            0.0
+        } else {
+            (x.x * y.y - x.y * y.x) / x_scale
+        };
+        (self.angle(rotation), x_scale, y_scale)
+    }
+}
+
+fn normalize_angle(angle: f64) -> f64 {
+    let angle = angle.rem_euclid(360.0);
+    // rounding can produce exactly 360
+    if angle >= 360.0 {
+        0.0
+    } else {
+        angle
+    }
+}
+
+// the transformed normal of a plane; this is the normalized inverse transpose of the matrix applied
+// to `normal`, which is also correct for singular matrices
+fn transform_normal(matrix: &TransformationMatrix, normal: &Vector) -> Vector {
+    let ocs = TransformationMatrix::from_ocs(normal);
+    let x = matrix.transform_vector(&ocs.transform_vector(&Vector::x_axis()));
+    let y = matrix.transform_vector(&ocs.transform_vector(&Vector::y_axis()));
+    let result = x.cross(&y).normalize();
+    if result.is_zero() {
+        normal.clone()
+    } else if matrix.determinant() < 0.0 {
+        -result
+    } else {
+        result
+    }
+}
+
+fn thickness_scale(matrix: &TransformationMatrix, normal: &Vector) -> f64 {
+    if normal.is_zero() {
+        return 1.0;
+    }
+
+    matrix
+        .transform_vector(&normal.normalize())
+        .dot(&transform_normal(matrix, normal))
+}
+
+fn uniform_scale(matrix: &TransformationMatrix) -> f64 {
+    matrix.determinant().abs().cbrt()
+}
+
+fn transform_points(points: &mut [Point], matrix: &TransformationMatrix) {
+    for p in points.iter_mut() {
+        *p = matrix.transform_point(p);
+    }
+}
+
+// converts an arc with a center in the OCS defined by `normal` and angles in radians to the
+// ellipse it becomes after the transformation
+fn ellipse_from_arc(
+    matrix: &TransformationMatrix,
+    normal: &Vector,
+    center: &Point,
+    radius: f64,
+    start: f64,
+    end: f64,
+) -> Ellipse {
+    let full = *matrix * TransformationMatrix::from_ocs(normal);
+    ellipse_from_conjugates(
+        full.transform_point(center),
+        &full.transform_vector(&(Vector::x_axis() * radius)),
+        &full.transform_vector(&(Vector::y_axis() * radius)),
+        start,
+        end,
+    )
+}
+
+// converts the curve `center + cos(t) * u + sin(t) * v` for `t` from `start` to `end` radians to an
+// ellipse with perpendicular axes
+fn ellipse_from_conjugates(center: Point, u: &Vector, v: &Vector, start: f64, end: f64) -> Ellipse {
+    let axis_at = |t: f64| u * t.cos() + v * t.sin();
+    let mut t0 = 0.5 * (2.0 * u.dot(v)).atan2(u.dot(u) - v.dot(v));
+    if axis_at(t0 + PI / 2.0).length() > axis_at(t0).length() {
+        t0 += PI / 2.0;
+    }
+
+    let major_axis = axis_at(t0);
+    let minor_length = axis_at(t0 + PI / 2.0).length();
+    let major_length = major_axis.length();
+    let mut sweep = (end - start).rem_euclid(2.0 * PI);
+    if sweep == 0.0 {
+        sweep = 2.0 * PI;
+    }
+
+    let start = (start - t0).rem_euclid(2.0 * PI);
+    Ellipse {
+        center,
+        major_axis,
+        normal: u.cross(v).normalize(),
+        minor_axis_ratio: if major_length == 0.0 {
+            1.0
+        } else {
+            minor_length / major_length
+        },
+        start_parameter: start,
+        end_parameter: start + sweep,
+    }
+}
+
+fn transform_m_text(mtext: &mut MText, matrix: &TransformationMatrix) {
+    let ocs = TransformationMatrix::from_ocs(&mtext.extrusion_direction);
+    let x_axis = if mtext.x_axis_direction.is_zero() {
+        ocs.transform_vector(&Vector::new(
+            mtext.rotation_angle.cos(),
+            mtext.rotation_angle.sin(),
+            0.0,
+        ))
+    } else {
+        mtext.x_axis_direction.normalize()
+    };
+    let new_x_axis = matrix.transform_vector(&x_axis);
+    let scale = new_x_axis.length();
+    let normal = transform_normal(matrix, &mtext.extrusion_direction);
+    let ocs_x_axis = TransformationMatrix::to_ocs(&normal).transform_vector(&new_x_axis);
+
+    mtext.insertion_point = matrix.transform_point(&mtext.insertion_point);
+    mtext.x_axis_direction = new_x_axis.normalize();
+    mtext.rotation_angle = ocs_x_axis.y.atan2(ocs_x_axis.x);
+    mtext.extrusion_direction = normal;
+    mtext.initial_text_height *= scale;
+    mtext.reference_rectangle_width *= scale;
+    mtext.horizontal_width *= scale;
+    mtext.vertical_height *= scale;
+    mtext.column_width *= scale;
+    mtext.column_gutter *= scale;
+    for height in mtext.column_heights.iter_mut() {
+        *height *= scale;
+    }
+}
+
+fn transform_hatch(hatch: &mut Hatch, matrix: &TransformationMatrix) {
+    let plane = PlaneTransform::new(matrix, &hatch.extrusion_direction);
+    let scale = plane.scale();
+    let elevation = hatch.elevation_point.z;
+    // boundary coordinates are 2D and share the hatch's elevation
+    let point_2d = |p: &Point| {
+        let p = plane.point(&Point::new(p.x, p.y, elevation));
+        Point::new(p.x, p.y, 0.0)
+    };
+    let vector_2d = |v: &Vector| {
+        let v = plane.vector(&Vector::new(v.x, v.y, 0.0));
+        Vector::new(v.x, v.y, 0.0)
+    };
+
+    for path in hatch.boundary_paths.iter_mut() {
+        for v in path.polyline_vertices.iter_mut() {
+            let p = point_2d(&Point::new(v.x, v.y, 0.0));
+            v.x = p.x;
+            v.y = p.y;
+            if plane.is_mirrored() {
+                v.bulge = -v.bulge;
+            }
+        }
+
+        for edge in path.edges.iter_mut() {
+            match edge {
+                HatchBoundaryPathEdge::Line { start, end } => {
+                    *start = point_2d(start);
+                    *end = point_2d(end);
+                }
+                HatchBoundaryPathEdge::Arc {
+                    center,
+                    radius,
+                    start_angle,
+                    end_angle,
+                    is_counter_clockwise,
+                } => {
+                    *center = point_2d(center);
+                    *radius *= scale;
+                    *start_angle = plane.angle(*start_angle);
+                    *end_angle = plane.angle(*end_angle);
+                    if plane.is_mirrored() {
+                        *is_counter_clockwise = !*is_counter_clockwise;
+                    }
+                }
+                HatchBoundaryPathEdge::Ellipse {
+                    center,
+                    major_axis,
+                    start_angle,
+                    end_angle,
+                    is_counter_clockwise,
+                    ..
+                } => {
+                    *center = point_2d(center);
+                    *major_axis = vector_2d(major_axis);
+                    if plane.is_mirrored() {
+                        // the parameters run in the opposite direction
+                        *start_angle = normalize_angle(-*start_angle);
+                        *end_angle = normalize_angle(-*end_angle);
+                        *is_counter_clockwise = !*is_counter_clockwise;
+                    }
+                }
+                HatchBoundaryPathEdge::Spline {
+                    control_points,
+                    fit_points,
+                    start_tangent,
+                    end_tangent,
+                    ..
+                } => {
+                    for p in control_points.iter_mut().chain(fit_points.iter_mut()) {
+                        *p = point_2d(p);
+                    }
+
+                    *start_tangent = vector_2d(start_tangent);
+                    *end_tangent = vector_2d(end_tangent);
+                }
+            }
+        }
+    }
+
+    for p in hatch.seed_points.iter_mut() {
+        *p = point_2d(p);
+    }
+
+    for line in hatch.pattern_definition_lines.iter_mut() {
+        line.angle = plane.angle(line.angle);
+        line.base_point = point_2d(&line.base_point);
+        line.offset = vector_2d(&line.offset);
+        for dash in line.dash_lengths.iter_mut() {
+            *dash *= scale;
+        }
+    }
+
+    hatch.pattern_angle = plane.angle(hatch.pattern_angle);
+    hatch.pattern_scale *= scale;
+    hatch.elevation_point = Point::new(0.0, 0.0, plane.point(&hatch.elevation_point).z);
+    hatch.extrusion_direction = plane.normal;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::*;
+    use crate::helper_functions::tests::*;
+    use crate::*;
+
+    fn assert_close(expected: f64, actual: f64) {
+        assert!(
+            (expected - actual).abs() < 1e-9,
+            "expected {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    fn mirror_x() -> TransformationMatrix {
+        TransformationMatrix::mirror(&Vector::x_axis())
+    }
+
+    #[test]
+    fn transform_line() {
+        let m = TransformationMatrix::translation(&Vector::new(1.0, 2.0, 3.0))
+            * TransformationMatrix::uniform_scale(2.0);
+        match transformed(
+            EntityType::Line(Line::new(Point::origin(), Point::new(1.0, 1.0, 0.0))),
+            &m,
+        ) {
+            EntityType::Line(line) => {
+                assert_eq!(Point::new(1.0, 2.0, 3.0), line.p1);
+                assert_eq!(Point::new(3.0, 4.0, 3.0), line.p2);
+                assert_eq!(Vector::z_axis(), line.extrusion_direction);
+            }
+            _ => panic!("expected a line"),
+        }
+    }
+
+    #[test]
+    fn rotate_and_scale_arc() {
+        let m =
+            TransformationMatrix::rotation_about_z(90.0) * TransformationMatrix::uniform_scale(2.0);
+        match transformed(
+            EntityType::Arc(Arc::new(Point::new(1.0, 0.0, 0.0), 1.0, 0.0, 90.0)),
+            &m,
+        ) {
+            EntityType::Arc(arc) => {
+                assert_points_close(Point::new(0.0, 2.0, 0.0), &arc.center);
+                assert_close(2.0, arc.radius);
+                assert_close(90.0, arc.start_angle);
+                assert_close(180.0, arc.end_angle);
+            }
+            _ => panic!("expected an arc"),
+        }
+    }
+
+    #[test]
+    fn mirror_arc_reverses_angles() {
+        match transformed(
+            EntityType::Arc(Arc::new(Point::new(1.0, 0.0, 0.0), 1.0, 0.0, 90.0)),
+            &mirror_x(),
+        ) {
+            EntityType::Arc(arc) => {
+                assert_points_close(Point::new(-1.0, 0.0, 0.0), &arc.center);
+                assert_eq!(Vector::z_axis(), arc.normal);
+                // the arc from (2, 0) to (1, 1) is now counter-clockwise from (-1, 1) to (-2, 0)
+                assert_close(90.0, arc.start_angle);
+                assert_close(180.0, arc.end_angle);
+            }
+            _ => panic!("expected an arc"),
+        }
+    }
+
+    #[test]
+    fn non_uniform_scale_turns_circle_into_ellipse() {
+        let m = TransformationMatrix::scale(2.0, 1.0, 1.0);
+        match transformed(
+            EntityType::Circle(Circle::new(Point::new(1.0, 1.0, 0.0), 1.0)),
+            &m,
+        ) {
+            EntityType::Ellipse(el) => {
+                assert_points_close(Point::new(2.0, 1.0, 0.0), &el.center);
+                assert_close(2.0, el.major_axis.length());
+                assert_close(0.0, el.major_axis.y);
+                assert_close(0.5, el.minor_axis_ratio);
+                assert_close(
+                    2.0 * std::f64::consts::PI,
+                    el.end_parameter - el.start_parameter,
+                );
+            }
+            _ => panic!("expected an ellipse"),
+        }
+    }
+
+    #[test]
+    fn non_uniform_scale_turns_arc_into_elliptical_arc() {
+        let m = TransformationMatrix::scale(1.0, 3.0, 1.0);
+        let arc = Arc::new(Point::origin(), 1.0, 0.0, 90.0);
+        let ellipse = match transformed(EntityType::Arc(arc), &m) {
+            EntityType::Ellipse(el) => el,
+            _ => panic!("expected an ellipse"),
+        };
+        // the end points are still on the scaled arc
+        let minor_axis = ellipse.normal.cross(&ellipse.major_axis) * ellipse.minor_axis_ratio;
+        let point_at =
+            |t: f64| &ellipse.center + &ellipse.major_axis * t.cos() + &minor_axis * t.sin();
+        assert_points_close(
+            Point::new(1.0, 0.0, 0.0),
+            &point_at(ellipse.start_parameter),
+        );
+        assert_points_close(Point::new(0.0, 3.0, 0.0), &point_at(ellipse.end_parameter));
+    }
+
+    #[test]
+    fn mirror_lw_polyline_flips_bulges() {
+        let mut poly = LwPolyline::default();
+        poly.vertices.push(LwPolylineVertex {
+            x: 1.0,
+            y: 0.0,
+            bulge: 0.5,
+            ..Default::default()
+        });
+        poly.vertices.push(LwPolylineVertex {
+            x: 2.0,
+            y: 1.0,
+            ..Default::default()
+        });
+        match transformed(EntityType::LwPolyline(poly), &mirror_x()) {
+            EntityType::LwPolyline(poly) => {
+                assert_eq!(Vector::z_axis(), poly.extrusion_direction);
+                assert_close(-1.0, poly.vertices[0].x);
+                assert_close(-0.5, poly.vertices[0].bulge);
+                assert_close(-2.0, poly.vertices[1].x);
+                assert_close(1.0, poly.vertices[1].y);
+            }
+            _ => panic!("expected an lwpolyline"),
+        }
+    }
+
+    #[test]
+    fn lw_polyline_in_mirrored_ocs() {
+        let mut ent = Entity::new(EntityType::LwPolyline(LwPolyline {
+            extrusion_direction: Vector::new(0.0, 0.0, -1.0),
+            vertices: vec![LwPolylineVertex {
+                x: 1.0,
+                y: 2.0,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }));
+        ent.common.elevation = 3.0;
+        let m = TransformationMatrix::translation(&Vector::new(10.0, 0.0, 1.0));
+        let expected = &ent.to_wcs(&Point::new(1.0, 2.0, 3.0)) + Vector::new(10.0, 0.0, 1.0);
+        ent.transform(&m);
+        let vertex = match ent.specific {
+            EntityType::LwPolyline(ref poly) => &poly.vertices[0],
+            _ => panic!("expected an lwpolyline"),
+        };
+        let actual = ent.to_wcs(&Point::new(vertex.x, vertex.y, ent.common.elevation));
+        assert_points_close(expected, &actual);
+    }
+
+    #[test]
+    fn rotate_and_mirror_text() {
+        let text = Text {
+            location: Point::new(1.0, 0.0, 0.0),
+            text_height: 1.0,
+            rotation: 10.0,
+            ..Default::default()
+        };
+        let m =
+            TransformationMatrix::rotation_about_z(90.0) * TransformationMatrix::uniform_scale(2.0);
+        match transformed(EntityType::Text(text.clone()), &m) {
+            EntityType::Text(text) => {
+                assert_points_close(Point::new(0.0, 2.0, 0.0), &text.location);
+                assert_close(100.0, text.rotation);
+                assert_close(2.0, text.text_height);
+                assert_close(1.0, text.relative_x_scale_factor);
+                assert!(!text.get_is_text_upside_down());
+            }
+            _ => panic!("expected text"),
+        }
+
+        match transformed(EntityType::Text(text), &mirror_x()) {
+            EntityType::Text(text) => {
+                assert_points_close(Point::new(-1.0, 0.0, 0.0), &text.location);
+                assert_close(170.0, text.rotation);
+                assert_close(1.0, text.text_height);
+                assert!(text.get_is_text_upside_down());
+            }
+            _ => panic!("expected text"),
+        }
+    }
+
+    #[test]
+    fn transform_insert() {
+        let insert = Insert {
+            location: Point::new(1.0, 0.0, 0.0),
+            x_scale_factor: 2.0,
+            row_count: 2,
+            row_spacing: 5.0,
+            ..Default::default()
+        };
+        let m =
+            TransformationMatrix::rotation_about_z(90.0) * TransformationMatrix::uniform_scale(3.0);
+        match transformed(EntityType::Insert(insert.clone()), &m) {
+            EntityType::Insert(ins) => {
+                assert_points_close(Point::new(0.0, 3.0, 0.0), &ins.location);
+                assert_close(90.0, ins.rotation);
+                assert_close(6.0, ins.x_scale_factor);
+                assert_close(3.0, ins.y_scale_factor);
+                assert_close(3.0, ins.z_scale_factor);
+                assert_close(15.0, ins.row_spacing);
+            }
+            _ => panic!("expected an insert"),
+        }
+
+        match transformed(EntityType::Insert(insert), &mirror_x()) {
+            EntityType::Insert(ins) => {
+                assert_points_close(Point::new(-1.0, 0.0, 0.0), &ins.location);
+                assert_close(180.0, ins.rotation);
+                assert_close(2.0, ins.x_scale_factor);
+                assert_close(-1.0, ins.y_scale_factor);
+                assert_close(-5.0, ins.row_spacing);
+            }
+            _ => panic!("expected an insert"),
+        }
+    }
+
+    #[test]
+    fn mirrored_insert_places_block_geometry_correctly() {
+        let insert = Insert {
+            location: Point::new(1.0, 2.0, 0.0),
+            rotation: 30.0,
+            ..Default::default()
+        };
+        let m = TransformationMatrix::mirror(&Vector::new(1.0, 1.0, 0.0));
+        let block_point = Point::new(1.0, 3.0, 0.0);
+        let expected = m.transform_point(
+            &insert.block_transformations(&Point::origin())[0].transform_point(&block_point),
+        );
+        let ins = match transformed(EntityType::Insert(insert), &m) {
+            EntityType::Insert(ins) => ins,
+            _ => panic!("expected an insert"),
+        };
+        let actual = ins.block_transformations(&Point::origin())[0].transform_point(&block_point);
+        assert_points_close(expected, &actual);
+    }
+
+    #[test]
+    fn transform_dimension_points() {
+        let mut dim = RotatedDimension::default();
+        dim.dimension_base.definition_point_1 = Point::new(1.0, 1.0, 0.0);
+        dim.definition_point_2 = Point::new(2.0, 0.0, 0.0);
+        dim.definition_point_3 = Point::new(3.0, 0.0, 0.0);
+        let m = TransformationMatrix::translation(&Vector::new(0.0, 10.0, 0.0));
+        match transformed(EntityType::RotatedDimension(dim), &m) {
+            EntityType::RotatedDimension(dim) => {
+                assert_eq!(
+                    Point::new(1.0, 11.0, 0.0),
+                    dim.dimension_base.definition_point_1
+                );
+                assert_eq!(Point::new(2.0, 10.0, 0.0), dim.definition_point_2);
+                assert_eq!(Point::new(3.0, 10.0, 0.0), dim.definition_point_3);
+            }
+            _ => panic!("expected a dimension"),
+        }
+    }
+
+    #[test]
+    fn transform_all_entities() {
+        let mut drawing = Drawing::new();
+        drawing.add_entity(Entity::new(EntityType::ModelPoint(ModelPoint::new(
+            Point::new(1.0, 0.0, 0.0),
+        ))));
+        drawing.add_entity(Entity::new(EntityType::Circle(Circle::new(
+            Point::origin(),
+            1.0,
+        ))));
+        drawing.transform_all(&TransformationMatrix::translation(&Vector::new(
+            5.0, 0.0, 0.0,
+        )));
+        let extents = drawing.compute_extents().unwrap();
+        assert_eq!(Point::new(4.0, -1.0, 0.0), extents.min);
+        assert_eq!(Point::new(6.0, 1.0, 0.0), extents.max);
+    }
+}
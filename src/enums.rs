@@ -485,6 +485,26 @@ pub enum HelixConstraint {
 }
 }
 
+enum_from_primitive! {
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum HatchPatternType {
+    UserDefined = 0,
+    Predefined = 1,
+    Custom = 2,
+}
+}
+
+enum_from_primitive! {
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum HatchStyle {
+    OddParity = 0,
+    OutermostAreaOnly = 1,
+    EntireArea = 2,
+}
+}
+
 enum_from_primitive! {
 #[derive(Clone, Copy, Debug, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
@@ -666,6 +686,17 @@ pub enum MapTilingMethod
 }
 }
 
+enum_from_primitive! {
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum MeshPropertyType {
+    Color = 0,
+    Material = 1,
+    Transparency = 2,
+    MaterialMapper = 3,
+}
+}
+
 enum_from_primitive! {
 #[derive(Clone, Copy, Debug, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
@@ -1089,6 +1120,26 @@ pub enum SubentityType
 }
 }
 
+enum_from_primitive! {
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum SweepAlignment {
+    NoAlignment = 0,
+    AlignSweepEntityToPath = 1,
+    TranslateSweepEntityToPath = 2,
+    TranslatePathToSweepEntity = 3,
+}
+}
+
+enum_from_primitive! {
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum TableCellType {
+    Text = 1,
+    Block = 2,
+}
+}
+
 enum_from_primitive! {
 #[derive(Clone, Copy, Debug, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
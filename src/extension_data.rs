@@ -1,9 +1,10 @@
 use std::io::{Read, Write};
 
-use crate::{CodePair, DxfError, DxfResult};
+use crate::{CodePair, DxfError, DxfResult, Handle};
 
 use crate::code_pair_put_back::CodePairPutBack;
 use crate::code_pair_writer::CodePairWriter;
+use crate::helper_functions::*;
 
 pub(crate) const EXTENSION_DATA_GROUP: i32 = 102;
 
@@ -69,6 +70,43 @@ impl ExtensionGroup {
             items,
         })
     }
+    /// Removes any handle values referring to the specified item, e.g., from `{ACAD_REACTORS`.
+    pub(crate) fn remove_handle(&mut self, handle: Handle) -> bool {
+        let count = self.items.len();
+        self.items.retain(|item| match item {
+            ExtensionGroupItem::CodePair(ref pair) => {
+                !is_handle_code(pair.code) || pair.as_handle().ok() != Some(handle)
+            }
+            ExtensionGroupItem::Group(_) => true,
+        });
+        let mut removed = self.items.len() != count;
+        for item in self.items.iter_mut() {
+            if let ExtensionGroupItem::Group(ref mut group) = item {
+                removed |= group.remove_handle(handle);
+            }
+        }
+
+        removed
+    }
+    /// Collects the handles this group refers to.  `{ACAD_REACTORS` groups only point back to the
+    /// items watching this one and are skipped.
+    pub(crate) fn collect_handles(&self, handles: &mut Vec<Handle>) {
+        if self.application_name == "ACAD_REACTORS" {
+            return;
+        }
+
+        for item in &self.items {
+            match item {
+                ExtensionGroupItem::CodePair(ref pair) if is_handle_code(pair.code) => {
+                    if let Ok(handle) = pair.as_handle() {
+                        handles.push(handle);
+                    }
+                }
+                ExtensionGroupItem::CodePair(_) => (),
+                ExtensionGroupItem::Group(ref group) => group.collect_handles(handles),
+            }
+        }
+    }
     pub(crate) fn write<T>(&self, writer: &mut CodePairWriter<T>) -> DxfResult<()>
     where
         T: Write + ?Sized,
@@ -0,0 +1,91 @@
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::{DxfError, DxfResult};
+
+/// Identifies an item in a `Drawing`.  Handles are written to DXF files as up to 16 hexadecimal
+/// digits; the empty handle, `0`, refers to no item.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Handle(pub u64);
+
+// public implementation
+impl Handle {
+    /// The handle that refers to no item.
+    pub fn empty() -> Self {
+        Handle(0)
+    }
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+    /// The handle formatted as it appears in a DXF file, e.g., `1A2B`.
+    pub fn as_string(&self) -> String {
+        format!("{:X}", self.0)
+    }
+}
+
+// internal visibility only
+impl Handle {
+    pub(crate) fn next_handle(&self) -> Handle {
+        Handle(self.0 + 1)
+    }
+    pub(crate) fn parse(s: &str, offset: usize) -> DxfResult<Handle> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Ok(Handle::empty());
+        }
+
+        match u64::from_str_radix(s, 16) {
+            Ok(h) => Ok(Handle(h)),
+            Err(_) => Err(DxfError::ParseError(offset)),
+        }
+    }
+}
+
+impl Display for Handle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:X}", self.0)
+    }
+}
+
+impl FromStr for Handle {
+    type Err = DxfError;
+    fn from_str(s: &str) -> DxfResult<Handle> {
+        Handle::parse(s, 0)
+    }
+}
+
+impl From<u64> for Handle {
+    fn from(h: u64) -> Self {
+        Handle(h)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn parse_handles() {
+        assert_eq!(Handle(0), "".parse::<Handle>().unwrap());
+        assert_eq!(Handle(0xAB), "ab".parse::<Handle>().unwrap());
+        assert_eq!(
+            Handle(0x1_0000_0000),
+            "100000000".parse::<Handle>().unwrap()
+        );
+        assert_eq!(
+            Handle(0xFEDC_BA98_7654_3210),
+            "FEDCBA9876543210".parse::<Handle>().unwrap()
+        );
+        assert!("not-a-handle".parse::<Handle>().is_err());
+        assert!("1FEDCBA9876543210".parse::<Handle>().is_err());
+    }
+
+    #[test]
+    fn format_handles() {
+        assert_eq!("0", Handle::empty().as_string());
+        assert_eq!("1A2B", Handle(0x1A2B).to_string());
+        assert_eq!("123456789ABC", Handle(0x1234_5678_9ABC).as_string());
+    }
+}
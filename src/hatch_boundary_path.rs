@@ -0,0 +1,468 @@
+use std::io::{Read, Write};
+
+use crate::{CodePair, DxfError, DxfResult, Handle, Point, Vector};
+
+use crate::code_pair_put_back::CodePairPutBack;
+use crate::code_pair_writer::CodePairWriter;
+use crate::enums::*;
+use crate::helper_functions::*;
+
+//------------------------------------------------------------------------------
+//                                                           HatchPolylineVertex
+//------------------------------------------------------------------------------
+/// Represents a single vertex of a polyline `HatchBoundaryPath`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct HatchPolylineVertex {
+    pub x: f64,
+    pub y: f64,
+    pub bulge: f64,
+}
+
+impl HatchPolylineVertex {
+    pub fn new(x: f64, y: f64, bulge: f64) -> Self {
+        HatchPolylineVertex { x, y, bulge }
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                        HatchBoundaryPathEdge
+//------------------------------------------------------------------------------
+/// Represents a single edge of a non-polyline `HatchBoundaryPath`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum HatchBoundaryPathEdge {
+    Line {
+        start: Point,
+        end: Point,
+    },
+    /// Start and end angles are in degrees.
+    Arc {
+        center: Point,
+        radius: f64,
+        start_angle: f64,
+        end_angle: f64,
+        is_counter_clockwise: bool,
+    },
+    /// The major axis is relative to the center.  Start and end angles are in degrees.
+    Ellipse {
+        center: Point,
+        major_axis: Vector,
+        minor_axis_ratio: f64,
+        start_angle: f64,
+        end_angle: f64,
+        is_counter_clockwise: bool,
+    },
+    Spline {
+        degree: i32,
+        is_rational: bool,
+        is_periodic: bool,
+        knot_values: Vec<f64>,
+        control_points: Vec<Point>,
+        weights: Vec<f64>,
+        fit_points: Vec<Point>,
+        start_tangent: Vector,
+        end_tangent: Vector,
+    },
+}
+
+// internal visibility only
+impl HatchBoundaryPathEdge {
+    fn from_edge_type(edge_type: i16) -> Option<HatchBoundaryPathEdge> {
+        match edge_type {
+            1 => Some(HatchBoundaryPathEdge::Line {
+                start: Point::origin(),
+                end: Point::origin(),
+            }),
+            2 => Some(HatchBoundaryPathEdge::Arc {
+                center: Point::origin(),
+                radius: 0.0,
+                start_angle: 0.0,
+                end_angle: 360.0,
+                is_counter_clockwise: true,
+            }),
+            3 => Some(HatchBoundaryPathEdge::Ellipse {
+                center: Point::origin(),
+                major_axis: Vector::x_axis(),
+                minor_axis_ratio: 1.0,
+                start_angle: 0.0,
+                end_angle: 360.0,
+                is_counter_clockwise: true,
+            }),
+            4 => Some(HatchBoundaryPathEdge::Spline {
+                degree: 3,
+                is_rational: false,
+                is_periodic: false,
+                knot_values: vec![],
+                control_points: vec![],
+                weights: vec![],
+                fit_points: vec![],
+                start_tangent: Vector::zero(),
+                end_tangent: Vector::zero(),
+            }),
+            _ => None,
+        }
+    }
+    fn edge_type(&self) -> i16 {
+        match self {
+            HatchBoundaryPathEdge::Line { .. } => 1,
+            HatchBoundaryPathEdge::Arc { .. } => 2,
+            HatchBoundaryPathEdge::Ellipse { .. } => 3,
+            HatchBoundaryPathEdge::Spline { .. } => 4,
+        }
+    }
+    fn apply_code_pair(&mut self, pair: &CodePair) -> DxfResult<bool> {
+        match self {
+            HatchBoundaryPathEdge::Line { start, end } => match pair.code {
+                10 => start.x = pair.assert_f64()?,
+                20 => start.y = pair.assert_f64()?,
+                11 => end.x = pair.assert_f64()?,
+                21 => end.y = pair.assert_f64()?,
+                _ => return Ok(false),
+            },
+            HatchBoundaryPathEdge::Arc {
+                center,
+                radius,
+                start_angle,
+                end_angle,
+                is_counter_clockwise,
+            } => match pair.code {
+                10 => center.x = pair.assert_f64()?,
+                20 => center.y = pair.assert_f64()?,
+                40 => *radius = pair.assert_f64()?,
+                50 => *start_angle = pair.assert_f64()?,
+                51 => *end_angle = pair.assert_f64()?,
+                73 => *is_counter_clockwise = as_bool(pair.assert_i16()?),
+                _ => return Ok(false),
+            },
+            HatchBoundaryPathEdge::Ellipse {
+                center,
+                major_axis,
+                minor_axis_ratio,
+                start_angle,
+                end_angle,
+                is_counter_clockwise,
+            } => match pair.code {
+                10 => center.x = pair.assert_f64()?,
+                20 => center.y = pair.assert_f64()?,
+                11 => major_axis.x = pair.assert_f64()?,
+                21 => major_axis.y = pair.assert_f64()?,
+                40 => *minor_axis_ratio = pair.assert_f64()?,
+                50 => *start_angle = pair.assert_f64()?,
+                51 => *end_angle = pair.assert_f64()?,
+                73 => *is_counter_clockwise = as_bool(pair.assert_i16()?),
+                _ => return Ok(false),
+            },
+            HatchBoundaryPathEdge::Spline {
+                degree,
+                is_rational,
+                is_periodic,
+                knot_values,
+                control_points,
+                weights,
+                fit_points,
+                start_tangent,
+                end_tangent,
+            } => match pair.code {
+                94 => *degree = pair.assert_i32()?,
+                73 => *is_rational = as_bool(pair.assert_i16()?),
+                74 => *is_periodic = as_bool(pair.assert_i16()?),
+                95 => (), // knot count; we just read as many as we're given
+                96 => (), // control point count; we just read as many as we're given
+                40 => knot_values.push(pair.assert_f64()?),
+                10 => control_points.push(Point::new(pair.assert_f64()?, 0.0, 0.0)),
+                20 => vec_last!(control_points).y = pair.assert_f64()?,
+                42 => weights.push(pair.assert_f64()?),
+                11 => fit_points.push(Point::new(pair.assert_f64()?, 0.0, 0.0)),
+                21 => vec_last!(fit_points).y = pair.assert_f64()?,
+                12 => start_tangent.x = pair.assert_f64()?,
+                22 => start_tangent.y = pair.assert_f64()?,
+                13 => end_tangent.x = pair.assert_f64()?,
+                23 => end_tangent.y = pair.assert_f64()?,
+                _ => return Ok(false),
+            },
+        }
+
+        Ok(true)
+    }
+    fn write<T>(&self, version: AcadVersion, writer: &mut CodePairWriter<T>) -> DxfResult<()>
+    where
+        T: Write + ?Sized,
+    {
+        writer.write_code_pair(&CodePair::new_i16(72, self.edge_type()))?;
+        match self {
+            HatchBoundaryPathEdge::Line { start, end } => {
+                writer.write_code_pair(&CodePair::new_f64(10, start.x))?;
+                writer.write_code_pair(&CodePair::new_f64(20, start.y))?;
+                writer.write_code_pair(&CodePair::new_f64(11, end.x))?;
+                writer.write_code_pair(&CodePair::new_f64(21, end.y))?;
+            }
+            HatchBoundaryPathEdge::Arc {
+                center,
+                radius,
+                start_angle,
+                end_angle,
+                is_counter_clockwise,
+            } => {
+                writer.write_code_pair(&CodePair::new_f64(10, center.x))?;
+                writer.write_code_pair(&CodePair::new_f64(20, center.y))?;
+                writer.write_code_pair(&CodePair::new_f64(40, *radius))?;
+                writer.write_code_pair(&CodePair::new_f64(50, *start_angle))?;
+                writer.write_code_pair(&CodePair::new_f64(51, *end_angle))?;
+                writer.write_code_pair(&CodePair::new_i16(73, as_i16(*is_counter_clockwise)))?;
+            }
+            HatchBoundaryPathEdge::Ellipse {
+                center,
+                major_axis,
+                minor_axis_ratio,
+                start_angle,
+                end_angle,
+                is_counter_clockwise,
+            } => {
+                writer.write_code_pair(&CodePair::new_f64(10, center.x))?;
+                writer.write_code_pair(&CodePair::new_f64(20, center.y))?;
+                writer.write_code_pair(&CodePair::new_f64(11, major_axis.x))?;
+                writer.write_code_pair(&CodePair::new_f64(21, major_axis.y))?;
+                writer.write_code_pair(&CodePair::new_f64(40, *minor_axis_ratio))?;
+                writer.write_code_pair(&CodePair::new_f64(50, *start_angle))?;
+                writer.write_code_pair(&CodePair::new_f64(51, *end_angle))?;
+                writer.write_code_pair(&CodePair::new_i16(73, as_i16(*is_counter_clockwise)))?;
+            }
+            HatchBoundaryPathEdge::Spline {
+                degree,
+                is_rational,
+                is_periodic,
+                knot_values,
+                control_points,
+                weights,
+                fit_points,
+                start_tangent,
+                end_tangent,
+            } => {
+                writer.write_code_pair(&CodePair::new_i32(94, *degree))?;
+                writer.write_code_pair(&CodePair::new_i16(73, as_i16(*is_rational)))?;
+                writer.write_code_pair(&CodePair::new_i16(74, as_i16(*is_periodic)))?;
+                writer.write_code_pair(&CodePair::new_i32(95, knot_values.len() as i32))?;
+                writer.write_code_pair(&CodePair::new_i32(96, control_points.len() as i32))?;
+                for knot in knot_values {
+                    writer.write_code_pair(&CodePair::new_f64(40, *knot))?;
+                }
+                for (i, point) in control_points.iter().enumerate() {
+                    writer.write_code_pair(&CodePair::new_f64(10, point.x))?;
+                    writer.write_code_pair(&CodePair::new_f64(20, point.y))?;
+                    if *is_rational && i < weights.len() {
+                        writer.write_code_pair(&CodePair::new_f64(42, weights[i]))?;
+                    }
+                }
+                if version >= AcadVersion::R2010 {
+                    writer.write_code_pair(&CodePair::new_i32(97, fit_points.len() as i32))?;
+                    for point in fit_points {
+                        writer.write_code_pair(&CodePair::new_f64(11, point.x))?;
+                        writer.write_code_pair(&CodePair::new_f64(21, point.y))?;
+                    }
+                    if !fit_points.is_empty() {
+                        writer.write_code_pair(&CodePair::new_f64(12, start_tangent.x))?;
+                        writer.write_code_pair(&CodePair::new_f64(22, start_tangent.y))?;
+                        writer.write_code_pair(&CodePair::new_f64(13, end_tangent.x))?;
+                        writer.write_code_pair(&CodePair::new_f64(23, end_tangent.y))?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                             HatchBoundaryPath
+//------------------------------------------------------------------------------
+/// Represents a single boundary path of a `Hatch`.  Polyline paths store their vertices in
+/// `polyline_vertices`; all other paths store their edges in `edges`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct HatchBoundaryPath {
+    pub flags: i32,
+    pub is_closed: bool,
+    pub polyline_vertices: Vec<HatchPolylineVertex>,
+    pub edges: Vec<HatchBoundaryPathEdge>,
+    pub source_boundary_object_handles: Vec<Handle>,
+}
+
+impl Default for HatchBoundaryPath {
+    fn default() -> Self {
+        HatchBoundaryPath {
+            flags: 0,
+            is_closed: true,
+            polyline_vertices: vec![],
+            edges: vec![],
+            source_boundary_object_handles: vec![],
+        }
+    }
+}
+
+impl HatchBoundaryPath {
+    pub fn get_is_external(&self) -> bool {
+        self.flags & 1 != 0
+    }
+    pub fn set_is_external(&mut self, val: bool) {
+        self.set_flag(1, val);
+    }
+    pub fn get_is_polyline(&self) -> bool {
+        self.flags & 2 != 0
+    }
+    pub fn set_is_polyline(&mut self, val: bool) {
+        self.set_flag(2, val);
+    }
+    pub fn get_is_derived(&self) -> bool {
+        self.flags & 4 != 0
+    }
+    pub fn set_is_derived(&mut self, val: bool) {
+        self.set_flag(4, val);
+    }
+    pub fn get_is_text_box(&self) -> bool {
+        self.flags & 8 != 0
+    }
+    pub fn set_is_text_box(&mut self, val: bool) {
+        self.set_flag(8, val);
+    }
+    pub fn get_is_outermost(&self) -> bool {
+        self.flags & 16 != 0
+    }
+    pub fn set_is_outermost(&mut self, val: bool) {
+        self.set_flag(16, val);
+    }
+    fn set_flag(&mut self, mask: i32, val: bool) {
+        if val {
+            self.flags |= mask;
+        } else {
+            self.flags &= !mask;
+        }
+    }
+}
+
+// internal visibility only
+impl HatchBoundaryPath {
+    pub(crate) fn read<I>(iter: &mut CodePairPutBack<I>) -> DxfResult<Option<HatchBoundaryPath>>
+    where
+        I: Read,
+    {
+        // the first pair must be 92/path type flags or this isn't a boundary path
+        let mut path = HatchBoundaryPath::default();
+        match iter.next() {
+            Some(Ok(pair @ CodePair { code: 92, .. })) => {
+                path.flags = pair.assert_i32()?;
+            }
+            Some(Ok(pair)) => {
+                iter.put_back(Ok(pair));
+                return Ok(None);
+            }
+            Some(Err(e)) => return Err(e),
+            None => return Ok(None),
+        }
+
+        loop {
+            let pair = match iter.next() {
+                Some(Ok(pair @ CodePair { code: 0, .. }))
+                | Some(Ok(pair @ CodePair { code: 92, .. })) => {
+                    // new entity or next boundary path
+                    iter.put_back(Ok(pair));
+                    return Ok(Some(path));
+                }
+                Some(Ok(pair)) => pair,
+                Some(Err(e)) => return Err(e),
+                None => return Ok(Some(path)),
+            };
+
+            let handled = match pair.code {
+                // source boundary object count or spline fit point count; we just read as many as
+                // we're given
+                97 => true,
+                330 => {
+                    path.source_boundary_object_handles.push(pair.as_handle()?);
+                    true
+                }
+                _ if path.get_is_polyline() => path.apply_polyline_code_pair(&pair)?,
+                _ => path.apply_edge_code_pair(&pair)?,
+            };
+            if !handled {
+                // unexpected end; put the pair back and return what we have
+                iter.put_back(Ok(pair));
+                return Ok(Some(path));
+            }
+        }
+    }
+    fn apply_polyline_code_pair(&mut self, pair: &CodePair) -> DxfResult<bool> {
+        match pair.code {
+            72 => (), // has bulge flag; computed when written
+            73 => {
+                self.is_closed = as_bool(pair.assert_i16()?);
+            }
+            93 => (), // vertex count; we just read as many as we're given
+            10 => {
+                self.polyline_vertices
+                    .push(HatchPolylineVertex::new(pair.assert_f64()?, 0.0, 0.0));
+            }
+            20 => {
+                vec_last!(self.polyline_vertices).y = pair.assert_f64()?;
+            }
+            42 => {
+                vec_last!(self.polyline_vertices).bulge = pair.assert_f64()?;
+            }
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+    fn apply_edge_code_pair(&mut self, pair: &CodePair) -> DxfResult<bool> {
+        match pair.code {
+            72 => match HatchBoundaryPathEdge::from_edge_type(pair.assert_i16()?) {
+                Some(edge) => self.edges.push(edge),
+                None => return Ok(false),
+            },
+            93 => (), // edge count; we just read as many as we're given
+            _ => match self.edges.last_mut() {
+                Some(edge) => return edge.apply_code_pair(pair),
+                None => return Ok(false),
+            },
+        }
+
+        Ok(true)
+    }
+    pub(crate) fn write<T>(
+        &self,
+        version: AcadVersion,
+        writer: &mut CodePairWriter<T>,
+    ) -> DxfResult<()>
+    where
+        T: Write + ?Sized,
+    {
+        writer.write_code_pair(&CodePair::new_i32(92, self.flags))?;
+        if self.get_is_polyline() {
+            let has_bulge = self.polyline_vertices.iter().any(|v| v.bulge != 0.0);
+            writer.write_code_pair(&CodePair::new_i16(72, as_i16(has_bulge)))?;
+            writer.write_code_pair(&CodePair::new_i16(73, as_i16(self.is_closed)))?;
+            writer.write_code_pair(&CodePair::new_i32(93, self.polyline_vertices.len() as i32))?;
+            for vertex in &self.polyline_vertices {
+                writer.write_code_pair(&CodePair::new_f64(10, vertex.x))?;
+                writer.write_code_pair(&CodePair::new_f64(20, vertex.y))?;
+                if has_bulge {
+                    writer.write_code_pair(&CodePair::new_f64(42, vertex.bulge))?;
+                }
+            }
+        } else {
+            writer.write_code_pair(&CodePair::new_i32(93, self.edges.len() as i32))?;
+            for edge in &self.edges {
+                edge.write(version, writer)?;
+            }
+        }
+        writer.write_code_pair(&CodePair::new_i32(
+            97,
+            self.source_boundary_object_handles.len() as i32,
+        ))?;
+        for handle in &self.source_boundary_object_handles {
+            writer.write_code_pair(&CodePair::new_string(330, &as_handle(*handle)))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,20 @@
+use crate::Color;
+
+/// Represents a single color of a gradient `Hatch`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct HatchGradientColor {
+    pub value: f64,
+    pub color: Color,
+    pub true_color: i32,
+}
+
+impl Default for HatchGradientColor {
+    fn default() -> Self {
+        HatchGradientColor {
+            value: 0.0,
+            color: Color::by_block(),
+            true_color: 0,
+        }
+    }
+}
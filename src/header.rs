@@ -6,7 +6,8 @@ use crate::code_pair_put_back::CodePairPutBack;
 use crate::code_pair_writer::CodePairWriter;
 use crate::enums::*;
 use crate::helper_functions::*;
-use crate::{CodePair, DxfError, DxfResult};
+use crate::{CodePair, Drawing, DxfError, DxfResult, SectionFilter};
+use encoding_rs::Encoding;
 
 pub use crate::generated::header::*;
 
@@ -21,6 +22,25 @@ impl Header {
         default_if_empty(&mut self.dimension_style_name, "STANDARD");
         default_if_empty(&mut self.file_name, ".");
     }
+    /// Loads only the `HEADER` section of a drawing from anything that implements the `Read` trait.
+    /// Reading stops as soon as the header has been read.
+    pub fn load<T>(reader: &mut T) -> DxfResult<Header>
+    where
+        T: Read + ?Sized,
+    {
+        let drawing = Drawing::load_sections(reader, SectionFilter::header_only())?;
+        Ok(drawing.header)
+    }
+    /// The encoding used for strings: UTF-8 for R2007 and later, otherwise the encoding matching
+    /// `drawing_code_page`, falling back to Windows-1252 if that isn't recognized.
+    pub(crate) fn string_encoding(&self) -> &'static Encoding {
+        if self.version >= AcadVersion::R2007 {
+            encoding_rs::UTF_8
+        } else {
+            get_encoding_from_code_page(&self.drawing_code_page)
+                .unwrap_or(encoding_rs::WINDOWS_1252)
+        }
+    }
     pub(crate) fn read<T>(iter: &mut CodePairPutBack<T>) -> DxfResult<Header>
     where
         T: Read,
@@ -46,10 +66,15 @@ impl Header {
                                         } else {
                                             header
                                                 .set_header_value(&last_header_variable, &pair)?;
-                                            if last_header_variable == "$ACADVER"
-                                                && header.version >= AcadVersion::R2007
-                                            {
-                                                iter.read_as_utf8();
+                                            match &*last_header_variable {
+                                                "$ACADVER"
+                                                    if header.version >= AcadVersion::R2007 =>
+                                                {
+                                                    iter.read_as_utf8()
+                                                }
+                                                "$DWGCODEPAGE" => iter
+                                                    .read_with_code_page(&header.drawing_code_page),
+                                                _ => (),
                                             }
                                         }
                                     }
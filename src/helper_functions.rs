@@ -19,7 +19,7 @@ use enum_primitive::FromPrimitive;
 
 use crate::enums::*;
 use crate::tables::Layer;
-use crate::{CodePair, Color, DxfError, DxfResult};
+use crate::{CodePair, Color, DxfError, DxfResult, Handle};
 
 pub(crate) fn verify_code(pair: &CodePair, expected: i32) -> DxfResult<()> {
     if expected == pair.code {
@@ -115,8 +115,13 @@ pub(crate) fn as_duration(d: f64) -> StdDuration {
     StdDuration::from_secs(d as u64)
 }
 
-pub(crate) fn as_handle(h: u32) -> String {
-    format!("{:X}", h)
+pub(crate) fn as_handle(h: Handle) -> String {
+    h.as_string()
+}
+
+/// Returns `true` if values with the specified code are handles of other items.
+pub(crate) fn is_handle_code(code: i32) -> bool {
+    matches!(code, 320..=369 | 390..=399 | 480..=481 | 1005)
 }
 
 pub(crate) fn as_uuid(s: String, offset: usize) -> DxfResult<Uuid> {
@@ -314,6 +319,46 @@ where
     Some(Ok(result))
 }
 
+/// Returns the text encoding for a `$DWGCODEPAGE` value, e.g., `ANSI_1252` or `DOS932`.
+pub(crate) fn get_encoding_from_code_page(code_page: &str) -> Option<&'static Encoding> {
+    let code_page = code_page.trim().to_uppercase();
+    let encoding = match &*code_page {
+        "ANSI_874" | "DOS874" => encoding_rs::WINDOWS_874,
+        "ANSI_932" | "DOS932" => encoding_rs::SHIFT_JIS,
+        "ANSI_936" | "DOS936" | "GB2312" => encoding_rs::GBK,
+        "ANSI_949" | "DOS949" | "KSC5601" => encoding_rs::EUC_KR,
+        "ANSI_950" | "DOS950" | "BIG5" => encoding_rs::BIG5,
+        "ANSI_1250" => encoding_rs::WINDOWS_1250,
+        "ANSI_1251" => encoding_rs::WINDOWS_1251,
+        "ANSI_1252" | "ISO8859-1" | "ISO8859_1" => encoding_rs::WINDOWS_1252,
+        "ANSI_1253" => encoding_rs::WINDOWS_1253,
+        "ANSI_1254" => encoding_rs::WINDOWS_1254,
+        "ANSI_1255" => encoding_rs::WINDOWS_1255,
+        "ANSI_1256" => encoding_rs::WINDOWS_1256,
+        "ANSI_1257" => encoding_rs::WINDOWS_1257,
+        "ANSI_1258" => encoding_rs::WINDOWS_1258,
+        "DOS866" => encoding_rs::IBM866,
+        "MACINTOSH" => encoding_rs::MACINTOSH,
+        "UTF8" | "UTF-8" => encoding_rs::UTF_8,
+        _ => match code_page
+            .strip_prefix("ISO8859")
+            .map(|n| n.trim_start_matches(&['-', '_'][..]))
+        {
+            Some("2") => encoding_rs::ISO_8859_2,
+            Some("3") => encoding_rs::ISO_8859_3,
+            Some("4") => encoding_rs::ISO_8859_4,
+            Some("5") => encoding_rs::ISO_8859_5,
+            Some("6") => encoding_rs::ISO_8859_6,
+            Some("7") => encoding_rs::ISO_8859_7,
+            Some("8") => encoding_rs::ISO_8859_8,
+            Some("9") => encoding_rs::WINDOWS_1254,
+            Some("10") => encoding_rs::ISO_8859_10,
+            _ => return None,
+        },
+    };
+    Some(encoding)
+}
+
 pub(crate) fn read_u8<T: Read + ?Sized>(reader: &mut T) -> Option<io::Result<u8>> {
     let mut buf = [0];
     let size = match reader.read(&mut buf) {
@@ -326,6 +371,37 @@ pub(crate) fn read_u8<T: Read + ?Sized>(reader: &mut T) -> Option<io::Result<u8>
     }
 }
 
+// implements a binary operator for every owned/borrowed combination of the operands by forwarding
+// to a function that takes both by reference
+macro_rules! impl_binary_op {
+    ($trait: ident, $method: ident, $lhs: ty, $rhs: ty, $output: ty, $fun: path) => {
+        impl<'a, 'b> $trait<&'b $rhs> for &'a $lhs {
+            type Output = $output;
+            fn $method(self, rhs: &'b $rhs) -> $output {
+                $fun(self, rhs)
+            }
+        }
+        impl<'a> $trait<$rhs> for &'a $lhs {
+            type Output = $output;
+            fn $method(self, rhs: $rhs) -> $output {
+                $fun(self, &rhs)
+            }
+        }
+        impl<'b> $trait<&'b $rhs> for $lhs {
+            type Output = $output;
+            fn $method(self, rhs: &'b $rhs) -> $output {
+                $fun(&self, rhs)
+            }
+        }
+        impl $trait<$rhs> for $lhs {
+            type Output = $output;
+            fn $method(self, rhs: $rhs) -> $output {
+                $fun(&self, &rhs)
+            }
+        }
+    };
+}
+
 // safely unwrap an Option<io::Result<T>>
 macro_rules! try_from_option_io_result {
     ($expr : expr) => {
@@ -516,6 +592,7 @@ fn parse_hex_string_test() {
 #[cfg(test)]
 #[allow(dead_code)]
 pub mod tests {
+    use crate::entities::*;
     use crate::*;
     use std::io::{BufRead, BufReader, Cursor, Seek, SeekFrom};
 
@@ -575,4 +652,19 @@ pub mod tests {
         let actual = to_test_string(&drawing);
         assert!(!actual.contains(&contents));
     }
+
+    pub fn assert_points_close(expected: Point, actual: &Point) {
+        assert!(
+            expected.distance_to(actual) < 1e-9,
+            "expected {:?}, got {:?}",
+            expected,
+            actual
+        );
+    }
+
+    pub fn transformed(specific: EntityType, matrix: &TransformationMatrix) -> EntityType {
+        let mut ent = Entity::new(specific);
+        ent.transform(matrix);
+        ent.specific
+    }
 }
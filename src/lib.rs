@@ -154,21 +154,52 @@ mod table;
 mod drawing;
 pub use crate::drawing::Drawing;
 
+mod handle;
+pub use crate::handle::Handle;
+
+mod acds_data;
+pub use crate::acds_data::{AcdsData, AcdsRecord, AcdsSchema};
+
+mod unknown_section;
+pub use crate::unknown_section::{SectionPosition, UnknownSection};
+
 mod drawing_item;
 pub use crate::drawing_item::{DrawingItem, DrawingItemMut};
 
+mod mleader_context_data;
+pub use crate::mleader_context_data::{MLeaderContextData, MLeaderLine, MLeaderRoot};
+
 mod section_geometry_settings;
 pub use crate::section_geometry_settings::SectionGeometrySettings;
 
 mod section_type_settings;
 pub use crate::section_type_settings::SectionTypeSettings;
 
+mod hatch_boundary_path;
+pub use crate::hatch_boundary_path::{
+    HatchBoundaryPath, HatchBoundaryPathEdge, HatchPolylineVertex,
+};
+
+mod hatch_pattern_definition_line;
+pub use crate::hatch_pattern_definition_line::HatchPatternDefinitionLine;
+
+mod hatch_gradient_color;
+pub use crate::hatch_gradient_color::HatchGradientColor;
+
+mod table_cell;
+pub use crate::table_cell::{TableCell, TableCellRange};
+
 mod table_cell_style;
 pub use crate::table_cell_style::TableCellStyle;
 
 mod transformation_matrix;
 pub use crate::transformation_matrix::TransformationMatrix;
 
+mod bounding_box;
+pub use crate::bounding_box::BoundingBox;
+
+mod entity_transform;
+
 pub mod enums;
 
 mod color;
@@ -210,10 +241,12 @@ mod line_weight;
 pub use crate::line_weight::LineWeight;
 
 mod entity;
-pub use crate::entity::LwPolylineVertex;
+pub use crate::entity::{
+    LwPolylineVertex, MeshPropertyOverride, MeshSubEntityOverride, UnknownEntity,
+};
 
 mod object;
-pub use crate::object::{GeoMeshPoint, MLineStyleElement};
+pub use crate::object::{GeoMeshPoint, MLineStyleElement, UnknownObject};
 
 mod dxf_error;
 pub use crate::dxf_error::DxfError;
@@ -224,6 +257,24 @@ pub use crate::dxf_result::DxfResult;
 mod entity_iter;
 mod object_iter;
 
+mod code_pair_reader;
+pub use crate::code_pair_reader::CodePairReader;
+
+mod entity_stream;
+pub use crate::entity_stream::EntityStream;
+
+mod dxf_stream_writer;
+pub use crate::dxf_stream_writer::DxfStreamWriter;
+
+mod load_options;
+pub use crate::load_options::{Diagnostic, DiagnosticAction, LoadMode, LoadOptions, SectionFilter};
+
+mod removal;
+pub use crate::removal::{RemovalMode, RemovalReport};
+
+mod purge;
+pub use crate::purge::{PurgeOptions, PurgeReport};
+
 //------------------------------- test imports ---------------------------------
 
 #[cfg(test)]
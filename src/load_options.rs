@@ -0,0 +1,374 @@
+use encoding_rs::Encoding;
+
+use crate::Handle;
+
+/// Determines how errors are handled while loading a `Drawing`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum LoadMode {
+    /// Any error aborts loading.
+    Strict,
+    /// Malformed values and items are skipped and reported as `Diagnostic`s.  Errors that prevent
+    /// reading any further, e.g., I/O errors, still abort loading.
+    Lenient,
+}
+
+/// Options used by `Drawing::load_with_options`.
+#[derive(Clone, Copy, Debug)]
+pub struct LoadOptions {
+    pub mode: LoadMode,
+    /// The text encoding used until one is specified by the drawing itself.
+    pub encoding: &'static Encoding,
+    /// Whether to switch to the encoding named by the drawing's `$DWGCODEPAGE`.  If `false`, `encoding`
+    /// is used for all pre-R2007 drawings.
+    pub detect_encoding: bool,
+    /// The sections to read; all others are skipped.
+    pub sections: SectionFilter,
+}
+
+impl Default for LoadOptions {
+    fn default() -> Self {
+        LoadOptions {
+            mode: LoadMode::Strict,
+            encoding: encoding_rs::WINDOWS_1252,
+            detect_encoding: true,
+            sections: SectionFilter::all(),
+        }
+    }
+}
+
+// public implementation
+impl LoadOptions {
+    pub fn strict() -> Self {
+        LoadOptions::default()
+    }
+    pub fn lenient() -> Self {
+        LoadOptions {
+            mode: LoadMode::Lenient,
+            ..Default::default()
+        }
+    }
+}
+
+/// Specifies which sections are read by `Drawing::load_sections`.  Skipped sections are only scanned
+/// for their end and leave the corresponding parts of the `Drawing` empty.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct SectionFilter {
+    pub header: bool,
+    pub classes: bool,
+    pub tables: bool,
+    pub blocks: bool,
+    pub entities: bool,
+    pub objects: bool,
+    pub thumbnail: bool,
+    /// Sections not otherwise supported, e.g., `ACDSDATA`.
+    pub unknown_sections: bool,
+}
+
+impl Default for SectionFilter {
+    fn default() -> Self {
+        SectionFilter::all()
+    }
+}
+
+// public implementation
+impl SectionFilter {
+    /// Reads every section.
+    pub fn all() -> Self {
+        SectionFilter {
+            header: true,
+            classes: true,
+            tables: true,
+            blocks: true,
+            entities: true,
+            objects: true,
+            thumbnail: true,
+            unknown_sections: true,
+        }
+    }
+    /// Reads no sections; combine with struct update syntax to select individual sections.
+    pub fn none() -> Self {
+        SectionFilter {
+            header: false,
+            classes: false,
+            tables: false,
+            blocks: false,
+            entities: false,
+            objects: false,
+            thumbnail: false,
+            unknown_sections: false,
+        }
+    }
+    /// Reads only the `HEADER` section.
+    pub fn header_only() -> Self {
+        SectionFilter {
+            header: true,
+            ..SectionFilter::none()
+        }
+    }
+}
+
+// internal visibility only
+impl SectionFilter {
+    /// Returns `true` if the section with the specified name should be read.
+    pub(crate) fn includes(&self, section_name: &str) -> bool {
+        match section_name {
+            "HEADER" => self.header,
+            "CLASSES" => self.classes,
+            "TABLES" => self.tables,
+            "BLOCKS" => self.blocks,
+            "ENTITIES" => self.entities,
+            "OBJECTS" => self.objects,
+            "THUMBNAILIMAGE" => self.thumbnail,
+            _ => self.unknown_sections,
+        }
+    }
+}
+
+/// The action taken when a problem was encountered while loading a `Drawing` leniently.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum DiagnosticAction {
+    /// A malformed value was dropped; the item containing it was kept.
+    DroppedValue,
+    /// The item could not be read and was skipped entirely.
+    SkippedItem,
+}
+
+/// Describes an item that was skipped or repaired while loading a `Drawing` leniently.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Diagnostic {
+    /// The line number (ASCII files) or byte offset (binary files) of the problem.
+    pub offset: usize,
+    /// The section being read, e.g., `ENTITIES`.
+    pub section: String,
+    /// The type of the item being read, e.g., `LINE`, if any.
+    pub item_type: Option<String>,
+    /// The handle of the item being read, if it was known.
+    pub handle: Option<Handle>,
+    pub action: DiagnosticAction,
+    /// A description of the problem.
+    pub message: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::*;
+    use crate::enums::*;
+    use crate::objects::*;
+    use crate::*;
+
+    fn load_lenient(text: &str) -> (Drawing, Vec<Diagnostic>) {
+        let mut bytes = text.as_bytes();
+        Drawing::load_with_options(&mut bytes, LoadOptions::lenient()).unwrap()
+    }
+
+    #[test]
+    fn lenient_load_drops_malformed_values() {
+        let (drawing, diagnostics) = load_lenient(
+            &[
+                "0",
+                "SECTION",
+                "2",
+                "ENTITIES",
+                "0",
+                "LINE",
+                "5",
+                "A1",
+                "10",
+                "not-a-number",
+                "20",
+                "2.0",
+                "0",
+                "ENDSEC",
+                "0",
+                "EOF",
+            ]
+            .join("\r\n"),
+        );
+        let entities = drawing.entities().collect::<Vec<_>>();
+        assert_eq!(1, entities.len());
+        match entities[0].specific {
+            EntityType::Line(ref line) => assert_eq!(Point::new(0.0, 2.0, 0.0), line.p1),
+            _ => panic!("expected a line"),
+        }
+        assert_eq!(
+            vec![Diagnostic {
+                offset: 10,
+                section: String::from("ENTITIES"),
+                item_type: Some(String::from("LINE")),
+                handle: Some(Handle(0xA1)),
+                action: DiagnosticAction::DroppedValue,
+                message: diagnostics[0].message.clone(),
+            }],
+            diagnostics
+        );
+    }
+
+    #[test]
+    fn lenient_load_skips_unreadable_entities() {
+        let (drawing, diagnostics) = load_lenient(
+            &[
+                "0",
+                "SECTION",
+                "2",
+                "ENTITIES",
+                "0",
+                "LINE",
+                "5",
+                "not-a-handle",
+                "10",
+                "1.0",
+                "0",
+                "CIRCLE",
+                "5",
+                "A2",
+                "0",
+                "ENDSEC",
+                "0",
+                "EOF",
+            ]
+            .join("\r\n"),
+        );
+        let entities = drawing.entities().collect::<Vec<_>>();
+        assert_eq!(1, entities.len());
+        match entities[0].specific {
+            EntityType::Circle(_) => (),
+            _ => panic!("expected a circle"),
+        }
+        assert_eq!(1, diagnostics.len());
+        assert_eq!(7, diagnostics[0].offset);
+        assert_eq!("ENTITIES", diagnostics[0].section);
+        assert_eq!(Some(String::from("LINE")), diagnostics[0].item_type);
+        assert_eq!(DiagnosticAction::SkippedItem, diagnostics[0].action);
+    }
+
+    #[test]
+    fn lenient_load_skips_unreadable_objects() {
+        let (drawing, diagnostics) = load_lenient(
+            &[
+                "0",
+                "SECTION",
+                "2",
+                "OBJECTS",
+                "0",
+                "DICTIONARY",
+                "5",
+                "not-a-handle",
+                "0",
+                "DICTIONARYVAR",
+                "5",
+                "B2",
+                "0",
+                "ENDSEC",
+                "0",
+                "EOF",
+            ]
+            .join("\r\n"),
+        );
+        let objects = drawing.objects().collect::<Vec<_>>();
+        assert_eq!(1, objects.len());
+        match objects[0].specific {
+            ObjectType::DictionaryVariable(_) => (),
+            _ => panic!("expected a dictionary variable"),
+        }
+        assert_eq!(1, diagnostics.len());
+        assert_eq!("OBJECTS", diagnostics[0].section);
+        assert_eq!(Some(String::from("DICTIONARY")), diagnostics[0].item_type);
+    }
+
+    fn sample_drawing_text() -> String {
+        [
+            "0", "SECTION", "2", "HEADER", "9", "$ACADVER", "1", "AC1015", "0", "ENDSEC", "0",
+            "SECTION", "2", "TABLES", "0", "TABLE", "2", "LAYER", "0", "LAYER", "2", "walls", "0",
+            "ENDTAB", "0", "ENDSEC", "0", "SECTION", "2", "ENTITIES", "0", "LINE", "8", "walls",
+            "0", "ENDSEC", "0", "SECTION", "2", "ACDSDATA", "0", "ENDSEC", "0", "EOF",
+        ]
+        .join("\r\n")
+    }
+
+    #[test]
+    fn load_selected_sections() {
+        let filter = SectionFilter {
+            header: true,
+            tables: true,
+            ..SectionFilter::none()
+        };
+        let drawing =
+            Drawing::load_sections(&mut sample_drawing_text().as_bytes(), filter).unwrap();
+        assert_eq!(AcadVersion::R2000, drawing.header.version);
+        assert!(drawing.layers().any(|l| l.name == "walls"));
+        assert_eq!(0, drawing.entities().count());
+        assert!(drawing.unknown_sections.is_empty());
+    }
+
+    #[test]
+    fn load_sections_without_header() {
+        let filter = SectionFilter {
+            entities: true,
+            ..SectionFilter::none()
+        };
+        let drawing =
+            Drawing::load_sections(&mut sample_drawing_text().as_bytes(), filter).unwrap();
+        assert_eq!(Header::default().version, drawing.header.version);
+        assert_eq!(1, drawing.entities().count());
+    }
+
+    #[test]
+    fn load_header_only() {
+        // nothing after the header is read, so the malformed entity doesn't matter
+        let text = [
+            "0",
+            "SECTION",
+            "2",
+            "HEADER",
+            "9",
+            "$ACADVER",
+            "1",
+            "AC1018",
+            "9",
+            "$INSUNITS",
+            "70",
+            "4",
+            "0",
+            "ENDSEC",
+            "0",
+            "SECTION",
+            "2",
+            "ENTITIES",
+            "0",
+            "LINE",
+            "5",
+            "not-a-handle",
+        ]
+        .join("\r\n");
+        let header = Header::load(&mut text.as_bytes()).unwrap();
+        assert_eq!(AcadVersion::R2004, header.version);
+        assert_eq!(Units::Millimeters, header.default_drawing_units);
+    }
+
+    #[test]
+    fn strict_load_fails_on_unreadable_entities() {
+        let text = [
+            "0",
+            "SECTION",
+            "2",
+            "ENTITIES",
+            "0",
+            "LINE",
+            "5",
+            "not-a-handle",
+            "0",
+            "ENDSEC",
+            "0",
+            "EOF",
+        ]
+        .join("\r\n");
+        let mut bytes = text.as_bytes();
+        assert!(Drawing::load_with_options(&mut bytes, LoadOptions::strict()).is_err());
+        let mut bytes = text.as_bytes();
+        assert!(Drawing::load(&mut bytes).is_err());
+    }
+}
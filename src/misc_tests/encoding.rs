@@ -1,6 +1,7 @@
 use crate::entities::*;
 use crate::enums::*;
 use crate::helper_functions::tests::*;
+use crate::tables::*;
 use crate::*;
 
 use std::io::{BufReader, Cursor, Seek, SeekFrom};
@@ -372,13 +373,87 @@ fn dont_write_utf8_bom() {
 fn write_unicode_as_ascii() {
     let mut drawing = Drawing::new();
     drawing.header.version = AcadVersion::R2004;
-    drawing.header.project_name = String::from("è");
+    drawing.header.project_name = String::from("你"); // not representable in the default ANSI_1252
     assert_contains(
         &drawing,
-        vec!["  9", "$PROJECTNAME", "  1", "\\U+00E8"].join("\r\n"),
+        ["  9", "$PROJECTNAME", "  1", "\\U+4F60"].join("\r\n"),
     );
 }
 
+#[test]
+fn write_with_drawing_code_page() {
+    let mut drawing = Drawing::new();
+    drawing.header.version = AcadVersion::R2004;
+    drawing.header.drawing_code_page = String::from("ANSI_936");
+    drawing.header.project_name = String::from("不");
+    let mut buf = Cursor::new(vec![]);
+    drawing.save(&mut buf).unwrap();
+    let bytes = buf.into_inner();
+    let expected = b"$PROJECTNAME\r\n  1\r\n\xB2\xBB\r\n";
+    assert!(bytes.windows(expected.len()).any(|w| w == expected));
+}
+
+#[test]
+fn read_with_drawing_code_page() {
+    let head = "
+  0
+SECTION
+  2
+HEADER
+  9
+$ACADVER
+  1
+AC1018
+  9
+$DWGCODEPAGE
+  3
+ANSI_936
+  9
+$PROJECTNAME
+  1"
+    .trim();
+    let tail = "
+  0
+ENDSEC
+  0
+EOF"
+    .trim();
+    let mut bytes = head.as_bytes().to_vec();
+    bytes.extend_from_slice(b"\r\n\xB2\xBB\r\n"); // `不` in GBK
+    bytes.extend_from_slice(tail.as_bytes());
+    let drawing = unwrap_drawing(Drawing::load(&mut bytes.as_slice()));
+    assert_eq!("不", drawing.header.project_name);
+
+    // an explicit encoding takes precedence
+    let drawing = unwrap_drawing(Drawing::load_with_encoding(
+        &mut bytes.as_slice(),
+        encoding_rs::WINDOWS_1252,
+    ));
+    assert_eq!("²»", drawing.header.project_name);
+}
+
+#[test]
+fn round_trip_drawing_code_page() {
+    for version in &[AcadVersion::R13, AcadVersion::R2004, AcadVersion::R2013] {
+        for code_page in &["ANSI_932", "ANSI_1251", "ANSI_1252"] {
+            let mut drawing = Drawing::new();
+            drawing.header.version = *version;
+            drawing.header.drawing_code_page = String::from(*code_page);
+            drawing.add_layer(Layer {
+                name: String::from("Чайка 不 è"),
+                ..Default::default()
+            });
+            let mut buf = Cursor::new(vec![]);
+            drawing.save(&mut buf).unwrap();
+            let text = unwrap_drawing(Drawing::load(&mut buf.into_inner().as_slice()));
+            let binary = unwrap_drawing(Drawing::load(&mut to_binary(&drawing).as_slice()));
+            for drawing in &[text, binary] {
+                assert!(drawing.layers().any(|l| l.name == "Чайка 不 è"));
+            }
+        }
+    }
+}
+
 #[test]
 fn write_unicode_as_utf8() {
     let mut drawing = Drawing::new();
@@ -0,0 +1,347 @@
+use crate::entities::*;
+use crate::helper_functions::tests::*;
+use crate::objects::*;
+use crate::*;
+
+fn add_block(drawing: &mut Drawing, name: &str, base_point: Point, entities: Vec<Entity>) {
+    drawing.add_block(Block {
+        name: String::from(name),
+        base_point,
+        entities,
+        ..Default::default()
+    });
+}
+
+fn unit_line() -> Entity {
+    Entity::new(EntityType::Line(Line::new(
+        Point::origin(),
+        Point::new(1.0, 0.0, 0.0),
+    )))
+}
+
+fn insert(name: &str) -> Insert {
+    Insert {
+        name: String::from(name),
+        ..Default::default()
+    }
+}
+
+fn line_points(entity: &Entity) -> (Point, Point) {
+    match entity.specific {
+        EntityType::Line(ref line) => (line.p1.clone(), line.p2.clone()),
+        _ => panic!("expected a line"),
+    }
+}
+
+#[test]
+fn explode_insert_applies_placement() {
+    let mut drawing = Drawing::new();
+    add_block(
+        &mut drawing,
+        "b",
+        Point::new(1.0, 0.0, 0.0),
+        vec![unit_line()],
+    );
+    let ent = Entity::new(EntityType::Insert(Insert {
+        location: Point::new(10.0, 0.0, 0.0),
+        x_scale_factor: 2.0,
+        rotation: 90.0,
+        ..insert("b")
+    }));
+
+    let exploded = drawing.explode_insert(&ent).unwrap();
+    assert_eq!(1, exploded.len());
+    assert!(exploded[0].common.handle.is_empty());
+    let (p1, p2) = line_points(&exploded[0]);
+    assert_points_close(Point::new(10.0, -2.0, 0.0), &p1);
+    assert_points_close(Point::new(10.0, 0.0, 0.0), &p2);
+}
+
+#[test]
+fn explode_insert_with_extrusion() {
+    let mut drawing = Drawing::new();
+    add_block(&mut drawing, "b", Point::origin(), vec![unit_line()]);
+    let ent = Entity::new(EntityType::Insert(Insert {
+        location: Point::new(1.0, 2.0, 3.0),
+        extrusion_direction: Vector::new(0.0, 0.0, -1.0),
+        ..insert("b")
+    }));
+
+    let exploded = drawing.explode_insert(&ent).unwrap();
+    let (p1, p2) = line_points(&exploded[0]);
+    assert_points_close(Point::new(-1.0, 2.0, -3.0), &p1);
+    assert_points_close(Point::new(-2.0, 2.0, -3.0), &p2);
+}
+
+#[test]
+fn explode_insert_array() {
+    let mut drawing = Drawing::new();
+    add_block(&mut drawing, "b", Point::origin(), vec![unit_line()]);
+    let ent = Entity::new(EntityType::Insert(Insert {
+        column_count: 3,
+        column_spacing: 5.0,
+        row_count: 2,
+        row_spacing: 10.0,
+        ..insert("b")
+    }));
+
+    let starts = drawing
+        .explode_insert(&ent)
+        .unwrap()
+        .iter()
+        .map(|e| line_points(e).0)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            Point::new(0.0, 0.0, 0.0),
+            Point::new(5.0, 0.0, 0.0),
+            Point::new(10.0, 0.0, 0.0),
+            Point::new(0.0, 10.0, 0.0),
+            Point::new(5.0, 10.0, 0.0),
+            Point::new(10.0, 10.0, 0.0),
+        ],
+        starts
+    );
+}
+
+#[test]
+fn explode_insert_resolves_by_block_properties() {
+    let mut drawing = Drawing::new();
+    let mut by_block = unit_line();
+    by_block.common.color = Color::by_block();
+    by_block.common.line_type_name = String::from("BYBLOCK");
+    by_block.common.lineweight_enum_value = LineWeight::by_block().get_raw_value();
+    let mut explicit = unit_line();
+    explicit.common.layer = String::from("inner");
+    explicit.common.color = Color::from_index(3);
+    explicit.common.line_type_name = String::from("DOTTED");
+    add_block(&mut drawing, "b", Point::origin(), vec![by_block, explicit]);
+    let mut ent = Entity::new(EntityType::Insert(insert("b")));
+    ent.common.layer = String::from("outer");
+    ent.common.color = Color::from_index(1);
+    ent.common.line_type_name = String::from("DASHED");
+    ent.common.lineweight_enum_value = 50;
+
+    let exploded = drawing.explode_insert(&ent).unwrap();
+    assert_eq!("outer", exploded[0].common.layer);
+    assert_eq!(Color::from_index(1), exploded[0].common.color);
+    assert_eq!("DASHED", exploded[0].common.line_type_name);
+    assert_eq!(50, exploded[0].common.lineweight_enum_value);
+    assert_eq!("inner", exploded[1].common.layer);
+    assert_eq!(Color::from_index(3), exploded[1].common.color);
+    assert_eq!("DOTTED", exploded[1].common.line_type_name);
+}
+
+#[test]
+fn explode_insert_turns_attributes_into_text() {
+    let mut drawing = Drawing::new();
+    let constant = AttributeDefinition {
+        value: String::from("constant"),
+        flags: 2,
+        ..Default::default()
+    };
+    let prompted = AttributeDefinition {
+        value: String::from("prompted"),
+        ..Default::default()
+    };
+    add_block(
+        &mut drawing,
+        "b",
+        Point::origin(),
+        vec![
+            Entity::new(EntityType::AttributeDefinition(constant)),
+            Entity::new(EntityType::AttributeDefinition(prompted)),
+        ],
+    );
+    let mut ins = Insert {
+        location: Point::new(5.0, 0.0, 0.0),
+        ..insert("b")
+    };
+    ins.add_attribute(
+        &mut drawing,
+        Attribute {
+            location: Point::new(5.0, 1.0, 0.0),
+            value: String::from("visible"),
+            ..Default::default()
+        },
+    );
+    ins.add_attribute(
+        &mut drawing,
+        Attribute {
+            value: String::from("invisible"),
+            flags: 1,
+            ..Default::default()
+        },
+    );
+
+    let exploded = drawing
+        .explode_insert(&Entity::new(EntityType::Insert(ins)))
+        .unwrap();
+    let texts = exploded
+        .iter()
+        .map(|e| match e.specific {
+            EntityType::Text(ref t) => t,
+            _ => panic!("expected text"),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(2, texts.len());
+    assert_eq!("constant", texts[0].value);
+    assert_eq!(Point::new(5.0, 0.0, 0.0), texts[0].location);
+    assert_eq!("visible", texts[1].value);
+    assert_eq!(Point::new(5.0, 1.0, 0.0), texts[1].location);
+}
+
+#[test]
+fn explode_insert_keeps_nested_inserts() {
+    let mut drawing = Drawing::new();
+    add_block(&mut drawing, "inner", Point::origin(), vec![unit_line()]);
+    add_block(
+        &mut drawing,
+        "outer",
+        Point::origin(),
+        vec![Entity::new(EntityType::Insert(insert("inner")))],
+    );
+    let ent = Entity::new(EntityType::Insert(Insert {
+        location: Point::new(0.0, 3.0, 0.0),
+        ..insert("outer")
+    }));
+
+    let exploded = drawing.explode_insert(&ent).unwrap();
+    match exploded[0].specific {
+        EntityType::Insert(ref ins) => {
+            assert_eq!("inner", ins.name);
+            assert_eq!(Point::new(0.0, 3.0, 0.0), ins.location);
+        }
+        _ => panic!("expected an insert"),
+    }
+}
+
+#[test]
+fn explode_insert_drops_extension_dictionaries_and_reactors() {
+    let mut drawing = Drawing::new();
+    let mut line = unit_line();
+    for name in ["ACAD_XDICTIONARY", "ACAD_REACTORS", "IXMILIA"] {
+        line.common.extension_data_groups.push(ExtensionGroup {
+            application_name: String::from(name),
+            items: vec![ExtensionGroupItem::CodePair(CodePair::new_str(330, "FFFF"))],
+        });
+    }
+    add_block(&mut drawing, "b", Point::origin(), vec![line]);
+    let ent = Entity::new(EntityType::Insert(Insert {
+        column_count: 2,
+        ..insert("b")
+    }));
+
+    let exploded = drawing.explode_insert(&ent).unwrap();
+    assert_eq!(2, exploded.len());
+    for ent in exploded {
+        assert_eq!(
+            vec!["IXMILIA"],
+            ent.common
+                .extension_data_groups
+                .iter()
+                .map(|g| &*g.application_name)
+                .collect::<Vec<_>>()
+        );
+    }
+}
+
+#[test]
+fn explode_insert_errors() {
+    let drawing = Drawing::new();
+    let line = unit_line();
+    match drawing.explode_insert(&line) {
+        Err(DxfError::WrongItemType) => (),
+        _ => panic!("expected an error"),
+    }
+
+    let missing = Entity::new(EntityType::Insert(insert("missing")));
+    match drawing.explode_insert(&missing) {
+        Err(DxfError::ItemNotFound(name)) => assert_eq!("missing", name),
+        _ => panic!("expected an error"),
+    }
+}
+
+#[test]
+fn flatten_nested_inserts() {
+    let mut drawing = Drawing::new();
+    let mut inner_line = unit_line();
+    inner_line.common.color = Color::by_block();
+    add_block(&mut drawing, "inner", Point::origin(), vec![inner_line]);
+    let mut nested = Entity::new(EntityType::Insert(Insert {
+        location: Point::new(0.0, 1.0, 0.0),
+        x_scale_factor: 2.0,
+        ..insert("inner")
+    }));
+    nested.common.color = Color::by_block();
+    add_block(&mut drawing, "outer", Point::origin(), vec![nested]);
+    let mut ent = Entity::new(EntityType::Insert(Insert {
+        location: Point::new(10.0, 0.0, 0.0),
+        rotation: 90.0,
+        ..insert("outer")
+    }));
+    ent.common.color = Color::from_index(5);
+    drawing.add_entity(ent);
+    drawing.add_entity(unit_line());
+
+    drawing.flatten_inserts();
+    let entities = drawing.entities().collect::<Vec<_>>();
+    assert_eq!(2, entities.len());
+    let (p1, p2) = line_points(entities[0]);
+    assert_points_close(Point::new(9.0, 0.0, 0.0), &p1);
+    assert_points_close(Point::new(9.0, 2.0, 0.0), &p2);
+    assert_eq!(Color::from_index(5), entities[0].common.color);
+    assert!(!entities[0].common.handle.is_empty());
+    assert!(drawing
+        .get_item_by_handle(entities[0].common.handle)
+        .is_some());
+    assert_eq!(
+        (Point::origin(), Point::new(1.0, 0.0, 0.0)),
+        line_points(entities[1])
+    );
+}
+
+#[test]
+fn flatten_keeps_self_referencing_inserts() {
+    let mut drawing = Drawing::new();
+    add_block(
+        &mut drawing,
+        "b",
+        Point::origin(),
+        vec![unit_line(), Entity::new(EntityType::Insert(insert("B")))],
+    );
+    drawing.add_entity(Entity::new(EntityType::Insert(insert("b"))));
+
+    drawing.flatten_inserts();
+    let entities = drawing.entities().collect::<Vec<_>>();
+    assert_eq!(2, entities.len());
+    line_points(entities[0]);
+    match entities[1].specific {
+        EntityType::Insert(ref ins) => assert_eq!("B", ins.name),
+        _ => panic!("expected an insert"),
+    }
+}
+
+#[test]
+fn flatten_removes_references_to_replaced_inserts() {
+    let mut drawing = Drawing::new();
+    add_block(&mut drawing, "b", Point::origin(), vec![unit_line()]);
+    let insert = drawing
+        .add_entity(Entity::new(EntityType::Insert(insert("b"))))
+        .common
+        .handle;
+    let line = drawing.add_entity(unit_line()).common.handle;
+    let group = Group {
+        __entities_handle: vec![insert, line],
+        ..Default::default()
+    };
+    drawing.add_object(Object::new(ObjectType::Group(group)));
+
+    drawing.flatten_inserts();
+    assert!(!drawing.contains_handle(insert));
+    let group = drawing.objects().next().unwrap();
+    match group.specific {
+        ObjectType::Group(ref g) => assert_eq!(vec![line], g.__entities_handle),
+        _ => panic!("expected a group"),
+    }
+}
@@ -6,3 +6,15 @@ mod integration;
 
 #[cfg(test)]
 mod pointers;
+
+#[cfg(test)]
+mod removal;
+
+#[cfg(test)]
+mod rename;
+
+#[cfg(test)]
+mod purge;
+
+#[cfg(test)]
+mod explode;
@@ -51,6 +51,50 @@ fn follow_entity_pointer_to_object() {
     assert_eq!("material-name", bound_material.name);
 }
 
+#[test]
+fn follow_mleader_pointer_to_mleader_style() {
+    let drawing = parse_drawing(
+        vec![
+            "  0",
+            "SECTION",
+            "  2",
+            "OBJECTS",
+            "  0",
+            "MLEADERSTYLE",
+            "  5",
+            "ABCD",
+            "  3",
+            "style-description",
+            "  0",
+            "ENDSEC",
+            "  0",
+            "SECTION",
+            "  2",
+            "ENTITIES",
+            "  0",
+            "MULTILEADER",
+            "340",
+            "ABCD",
+            "  0",
+            "ENDSEC",
+            "  0",
+            "EOF",
+        ]
+        .join("\r\n")
+        .as_str(),
+    );
+    let entities = drawing.entities().collect::<Vec<_>>();
+    let mleader = match entities[0].specific {
+        EntityType::MLeader(ref mleader) => mleader,
+        _ => panic!("expected an mleader"),
+    };
+    let style = match mleader.get_leader_style(&drawing).unwrap().specific {
+        ObjectType::MLeaderStyle(ref style) => style,
+        _ => panic!("expected an mleader style"),
+    };
+    assert_eq!("style-description", style.m_leader_style_description);
+}
+
 #[test]
 fn follow_object_pointer_to_entity_collection() {
     let drawing = parse_drawing(
@@ -122,10 +166,10 @@ fn set_pointer_on_entity() {
         common: Default::default(),
         specific: EntityType::Line(Default::default()),
     };
-    assert_eq!(0, material.common.handle);
+    assert!(material.common.handle.is_empty());
 
     let material = drawing.add_object(material);
-    assert_eq!(0x10, material.common.handle);
+    assert_eq!(Handle(0x10), material.common.handle);
     line.common.set_material(material).ok().unwrap();
     drawing.add_entity(line);
 
@@ -0,0 +1,230 @@
+use crate::entities::*;
+use crate::objects::*;
+use crate::tables::*;
+use crate::*;
+
+#[test]
+fn purge_new_drawing_removes_nothing() {
+    let mut drawing = Drawing::new();
+    drawing.normalize();
+    let report = drawing.purge(PurgeOptions::default());
+    assert!(report.is_empty());
+}
+
+#[test]
+fn purge_unused_table_items() {
+    let mut drawing = Drawing::new();
+    drawing.add_layer(Layer {
+        name: String::from("unused-layer"),
+        ..Default::default()
+    });
+    drawing.add_line_type(LineType {
+        name: String::from("unused-line-type"),
+        ..Default::default()
+    });
+    drawing.add_style(Style {
+        name: String::from("unused-style"),
+        ..Default::default()
+    });
+    drawing.add_dim_style(DimStyle {
+        name: String::from("unused-dim-style"),
+        ..Default::default()
+    });
+    let mut line = Entity::new(EntityType::Line(Line::default()));
+    line.common.layer = String::from("walls");
+    drawing.add_entity(line);
+    drawing.add_layer(Layer {
+        name: String::from("current"),
+        ..Default::default()
+    });
+    drawing.header.current_layer = String::from("current");
+
+    let report = drawing.purge(PurgeOptions::default());
+    assert_eq!(vec!["unused-layer"], report.layers);
+    assert_eq!(vec!["unused-line-type"], report.line_types);
+    assert_eq!(vec!["unused-style"], report.text_styles);
+    assert_eq!(vec!["unused-dim-style"], report.dim_styles);
+
+    let layers = drawing.layers().map(|l| &*l.name).collect::<Vec<_>>();
+    assert_eq!(vec!["0", "walls", "current"], layers);
+    for name in ["BYLAYER", "BYBLOCK", "CONTINUOUS"] {
+        assert!(drawing.line_types().any(|l| l.name == name));
+    }
+    for name in ["STANDARD", "ANNOTATIVE"] {
+        assert!(drawing.styles().any(|s| s.name == name));
+        assert!(drawing.dim_styles().any(|d| d.name == name));
+    }
+}
+
+#[test]
+fn purge_items_used_by_handle_are_kept() {
+    let mut drawing = Drawing::new();
+    let frozen = drawing
+        .add_layer(Layer {
+            name: String::from("frozen"),
+            ..Default::default()
+        })
+        .handle;
+    let mut view_port = Viewport::default();
+    view_port.__frozen_layers_handle.push(frozen);
+    drawing.add_entity(Entity::new(EntityType::Viewport(view_port)));
+
+    let report = drawing.purge(PurgeOptions::default());
+    assert!(report.layers.is_empty());
+    assert!(drawing.layers().any(|l| l.name == "frozen"));
+}
+
+#[test]
+fn purge_nested_items() {
+    let mut drawing = Drawing::new();
+    drawing.add_line_type(LineType {
+        name: String::from("DASHED"),
+        ..Default::default()
+    });
+    drawing.add_layer(Layer {
+        name: String::from("hidden"),
+        line_type_name: String::from("DASHED"),
+        ..Default::default()
+    });
+
+    let options = PurgeOptions {
+        nested: false,
+        ..Default::default()
+    };
+    let report = drawing.purge(options);
+    assert_eq!(vec!["hidden"], report.layers);
+    assert!(report.line_types.is_empty());
+
+    let report = drawing.purge(PurgeOptions::default());
+    assert_eq!(vec!["DASHED"], report.line_types);
+}
+
+#[test]
+fn purge_only_selected_items() {
+    let mut drawing = Drawing::new();
+    drawing.add_layer(Layer {
+        name: String::from("unused-layer"),
+        ..Default::default()
+    });
+    drawing.add_style(Style {
+        name: String::from("unused-style"),
+        ..Default::default()
+    });
+
+    let options = PurgeOptions {
+        layers: false,
+        ..Default::default()
+    };
+    let report = drawing.purge(options);
+    assert!(report.layers.is_empty());
+    assert_eq!(vec!["unused-style"], report.text_styles);
+    assert!(drawing.layers().any(|l| l.name == "unused-layer"));
+}
+
+#[test]
+fn purge_unused_blocks() {
+    let mut drawing = Drawing::new();
+    for name in ["*MODEL_SPACE", "used", "unused", "nested"] {
+        drawing.add_block_record(BlockRecord {
+            name: String::from(name),
+            ..Default::default()
+        });
+    }
+    drawing.add_block(Block {
+        name: String::from("*MODEL_SPACE"),
+        ..Default::default()
+    });
+    drawing.add_block(Block {
+        name: String::from("used"),
+        ..Default::default()
+    });
+    drawing.add_block(Block {
+        name: String::from("unused"),
+        entities: vec![Entity::new(EntityType::Insert(Insert {
+            name: String::from("nested"),
+            ..Default::default()
+        }))],
+        ..Default::default()
+    });
+    drawing.add_block(Block {
+        name: String::from("nested"),
+        ..Default::default()
+    });
+    drawing.add_entity(Entity::new(EntityType::Insert(Insert {
+        name: String::from("used"),
+        ..Default::default()
+    })));
+
+    let report = drawing.purge(PurgeOptions::default());
+    assert_eq!(vec!["unused", "nested"], report.blocks);
+    assert_eq!(
+        vec!["*MODEL_SPACE", "used"],
+        drawing.blocks().map(|b| &*b.name).collect::<Vec<_>>()
+    );
+    assert!(!drawing.block_records().any(|r| r.name == "unused"));
+    assert!(!drawing.block_records().any(|r| r.name == "nested"));
+    assert!(drawing.block_records().any(|r| r.name == "used"));
+}
+
+#[test]
+fn purge_unused_mline_styles_and_dictionary_entries() {
+    let mut drawing = Drawing::new();
+    let unused = drawing
+        .add_object(Object::new(ObjectType::MLineStyle(MLineStyle {
+            style_name: String::from("unused"),
+            ..Default::default()
+        })))
+        .common
+        .handle;
+    let standard = drawing
+        .add_object(Object::new(ObjectType::MLineStyle(MLineStyle {
+            style_name: String::from("STANDARD"),
+            ..Default::default()
+        })))
+        .common
+        .handle;
+    let mut dict = Dictionary::default();
+    dict.value_handles.insert(String::from("unused"), unused);
+    dict.value_handles
+        .insert(String::from("STANDARD"), standard);
+    drawing.add_object(Object::new(ObjectType::Dictionary(dict)));
+
+    let report = drawing.purge(PurgeOptions::default());
+    assert_eq!(vec!["unused"], report.mline_styles);
+    assert!(report.objects.is_empty());
+    let dict = drawing
+        .objects()
+        .find_map(|o| match o.specific {
+            ObjectType::Dictionary(ref d) => Some(d),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(1, dict.value_handles.len());
+    assert_eq!(Some(&standard), dict.value_handles.get("STANDARD"));
+}
+
+#[test]
+fn purge_orphaned_objects() {
+    let mut drawing = Drawing::new();
+    let orphan = drawing
+        .add_object(Object::new(ObjectType::Material(Material::default())))
+        .common
+        .handle;
+    let used = drawing
+        .add_object(Object::new(ObjectType::Material(Material::default())))
+        .common
+        .handle;
+    let mut line = Entity::new(EntityType::Line(Line::default()));
+    line.common.__material_handle = used;
+    drawing.add_entity(line);
+    let root = drawing
+        .add_object(Object::new(ObjectType::Dictionary(Dictionary::default())))
+        .common
+        .handle;
+
+    let report = drawing.purge(PurgeOptions::default());
+    assert_eq!(vec![orphan], report.objects);
+    assert!(!drawing.contains_handle(orphan));
+    assert!(drawing.contains_handle(used));
+    assert!(drawing.contains_handle(root));
+}
@@ -0,0 +1,353 @@
+use crate::entities::*;
+use crate::objects::*;
+use crate::tables::*;
+use crate::*;
+
+fn add_line(drawing: &mut Drawing, layer: &str) -> Handle {
+    let mut line = Entity::new(EntityType::Line(Line::default()));
+    line.common.layer = String::from(layer);
+    drawing.add_entity(line).common.handle
+}
+
+#[test]
+fn remove_missing_handle() {
+    let mut drawing = Drawing::new();
+    let report = drawing
+        .remove_by_handle(Handle(0xFFFF), RemovalMode::Strict)
+        .unwrap();
+    assert_eq!(RemovalReport::default(), report);
+    let report = drawing
+        .remove_by_handle(Handle::empty(), RemovalMode::Strict)
+        .unwrap();
+    assert_eq!(RemovalReport::default(), report);
+}
+
+#[test]
+fn remove_entity_from_group_and_sortents_table() {
+    let mut drawing = Drawing::new();
+    let first = add_line(&mut drawing, "0");
+    let second = add_line(&mut drawing, "0");
+    let group = Group {
+        __entities_handle: vec![first, second],
+        ..Default::default()
+    };
+    let group = drawing
+        .add_object(Object::new(ObjectType::Group(group)))
+        .common
+        .handle;
+    let sortents = SortentsTable {
+        __entities_handle: vec![first, second],
+        __sort_items_handle: vec![Handle(0xA1), Handle(0xA2)],
+    };
+    let sortents = drawing
+        .add_object(Object::new(ObjectType::SortentsTable(sortents)))
+        .common
+        .handle;
+
+    let report = drawing
+        .remove_by_handle(first, RemovalMode::Strict)
+        .unwrap();
+    assert_eq!(vec![first], report.removed);
+    assert_eq!(vec![group, sortents], report.updated);
+    assert!(!drawing.contains_handle(first));
+    assert_eq!(1, drawing.entities().count());
+
+    let objects = drawing.objects().collect::<Vec<_>>();
+    match objects[0].specific {
+        ObjectType::Group(ref g) => assert_eq!(vec![second], g.__entities_handle),
+        _ => panic!("expected a group"),
+    }
+    match objects[1].specific {
+        ObjectType::SortentsTable(ref s) => {
+            assert_eq!(vec![second], s.__entities_handle);
+            assert_eq!(vec![Handle(0xA2)], s.__sort_items_handle);
+        }
+        _ => panic!("expected a sortents table"),
+    }
+}
+
+#[test]
+fn remove_object_clears_owners_reactors_and_dictionary_entries() {
+    let mut drawing = Drawing::new();
+    let material = drawing
+        .add_object(Object::new(ObjectType::Material(Material::default())))
+        .common
+        .handle;
+    let mut line = Entity::new(EntityType::Line(Line::default()));
+    line.common.__material_handle = material;
+    line.common.extension_data_groups.push(ExtensionGroup {
+        application_name: String::from("ACAD_REACTORS"),
+        items: vec![
+            ExtensionGroupItem::CodePair(CodePair::new_string(330, &material.as_string())),
+            ExtensionGroupItem::CodePair(CodePair::new_str(330, "FFFF")),
+        ],
+    });
+    let line = drawing.add_entity(line).common.handle;
+    let mut dict = Dictionary::default();
+    dict.value_handles
+        .insert(String::from("material"), material);
+    dict.value_handles
+        .insert(String::from("other"), Handle(0xFFFF));
+    let dict = drawing
+        .add_object(Object::new(ObjectType::Dictionary(dict)))
+        .common
+        .handle;
+    let mut owned = Object::new(ObjectType::XRecordObject(XRecordObject::default()));
+    owned.common.__owner_handle = material;
+    let owned = drawing.add_object(owned).common.handle;
+
+    let report = drawing
+        .remove_by_handle(material, RemovalMode::Strict)
+        .unwrap();
+    assert_eq!(vec![material], report.removed);
+    assert_eq!(vec![line, dict, owned], report.updated);
+
+    let line = drawing.entities().next().unwrap();
+    assert!(line.common.__material_handle.is_empty());
+    assert_eq!(
+        vec![ExtensionGroupItem::CodePair(CodePair::new_str(330, "FFFF"))],
+        line.common.extension_data_groups[0].items
+    );
+    match drawing.objects().next().unwrap().specific {
+        ObjectType::Dictionary(ref d) => {
+            assert_eq!(1, d.value_handles.len());
+            assert!(d.value_handles.contains_key("other"));
+        }
+        _ => panic!("expected a dictionary"),
+    }
+    match drawing.get_item_by_handle(owned) {
+        Some(DrawingItem::Object(obj)) => assert!(obj.common.__owner_handle.is_empty()),
+        _ => panic!("expected an object"),
+    }
+}
+
+#[test]
+fn remove_dimension_removes_dimension_assoc() {
+    let mut drawing = Drawing::new();
+    let dimension = drawing
+        .add_entity(Entity::new(EntityType::RotatedDimension(
+            RotatedDimension::default(),
+        )))
+        .common
+        .handle;
+    let assoc = DimensionAssoc {
+        __dimension_handle: dimension,
+        ..Default::default()
+    };
+    let assoc = drawing
+        .add_object(Object::new(ObjectType::DimensionAssoc(assoc)))
+        .common
+        .handle;
+
+    let report = drawing
+        .remove_by_handle(dimension, RemovalMode::Strict)
+        .unwrap();
+    assert_eq!(vec![dimension, assoc], report.removed);
+    assert!(report.updated.is_empty());
+    assert_eq!(0, drawing.objects().count());
+}
+
+#[test]
+fn remove_layer_in_use_fails() {
+    let mut drawing = Drawing::new();
+    add_line(&mut drawing, "walls");
+    let mut block = Block::default();
+    let mut line = Entity::new(EntityType::Line(Line::default()));
+    line.common.layer = String::from("walls");
+    block.entities.push(line);
+    drawing.add_block(block);
+    let layer = drawing.layers().find(|l| l.name == "walls").unwrap().handle;
+
+    match drawing.remove_by_handle(layer, RemovalMode::Strict) {
+        Err(DxfError::ItemInUse(h, count)) => {
+            assert_eq!(layer, h);
+            assert_eq!(2, count);
+        }
+        _ => panic!("expected an error"),
+    }
+    assert!(drawing.contains_handle(layer));
+    assert_eq!(1, drawing.entities().count());
+}
+
+#[test]
+fn remove_layer_in_use_with_cascade() {
+    let mut drawing = Drawing::new();
+    let wall = add_line(&mut drawing, "walls");
+    let other = add_line(&mut drawing, "0");
+    let group = Group {
+        __entities_handle: vec![wall, other],
+        ..Default::default()
+    };
+    let group = drawing
+        .add_object(Object::new(ObjectType::Group(group)))
+        .common
+        .handle;
+    let layer = drawing.layers().find(|l| l.name == "walls").unwrap().handle;
+
+    let report = drawing
+        .remove_by_handle(layer, RemovalMode::Cascade)
+        .unwrap();
+    assert_eq!(vec![layer, wall], report.removed);
+    assert_eq!(vec![group], report.updated);
+    assert!(!drawing.layers().any(|l| l.name == "walls"));
+    assert_eq!(
+        vec![other],
+        drawing
+            .entities()
+            .map(|e| e.common.handle)
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn remove_line_type_used_by_layer() {
+    let mut drawing = Drawing::new();
+    let line_type = drawing
+        .add_line_type(LineType {
+            name: String::from("DASHED"),
+            ..Default::default()
+        })
+        .handle;
+    let layer = drawing
+        .add_layer(Layer {
+            name: String::from("hidden"),
+            line_type_name: String::from("DASHED"),
+            ..Default::default()
+        })
+        .handle;
+
+    let report = drawing
+        .remove_by_handle(line_type, RemovalMode::Strict)
+        .unwrap();
+    assert_eq!(vec![line_type], report.removed);
+    assert_eq!(vec![layer], report.updated);
+    match drawing.get_item_by_handle(layer) {
+        Some(DrawingItem::Layer(l)) => assert_eq!("CONTINUOUS", l.line_type_name),
+        _ => panic!("expected a layer"),
+    }
+}
+
+#[test]
+fn remove_text_style_in_use_fails() {
+    let mut drawing = Drawing::new();
+    let text = Text {
+        text_style_name: String::from("notes"),
+        ..Default::default()
+    };
+    drawing.add_entity(Entity::new(EntityType::Text(text)));
+    let style = drawing.styles().find(|s| s.name == "notes").unwrap().handle;
+    assert!(drawing
+        .remove_by_handle(style, RemovalMode::Strict)
+        .is_err());
+
+    let report = drawing
+        .remove_by_handle(style, RemovalMode::Cascade)
+        .unwrap();
+    assert_eq!(2, report.removed.len());
+    assert_eq!(0, drawing.entities().count());
+}
+
+#[test]
+fn remove_layer_in_use_with_different_case_fails() {
+    let mut drawing = Drawing::new();
+    let layer = drawing
+        .add_layer(Layer {
+            name: String::from("walls"),
+            ..Default::default()
+        })
+        .handle;
+    add_line(&mut drawing, "WALLS");
+
+    match drawing.remove_by_handle(layer, RemovalMode::Strict) {
+        Err(DxfError::ItemInUse(h, count)) => {
+            assert_eq!(layer, h);
+            assert_eq!(1, count);
+        }
+        _ => panic!("expected an error"),
+    }
+}
+
+fn add_block_and_record(drawing: &mut Drawing, name: &str) -> (Handle, Handle) {
+    let block = drawing
+        .add_block(Block {
+            name: String::from(name),
+            ..Default::default()
+        })
+        .handle;
+    let record = drawing
+        .add_block_record(BlockRecord {
+            name: String::from(name),
+            ..Default::default()
+        })
+        .handle;
+    (block, record)
+}
+
+#[test]
+fn remove_block_in_use_fails() {
+    let mut drawing = Drawing::new();
+    let (block, record) = add_block_and_record(&mut drawing, "door");
+    let insert = Insert {
+        name: String::from("DOOR"),
+        ..Default::default()
+    };
+    drawing.add_entity(Entity::new(EntityType::Insert(insert)));
+    let mut dimension = RotatedDimension::default();
+    dimension.dimension_base.block_name = String::from("door");
+    drawing.add_entity(Entity::new(EntityType::RotatedDimension(dimension)));
+
+    for handle in [block, record] {
+        match drawing.remove_by_handle(handle, RemovalMode::Strict) {
+            Err(DxfError::ItemInUse(h, count)) => {
+                assert_eq!(handle, h);
+                assert_eq!(2, count);
+            }
+            _ => panic!("expected an error"),
+        }
+    }
+    assert!(drawing.contains_handle(block));
+    assert!(drawing.contains_handle(record));
+}
+
+#[test]
+fn remove_block_removes_block_record() {
+    let mut drawing = Drawing::new();
+    let (block, record) = add_block_and_record(&mut drawing, "door");
+    let report = drawing
+        .remove_by_handle(block, RemovalMode::Strict)
+        .unwrap();
+    assert_eq!(vec![block, record], report.removed);
+    assert!(!drawing.contains_handle(record));
+
+    let (block, record) = add_block_and_record(&mut drawing, "window");
+    let report = drawing
+        .remove_by_handle(record, RemovalMode::Strict)
+        .unwrap();
+    assert_eq!(vec![record, block], report.removed);
+    assert!(!drawing.blocks().any(|b| b.name == "window"));
+}
+
+#[test]
+fn remove_required_items_fails() {
+    let mut drawing = Drawing::new();
+    let mut handles = vec![drawing.layers().find(|l| l.name == "0").unwrap().handle];
+    for name in ["BYLAYER", "BYBLOCK", "CONTINUOUS"] {
+        handles.push(
+            drawing
+                .line_types()
+                .find(|l| l.name == name)
+                .unwrap()
+                .handle,
+        );
+    }
+
+    for handle in handles {
+        for mode in [RemovalMode::Strict, RemovalMode::Cascade] {
+            match drawing.remove_by_handle(handle, mode) {
+                Err(DxfError::RequiredItem(_)) => (),
+                _ => panic!("expected an error"),
+            }
+        }
+        assert!(drawing.contains_handle(handle));
+    }
+}
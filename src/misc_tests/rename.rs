@@ -0,0 +1,320 @@
+use crate::entities::*;
+use crate::objects::*;
+use crate::tables::*;
+use crate::*;
+
+fn entity_on_layer(specific: EntityType, layer: &str) -> Entity {
+    let mut ent = Entity::new(specific);
+    ent.common.layer = String::from(layer);
+    ent
+}
+
+#[test]
+fn rename_layer_updates_references() {
+    let mut drawing = Drawing::new();
+    let mut line = entity_on_layer(EntityType::Line(Line::default()), "walls");
+    line.common.x_data.push(XData {
+        application_name: String::from("IXMILIA"),
+        items: vec![XDataItem::ControlGroup(vec![XDataItem::LayerName(
+            String::from("walls"),
+        )])],
+    });
+    drawing.add_entity(line);
+    drawing.add_block(Block {
+        name: String::from("door"),
+        layer: String::from("walls"),
+        entities: vec![entity_on_layer(EntityType::Line(Line::default()), "walls")],
+        ..Default::default()
+    });
+    drawing.add_object(Object::new(ObjectType::LayerFilter(LayerFilter {
+        layer_names: vec![String::from("walls"), String::from("0")],
+    })));
+    drawing.header.current_layer = String::from("walls");
+
+    drawing.rename_layer("walls", "exterior").unwrap();
+    assert!(drawing.layers().any(|l| l.name == "exterior"));
+    assert!(!drawing.layers().any(|l| l.name == "walls"));
+
+    let line = drawing.entities().next().unwrap();
+    assert_eq!("exterior", line.common.layer);
+    match line.common.x_data[0].items[0] {
+        XDataItem::ControlGroup(ref items) => {
+            assert_eq!(vec![XDataItem::LayerName(String::from("exterior"))], *items)
+        }
+        _ => panic!("expected a control group"),
+    }
+
+    let block = drawing.blocks().next().unwrap();
+    assert_eq!("exterior", block.layer);
+    assert_eq!("exterior", block.entities[0].common.layer);
+    match drawing.objects().next().unwrap().specific {
+        ObjectType::LayerFilter(ref f) => assert_eq!(vec!["exterior", "0"], f.layer_names),
+        _ => panic!("expected a layer filter"),
+    }
+    assert_eq!("exterior", drawing.header.current_layer);
+}
+
+#[test]
+fn rename_missing_or_duplicate_layer() {
+    let mut drawing = Drawing::new();
+    drawing.add_entity(entity_on_layer(EntityType::Line(Line::default()), "walls"));
+    drawing.add_entity(entity_on_layer(EntityType::Line(Line::default()), "doors"));
+
+    match drawing.rename_layer("windows", "glass") {
+        Err(DxfError::ItemNotFound(name)) => assert_eq!("windows", name),
+        _ => panic!("expected an error"),
+    }
+    match drawing.rename_layer("walls", "doors") {
+        Err(DxfError::DuplicateName(name)) => assert_eq!("doors", name),
+        _ => panic!("expected an error"),
+    }
+    assert!(drawing.rename_layer("walls", "walls").is_ok());
+    assert_eq!(
+        vec!["walls", "doors"],
+        drawing
+            .entities()
+            .map(|e| &*e.common.layer)
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn rename_required_items_fails() {
+    let mut drawing = Drawing::new();
+    match drawing.rename_layer("0", "base") {
+        Err(DxfError::RequiredItem(name)) => assert_eq!("0", name),
+        _ => panic!("expected an error"),
+    }
+    for name in ["BYLAYER", "ByBlock", "continuous"] {
+        match drawing.rename_line_type(name, "other") {
+            Err(DxfError::RequiredItem(_)) => (),
+            _ => panic!("expected an error"),
+        }
+    }
+    assert!(drawing.layers().any(|l| l.name == "0"));
+    assert!(!drawing.line_types().any(|l| l.name == "other"));
+}
+
+#[test]
+fn rename_layer_ignores_case() {
+    let mut drawing = Drawing::new();
+    drawing.add_layer(Layer {
+        name: String::from("walls"),
+        ..Default::default()
+    });
+    drawing.add_entity(entity_on_layer(EntityType::Line(Line::default()), "walls"));
+    drawing.add_block(Block {
+        name: String::from("door"),
+        layer: String::from("walls"),
+        ..Default::default()
+    });
+    drawing.entities_mut().next().unwrap().common.layer = String::from("WALLS");
+    drawing
+        .blocks_mut()
+        .find(|b| b.name == "door")
+        .unwrap()
+        .layer = String::from("Walls");
+    drawing.header.current_layer = String::from("WALLS");
+
+    drawing.rename_layer("Walls", "exterior").unwrap();
+    assert!(drawing.layers().any(|l| l.name == "exterior"));
+    assert!(!drawing
+        .layers()
+        .any(|l| l.name.eq_ignore_ascii_case("walls")));
+    assert_eq!("exterior", drawing.entities().next().unwrap().common.layer);
+    assert_eq!(
+        "exterior",
+        drawing.blocks().find(|b| b.name == "door").unwrap().layer
+    );
+    assert_eq!("exterior", drawing.header.current_layer);
+
+    drawing.rename_layer("exterior", "EXTERIOR").unwrap();
+    assert!(drawing.layers().any(|l| l.name == "EXTERIOR"));
+    assert_eq!("EXTERIOR", drawing.entities().next().unwrap().common.layer);
+}
+
+#[test]
+fn rename_line_type_updates_references() {
+    let mut drawing = Drawing::new();
+    drawing.add_line_type(LineType {
+        name: String::from("DASHED"),
+        ..Default::default()
+    });
+    drawing.add_layer(Layer {
+        name: String::from("hidden"),
+        line_type_name: String::from("DASHED"),
+        ..Default::default()
+    });
+    let mut line = Entity::new(EntityType::Line(Line::default()));
+    line.common.line_type_name = String::from("DASHED");
+    drawing.add_entity(line);
+    let mut style = MLineStyle::default();
+    style.elements.push(MLineStyleElement::new(
+        0.5,
+        Color::by_layer(),
+        String::from("DASHED"),
+    ));
+    drawing.add_object(Object::new(ObjectType::MLineStyle(style)));
+    drawing.header.current_entity_line_type = String::from("DASHED");
+
+    drawing.rename_line_type("DASHED", "DASHED2").unwrap();
+    assert!(drawing.line_types().any(|l| l.name == "DASHED2"));
+    assert_eq!(
+        "DASHED2",
+        drawing
+            .layers()
+            .find(|l| l.name == "hidden")
+            .unwrap()
+            .line_type_name
+    );
+    assert_eq!(
+        "DASHED2",
+        drawing.entities().next().unwrap().common.line_type_name
+    );
+    match drawing.objects().next().unwrap().specific {
+        ObjectType::MLineStyle(ref s) => assert_eq!("DASHED2", s.elements[0].line_type),
+        _ => panic!("expected an mline style"),
+    }
+    assert_eq!("DASHED2", drawing.header.current_entity_line_type);
+}
+
+#[test]
+fn rename_text_style_updates_references() {
+    let mut drawing = Drawing::new();
+    drawing.add_entity(Entity::new(EntityType::Text(Text {
+        text_style_name: String::from("notes"),
+        ..Default::default()
+    })));
+    let mut att = Attribute {
+        text_style_name: String::from("notes"),
+        ..Default::default()
+    };
+    att.m_text.text_style_name = String::from("notes");
+    let mut insert = Insert::default();
+    insert.add_attribute(&mut drawing, att);
+    drawing.add_entity(Entity::new(EntityType::Insert(insert)));
+    drawing.add_dim_style(DimStyle {
+        name: String::from("annotations"),
+        dimension_text_style: String::from("notes"),
+        ..Default::default()
+    });
+    drawing.header.text_style = String::from("notes");
+
+    drawing.rename_text_style("notes", "labels").unwrap();
+    assert!(drawing.styles().any(|s| s.name == "labels"));
+    assert!(!drawing.styles().any(|s| s.name == "notes"));
+    let entities = drawing.entities().collect::<Vec<_>>();
+    match entities[0].specific {
+        EntityType::Text(ref t) => assert_eq!("labels", t.text_style_name),
+        _ => panic!("expected text"),
+    }
+    match entities[1].specific {
+        EntityType::Insert(ref ins) => {
+            let att = ins.attributes().next().unwrap();
+            assert_eq!("labels", att.text_style_name);
+            assert_eq!("labels", att.m_text.text_style_name);
+        }
+        _ => panic!("expected an insert"),
+    }
+    assert_eq!(
+        "labels",
+        drawing
+            .dim_styles()
+            .find(|d| d.name == "annotations")
+            .unwrap()
+            .dimension_text_style
+    );
+    assert_eq!("labels", drawing.header.text_style);
+}
+
+#[test]
+fn rename_dim_style_updates_references() {
+    let mut drawing = Drawing::new();
+    let mut dimension = RotatedDimension::default();
+    dimension.dimension_base.dimension_style_name = String::from("metric");
+    drawing.add_entity(Entity::new(EntityType::RotatedDimension(dimension)));
+    drawing.add_entity(Entity::new(EntityType::Leader(Leader {
+        dimension_style_name: String::from("metric"),
+        ..Default::default()
+    })));
+    drawing.header.dimension_style_name = String::from("metric");
+
+    drawing.rename_dim_style("metric", "iso").unwrap();
+    assert!(drawing.dim_styles().any(|d| d.name == "iso"));
+    let entities = drawing.entities().collect::<Vec<_>>();
+    match entities[0].specific {
+        EntityType::RotatedDimension(ref d) => {
+            assert_eq!("iso", d.dimension_base.dimension_style_name)
+        }
+        _ => panic!("expected a dimension"),
+    }
+    match entities[1].specific {
+        EntityType::Leader(ref l) => assert_eq!("iso", l.dimension_style_name),
+        _ => panic!("expected a leader"),
+    }
+    assert_eq!("iso", drawing.header.dimension_style_name);
+}
+
+#[test]
+fn rename_block_updates_references() {
+    let mut drawing = Drawing::new();
+    drawing.add_block(Block {
+        name: String::from("arrow"),
+        ..Default::default()
+    });
+    drawing.add_block_record(BlockRecord {
+        name: String::from("arrow"),
+        ..Default::default()
+    });
+    drawing.add_entity(Entity::new(EntityType::Insert(Insert {
+        name: String::from("arrow"),
+        ..Default::default()
+    })));
+    let mut dimension = Entity::new(EntityType::RotatedDimension(RotatedDimension::default()));
+    dimension.common.x_data.push(XData {
+        application_name: String::from("ACAD"),
+        items: vec![
+            XDataItem::Str(String::from("DSTYLE")),
+            XDataItem::ControlGroup(vec![
+                XDataItem::Integer(5),
+                XDataItem::Str(String::from("arrow")),
+                XDataItem::Integer(3),
+                XDataItem::Str(String::from("arrow")),
+            ]),
+        ],
+    });
+    drawing.add_entity(dimension);
+    drawing.add_dim_style(DimStyle {
+        name: String::from("arrows"),
+        first_arrow_block_name: String::from("arrow"),
+        ..Default::default()
+    });
+    drawing.header.arrow_block_name = String::from("arrow");
+
+    drawing.rename_block("arrow", "closed-arrow").unwrap();
+    assert_eq!("closed-arrow", drawing.blocks().next().unwrap().name);
+    assert!(drawing.block_records().any(|r| r.name == "closed-arrow"));
+    assert!(!drawing.block_records().any(|r| r.name == "arrow"));
+    let entities = drawing.entities().collect::<Vec<_>>();
+    match entities[0].specific {
+        EntityType::Insert(ref ins) => assert_eq!("closed-arrow", ins.name),
+        _ => panic!("expected an insert"),
+    }
+    match entities[1].common.x_data[0].items[1] {
+        XDataItem::ControlGroup(ref items) => {
+            // only the DIMBLK override is a block name
+            assert_eq!(XDataItem::Str(String::from("closed-arrow")), items[1]);
+            assert_eq!(XDataItem::Str(String::from("arrow")), items[3]);
+        }
+        _ => panic!("expected a control group"),
+    }
+    assert_eq!(
+        "closed-arrow",
+        drawing
+            .dim_styles()
+            .find(|d| d.name == "arrows")
+            .unwrap()
+            .first_arrow_block_name
+    );
+    assert_eq!("closed-arrow", drawing.header.arrow_block_name);
+}
@@ -0,0 +1,741 @@
+use std::io::{Read, Write};
+
+use crate::{CodePair, DxfError, DxfResult, Handle, Point, TransformationMatrix, Vector};
+
+use crate::code_pair_put_back::CodePairPutBack;
+use crate::code_pair_writer::CodePairWriter;
+use crate::enums::*;
+use crate::helper_functions::*;
+
+//------------------------------------------------------------------------------
+//                                                                   MLeaderLine
+//------------------------------------------------------------------------------
+/// Represents a single `LEADER_LINE{}` of an `MLeaderRoot`.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct MLeaderLine {
+    pub vertices: Vec<Point>,
+    pub break_point_indices: Vec<i32>,
+    pub break_start_points: Vec<Point>,
+    pub break_end_points: Vec<Point>,
+    pub leader_line_index: i32,
+}
+
+// internal visibility only
+impl MLeaderLine {
+    pub(crate) fn read<I>(iter: &mut CodePairPutBack<I>) -> DxfResult<MLeaderLine>
+    where
+        I: Read,
+    {
+        let mut line = MLeaderLine::default();
+        loop {
+            let pair = match iter.next() {
+                Some(Ok(pair @ CodePair { code: 0, .. })) => {
+                    // unexpected end; put the pair back and return what we have
+                    iter.put_back(Ok(pair));
+                    return Ok(line);
+                }
+                Some(Ok(pair)) => pair,
+                Some(Err(e)) => return Err(e),
+                None => return Ok(line),
+            };
+
+            match pair.code {
+                10 => {
+                    line.vertices.push(Point::new(pair.assert_f64()?, 0.0, 0.0));
+                }
+                20 => {
+                    vec_last!(line.vertices).y = pair.assert_f64()?;
+                }
+                30 => {
+                    vec_last!(line.vertices).z = pair.assert_f64()?;
+                }
+                11 => {
+                    line.break_start_points
+                        .push(Point::new(pair.assert_f64()?, 0.0, 0.0));
+                }
+                21 => {
+                    vec_last!(line.break_start_points).y = pair.assert_f64()?;
+                }
+                31 => {
+                    vec_last!(line.break_start_points).z = pair.assert_f64()?;
+                }
+                12 => {
+                    line.break_end_points
+                        .push(Point::new(pair.assert_f64()?, 0.0, 0.0));
+                }
+                22 => {
+                    vec_last!(line.break_end_points).y = pair.assert_f64()?;
+                }
+                32 => {
+                    vec_last!(line.break_end_points).z = pair.assert_f64()?;
+                }
+                90 => {
+                    line.break_point_indices.push(pair.assert_i32()?);
+                }
+                91 => {
+                    line.leader_line_index = pair.assert_i32()?;
+                }
+                305 => return Ok(line), // value should be "}", but it doesn't really matter
+                _ => (),
+            }
+        }
+    }
+    pub(crate) fn write<T>(&self, writer: &mut CodePairWriter<T>) -> DxfResult<()>
+    where
+        T: Write + ?Sized,
+    {
+        writer.write_code_pair(&CodePair::new_str(304, "LEADER_LINE{"))?;
+        for vertex in &self.vertices {
+            write_point(writer, 10, vertex)?;
+        }
+        for (i, index) in self.break_point_indices.iter().enumerate() {
+            writer.write_code_pair(&CodePair::new_i32(90, *index))?;
+            if let Some(point) = self.break_start_points.get(i) {
+                write_point(writer, 11, point)?;
+            }
+            if let Some(point) = self.break_end_points.get(i) {
+                write_point(writer, 12, point)?;
+            }
+        }
+        writer.write_code_pair(&CodePair::new_i32(91, self.leader_line_index))?;
+        writer.write_code_pair(&CodePair::new_str(305, "}"))?;
+        Ok(())
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                                   MLeaderRoot
+//------------------------------------------------------------------------------
+/// Represents a single `LEADER{}` of an `MLeaderContextData`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct MLeaderRoot {
+    pub has_connection_point: bool,
+    pub has_direction: bool,
+    pub connection_point: Point,
+    pub direction: Vector,
+    pub break_start_points: Vec<Point>,
+    pub break_end_points: Vec<Point>,
+    pub leader_index: i32,
+    pub landing_distance: f64,
+    pub lines: Vec<MLeaderLine>,
+    pub attachment_direction: i16,
+}
+
+impl Default for MLeaderRoot {
+    fn default() -> Self {
+        MLeaderRoot {
+            has_connection_point: true,
+            has_direction: true,
+            connection_point: Point::origin(),
+            direction: Vector::x_axis(),
+            break_start_points: vec![],
+            break_end_points: vec![],
+            leader_index: 0,
+            landing_distance: 0.0,
+            lines: vec![],
+            attachment_direction: 0,
+        }
+    }
+}
+
+// internal visibility only
+impl MLeaderRoot {
+    pub(crate) fn read<I>(iter: &mut CodePairPutBack<I>) -> DxfResult<MLeaderRoot>
+    where
+        I: Read,
+    {
+        let mut root = MLeaderRoot::default();
+        loop {
+            let pair = match iter.next() {
+                Some(Ok(pair @ CodePair { code: 0, .. })) => {
+                    // unexpected end; put the pair back and return what we have
+                    iter.put_back(Ok(pair));
+                    return Ok(root);
+                }
+                Some(Ok(pair)) => pair,
+                Some(Err(e)) => return Err(e),
+                None => return Ok(root),
+            };
+
+            match pair.code {
+                290 => {
+                    root.has_connection_point = pair.assert_bool()?;
+                }
+                291 => {
+                    root.has_direction = pair.assert_bool()?;
+                }
+                10 => {
+                    root.connection_point.x = pair.assert_f64()?;
+                }
+                20 => {
+                    root.connection_point.y = pair.assert_f64()?;
+                }
+                30 => {
+                    root.connection_point.z = pair.assert_f64()?;
+                }
+                11 => {
+                    root.direction.x = pair.assert_f64()?;
+                }
+                21 => {
+                    root.direction.y = pair.assert_f64()?;
+                }
+                31 => {
+                    root.direction.z = pair.assert_f64()?;
+                }
+                12 => {
+                    root.break_start_points
+                        .push(Point::new(pair.assert_f64()?, 0.0, 0.0));
+                }
+                22 => {
+                    vec_last!(root.break_start_points).y = pair.assert_f64()?;
+                }
+                32 => {
+                    vec_last!(root.break_start_points).z = pair.assert_f64()?;
+                }
+                13 => {
+                    root.break_end_points
+                        .push(Point::new(pair.assert_f64()?, 0.0, 0.0));
+                }
+                23 => {
+                    vec_last!(root.break_end_points).y = pair.assert_f64()?;
+                }
+                33 => {
+                    vec_last!(root.break_end_points).z = pair.assert_f64()?;
+                }
+                90 => {
+                    root.leader_index = pair.assert_i32()?;
+                }
+                40 => {
+                    root.landing_distance = pair.assert_f64()?;
+                }
+                304 => {
+                    // value should be "LEADER_LINE{", but it doesn't really matter
+                    root.lines.push(MLeaderLine::read(iter)?);
+                }
+                271 => {
+                    root.attachment_direction = pair.assert_i16()?;
+                }
+                303 => return Ok(root), // value should be "}", but it doesn't really matter
+                _ => (),
+            }
+        }
+    }
+    pub(crate) fn write<T>(
+        &self,
+        version: AcadVersion,
+        writer: &mut CodePairWriter<T>,
+    ) -> DxfResult<()>
+    where
+        T: Write + ?Sized,
+    {
+        writer.write_code_pair(&CodePair::new_str(302, "LEADER{"))?;
+        writer.write_code_pair(&CodePair::new_bool(290, self.has_connection_point))?;
+        writer.write_code_pair(&CodePair::new_bool(291, self.has_direction))?;
+        write_point(writer, 10, &self.connection_point)?;
+        write_vector(writer, 11, &self.direction)?;
+        for (start, end) in self.break_start_points.iter().zip(&self.break_end_points) {
+            write_point(writer, 12, start)?;
+            write_point(writer, 13, end)?;
+        }
+        writer.write_code_pair(&CodePair::new_i32(90, self.leader_index))?;
+        writer.write_code_pair(&CodePair::new_f64(40, self.landing_distance))?;
+        for line in &self.lines {
+            line.write(writer)?;
+        }
+        if version >= AcadVersion::R2010 {
+            writer.write_code_pair(&CodePair::new_i16(271, self.attachment_direction))?;
+        }
+        writer.write_code_pair(&CodePair::new_str(303, "}"))?;
+        Ok(())
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                           MLeaderContextData
+//------------------------------------------------------------------------------
+/// Represents the `CONTEXT_DATA{}` of an `MLeader`.  Depending on `has_text_contents` and
+/// `has_block_contents`, either the text or the block values are used.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct MLeaderContextData {
+    pub content_scale: f64,
+    pub content_base_point: Point,
+    pub text_height: f64,
+    pub arrowhead_size: f64,
+    pub landing_gap: f64,
+    pub text_left_attachment: i16,
+    pub text_right_attachment: i16,
+    pub text_alignment_type: i16,
+    pub attachment_type: i16,
+    pub has_text_contents: bool,
+    pub text_contents: String,
+    pub text_normal: Vector,
+    pub text_style_handle: Handle,
+    pub text_location: Point,
+    pub text_direction: Vector,
+    pub text_rotation: f64,
+    pub boundary_width: f64,
+    pub boundary_height: f64,
+    pub line_spacing_factor: f64,
+    pub line_spacing_style: i16,
+    pub text_color: i32,
+    pub text_attachment: i16,
+    pub flow_direction: i16,
+    pub background_color: i32,
+    pub background_scale_factor: f64,
+    pub background_transparency: i32,
+    pub is_background_color_on: bool,
+    pub is_background_fill_on: bool,
+    pub column_type: i16,
+    pub use_text_auto_height: bool,
+    pub column_width: f64,
+    pub column_gutter: f64,
+    pub is_column_flow_reversed: bool,
+    pub column_sizes: Vec<f64>,
+    pub use_word_break: bool,
+    pub has_block_contents: bool,
+    pub block_content_handle: Handle,
+    pub block_content_normal: Vector,
+    pub block_content_position: Point,
+    pub block_content_scale: Vector,
+    pub block_content_rotation: f64,
+    pub block_content_color: i32,
+    pub block_transformation_matrix: TransformationMatrix,
+    pub plane_origin: Point,
+    pub plane_x_axis: Vector,
+    pub plane_y_axis: Vector,
+    pub is_plane_normal_reversed: bool,
+    pub leaders: Vec<MLeaderRoot>,
+    pub text_top_attachment: i16,
+    pub text_bottom_attachment: i16,
+}
+
+impl Default for MLeaderContextData {
+    fn default() -> Self {
+        MLeaderContextData {
+            content_scale: 1.0,
+            content_base_point: Point::origin(),
+            text_height: 0.18,
+            arrowhead_size: 0.18,
+            landing_gap: 0.09,
+            text_left_attachment: 1,
+            text_right_attachment: 1,
+            text_alignment_type: 0,
+            attachment_type: 0,
+            has_text_contents: false,
+            text_contents: String::new(),
+            text_normal: Vector::z_axis(),
+            text_style_handle: Handle::empty(),
+            text_location: Point::origin(),
+            text_direction: Vector::x_axis(),
+            text_rotation: 0.0,
+            boundary_width: 0.0,
+            boundary_height: 0.0,
+            line_spacing_factor: 1.0,
+            line_spacing_style: 1,
+            text_color: -1_056_964_608, // by block
+            text_attachment: 1,
+            flow_direction: 1,
+            background_color: -939_524_096, // by layer
+            background_scale_factor: 1.5,
+            background_transparency: 0,
+            is_background_color_on: false,
+            is_background_fill_on: false,
+            column_type: 0,
+            use_text_auto_height: false,
+            column_width: 0.0,
+            column_gutter: 0.0,
+            is_column_flow_reversed: false,
+            column_sizes: vec![],
+            use_word_break: true,
+            has_block_contents: false,
+            block_content_handle: Handle::empty(),
+            block_content_normal: Vector::z_axis(),
+            block_content_position: Point::origin(),
+            block_content_scale: Vector::new(1.0, 1.0, 1.0),
+            block_content_rotation: 0.0,
+            block_content_color: -1_056_964_608, // by block
+            block_transformation_matrix: TransformationMatrix::identity(),
+            plane_origin: Point::origin(),
+            plane_x_axis: Vector::x_axis(),
+            plane_y_axis: Vector::y_axis(),
+            is_plane_normal_reversed: false,
+            leaders: vec![],
+            text_top_attachment: 9,
+            text_bottom_attachment: 9,
+        }
+    }
+}
+
+// internal visibility only
+impl MLeaderContextData {
+    pub(crate) fn read<I>(iter: &mut CodePairPutBack<I>) -> DxfResult<MLeaderContextData>
+    where
+        I: Read,
+    {
+        let mut context = MLeaderContextData::default();
+        let mut matrix_values = vec![];
+        loop {
+            let pair = match iter.next() {
+                Some(Ok(pair @ CodePair { code: 0, .. })) => {
+                    // unexpected end; put the pair back and return what we have
+                    iter.put_back(Ok(pair));
+                    break;
+                }
+                Some(Ok(pair)) => pair,
+                Some(Err(e)) => return Err(e),
+                None => break,
+            };
+
+            match pair.code {
+                40 => {
+                    context.content_scale = pair.assert_f64()?;
+                }
+                10 => {
+                    context.content_base_point.x = pair.assert_f64()?;
+                }
+                20 => {
+                    context.content_base_point.y = pair.assert_f64()?;
+                }
+                30 => {
+                    context.content_base_point.z = pair.assert_f64()?;
+                }
+                41 => {
+                    context.text_height = pair.assert_f64()?;
+                }
+                140 => {
+                    context.arrowhead_size = pair.assert_f64()?;
+                }
+                145 => {
+                    context.landing_gap = pair.assert_f64()?;
+                }
+                174 => {
+                    context.text_left_attachment = pair.assert_i16()?;
+                }
+                175 => {
+                    context.text_right_attachment = pair.assert_i16()?;
+                }
+                176 => {
+                    context.text_alignment_type = pair.assert_i16()?;
+                }
+                177 => {
+                    context.attachment_type = pair.assert_i16()?;
+                }
+                290 => {
+                    context.has_text_contents = pair.assert_bool()?;
+                }
+                304 => {
+                    context.text_contents = pair.assert_string()?;
+                }
+                11 => {
+                    context.text_normal.x = pair.assert_f64()?;
+                }
+                21 => {
+                    context.text_normal.y = pair.assert_f64()?;
+                }
+                31 => {
+                    context.text_normal.z = pair.assert_f64()?;
+                }
+                340 => {
+                    context.text_style_handle = pair.as_handle()?;
+                }
+                12 => {
+                    context.text_location.x = pair.assert_f64()?;
+                }
+                22 => {
+                    context.text_location.y = pair.assert_f64()?;
+                }
+                32 => {
+                    context.text_location.z = pair.assert_f64()?;
+                }
+                13 => {
+                    context.text_direction.x = pair.assert_f64()?;
+                }
+                23 => {
+                    context.text_direction.y = pair.assert_f64()?;
+                }
+                33 => {
+                    context.text_direction.z = pair.assert_f64()?;
+                }
+                42 => {
+                    context.text_rotation = pair.assert_f64()?;
+                }
+                43 => {
+                    context.boundary_width = pair.assert_f64()?;
+                }
+                44 => {
+                    context.boundary_height = pair.assert_f64()?;
+                }
+                45 => {
+                    context.line_spacing_factor = pair.assert_f64()?;
+                }
+                170 => {
+                    context.line_spacing_style = pair.assert_i16()?;
+                }
+                90 => {
+                    context.text_color = pair.assert_i32()?;
+                }
+                171 => {
+                    context.text_attachment = pair.assert_i16()?;
+                }
+                172 => {
+                    context.flow_direction = pair.assert_i16()?;
+                }
+                91 => {
+                    context.background_color = pair.assert_i32()?;
+                }
+                141 => {
+                    context.background_scale_factor = pair.assert_f64()?;
+                }
+                92 => {
+                    context.background_transparency = pair.assert_i32()?;
+                }
+                291 => {
+                    context.is_background_color_on = pair.assert_bool()?;
+                }
+                292 => {
+                    context.is_background_fill_on = pair.assert_bool()?;
+                }
+                173 => {
+                    context.column_type = pair.assert_i16()?;
+                }
+                293 => {
+                    context.use_text_auto_height = pair.assert_bool()?;
+                }
+                142 => {
+                    context.column_width = pair.assert_f64()?;
+                }
+                143 => {
+                    context.column_gutter = pair.assert_f64()?;
+                }
+                294 => {
+                    context.is_column_flow_reversed = pair.assert_bool()?;
+                }
+                144 => {
+                    context.column_sizes.push(pair.assert_f64()?);
+                }
+                295 => {
+                    context.use_word_break = pair.assert_bool()?;
+                }
+                296 => {
+                    context.has_block_contents = pair.assert_bool()?;
+                }
+                341 => {
+                    context.block_content_handle = pair.as_handle()?;
+                }
+                14 => {
+                    context.block_content_normal.x = pair.assert_f64()?;
+                }
+                24 => {
+                    context.block_content_normal.y = pair.assert_f64()?;
+                }
+                34 => {
+                    context.block_content_normal.z = pair.assert_f64()?;
+                }
+                15 => {
+                    context.block_content_position.x = pair.assert_f64()?;
+                }
+                25 => {
+                    context.block_content_position.y = pair.assert_f64()?;
+                }
+                35 => {
+                    context.block_content_position.z = pair.assert_f64()?;
+                }
+                16 => {
+                    context.block_content_scale.x = pair.assert_f64()?;
+                }
+                26 => {
+                    context.block_content_scale.y = pair.assert_f64()?;
+                }
+                36 => {
+                    context.block_content_scale.z = pair.assert_f64()?;
+                }
+                46 => {
+                    context.block_content_rotation = pair.assert_f64()?;
+                }
+                93 => {
+                    context.block_content_color = pair.assert_i32()?;
+                }
+                47 => {
+                    matrix_values.push(pair.assert_f64()?);
+                }
+                110 => {
+                    context.plane_origin.x = pair.assert_f64()?;
+                }
+                120 => {
+                    context.plane_origin.y = pair.assert_f64()?;
+                }
+                130 => {
+                    context.plane_origin.z = pair.assert_f64()?;
+                }
+                111 => {
+                    context.plane_x_axis.x = pair.assert_f64()?;
+                }
+                121 => {
+                    context.plane_x_axis.y = pair.assert_f64()?;
+                }
+                131 => {
+                    context.plane_x_axis.z = pair.assert_f64()?;
+                }
+                112 => {
+                    context.plane_y_axis.x = pair.assert_f64()?;
+                }
+                122 => {
+                    context.plane_y_axis.y = pair.assert_f64()?;
+                }
+                132 => {
+                    context.plane_y_axis.z = pair.assert_f64()?;
+                }
+                297 => {
+                    context.is_plane_normal_reversed = pair.assert_bool()?;
+                }
+                302 => {
+                    // value should be "LEADER{", but it doesn't really matter
+                    context.leaders.push(MLeaderRoot::read(iter)?);
+                }
+                272 => {
+                    context.text_bottom_attachment = pair.assert_i16()?;
+                }
+                273 => {
+                    context.text_top_attachment = pair.assert_i16()?;
+                }
+                301 => break, // value should be "}", but it doesn't really matter
+                _ => (),
+            }
+        }
+
+        if !matrix_values.is_empty() {
+            context.block_transformation_matrix = TransformationMatrix::from_vec(&matrix_values);
+        }
+
+        Ok(context)
+    }
+    pub(crate) fn write<T>(
+        &self,
+        version: AcadVersion,
+        writer: &mut CodePairWriter<T>,
+    ) -> DxfResult<()>
+    where
+        T: Write + ?Sized,
+    {
+        writer.write_code_pair(&CodePair::new_str(300, "CONTEXT_DATA{"))?;
+        writer.write_code_pair(&CodePair::new_f64(40, self.content_scale))?;
+        write_point(writer, 10, &self.content_base_point)?;
+        writer.write_code_pair(&CodePair::new_f64(41, self.text_height))?;
+        writer.write_code_pair(&CodePair::new_f64(140, self.arrowhead_size))?;
+        writer.write_code_pair(&CodePair::new_f64(145, self.landing_gap))?;
+        writer.write_code_pair(&CodePair::new_i16(174, self.text_left_attachment))?;
+        writer.write_code_pair(&CodePair::new_i16(175, self.text_right_attachment))?;
+        writer.write_code_pair(&CodePair::new_i16(176, self.text_alignment_type))?;
+        writer.write_code_pair(&CodePair::new_i16(177, self.attachment_type))?;
+        writer.write_code_pair(&CodePair::new_bool(290, self.has_text_contents))?;
+        if self.has_text_contents {
+            writer.write_code_pair(&CodePair::new_string(304, &self.text_contents))?;
+            write_vector(writer, 11, &self.text_normal)?;
+            writer.write_code_pair(&CodePair::new_string(
+                340,
+                &as_handle(self.text_style_handle),
+            ))?;
+            write_point(writer, 12, &self.text_location)?;
+            write_vector(writer, 13, &self.text_direction)?;
+            writer.write_code_pair(&CodePair::new_f64(42, self.text_rotation))?;
+            writer.write_code_pair(&CodePair::new_f64(43, self.boundary_width))?;
+            writer.write_code_pair(&CodePair::new_f64(44, self.boundary_height))?;
+            writer.write_code_pair(&CodePair::new_f64(45, self.line_spacing_factor))?;
+            writer.write_code_pair(&CodePair::new_i16(170, self.line_spacing_style))?;
+            writer.write_code_pair(&CodePair::new_i32(90, self.text_color))?;
+            writer.write_code_pair(&CodePair::new_i16(171, self.text_attachment))?;
+            writer.write_code_pair(&CodePair::new_i16(172, self.flow_direction))?;
+            writer.write_code_pair(&CodePair::new_i32(91, self.background_color))?;
+            writer.write_code_pair(&CodePair::new_f64(141, self.background_scale_factor))?;
+            writer.write_code_pair(&CodePair::new_i32(92, self.background_transparency))?;
+            writer.write_code_pair(&CodePair::new_bool(291, self.is_background_color_on))?;
+            writer.write_code_pair(&CodePair::new_bool(292, self.is_background_fill_on))?;
+            writer.write_code_pair(&CodePair::new_i16(173, self.column_type))?;
+            writer.write_code_pair(&CodePair::new_bool(293, self.use_text_auto_height))?;
+            writer.write_code_pair(&CodePair::new_f64(142, self.column_width))?;
+            writer.write_code_pair(&CodePair::new_f64(143, self.column_gutter))?;
+            writer.write_code_pair(&CodePair::new_bool(294, self.is_column_flow_reversed))?;
+            for size in &self.column_sizes {
+                writer.write_code_pair(&CodePair::new_f64(144, *size))?;
+            }
+            writer.write_code_pair(&CodePair::new_bool(295, self.use_word_break))?;
+        }
+        writer.write_code_pair(&CodePair::new_bool(296, self.has_block_contents))?;
+        if self.has_block_contents {
+            writer.write_code_pair(&CodePair::new_string(
+                341,
+                &as_handle(self.block_content_handle),
+            ))?;
+            write_vector(writer, 14, &self.block_content_normal)?;
+            write_point(writer, 15, &self.block_content_position)?;
+            write_vector(writer, 16, &self.block_content_scale)?;
+            writer.write_code_pair(&CodePair::new_f64(46, self.block_content_rotation))?;
+            writer.write_code_pair(&CodePair::new_i32(93, self.block_content_color))?;
+            for value in self.block_transformation_matrix.get_values() {
+                writer.write_code_pair(&CodePair::new_f64(47, value))?;
+            }
+        }
+        write_point_with_codes(writer, 110, 120, 130, &self.plane_origin)?;
+        write_vector_with_codes(writer, 111, 121, 131, &self.plane_x_axis)?;
+        write_vector_with_codes(writer, 112, 122, 132, &self.plane_y_axis)?;
+        writer.write_code_pair(&CodePair::new_bool(297, self.is_plane_normal_reversed))?;
+        for leader in &self.leaders {
+            leader.write(version, writer)?;
+        }
+        if version >= AcadVersion::R2010 {
+            writer.write_code_pair(&CodePair::new_i16(272, self.text_bottom_attachment))?;
+            writer.write_code_pair(&CodePair::new_i16(273, self.text_top_attachment))?;
+        }
+        writer.write_code_pair(&CodePair::new_str(301, "}"))?;
+        Ok(())
+    }
+}
+
+fn write_point<T>(writer: &mut CodePairWriter<T>, code: i32, point: &Point) -> DxfResult<()>
+where
+    T: Write + ?Sized,
+{
+    write_point_with_codes(writer, code, code + 10, code + 20, point)
+}
+
+fn write_vector<T>(writer: &mut CodePairWriter<T>, code: i32, vector: &Vector) -> DxfResult<()>
+where
+    T: Write + ?Sized,
+{
+    write_vector_with_codes(writer, code, code + 10, code + 20, vector)
+}
+
+fn write_point_with_codes<T>(
+    writer: &mut CodePairWriter<T>,
+    x_code: i32,
+    y_code: i32,
+    z_code: i32,
+    point: &Point,
+) -> DxfResult<()>
+where
+    T: Write + ?Sized,
+{
+    writer.write_code_pair(&CodePair::new_f64(x_code, point.x))?;
+    writer.write_code_pair(&CodePair::new_f64(y_code, point.y))?;
+    writer.write_code_pair(&CodePair::new_f64(z_code, point.z))?;
+    Ok(())
+}
+
+fn write_vector_with_codes<T>(
+    writer: &mut CodePairWriter<T>,
+    x_code: i32,
+    y_code: i32,
+    z_code: i32,
+    vector: &Vector,
+) -> DxfResult<()>
+where
+    T: Write + ?Sized,
+{
+    writer.write_code_pair(&CodePair::new_f64(x_code, vector.x))?;
+    writer.write_code_pair(&CodePair::new_f64(y_code, vector.y))?;
+    writer.write_code_pair(&CodePair::new_f64(z_code, vector.z))?;
+    Ok(())
+}
@@ -9,15 +9,17 @@ extern crate chrono;
 use self::chrono::Duration;
 
 use crate::{
-    CodePair, Color, DataTableValue, DxfError, DxfResult, Point, SectionTypeSettings,
-    TableCellStyle, TransformationMatrix,
+    CodePair, CodePairValue, Color, DataTableValue, Drawing, DrawingItem, DxfError, DxfResult,
+    Handle, Point, SectionTypeSettings, TableCellStyle, TransformationMatrix,
 };
 
 use crate::code_pair_put_back::CodePairPutBack;
 use crate::code_pair_writer::CodePairWriter;
+use crate::entities::*;
 use crate::enums::*;
 use crate::helper_functions::*;
 use crate::objects::*;
+use crate::x_data;
 
 //------------------------------------------------------------------------------
 //                                                                  GeoMeshPoint
@@ -70,6 +72,68 @@ impl DataTable {
     }
 }
 
+//------------------------------------------------------------------------------
+//                                                                        Layout
+//------------------------------------------------------------------------------
+impl Layout {
+    /// Gets the `Viewport` entities placed on this layout's paper space block.
+    pub fn viewports<'a>(&self, drawing: &'a Drawing) -> Vec<&'a Viewport> {
+        let block_record = match drawing.get_item_by_handle(self.__viewport_handle) {
+            Some(DrawingItem::BlockRecord(block_record)) => block_record,
+            _ => return vec![],
+        };
+
+        // the active layout keeps its entities in the ENTITIES section instead of its block
+        let is_active_layout = block_record.name.eq_ignore_ascii_case("*Paper_Space");
+        let active_entities = drawing
+            .entities()
+            .filter(move |e| is_active_layout && e.common.is_in_paper_space);
+        let block_entities = drawing
+            .blocks()
+            .filter(|b| b.name.eq_ignore_ascii_case(&block_record.name))
+            .flat_map(|b| b.entities.iter());
+        active_entities
+            .chain(block_entities)
+            .filter_map(|e| match e.specific {
+                EntityType::Viewport(ref v) => Some(v),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                                 UnknownObject
+//------------------------------------------------------------------------------
+/// Represents an object of a type that isn't otherwise supported, e.g., one defined by a third-party
+/// application.  The object-specific code pairs are kept as-is and written back unchanged.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct UnknownObject {
+    pub type_string: String,
+    /// The raw code pairs following the common object values, including any subclass markers.
+    pub code_pairs: Vec<CodePair>,
+}
+
+impl UnknownObject {
+    pub fn new(type_string: &str) -> Self {
+        UnknownObject {
+            type_string: String::from(type_string),
+            code_pairs: vec![],
+        }
+    }
+    /// Returns the subclass markers (code 100) of the object-specific data.
+    pub fn subclass_markers(&self) -> impl Iterator<Item = &str> {
+        self.code_pairs
+            .iter()
+            .filter(|pair| pair.code == 100)
+            .filter_map(|pair| match pair.value {
+                CodePairValue::Str(ref s) => Some(s.as_str()),
+                _ => None,
+            })
+    }
+}
+
 //------------------------------------------------------------------------------
 //                                                                    VbaProject
 //------------------------------------------------------------------------------
@@ -114,69 +178,131 @@ impl Object {
         self.common.normalize();
         // no object-specific values to set
     }
-    pub(crate) fn read<I>(iter: &mut CodePairPutBack<I>) -> DxfResult<Option<Object>>
-    where
-        I: Read,
-    {
-        loop {
-            match iter.next() {
-                // first code pair must be 0/object-type
-                Some(Ok(pair @ CodePair { code: 0, .. })) => {
-                    let type_string = pair.assert_string()?;
-                    if type_string == "ENDSEC" || type_string == "ENDBLK" {
-                        iter.put_back(Ok(pair));
-                        return Ok(None);
+    /// Removes any pointers to the item with the specified handle.  Returns `true` if anything was
+    /// removed.
+    pub(crate) fn remove_references_to(&mut self, handle: Handle) -> bool {
+        let mut removed = self.common.remove_pointers_to(handle);
+        for group in self.common.extension_data_groups.iter_mut() {
+            removed |= group.remove_handle(handle);
+        }
+
+        match self.specific {
+            ObjectType::Dictionary(ref mut dict) => {
+                let count = dict.value_handles.len();
+                dict.value_handles.retain(|_, h| *h != handle);
+                removed |= dict.value_handles.len() != count;
+            }
+            ObjectType::DictionaryWithDefault(ref mut dict) => {
+                let count = dict.value_handles.len();
+                dict.value_handles.retain(|_, h| *h != handle);
+                removed |= dict.value_handles.len() != count;
+                if dict.default_handle == handle {
+                    dict.default_handle = Handle::empty();
+                    removed = true;
+                }
+            }
+            ObjectType::SectionSettings(ref mut settings) => {
+                for geometry in settings.geometry_settings.iter_mut() {
+                    let count = geometry.source_object_handles.len();
+                    geometry.source_object_handles.retain(|&h| h != handle);
+                    removed |= geometry.source_object_handles.len() != count;
+                    if geometry.destination_object_handle == handle {
+                        geometry.destination_object_handle = Handle::empty();
+                        removed = true;
+                    }
+                }
+            }
+            ObjectType::SortentsTable(ref mut sortents) => {
+                // the entity and sort handles are parallel lists and have to stay in sync; the sort
+                // handles themselves don't refer to anything
+                while let Some(index) = sortents.__entities_handle.iter().position(|&h| h == handle)
+                {
+                    sortents.__entities_handle.remove(index);
+                    if index < sortents.__sort_items_handle.len() {
+                        sortents.__sort_items_handle.remove(index);
                     }
 
-                    match ObjectType::from_type_string(&type_string) {
-                        Some(e) => {
-                            let mut obj = Object::new(e);
-                            if !obj.apply_custom_reader(iter)? {
-                                // no custom reader, use the auto-generated one
-                                loop {
-                                    match iter.next() {
-                                        Some(Ok(pair @ CodePair { code: 0, .. })) => {
-                                            // new object or ENDSEC
-                                            iter.put_back(Ok(pair));
-                                            break;
-                                        }
-                                        Some(Ok(pair)) => obj.apply_code_pair(&pair, iter)?,
-                                        Some(Err(e)) => return Err(e),
-                                        None => return Err(DxfError::UnexpectedEndOfInput),
-                                    }
-                                }
+                    removed = true;
+                }
 
-                                obj.post_parse(pair.offset)?;
-                            }
+                return removed;
+            }
+            _ => (),
+        }
 
-                            return Ok(Some(obj));
-                        }
-                        None => {
-                            // swallow unsupported object
-                            loop {
-                                match iter.next() {
-                                    Some(Ok(pair @ CodePair { code: 0, .. })) => {
-                                        // found another object or ENDSEC
-                                        iter.put_back(Ok(pair));
-                                        break;
-                                    }
-                                    Some(Ok(_)) => (), // part of the unsupported object
-                                    Some(Err(e)) => return Err(e),
-                                    None => return Err(DxfError::UnexpectedEndOfInput),
-                                }
+        removed |= self.specific.remove_pointers_to(handle);
+        removed
+    }
+    /// Collects the handles of the items this object refers to.
+    pub(crate) fn collect_references(&self, handles: &mut Vec<Handle>) {
+        for group in &self.common.extension_data_groups {
+            group.collect_handles(handles);
+        }
+        for x_data in &self.common.x_data {
+            x_data.collect_handles(handles);
+        }
+
+        self.specific.collect_pointers(handles);
+        match self.specific {
+            ObjectType::Dictionary(ref dict) => {
+                handles.extend(dict.value_handles.values().cloned())
+            }
+            ObjectType::DictionaryWithDefault(ref dict) => {
+                handles.extend(dict.value_handles.values().cloned());
+                handles.push(dict.default_handle);
+            }
+            ObjectType::SectionSettings(ref settings) => {
+                for geometry in &settings.geometry_settings {
+                    handles.extend(geometry.source_object_handles.iter().cloned());
+                    handles.push(geometry.destination_object_handle);
+                }
+            }
+            _ => (),
+        }
+    }
+    pub(crate) fn read<I>(iter: &mut CodePairPutBack<I>) -> DxfResult<Option<Object>>
+    where
+        I: Read,
+    {
+        match iter.next() {
+            // first code pair must be 0/object-type
+            Some(Ok(pair @ CodePair { code: 0, .. })) => {
+                let type_string = pair.assert_string()?;
+                if type_string == "ENDSEC" || type_string == "ENDBLK" {
+                    iter.put_back(Ok(pair));
+                    return Ok(None);
+                }
+
+                // unsupported objects are kept as raw code pairs
+                let specific = ObjectType::from_type_string(&type_string)
+                    .unwrap_or_else(|| ObjectType::Unknown(UnknownObject::new(&type_string)));
+                let mut obj = Object::new(specific);
+                if !obj.apply_custom_reader(iter)? {
+                    // no custom reader, use the auto-generated one
+                    loop {
+                        match iter.next() {
+                            Some(Ok(pair @ CodePair { code: 0, .. })) => {
+                                // new object or ENDSEC
+                                iter.put_back(Ok(pair));
+                                break;
                             }
+                            Some(Ok(pair)) => obj.apply_code_pair(&pair, iter)?,
+                            Some(Err(e)) => return Err(e),
+                            None => return Err(DxfError::UnexpectedEndOfInput),
                         }
                     }
+
+                    obj.post_parse(pair.offset)?;
                 }
-                Some(Ok(pair)) => {
-                    return Err(DxfError::UnexpectedCodePair(
-                        pair,
-                        String::from("expected 0/object-type or 0/ENDSEC"),
-                    ))
-                }
-                Some(Err(e)) => return Err(e),
-                None => return Err(DxfError::UnexpectedEndOfInput),
+
+                Ok(Some(obj))
             }
+            Some(Ok(pair)) => Err(DxfError::UnexpectedCodePair(
+                pair,
+                String::from("expected 0/object-type or 0/ENDSEC"),
+            )),
+            Some(Err(e)) => Err(e),
+            None => Err(DxfError::UnexpectedEndOfInput),
         }
     }
     fn apply_code_pair<I>(
@@ -342,6 +468,9 @@ impl Object {
             ObjectType::XRecordObject(ref mut xr) => {
                 Object::apply_custom_reader_xrecordobject(&mut self.common, xr, iter)
             }
+            ObjectType::Unknown(ref mut unknown) => {
+                Object::apply_custom_reader_unknown(&mut self.common, unknown, iter)
+            }
             _ => Ok(false), // no custom reader
         }
     }
@@ -1542,6 +1671,31 @@ impl Object {
             }
         }
     }
+    fn apply_custom_reader_unknown<I>(
+        common: &mut ObjectCommon,
+        unknown: &mut UnknownObject,
+        iter: &mut CodePairPutBack<I>,
+    ) -> DxfResult<bool>
+    where
+        I: Read,
+    {
+        // only the values before the first subclass marker and the trailing XDATA are common
+        let mut reading_common = true;
+        loop {
+            let pair = next_pair!(iter);
+            if pair.code == 100 {
+                reading_common = false;
+            }
+
+            if (reading_common || pair.code == x_data::XDATA_APPLICATIONNAME)
+                && common.apply_individual_pair(&pair, iter)?
+            {
+                continue;
+            }
+
+            unknown.code_pairs.push(pair);
+        }
+    }
     pub(crate) fn write<T>(
         &self,
         version: AcadVersion,
@@ -1748,6 +1902,11 @@ impl Object {
                     writer.write_code_pair(&pair)?;
                 }
             }
+            ObjectType::Unknown(ref unknown) => {
+                for pair in &unknown.code_pairs {
+                    writer.write_code_pair(pair)?;
+                }
+            }
             _ => return Ok(false), // no custom writer
         }
 
@@ -1767,9 +1926,11 @@ impl Object {
 
 #[cfg(test)]
 mod tests {
+    use crate::entities::*;
     use crate::enums::*;
     use crate::helper_functions::tests::*;
     use crate::objects::*;
+    use crate::tables::*;
     use crate::*;
 
     fn read_object(object_type: &str, body: String) -> Object {
@@ -1812,7 +1973,73 @@ mod tests {
             .join("\r\n")
             .as_str(),
         );
-        assert_eq!(0, drawing.objects().count());
+        let objects = drawing.objects().collect::<Vec<_>>();
+        assert_eq!(1, objects.len());
+        match objects[0].specific {
+            ObjectType::Unknown(ref unknown) => {
+                assert_eq!("UNSUPPORTED_OBJECT", unknown.type_string);
+                assert_eq!(
+                    vec![CodePair::new_str(1, "unsupported string")],
+                    unknown.code_pairs
+                );
+            }
+            _ => panic!("expected an unknown object"),
+        }
+    }
+
+    #[test]
+    fn round_trip_unsupported_object() {
+        let mut drawing = from_section(
+            "OBJECTS",
+            [
+                "0",
+                "CUSTOM_OBJECT",
+                "5",
+                "A1",
+                "330",
+                "C",
+                "100",
+                "AcDbCustomObject",
+                "330",
+                "D",
+                "70",
+                "3",
+            ]
+            .join("\r\n")
+            .as_str(),
+        );
+        let objects = drawing.objects().collect::<Vec<_>>();
+        assert_eq!(1, objects.len());
+        assert_eq!(Handle(0xA1), objects[0].common.handle);
+        assert_eq!(Handle(0xC), objects[0].common.__owner_handle);
+        match objects[0].specific {
+            ObjectType::Unknown(ref unknown) => {
+                assert_eq!(
+                    vec!["AcDbCustomObject"],
+                    unknown.subclass_markers().collect::<Vec<_>>()
+                );
+                assert_eq!(3, unknown.code_pairs.len());
+            }
+            _ => panic!("expected an unknown object"),
+        }
+        drawing.header.version = AcadVersion::R2000;
+        assert_contains(
+            &drawing,
+            ["100", "AcDbCustomObject", "330", "D", " 70", "     3"].join("\r\n"),
+        );
+    }
+
+    #[test]
+    fn unsupported_object_is_not_written_on_r12() {
+        let mut unknown = UnknownObject::new("CUSTOM_OBJECT");
+        unknown
+            .code_pairs
+            .push(CodePair::new_str(100, "AcDbCustomObject"));
+        let mut drawing = Drawing::new();
+        drawing.header.version = AcadVersion::R12;
+        drawing.add_object(Object::new(ObjectType::Unknown(unknown)));
+        assert_not_contains(&drawing, String::from("CUSTOM_OBJECT"));
+        assert_not_contains(&drawing, String::from("AcDbCustomObject"));
     }
 
     #[test]
@@ -1840,12 +2067,18 @@ mod tests {
             .as_str(),
         );
         let objects = drawing.objects().collect::<Vec<_>>();
-        assert_eq!(2, objects.len());
+        assert_eq!(3, objects.len());
         match objects[0].specific {
             ObjectType::DictionaryVariable(_) => (),
             _ => panic!("expected a dictionary variable"),
         }
         match objects[1].specific {
+            ObjectType::Unknown(ref unknown) => {
+                assert_eq!("UNSUPPORTED_OBJECT", unknown.type_string)
+            }
+            _ => panic!("expected an unknown object"),
+        }
+        match objects[2].specific {
             ObjectType::ImageDefinition(_) => (),
             _ => panic!("expected an image definition"),
         }
@@ -1854,7 +2087,7 @@ mod tests {
     #[test]
     fn read_common_object_fields() {
         let obj = read_object("IMAGEDEF", vec!["5", "DEADBEEF"].join("\r\n"));
-        assert_eq!(0xDEAD_BEEF, obj.common.handle);
+        assert_eq!(Handle(0xDEAD_BEEF), obj.common.handle);
     }
 
     #[test]
@@ -2078,6 +2311,73 @@ mod tests {
         );
     }
 
+    #[test]
+    fn layout_viewports() {
+        let mut drawing = Drawing::new();
+        let active_handle = drawing
+            .add_block_record(BlockRecord {
+                name: String::from("*Paper_Space"),
+                ..Default::default()
+            })
+            .handle;
+        let inactive_handle = drawing
+            .add_block_record(BlockRecord {
+                name: String::from("*Paper_Space0"),
+                ..Default::default()
+            })
+            .handle;
+
+        // the active layout's viewports are in the ENTITIES section
+        let mut ent = Entity::new(EntityType::Viewport(Viewport {
+            viewport_id: 2,
+            ..Default::default()
+        }));
+        ent.common.is_in_paper_space = true;
+        drawing.add_entity(ent);
+        drawing.add_entity(Entity::new(EntityType::Viewport(Viewport {
+            viewport_id: 3, // model space; not part of any layout
+            ..Default::default()
+        })));
+
+        // other layouts keep their viewports in their block
+        let mut block = Block {
+            name: String::from("*Paper_Space0"),
+            ..Default::default()
+        };
+        block
+            .entities
+            .push(Entity::new(EntityType::Viewport(Viewport {
+                viewport_id: 4,
+                ..Default::default()
+            })));
+        block
+            .entities
+            .push(Entity::new(EntityType::Line(Default::default())));
+        drawing.add_block(block);
+
+        let active = Layout {
+            __viewport_handle: active_handle,
+            ..Default::default()
+        };
+        let ids = active
+            .viewports(&drawing)
+            .iter()
+            .map(|v| v.viewport_id)
+            .collect::<Vec<_>>();
+        assert_eq!(vec![2], ids);
+
+        let inactive = Layout {
+            __viewport_handle: inactive_handle,
+            ..Default::default()
+        };
+        let ids = inactive
+            .viewports(&drawing)
+            .iter()
+            .map(|v| v.viewport_id)
+            .collect::<Vec<_>>();
+        assert_eq!(vec![4], ids);
+    }
+
     #[test]
     fn read_object_with_handles() {
         let obj = read_object(
@@ -2088,8 +2388,8 @@ mod tests {
             ]
             .join("\r\n"),
         );
-        assert_eq!(0xa1, obj.common.handle);
-        assert_eq!(0xa2, obj.common.__owner_handle);
+        assert_eq!(Handle(0xa1), obj.common.handle);
+        assert_eq!(Handle(0xa2), obj.common.__owner_handle);
         match obj.specific {
             ObjectType::LightList(_) => (),
             _ => panic!("expected a light list"),
@@ -2102,7 +2402,7 @@ mod tests {
         drawing.header.version = AcadVersion::R2007; // LIGHTLIST only supported up to 2007
         drawing.add_object(Object {
             common: ObjectCommon {
-                __owner_handle: 0xa2,
+                __owner_handle: Handle(0xa2),
                 ..Default::default()
             },
             specific: ObjectType::LightList(Default::default()),
@@ -2122,8 +2422,8 @@ mod tests {
         match dict.specific {
             ObjectType::Dictionary(ref dict) => {
                 assert_eq!(2, dict.value_handles.len());
-                assert_eq!(Some(&0xAAAA), dict.value_handles.get("key1"));
-                assert_eq!(Some(&0xBBBB), dict.value_handles.get("key2"));
+                assert_eq!(Some(&Handle(0xAAAA)), dict.value_handles.get("key1"));
+                assert_eq!(Some(&Handle(0xBBBB)), dict.value_handles.get("key2"));
             }
             _ => panic!("expected a dictionary"),
         }
@@ -2132,8 +2432,10 @@ mod tests {
     #[test]
     fn write_dictionary() {
         let mut dict = Dictionary::default();
-        dict.value_handles.insert(String::from("key1"), 0xAAAA);
-        dict.value_handles.insert(String::from("key2"), 0xBBBB);
+        dict.value_handles
+            .insert(String::from("key1"), Handle(0xAAAA));
+        dict.value_handles
+            .insert(String::from("key2"), Handle(0xBBBB));
         let mut drawing = Drawing::new();
         drawing.add_object(Object {
             common: Default::default(),
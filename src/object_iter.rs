@@ -2,18 +2,51 @@ use std::io::Read;
 
 use crate::code_pair_put_back::CodePairPutBack;
 use crate::objects::Object;
+use crate::{DiagnosticAction, DxfError, DxfResult};
 
 pub(crate) struct ObjectIter<'a, I: 'a + Read> {
-    pub iter: &'a mut CodePairPutBack<I>,
+    iter: &'a mut CodePairPutBack<I>,
+    error: Option<DxfError>,
 }
 
 impl<'a, I: 'a + Read> Iterator for ObjectIter<'a, I> {
     type Item = Object;
 
     fn next(&mut self) -> Option<Object> {
-        match Object::read(self.iter) {
-            Ok(Some(o)) => Some(o),
-            Ok(None) | Err(_) => None,
+        loop {
+            let position = self.iter.read_position();
+            match Object::read(self.iter) {
+                Ok(Some(o)) => return Some(o),
+                Ok(None) => return None,
+                Err(e) if self.iter.is_lenient() && e.is_item_error() => {
+                    self.iter.add_diagnostic(&e, DiagnosticAction::SkippedItem);
+                    if self.iter.read_position() == position {
+                        // nothing was consumed; drop the offending pair so we don't try it again
+                        let _ = self.iter.next();
+                    }
+                    if let Err(e) = self.iter.skip_to_next_item() {
+                        self.error = Some(e);
+                        return None;
+                    }
+                }
+                Err(e) => {
+                    self.error = Some(e);
+                    return None;
+                }
+            }
+        }
+    }
+}
+
+impl<'a, I: 'a + Read> ObjectIter<'a, I> {
+    pub(crate) fn new(iter: &'a mut CodePairPutBack<I>) -> Self {
+        ObjectIter { iter, error: None }
+    }
+    /// Returns the error that ended the iteration, if any.
+    pub(crate) fn take_error(&mut self) -> DxfResult<()> {
+        match self.error.take() {
+            Some(e) => Err(e),
+            None => Ok(()),
         }
     }
 }
@@ -1,4 +1,6 @@
-use crate::{CodePair, DxfError, DxfResult};
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+
+use crate::{CodePair, DxfError, DxfResult, Vector};
 
 /// Represents a simple point in Cartesian space.
 #[derive(Clone, Debug, Default, PartialEq)]
@@ -21,6 +23,22 @@ impl Point {
     pub fn origin() -> Point {
         Point::new(0.0, 0.0, 0.0)
     }
+    /// Returns the distance between this point and `other`.
+    pub fn distance_to(&self, other: &Point) -> f64 {
+        (other - self).length()
+    }
+    /// Returns the point halfway between this point and `other`.
+    pub fn midpoint(&self, other: &Point) -> Point {
+        Point::new(
+            (self.x + other.x) / 2.0,
+            (self.y + other.y) / 2.0,
+            (self.z + other.z) / 2.0,
+        )
+    }
+    /// Returns the vector from the origin to this point.
+    pub fn to_vector(&self) -> Vector {
+        Vector::new(self.x, self.y, self.z)
+    }
     pub(crate) fn set(&mut self, pair: &CodePair) -> DxfResult<()> {
         match pair.code {
             10 => self.x = pair.assert_f64()?,
@@ -37,3 +55,74 @@ impl Point {
         Ok(())
     }
 }
+
+fn add_vector(point: &Point, vector: &Vector) -> Point {
+    Point::new(point.x + vector.x, point.y + vector.y, point.z + vector.z)
+}
+
+fn sub_vector(point: &Point, vector: &Vector) -> Point {
+    Point::new(point.x - vector.x, point.y - vector.y, point.z - vector.z)
+}
+
+fn sub_point(a: &Point, b: &Point) -> Vector {
+    Vector::new(a.x - b.x, a.y - b.y, a.z - b.z)
+}
+
+impl_binary_op!(Add, add, Point, Vector, Point, add_vector);
+impl_binary_op!(Sub, sub, Point, Vector, Point, sub_vector);
+impl_binary_op!(Sub, sub, Point, Point, Vector, sub_point);
+
+impl AddAssign<Vector> for Point {
+    fn add_assign(&mut self, rhs: Vector) {
+        *self += &rhs;
+    }
+}
+
+impl<'a> AddAssign<&'a Vector> for Point {
+    fn add_assign(&mut self, rhs: &'a Vector) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+        self.z += rhs.z;
+    }
+}
+
+impl SubAssign<Vector> for Point {
+    fn sub_assign(&mut self, rhs: Vector) {
+        *self -= &rhs;
+    }
+}
+
+impl<'a> SubAssign<&'a Vector> for Point {
+    fn sub_assign(&mut self, rhs: &'a Vector) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+        self.z -= rhs.z;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Point, Vector};
+
+    #[test]
+    fn point_vector_arithmetic() {
+        let p = Point::new(1.0, 2.0, 3.0);
+        let v = Vector::new(1.0, 1.0, 1.0);
+        assert_eq!(Point::new(2.0, 3.0, 4.0), &p + &v);
+        assert_eq!(Point::new(0.0, 1.0, 2.0), &p - &v);
+        assert_eq!(Vector::new(1.0, 2.0, 3.0), &p - Point::origin());
+
+        let mut q = p.clone();
+        q += &v;
+        q -= Vector::new(0.0, 0.0, 4.0);
+        assert_eq!(Point::new(2.0, 3.0, 0.0), q);
+    }
+
+    #[test]
+    fn point_distance() {
+        let a = Point::new(1.0, 1.0, 0.0);
+        let b = Point::new(4.0, 5.0, 0.0);
+        assert_eq!(5.0, a.distance_to(&b));
+        assert_eq!(Point::new(2.5, 3.0, 0.0), a.midpoint(&b));
+    }
+}
@@ -0,0 +1,77 @@
+use crate::Handle;
+
+/// Specifies what `Drawing::purge` may remove.  Only unused items are ever removed and the defaults
+/// created by `Drawing::normalize`, e.g., layer `0` or the `STANDARD` text style, are always kept.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct PurgeOptions {
+    pub layers: bool,
+    pub line_types: bool,
+    pub text_styles: bool,
+    pub dim_styles: bool,
+    pub mline_styles: bool,
+    /// Block definitions that aren't inserted or otherwise referenced, along with their block records.
+    pub blocks: bool,
+    /// Objects that aren't referenced by any other item.
+    pub orphaned_objects: bool,
+    /// Whether to keep purging items that became unused by the previous pass, e.g., a line type only
+    /// used by a purged layer.
+    pub nested: bool,
+}
+
+impl Default for PurgeOptions {
+    fn default() -> Self {
+        PurgeOptions::all()
+    }
+}
+
+// public implementation
+impl PurgeOptions {
+    /// Purges everything that's unused.
+    pub fn all() -> Self {
+        PurgeOptions {
+            layers: true,
+            line_types: true,
+            text_styles: true,
+            dim_styles: true,
+            mline_styles: true,
+            blocks: true,
+            orphaned_objects: true,
+            nested: true,
+        }
+    }
+}
+
+/// The items removed by `Drawing::purge`.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct PurgeReport {
+    /// The names of the removed layers.
+    pub layers: Vec<String>,
+    /// The names of the removed line types.
+    pub line_types: Vec<String>,
+    /// The names of the removed text styles.
+    pub text_styles: Vec<String>,
+    /// The names of the removed dimension styles.
+    pub dim_styles: Vec<String>,
+    /// The names of the removed `MLineStyle` objects.
+    pub mline_styles: Vec<String>,
+    /// The names of the removed blocks.
+    pub blocks: Vec<String>,
+    /// The handles of the removed orphaned objects.
+    pub objects: Vec<Handle>,
+}
+
+// public implementation
+impl PurgeReport {
+    /// Returns `true` if nothing was removed.
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+            && self.line_types.is_empty()
+            && self.text_styles.is_empty()
+            && self.dim_styles.is_empty()
+            && self.mline_styles.is_empty()
+            && self.blocks.is_empty()
+            && self.objects.is_empty()
+    }
+}
@@ -0,0 +1,23 @@
+use crate::Handle;
+
+/// Determines what `Drawing::remove_by_handle` does with entities that still use a removed layer,
+/// line type, text style or dimension style.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum RemovalMode {
+    /// Removing an item that's still in use fails with `DxfError::ItemInUse`.
+    Strict,
+    /// The entities using the item are removed along with it.
+    Cascade,
+}
+
+/// The changes made by `Drawing::remove_by_handle`.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct RemovalReport {
+    /// The handles of all removed items, starting with the requested one.  Empty if no item had the
+    /// requested handle.
+    pub removed: Vec<Handle>,
+    /// The handles of the items that referred to a removed item and were updated to no longer do so.
+    pub updated: Vec<Handle>,
+}
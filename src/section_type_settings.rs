@@ -1,6 +1,6 @@
 use std::io::{Read, Write};
 
-use crate::{CodePair, DxfResult, SectionGeometrySettings};
+use crate::{CodePair, DxfResult, Handle, SectionGeometrySettings};
 
 use crate::code_pair_put_back::CodePairPutBack;
 use crate::code_pair_writer::CodePairWriter;
@@ -11,8 +11,8 @@ use crate::helper_functions::*;
 pub struct SectionTypeSettings {
     pub section_type: i32,
     pub is_generation_option: bool,
-    pub source_object_handles: Vec<u32>,
-    pub destination_object_handle: u32,
+    pub source_object_handles: Vec<Handle>,
+    pub destination_object_handle: Handle,
     pub destination_file_name: String,
     pub geometry_settings: Vec<SectionGeometrySettings>,
 }
@@ -23,7 +23,7 @@ impl Default for SectionTypeSettings {
             section_type: 0,
             is_generation_option: false,
             source_object_handles: vec![],
-            destination_object_handle: 0,
+            destination_object_handle: Handle::empty(),
             destination_file_name: String::new(),
             geometry_settings: vec![],
         }
@@ -0,0 +1,402 @@
+use enum_primitive::FromPrimitive;
+use std::io::{Read, Write};
+
+use crate::{CodePair, Color, DxfResult, Handle};
+
+use crate::code_pair_put_back::CodePairPutBack;
+use crate::code_pair_writer::CodePairWriter;
+use crate::enums::*;
+use crate::helper_functions::*;
+
+//------------------------------------------------------------------------------
+//                                                                     TableCell
+//------------------------------------------------------------------------------
+/// Represents a single cell of an `AcadTable`.
+///
+/// Override values are only present when the cell deviates from the table's style.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct TableCell {
+    pub cell_type: TableCellType,
+    pub flags: i16,
+    pub is_merged: bool,
+    pub is_autofit: bool,
+    /// The number of columns spanned by a merged cell.
+    pub merged_column_count: i16,
+    /// The number of rows spanned by a merged cell.
+    pub merged_row_count: i16,
+    pub override_flags: i32,
+    pub virtual_edge_flags: i16,
+    pub rotation: f64,
+
+    // text content
+    pub text: String,
+    pub extended_text: Vec<String>,
+    pub field_handle: Handle,
+
+    // block content
+    pub block_handle: Handle,
+    pub block_scale: f64,
+    /// Attribute definition handles and their values.
+    pub block_attributes: Vec<(Handle, String)>,
+
+    // overrides
+    pub text_style_name: Option<String>,
+    pub text_height: Option<f64>,
+    pub cell_alignment: Option<i16>,
+    pub text_color: Option<Color>,
+    pub fill_color: Option<Color>,
+    pub is_background_fill_enabled: Option<bool>,
+    pub top_border_color: Option<Color>,
+    pub right_border_color: Option<Color>,
+    pub bottom_border_color: Option<Color>,
+    pub left_border_color: Option<Color>,
+    pub top_border_lineweight: Option<i16>,
+    pub right_border_lineweight: Option<i16>,
+    pub bottom_border_lineweight: Option<i16>,
+    pub left_border_lineweight: Option<i16>,
+    pub is_top_border_visible: Option<bool>,
+    pub is_right_border_visible: Option<bool>,
+    pub is_bottom_border_visible: Option<bool>,
+    pub is_left_border_visible: Option<bool>,
+}
+
+impl Default for TableCell {
+    fn default() -> Self {
+        TableCell {
+            cell_type: TableCellType::Text,
+            flags: 0,
+            is_merged: false,
+            is_autofit: false,
+            merged_column_count: 1,
+            merged_row_count: 1,
+            override_flags: 0,
+            virtual_edge_flags: 0,
+            rotation: 0.0,
+            text: String::new(),
+            extended_text: vec![],
+            field_handle: Handle::empty(),
+            block_handle: Handle::empty(),
+            block_scale: 1.0,
+            block_attributes: vec![],
+            text_style_name: None,
+            text_height: None,
+            cell_alignment: None,
+            text_color: None,
+            fill_color: None,
+            is_background_fill_enabled: None,
+            top_border_color: None,
+            right_border_color: None,
+            bottom_border_color: None,
+            left_border_color: None,
+            top_border_lineweight: None,
+            right_border_lineweight: None,
+            bottom_border_lineweight: None,
+            left_border_lineweight: None,
+            is_top_border_visible: None,
+            is_right_border_visible: None,
+            is_bottom_border_visible: None,
+            is_left_border_visible: None,
+        }
+    }
+}
+
+impl TableCell {
+    pub fn new_text(text: &str) -> Self {
+        TableCell {
+            text: String::from(text),
+            ..Default::default()
+        }
+    }
+    pub fn new_block(block_handle: Handle) -> Self {
+        TableCell {
+            cell_type: TableCellType::Block,
+            block_handle,
+            ..Default::default()
+        }
+    }
+}
+
+// internal visibility only
+impl TableCell {
+    #[allow(clippy::cognitive_complexity)]
+    pub(crate) fn read<I>(iter: &mut CodePairPutBack<I>) -> DxfResult<Option<TableCell>>
+    where
+        I: Read,
+    {
+        // the first pair must be 171/cell type or this isn't a cell
+        let mut cell = TableCell::default();
+        match iter.next() {
+            Some(Ok(pair @ CodePair { code: 171, .. })) => {
+                cell.cell_type =
+                    enum_from_number!(TableCellType, Text, from_i16, pair.assert_i16()?);
+            }
+            Some(Ok(pair)) => {
+                iter.put_back(Ok(pair));
+                return Ok(None);
+            }
+            Some(Err(e)) => return Err(e),
+            None => return Ok(None),
+        }
+
+        loop {
+            let pair = match iter.next() {
+                Some(Ok(pair)) => pair,
+                Some(Err(e)) => return Err(e),
+                None => return Ok(Some(cell)),
+            };
+
+            match pair.code {
+                1 => {
+                    cell.text = pair.assert_string()?;
+                }
+                2 => {
+                    cell.extended_text.push(pair.assert_string()?);
+                }
+                7 => {
+                    cell.text_style_name = Some(pair.assert_string()?);
+                }
+                63 => {
+                    cell.fill_color = Some(Color::from_raw_value(pair.assert_i16()?));
+                }
+                64 => {
+                    cell.text_color = Some(Color::from_raw_value(pair.assert_i16()?));
+                }
+                65 => {
+                    cell.right_border_color = Some(Color::from_raw_value(pair.assert_i16()?));
+                }
+                66 => {
+                    cell.bottom_border_color = Some(Color::from_raw_value(pair.assert_i16()?));
+                }
+                68 => {
+                    cell.left_border_color = Some(Color::from_raw_value(pair.assert_i16()?));
+                }
+                69 => {
+                    cell.top_border_color = Some(Color::from_raw_value(pair.assert_i16()?));
+                }
+                91 => {
+                    cell.override_flags = pair.assert_i32()?;
+                }
+                92 => (), // extended cell flags; not preserved
+                140 => {
+                    cell.text_height = Some(pair.assert_f64()?);
+                }
+                144 => {
+                    cell.block_scale = pair.assert_f64()?;
+                }
+                145 => {
+                    cell.rotation = pair.assert_f64()?;
+                }
+                170 => {
+                    cell.cell_alignment = Some(pair.assert_i16()?);
+                }
+                172 => {
+                    cell.flags = pair.assert_i16()?;
+                }
+                173 => {
+                    cell.is_merged = as_bool(pair.assert_i16()?);
+                }
+                174 => {
+                    cell.is_autofit = as_bool(pair.assert_i16()?);
+                }
+                175 => {
+                    cell.merged_column_count = pair.assert_i16()?;
+                }
+                176 => {
+                    cell.merged_row_count = pair.assert_i16()?;
+                }
+                178 => {
+                    cell.virtual_edge_flags = pair.assert_i16()?;
+                }
+                179 => (), // attribute definition count; we just read as many as we're given
+                275 => {
+                    cell.right_border_lineweight = Some(pair.assert_i16()?);
+                }
+                276 => {
+                    cell.bottom_border_lineweight = Some(pair.assert_i16()?);
+                }
+                278 => {
+                    cell.left_border_lineweight = Some(pair.assert_i16()?);
+                }
+                279 => {
+                    cell.top_border_lineweight = Some(pair.assert_i16()?);
+                }
+                283 => {
+                    cell.is_background_fill_enabled = Some(as_bool(pair.assert_i16()?));
+                }
+                285 => {
+                    cell.is_right_border_visible = Some(as_bool(pair.assert_i16()?));
+                }
+                286 => {
+                    cell.is_bottom_border_visible = Some(as_bool(pair.assert_i16()?));
+                }
+                288 => {
+                    cell.is_left_border_visible = Some(as_bool(pair.assert_i16()?));
+                }
+                289 => {
+                    cell.is_top_border_visible = Some(as_bool(pair.assert_i16()?));
+                }
+                300 => {
+                    if let Some(attribute) = cell.block_attributes.last_mut() {
+                        attribute.1 = pair.assert_string()?;
+                    }
+                }
+                301 => {
+                    // value should be "CELL_VALUE"
+                    TableCell::read_cell_value(&mut cell, iter)?;
+                }
+                331 => {
+                    cell.block_attributes
+                        .push((pair.as_handle()?, String::new()));
+                }
+                340 => {
+                    cell.block_handle = pair.as_handle()?;
+                }
+                344 => {
+                    cell.field_handle = pair.as_handle()?;
+                }
+                _ => {
+                    // next cell or unexpected pair; put the pair back and return what we have
+                    iter.put_back(Ok(pair));
+                    return Ok(Some(cell));
+                }
+            }
+        }
+    }
+    fn read_cell_value<I>(cell: &mut TableCell, iter: &mut CodePairPutBack<I>) -> DxfResult<()>
+    where
+        I: Read,
+    {
+        // newer files store the text in a `CELL_VALUE`/`ACVALUE_END` block; only the display string is kept
+        loop {
+            let pair = match iter.next() {
+                Some(Ok(pair @ CodePair { code: 0, .. })) => {
+                    // unexpected end; put the pair back and return
+                    iter.put_back(Ok(pair));
+                    return Ok(());
+                }
+                Some(Ok(pair)) => pair,
+                Some(Err(e)) => return Err(e),
+                None => return Ok(()),
+            };
+            match pair.code {
+                302 if cell.text.is_empty() => {
+                    cell.text = pair.assert_string()?;
+                }
+                304 => return Ok(()), // value should be "ACVALUE_END"
+                _ => (),
+            }
+        }
+    }
+    pub(crate) fn write<T>(&self, writer: &mut CodePairWriter<T>) -> DxfResult<()>
+    where
+        T: Write + ?Sized,
+    {
+        writer.write_code_pair(&CodePair::new_i16(171, self.cell_type as i16))?;
+        writer.write_code_pair(&CodePair::new_i16(172, self.flags))?;
+        writer.write_code_pair(&CodePair::new_i16(173, as_i16(self.is_merged)))?;
+        writer.write_code_pair(&CodePair::new_i16(174, as_i16(self.is_autofit)))?;
+        writer.write_code_pair(&CodePair::new_i16(175, self.merged_column_count))?;
+        writer.write_code_pair(&CodePair::new_i16(176, self.merged_row_count))?;
+        writer.write_code_pair(&CodePair::new_i32(91, self.override_flags))?;
+        writer.write_code_pair(&CodePair::new_i16(178, self.virtual_edge_flags))?;
+        writer.write_code_pair(&CodePair::new_f64(145, self.rotation))?;
+        match self.cell_type {
+            TableCellType::Text => {
+                if !self.field_handle.is_empty() {
+                    writer.write_code_pair(&CodePair::new_string(
+                        344,
+                        &as_handle(self.field_handle),
+                    ))?;
+                }
+                writer.write_code_pair(&CodePair::new_string(1, &self.text))?;
+                for text in &self.extended_text {
+                    writer.write_code_pair(&CodePair::new_string(2, text))?;
+                }
+            }
+            TableCellType::Block => {
+                writer
+                    .write_code_pair(&CodePair::new_string(340, &as_handle(self.block_handle)))?;
+                writer.write_code_pair(&CodePair::new_f64(144, self.block_scale))?;
+                writer
+                    .write_code_pair(&CodePair::new_i16(179, self.block_attributes.len() as i16))?;
+                for (handle, value) in &self.block_attributes {
+                    writer.write_code_pair(&CodePair::new_string(331, &as_handle(*handle)))?;
+                    writer.write_code_pair(&CodePair::new_string(300, value))?;
+                }
+            }
+        }
+        if let Some(ref text_style_name) = self.text_style_name {
+            writer.write_code_pair(&CodePair::new_string(7, text_style_name))?;
+        }
+        if let Some(text_height) = self.text_height {
+            writer.write_code_pair(&CodePair::new_f64(140, text_height))?;
+        }
+        if let Some(cell_alignment) = self.cell_alignment {
+            writer.write_code_pair(&CodePair::new_i16(170, cell_alignment))?;
+        }
+        write_color(writer, 64, &self.text_color)?;
+        write_color(writer, 63, &self.fill_color)?;
+        write_bool(writer, 283, &self.is_background_fill_enabled)?;
+        write_color(writer, 69, &self.top_border_color)?;
+        write_color(writer, 65, &self.right_border_color)?;
+        write_color(writer, 66, &self.bottom_border_color)?;
+        write_color(writer, 68, &self.left_border_color)?;
+        write_lineweight(writer, 279, &self.top_border_lineweight)?;
+        write_lineweight(writer, 275, &self.right_border_lineweight)?;
+        write_lineweight(writer, 276, &self.bottom_border_lineweight)?;
+        write_lineweight(writer, 278, &self.left_border_lineweight)?;
+        write_bool(writer, 289, &self.is_top_border_visible)?;
+        write_bool(writer, 285, &self.is_right_border_visible)?;
+        write_bool(writer, 286, &self.is_bottom_border_visible)?;
+        write_bool(writer, 288, &self.is_left_border_visible)?;
+        Ok(())
+    }
+}
+
+fn write_color<T>(writer: &mut CodePairWriter<T>, code: i32, color: &Option<Color>) -> DxfResult<()>
+where
+    T: Write + ?Sized,
+{
+    if let Some(color) = color {
+        writer.write_code_pair(&CodePair::new_i16(code, color.get_raw_value()))?;
+    }
+    Ok(())
+}
+
+fn write_lineweight<T>(
+    writer: &mut CodePairWriter<T>,
+    code: i32,
+    lineweight: &Option<i16>,
+) -> DxfResult<()>
+where
+    T: Write + ?Sized,
+{
+    if let Some(lineweight) = lineweight {
+        writer.write_code_pair(&CodePair::new_i16(code, *lineweight))?;
+    }
+    Ok(())
+}
+
+fn write_bool<T>(writer: &mut CodePairWriter<T>, code: i32, value: &Option<bool>) -> DxfResult<()>
+where
+    T: Write + ?Sized,
+{
+    if let Some(value) = value {
+        writer.write_code_pair(&CodePair::new_i16(code, as_i16(*value)))?;
+    }
+    Ok(())
+}
+
+//------------------------------------------------------------------------------
+//                                                                TableCellRange
+//------------------------------------------------------------------------------
+/// Represents an inclusive range of merged cells in an `AcadTable`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct TableCellRange {
+    pub top_row: usize,
+    pub left_column: usize,
+    pub bottom_row: usize,
+    pub right_column: usize,
+}
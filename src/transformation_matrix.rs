@@ -1,4 +1,13 @@
+use std::ops::{Mul, MulAssign};
+
+use crate::{Point, Vector};
+
 /// Applies a transformation to a point.
+///
+/// The values are stored in row-major order, the same as they appear in a DXF file, and points and
+/// vectors are treated as column vectors; i.e., a point is transformed as `M * p` and the
+/// translation is stored in `m14`, `m24`, and `m34`.  As a consequence, `a * b` is the
+/// transformation that applies `b` first and then `a`.
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct TransformationMatrix {
@@ -31,6 +40,237 @@ impl TransformationMatrix {
             ..Default::default()
         }
     }
+    /// Returns a matrix that moves points by `offset`.
+    pub fn translation(offset: &Vector) -> Self {
+        TransformationMatrix {
+            m14: offset.x,
+            m24: offset.y,
+            m34: offset.z,
+            ..TransformationMatrix::identity()
+        }
+    }
+    /// Returns a matrix that scales about the origin by the specified factors along each axis.
+    pub fn scale(x: f64, y: f64, z: f64) -> Self {
+        TransformationMatrix {
+            m11: x,
+            m22: y,
+            m33: z,
+            m44: 1.0,
+            ..Default::default()
+        }
+    }
+    /// Returns a matrix that scales about the origin by `factor` along every axis.
+    pub fn uniform_scale(factor: f64) -> Self {
+        TransformationMatrix::scale(factor, factor, factor)
+    }
+    /// Returns a matrix that rotates counter-clockwise about `axis` through the origin.  The angle
+    /// is in degrees, like the rotation angles stored on entities.
+    pub fn rotation(axis: &Vector, angle: f64) -> Self {
+        let axis = axis.normalize();
+        let (sin, cos) = angle.to_radians().sin_cos();
+        let t = 1.0 - cos;
+        let (x, y, z) = (axis.x, axis.y, axis.z);
+        TransformationMatrix {
+            m11: cos + x * x * t,
+            m12: x * y * t - z * sin,
+            m13: x * z * t + y * sin,
+            m21: x * y * t + z * sin,
+            m22: cos + y * y * t,
+            m23: y * z * t - x * sin,
+            m31: x * z * t - y * sin,
+            m32: y * z * t + x * sin,
+            m33: cos + z * z * t,
+            m44: 1.0,
+            ..Default::default()
+        }
+    }
+    /// Returns a matrix that rotates counter-clockwise about the X axis by `angle` degrees.
+    pub fn rotation_about_x(angle: f64) -> Self {
+        TransformationMatrix::rotation(&Vector::x_axis(), angle)
+    }
+    /// Returns a matrix that rotates counter-clockwise about the Y axis by `angle` degrees.
+    pub fn rotation_about_y(angle: f64) -> Self {
+        TransformationMatrix::rotation(&Vector::y_axis(), angle)
+    }
+    /// Returns a matrix that rotates counter-clockwise about the Z axis by `angle` degrees.
+    pub fn rotation_about_z(angle: f64) -> Self {
+        TransformationMatrix::rotation(&Vector::z_axis(), angle)
+    }
+    /// Returns a matrix that rotates counter-clockwise about the Z axis by `angle` degrees while
+    /// keeping `center` fixed.
+    pub fn rotation_about_point(center: &Point, angle: f64) -> Self {
+        let offset = center.to_vector();
+        TransformationMatrix::translation(&offset)
+            * TransformationMatrix::rotation_about_z(angle)
+            * TransformationMatrix::translation(&-offset)
+    }
+    /// Returns a matrix that reflects points across the plane through the origin with the given
+    /// `normal`.  E.g., a normal of (1, 0, 0) mirrors across the Y axis.
+    pub fn mirror(normal: &Vector) -> Self {
+        let n = normal.normalize();
+        TransformationMatrix {
+            m11: 1.0 - 2.0 * n.x * n.x,
+            m12: -2.0 * n.x * n.y,
+            m13: -2.0 * n.x * n.z,
+            m21: -2.0 * n.y * n.x,
+            m22: 1.0 - 2.0 * n.y * n.y,
+            m23: -2.0 * n.y * n.z,
+            m31: -2.0 * n.z * n.x,
+            m32: -2.0 * n.z * n.y,
+            m33: 1.0 - 2.0 * n.z * n.z,
+            m44: 1.0,
+            ..Default::default()
+        }
+    }
+    /// Returns a matrix that reflects points across the plane through `point` with the given
+    /// `normal`.
+    pub fn mirror_about_plane(point: &Point, normal: &Vector) -> Self {
+        let offset = point.to_vector();
+        TransformationMatrix::translation(&offset)
+            * TransformationMatrix::mirror(normal)
+            * TransformationMatrix::translation(&-offset)
+    }
+    /// Returns the matrix that converts points from the Object Coordinate System (OCS) defined by
+    /// `normal` to the World Coordinate System (WCS) using the DXF Arbitrary Axis Algorithm.
+    pub fn from_ocs(normal: &Vector) -> Self {
+        let z = if normal.is_zero() {
+            Vector::z_axis()
+        } else {
+            normal.normalize()
+        };
+        let arbitrary_axis = if z.x.abs() < 1.0 / 64.0 && z.y.abs() < 1.0 / 64.0 {
+            Vector::y_axis()
+        } else {
+            Vector::z_axis()
+        };
+        let x = arbitrary_axis.cross(&z).normalize();
+        let y = z.cross(&x).normalize();
+        TransformationMatrix {
+            m11: x.x,
+            m12: y.x,
+            m13: z.x,
+            m21: x.y,
+            m22: y.y,
+            m23: z.y,
+            m31: x.z,
+            m32: y.z,
+            m33: z.z,
+            m44: 1.0,
+            ..Default::default()
+        }
+    }
+    /// Returns the matrix that converts points from the World Coordinate System (WCS) to the Object
+    /// Coordinate System (OCS) defined by `normal`.  This is the inverse of `from_ocs`.
+    pub fn to_ocs(normal: &Vector) -> Self {
+        // the OCS axes are orthonormal so the inverse is the transpose
+        TransformationMatrix::from_ocs(normal).transpose()
+    }
+    /// Returns the matrix that applies `other` first and then this transformation.  This is the
+    /// same as `self * other`.
+    pub fn multiply(&self, other: &TransformationMatrix) -> Self {
+        let a = self.rows();
+        let b = other.rows();
+        let mut result = [[0.0; 4]; 4];
+        for (i, row) in result.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = (0..4).map(|k| a[i][k] * b[k][j]).sum();
+            }
+        }
+
+        TransformationMatrix::from_rows(&result)
+    }
+    /// Returns the determinant of the matrix.
+    pub fn determinant(&self) -> f64 {
+        let m = self.rows();
+        let minor = |r0: usize, r1: usize, r2: usize, c0: usize, c1: usize, c2: usize| {
+            m[r0][c0] * (m[r1][c1] * m[r2][c2] - m[r1][c2] * m[r2][c1])
+                - m[r0][c1] * (m[r1][c0] * m[r2][c2] - m[r1][c2] * m[r2][c0])
+                + m[r0][c2] * (m[r1][c0] * m[r2][c1] - m[r1][c1] * m[r2][c0])
+        };
+        m[0][0] * minor(1, 2, 3, 1, 2, 3) - m[0][1] * minor(1, 2, 3, 0, 2, 3)
+            + m[0][2] * minor(1, 2, 3, 0, 1, 3)
+            - m[0][3] * minor(1, 2, 3, 0, 1, 2)
+    }
+    /// Returns the inverse of the matrix, or `None` if the matrix is singular.
+    pub fn inverse(&self) -> Option<Self> {
+        // Gauss-Jordan elimination with partial pivoting
+        let mut m = self.rows();
+        let mut inv = TransformationMatrix::identity().rows();
+        for col in 0..4 {
+            let pivot = (col..4)
+                .max_by(|&a, &b| m[a][col].abs().total_cmp(&m[b][col].abs()))
+                .unwrap_or(col);
+            if m[pivot][col].abs() < 1e-12 {
+                return None;
+            }
+
+            m.swap(col, pivot);
+            inv.swap(col, pivot);
+            let scale = m[col][col];
+            for j in 0..4 {
+                m[col][j] /= scale;
+                inv[col][j] /= scale;
+            }
+
+            for row in 0..4 {
+                if row != col {
+                    let factor = m[row][col];
+                    for j in 0..4 {
+                        m[row][j] -= factor * m[col][j];
+                        inv[row][j] -= factor * inv[col][j];
+                    }
+                }
+            }
+        }
+
+        Some(TransformationMatrix::from_rows(&inv))
+    }
+    /// Returns the transpose of the matrix.
+    pub fn transpose(&self) -> Self {
+        let m = self.rows();
+        let mut result = [[0.0; 4]; 4];
+        for (i, row) in result.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = m[j][i];
+            }
+        }
+
+        TransformationMatrix::from_rows(&result)
+    }
+    /// Applies the transformation, including the translation, to `point`.
+    pub fn transform_point(&self, point: &Point) -> Point {
+        let x = self.m11 * point.x + self.m12 * point.y + self.m13 * point.z + self.m14;
+        let y = self.m21 * point.x + self.m22 * point.y + self.m23 * point.z + self.m24;
+        let z = self.m31 * point.x + self.m32 * point.y + self.m33 * point.z + self.m34;
+        let w = self.m41 * point.x + self.m42 * point.y + self.m43 * point.z + self.m44;
+        if w == 0.0 || w == 1.0 {
+            Point::new(x, y, z)
+        } else {
+            Point::new(x / w, y / w, z / w)
+        }
+    }
+    /// Applies the transformation to `vector`.  Since vectors represent a direction and magnitude,
+    /// the translation is not applied.
+    pub fn transform_vector(&self, vector: &Vector) -> Vector {
+        Vector::new(
+            self.m11 * vector.x + self.m12 * vector.y + self.m13 * vector.z,
+            self.m21 * vector.x + self.m22 * vector.y + self.m23 * vector.z,
+            self.m31 * vector.x + self.m32 * vector.y + self.m33 * vector.z,
+        )
+    }
+}
+
+impl Mul for TransformationMatrix {
+    type Output = TransformationMatrix;
+    fn mul(self, rhs: TransformationMatrix) -> TransformationMatrix {
+        self.multiply(&rhs)
+    }
+}
+
+impl MulAssign for TransformationMatrix {
+    fn mul_assign(&mut self, rhs: TransformationMatrix) {
+        *self = self.multiply(&rhs);
+    }
 }
 
 // internal visibility only
@@ -71,6 +311,17 @@ impl TransformationMatrix {
 
 // private implementation
 impl TransformationMatrix {
+    fn rows(&self) -> [[f64; 4]; 4] {
+        [
+            [self.m11, self.m12, self.m13, self.m14],
+            [self.m21, self.m22, self.m23, self.m24],
+            [self.m31, self.m32, self.m33, self.m34],
+            [self.m41, self.m42, self.m43, self.m44],
+        ]
+    }
+    fn from_rows(rows: &[[f64; 4]; 4]) -> Self {
+        TransformationMatrix::from_vec(&rows.concat())
+    }
     fn get_value_or_default(values: &[f64], index: usize) -> f64 {
         if values.len() > index {
             values[index]
@@ -79,3 +330,158 @@ impl TransformationMatrix {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::helper_functions::tests::*;
+    use crate::{Point, TransformationMatrix, Vector};
+
+    fn assert_matrices_eq(expected: TransformationMatrix, actual: TransformationMatrix) {
+        for (e, a) in expected.get_values().iter().zip(actual.get_values()) {
+            assert!(
+                (e - a).abs() < 1e-9,
+                "expected {:?}, got {:?}",
+                expected,
+                actual
+            );
+        }
+    }
+
+    #[test]
+    fn translate_point_but_not_vector() {
+        let m = TransformationMatrix::translation(&Vector::new(1.0, 2.0, 3.0));
+        assert_eq!(
+            Point::new(2.0, 3.0, 4.0),
+            m.transform_point(&Point::new(1.0, 1.0, 1.0))
+        );
+        assert_eq!(Vector::x_axis(), m.transform_vector(&Vector::x_axis()));
+    }
+
+    #[test]
+    fn rotate_point() {
+        let m = TransformationMatrix::rotation_about_z(90.0);
+        assert_points_close(
+            Point::new(0.0, 1.0, 0.0),
+            &m.transform_point(&Point::new(1.0, 0.0, 0.0)),
+        );
+        let m = TransformationMatrix::rotation_about_x(90.0);
+        assert_points_close(
+            Point::new(0.0, 0.0, 1.0),
+            &m.transform_point(&Point::new(0.0, 1.0, 0.0)),
+        );
+        let m = TransformationMatrix::rotation_about_point(&Point::new(1.0, 1.0, 0.0), 180.0);
+        assert_points_close(
+            Point::new(0.0, 1.0, 0.0),
+            &m.transform_point(&Point::new(2.0, 1.0, 0.0)),
+        );
+    }
+
+    #[test]
+    fn scale_and_mirror() {
+        let m = TransformationMatrix::scale(2.0, 3.0, 4.0);
+        assert_eq!(
+            Point::new(2.0, 3.0, 4.0),
+            m.transform_point(&Point::new(1.0, 1.0, 1.0))
+        );
+        let m = TransformationMatrix::mirror(&Vector::x_axis());
+        assert_eq!(
+            Point::new(-1.0, 2.0, 3.0),
+            m.transform_point(&Point::new(1.0, 2.0, 3.0))
+        );
+        assert!(m.determinant() < 0.0);
+        let m =
+            TransformationMatrix::mirror_about_plane(&Point::new(0.0, 5.0, 0.0), &Vector::y_axis());
+        assert_points_close(
+            Point::new(1.0, 8.0, 0.0),
+            &m.transform_point(&Point::new(1.0, 2.0, 0.0)),
+        );
+    }
+
+    #[test]
+    fn compose_applies_right_operand_first() {
+        let translate = TransformationMatrix::translation(&Vector::new(1.0, 0.0, 0.0));
+        let scale = TransformationMatrix::uniform_scale(2.0);
+        let p = Point::new(1.0, 0.0, 0.0);
+        assert_eq!(
+            Point::new(3.0, 0.0, 0.0),
+            (translate * scale).transform_point(&p)
+        );
+        assert_eq!(
+            Point::new(4.0, 0.0, 0.0),
+            (scale * translate).transform_point(&p)
+        );
+
+        let mut m = translate;
+        m *= scale;
+        assert_eq!(translate * scale, m);
+    }
+
+    #[test]
+    fn inverse() {
+        let m = TransformationMatrix::translation(&Vector::new(1.0, 2.0, 3.0))
+            * TransformationMatrix::rotation(&Vector::new(1.0, 1.0, 1.0), 30.0)
+            * TransformationMatrix::scale(2.0, 3.0, 4.0);
+        let inverse = m.inverse().unwrap();
+        assert_matrices_eq(TransformationMatrix::identity(), m * inverse);
+        let p = Point::new(5.0, -2.0, 7.0);
+        assert_points_close(p.clone(), &inverse.transform_point(&m.transform_point(&p)));
+
+        assert_eq!(None, TransformationMatrix::scale(1.0, 0.0, 1.0).inverse());
+        assert_eq!(None, TransformationMatrix::default().inverse());
+    }
+
+    #[test]
+    fn ocs_with_default_normal_is_identity() {
+        assert_eq!(
+            TransformationMatrix::identity(),
+            TransformationMatrix::from_ocs(&Vector::z_axis())
+        );
+    }
+
+    #[test]
+    fn ocs_with_negative_z_normal_mirrors_x() {
+        let m = TransformationMatrix::from_ocs(&Vector::new(0.0, 0.0, -1.0));
+        assert_eq!(
+            Point::new(-1.0, 2.0, -3.0),
+            m.transform_point(&Point::new(1.0, 2.0, 3.0))
+        );
+    }
+
+    #[test]
+    fn ocs_uses_world_z_for_arbitrary_axis() {
+        // the normal is far enough from the Z axis that Wz x N is used for the OCS X axis
+        let m = TransformationMatrix::from_ocs(&Vector::x_axis());
+        assert_points_close(
+            Point::new(0.0, 1.0, 0.0),
+            &m.transform_point(&Point::new(1.0, 0.0, 0.0)),
+        );
+        assert_points_close(
+            Point::new(0.0, 0.0, 1.0),
+            &m.transform_point(&Point::new(0.0, 1.0, 0.0)),
+        );
+        assert_points_close(
+            Point::new(1.0, 0.0, 0.0),
+            &m.transform_point(&Point::new(0.0, 0.0, 1.0)),
+        );
+    }
+
+    #[test]
+    fn ocs_round_trip() {
+        let normal = Vector::new(0.3, -0.2, 0.9);
+        let p = Point::new(4.0, 5.0, 6.0);
+        let wcs = TransformationMatrix::from_ocs(&normal).transform_point(&p);
+        assert_points_close(
+            p,
+            &TransformationMatrix::to_ocs(&normal).transform_point(&wcs),
+        );
+    }
+
+    #[test]
+    fn determinant() {
+        assert_eq!(1.0, TransformationMatrix::identity().determinant());
+        assert_eq!(
+            24.0,
+            TransformationMatrix::scale(2.0, 3.0, 4.0).determinant()
+        );
+    }
+}
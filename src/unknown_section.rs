@@ -0,0 +1,93 @@
+use std::io::{Read, Write};
+
+use crate::{CodePair, DxfError, DxfResult};
+
+use crate::code_pair_put_back::CodePairPutBack;
+use crate::code_pair_writer::CodePairWriter;
+
+/// Represents a section that isn't otherwise supported, e.g., `ACDSDATA` or one written by a
+/// third-party application.  The code pairs are kept as-is and written back unchanged.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct UnknownSection {
+    /// The section name, e.g., `ACDSDATA`.
+    pub name: String,
+    /// Where the section is written.  Sections read from a file keep their original position while
+    /// new sections default to `SectionPosition::AtEnd`.
+    pub position: SectionPosition,
+    /// The raw code pairs between the section name and `ENDSEC`.
+    pub code_pairs: Vec<CodePair>,
+}
+
+/// Where an `UnknownSection` is written relative to the supported sections.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum SectionPosition {
+    /// Written before the `HEADER` section.
+    BeforeHeader,
+    /// Written after the named supported section, e.g., `OBJECTS`.  Sections following a name that
+    /// isn't a supported section are written after all others.
+    After(String),
+    /// Written after all other sections.
+    #[default]
+    AtEnd,
+}
+
+// public implementation
+impl UnknownSection {
+    /// Creates an empty section that's written after all other sections.
+    pub fn new(name: &str) -> Self {
+        UnknownSection {
+            name: String::from(name),
+            position: SectionPosition::AtEnd,
+            code_pairs: vec![],
+        }
+    }
+}
+
+// internal visibility only
+impl UnknownSection {
+    pub(crate) fn read<I>(
+        name: &str,
+        preceding_section: Option<&str>,
+        iter: &mut CodePairPutBack<I>,
+    ) -> DxfResult<UnknownSection>
+    where
+        I: Read,
+    {
+        let mut section = UnknownSection::new(name);
+        section.position = match preceding_section {
+            Some(preceding_section) => SectionPosition::After(String::from(preceding_section)),
+            None => SectionPosition::BeforeHeader,
+        };
+        loop {
+            match iter.next() {
+                Some(Ok(pair)) => {
+                    if pair.code == 0 && pair.assert_string()? == "ENDSEC" {
+                        iter.put_back(Ok(pair));
+                        break;
+                    }
+
+                    section.code_pairs.push(pair);
+                }
+                Some(Err(e)) => return Err(e),
+                None => return Err(DxfError::UnexpectedEndOfInput),
+            }
+        }
+
+        Ok(section)
+    }
+    pub(crate) fn write<T>(&self, writer: &mut CodePairWriter<T>) -> DxfResult<()>
+    where
+        T: Write + ?Sized,
+    {
+        writer.write_code_pair(&CodePair::new_str(0, "SECTION"))?;
+        writer.write_code_pair(&CodePair::new_string(2, &self.name))?;
+        for pair in &self.code_pairs {
+            writer.write_code_pair(pair)?;
+        }
+
+        writer.write_code_pair(&CodePair::new_str(0, "ENDSEC"))?;
+        Ok(())
+    }
+}
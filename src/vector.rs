@@ -1,4 +1,6 @@
-use crate::{CodePair, DxfError, DxfResult};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::{CodePair, DxfError, DxfResult, Point};
 
 /// Represents a simple vector in Cartesian space.
 #[derive(Clone, Debug, Default, PartialEq)]
@@ -33,6 +35,52 @@ impl Vector {
     pub fn z_axis() -> Vector {
         Vector::new(0.0, 0.0, 1.0)
     }
+    /// Returns the dot product of this vector and `other`.
+    pub fn dot(&self, other: &Vector) -> f64 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+    /// Returns the cross product of this vector and `other`.
+    pub fn cross(&self, other: &Vector) -> Vector {
+        Vector::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+    /// Returns the length of the vector.
+    pub fn length(&self) -> f64 {
+        self.dot(self).sqrt()
+    }
+    /// Returns a vector with the same direction and a length of 1.  A zero vector is returned
+    /// unchanged.
+    pub fn normalize(&self) -> Vector {
+        let length = self.length();
+        if length == 0.0 {
+            self.clone()
+        } else {
+            self / length
+        }
+    }
+    /// Returns `true` if every component of the vector is 0.
+    pub fn is_zero(&self) -> bool {
+        self.x == 0.0 && self.y == 0.0 && self.z == 0.0
+    }
+    /// Returns the angle between this vector and `other` in degrees, in the range [0, 180].
+    pub fn angle_to(&self, other: &Vector) -> f64 {
+        let lengths = self.length() * other.length();
+        if lengths == 0.0 {
+            0.0
+        } else {
+            (self.dot(other) / lengths)
+                .clamp(-1.0, 1.0)
+                .acos()
+                .to_degrees()
+        }
+    }
+    /// Returns the point at the end of this vector when placed at the origin.
+    pub fn to_point(&self) -> Point {
+        Point::new(self.x, self.y, self.z)
+    }
     pub(crate) fn set(&mut self, pair: &CodePair) -> DxfResult<()> {
         match pair.code {
             10 => self.x = pair.assert_f64()?,
@@ -49,3 +97,134 @@ impl Vector {
         Ok(())
     }
 }
+
+fn add(a: &Vector, b: &Vector) -> Vector {
+    Vector::new(a.x + b.x, a.y + b.y, a.z + b.z)
+}
+
+fn sub(a: &Vector, b: &Vector) -> Vector {
+    Vector::new(a.x - b.x, a.y - b.y, a.z - b.z)
+}
+
+fn mul(v: &Vector, scalar: &f64) -> Vector {
+    Vector::new(v.x * scalar, v.y * scalar, v.z * scalar)
+}
+
+fn div(v: &Vector, scalar: &f64) -> Vector {
+    Vector::new(v.x / scalar, v.y / scalar, v.z / scalar)
+}
+
+fn scale(scalar: &f64, v: &Vector) -> Vector {
+    mul(v, scalar)
+}
+
+impl_binary_op!(Add, add, Vector, Vector, Vector, add);
+impl_binary_op!(Sub, sub, Vector, Vector, Vector, sub);
+impl_binary_op!(Mul, mul, Vector, f64, Vector, mul);
+impl_binary_op!(Div, div, Vector, f64, Vector, div);
+impl_binary_op!(Mul, mul, f64, Vector, Vector, scale);
+
+impl Neg for Vector {
+    type Output = Vector;
+    fn neg(self) -> Vector {
+        -&self
+    }
+}
+
+impl Neg for &Vector {
+    type Output = Vector;
+    fn neg(self) -> Vector {
+        Vector::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl AddAssign<Vector> for Vector {
+    fn add_assign(&mut self, rhs: Vector) {
+        *self += &rhs;
+    }
+}
+
+impl<'a> AddAssign<&'a Vector> for Vector {
+    fn add_assign(&mut self, rhs: &'a Vector) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+        self.z += rhs.z;
+    }
+}
+
+impl SubAssign<Vector> for Vector {
+    fn sub_assign(&mut self, rhs: Vector) {
+        *self -= &rhs;
+    }
+}
+
+impl<'a> SubAssign<&'a Vector> for Vector {
+    fn sub_assign(&mut self, rhs: &'a Vector) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+        self.z -= rhs.z;
+    }
+}
+
+impl MulAssign<f64> for Vector {
+    fn mul_assign(&mut self, rhs: f64) {
+        self.x *= rhs;
+        self.y *= rhs;
+        self.z *= rhs;
+    }
+}
+
+impl DivAssign<f64> for Vector {
+    fn div_assign(&mut self, rhs: f64) {
+        self.x /= rhs;
+        self.y /= rhs;
+        self.z /= rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Vector;
+
+    #[test]
+    fn vector_arithmetic() {
+        let a = Vector::new(1.0, 2.0, 3.0);
+        let b = Vector::new(4.0, 5.0, 6.0);
+        assert_eq!(Vector::new(5.0, 7.0, 9.0), &a + &b);
+        assert_eq!(Vector::new(3.0, 3.0, 3.0), &b - &a);
+        assert_eq!(Vector::new(-1.0, -2.0, -3.0), -&a);
+        assert_eq!(Vector::new(2.0, 4.0, 6.0), &a * 2.0);
+        assert_eq!(Vector::new(2.0, 4.0, 6.0), 2.0 * &a);
+        assert_eq!(Vector::new(2.0, 2.5, 3.0), b / 2.0);
+
+        let mut c = a.clone();
+        c += Vector::x_axis();
+        c *= 2.0;
+        assert_eq!(Vector::new(4.0, 4.0, 6.0), c);
+    }
+
+    #[test]
+    fn vector_products() {
+        let a = Vector::new(1.0, 2.0, 3.0);
+        let b = Vector::new(4.0, 5.0, 6.0);
+        assert_eq!(32.0, a.dot(&b));
+        assert_eq!(Vector::new(-3.0, 6.0, -3.0), a.cross(&b));
+        assert_eq!(Vector::z_axis(), Vector::x_axis().cross(&Vector::y_axis()));
+    }
+
+    #[test]
+    fn vector_length_and_normalize() {
+        let v = Vector::new(3.0, 0.0, 4.0);
+        assert_eq!(5.0, v.length());
+        assert_eq!(Vector::new(0.6, 0.0, 0.8), v.normalize());
+        assert_eq!(Vector::zero(), Vector::zero().normalize());
+    }
+
+    #[test]
+    fn vector_angle() {
+        let angle = Vector::x_axis().angle_to(&Vector::new(-1.0, 0.0, 0.0));
+        assert!(approx_eq!(f64, 180.0, angle));
+        let angle = Vector::x_axis().angle_to(&Vector::new(1.0, 1.0, 0.0));
+        assert!(approx_eq!(f64, 45.0, angle, epsilon = 1e-12));
+    }
+}
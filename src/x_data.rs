@@ -1,6 +1,6 @@
 use std::io::{Read, Write};
 
-use crate::{CodePair, DxfError, DxfResult, Point, Vector};
+use crate::{CodePair, DxfError, DxfResult, Handle, Point, Vector};
 
 use crate::code_pair_put_back::CodePairPutBack;
 use crate::code_pair_writer::CodePairWriter;
@@ -39,7 +39,7 @@ pub enum XDataItem {
     ControlGroup(Vec<XDataItem>),
     LayerName(String),
     BinaryData(Vec<u8>),
-    Handle(u32),
+    Handle(Handle),
     ThreeReals(f64, f64, f64),
     WorldSpacePosition(Point),
     WorldSpaceDisplacement(Point),
@@ -101,9 +101,87 @@ impl XData {
         }
         Ok(())
     }
+    /// Collects the handles this data refers to.
+    pub(crate) fn collect_handles(&self, handles: &mut Vec<Handle>) {
+        XDataItem::collect_handles(&self.items, handles);
+    }
+    /// Renames any references to the layer `old_name`.
+    pub(crate) fn rename_layer(&mut self, old_name: &str, new_name: &str) {
+        XDataItem::rename_layer(&mut self.items, old_name, new_name);
+    }
+    /// The arrow blocks named in dimension style overrides, i.e., the `1000` strings following the
+    /// `DIMBLK`, `DIMBLK1` and `DIMBLK2` codes in an `ACAD`/`DSTYLE` group.
+    pub(crate) fn dimension_style_block_names(&self) -> Vec<&String> {
+        let mut names = vec![];
+        if self.application_name != "ACAD" {
+            return names;
+        }
+
+        for pair in self.items.windows(2) {
+            if let [XDataItem::Str(s), XDataItem::ControlGroup(ref overrides)] = pair {
+                if s == "DSTYLE" {
+                    // the overrides alternate between the code and its value
+                    for pair in overrides.chunks(2) {
+                        if let [XDataItem::Integer(5..=7), XDataItem::Str(ref name)] = pair {
+                            names.push(name);
+                        }
+                    }
+                }
+            }
+        }
+
+        names
+    }
+    /// Renames the arrow blocks named in dimension style overrides.  See
+    /// `dimension_style_block_names`.
+    pub(crate) fn rename_dimension_style_block(&mut self, old_name: &str, new_name: &str) {
+        if self.application_name != "ACAD" {
+            return;
+        }
+
+        let mut items = self.items.iter_mut();
+        while let Some(item) = items.next() {
+            if !matches!(item, XDataItem::Str(s) if s == "DSTYLE") {
+                continue;
+            }
+
+            if let Some(XDataItem::ControlGroup(ref mut overrides)) = items.next() {
+                // the overrides alternate between the code and its value
+                for pair in overrides.chunks_mut(2) {
+                    if let [XDataItem::Integer(5..=7), XDataItem::Str(ref mut s)] = pair {
+                        if s.eq_ignore_ascii_case(old_name) {
+                            *s = String::from(new_name);
+                        }
+                    }
+                }
+            }
+        }
+    }
 }
 
 impl XDataItem {
+    fn collect_handles(items: &[XDataItem], handles: &mut Vec<Handle>) {
+        for item in items {
+            match item {
+                XDataItem::Handle(h) => handles.push(*h),
+                XDataItem::ControlGroup(ref items) => XDataItem::collect_handles(items, handles),
+                _ => (),
+            }
+        }
+    }
+    fn rename_layer(items: &mut [XDataItem], old_name: &str, new_name: &str) {
+        for item in items.iter_mut() {
+            match item {
+                XDataItem::LayerName(ref mut l) if l.eq_ignore_ascii_case(old_name) => {
+                    *l = String::from(new_name)
+                }
+                XDataItem::ControlGroup(ref mut items) => {
+                    XDataItem::rename_layer(items, old_name, new_name)
+                }
+                _ => (),
+            }
+        }
+    }
     fn read_item<I>(pair: &CodePair, iter: &mut CodePairPutBack<I>) -> DxfResult<XDataItem>
     where
         I: Read,